@@ -0,0 +1,116 @@
+/// Default tagging of an ASN.1 module.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TagDefault {
+    Explicit,
+    Implicit,
+    Automatic,
+}
+
+/// Tagging mode of a tagged type.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TagMode {
+    Explicit,
+    Implicit,
+}
+
+/// A bound of a range constraint.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Bound {
+    Number(i128),
+    Value(String),
+    Max,
+}
+
+/// A `SIZE` or value range constraint.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Range {
+    pub lower: Bound,
+    pub upper: Bound,
+}
+
+/// A value, as found in `DEFAULT` clauses.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Value {
+    Boolean(bool),
+    Number(i128),
+    Named(String),
+    Other,
+}
+
+/// An ASN.1 type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Type {
+    Boolean,
+    Null,
+    Integer {
+        named: Vec<(String, i128)>,
+        range: Option<Range>,
+    },
+    Enumerated(Vec<(String, i128)>),
+    BitString {
+        named: Vec<(String, i128)>,
+        size: Option<Range>,
+    },
+    OctetString {
+        size: Option<Range>,
+    },
+    ObjectIdentifier,
+    String {
+        name: String,
+        size: Option<Range>,
+    },
+    UtcTime,
+    GeneralizedTime,
+    Any,
+    Reference(String),
+    Sequence(Vec<Component>),
+    SequenceOf {
+        element: Box<Type>,
+        size: Option<Range>,
+    },
+    SetOf {
+        element: Box<Type>,
+        size: Option<Range>,
+    },
+    Choice(Vec<Component>),
+    Tagged {
+        number: u32,
+        mode: Option<TagMode>,
+        inner: Box<Type>,
+    },
+}
+
+/// A component of a `SEQUENCE`, or an alternative of a `CHOICE`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Component {
+    pub name: String,
+    pub ty: Type,
+    pub optional: bool,
+    pub default: Option<Value>,
+}
+
+/// A value of an `OBJECT IDENTIFIER`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Arc {
+    Number(u64),
+    Named(String),
+}
+
+/// An assignment in the body of a module.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Assignment {
+    Type(String, Type),
+    ObjectIdentifier(String, Vec<Arc>),
+    Integer(String, i128),
+    /// A value of a referenced type, given as `{ arcs }`.
+    Value(String, String, Vec<Arc>),
+    Unsupported(String, String),
+}
+
+/// An ASN.1 module.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Module {
+    pub name: String,
+    pub tag_default: TagDefault,
+    pub assignments: Vec<Assignment>,
+}
@@ -0,0 +1,818 @@
+use crate::{
+    ast::{Arc, Assignment, Bound, Component, Module, Range, TagDefault, TagMode, Type, Value},
+    naming::{const_name, field_name, snake_name, type_name},
+};
+
+use std::collections::BTreeSet;
+
+use proc_macro2::{Ident, Literal, Span, TokenStream};
+use quote::quote;
+
+type Result<T> = std::result::Result<T, String>;
+
+/// Well-known roots of the `OBJECT IDENTIFIER` tree.
+const ROOTS: &[(&str, u64)] = &[
+    ("itu-t", 0),
+    ("ccitt", 0),
+    ("iso", 1),
+    ("joint-iso-itu-t", 2),
+    ("joint-iso-ccitt", 2),
+];
+
+/// Generates Rust code for a set of modules.
+///
+/// References between the modules are resolved, e.g. those of `IMPORTS`.
+/// Types which cannot be generated are skipped, and references to them are
+/// generated as `AnyRef`.
+#[derive(Clone, Debug)]
+pub struct Generator<'a> {
+    modules: &'a [Module],
+
+    /// Module index and name of the types which cannot be generated.
+    skipped: BTreeSet<(usize, String)>,
+
+    warnings: Vec<String>,
+}
+
+impl<'a> Generator<'a> {
+    pub fn new(modules: &'a [Module]) -> Self {
+        let mut generator = Self {
+            modules,
+            skipped: BTreeSet::new(),
+            warnings: Vec::new(),
+        };
+
+        for (m, module) in modules.iter().enumerate() {
+            for assignment in &module.assignments {
+                match assignment {
+                    Assignment::Unsupported(name, reason) => generator.skip(m, name, reason),
+                    Assignment::ObjectIdentifier(name, _) | Assignment::Value(name, _, _)
+                        if generator.oid(m, name, 0).is_none() =>
+                    {
+                        generator.skip(m, name, "the OBJECT IDENTIFIER cannot be resolved")
+                    }
+                    _ => (),
+                }
+            }
+        }
+
+        // Skipping a type may prevent others from being generated, e.g. a
+        // CHOICE whose alternative no longer has a fixed tag.
+        loop {
+            let mut failed = Vec::new();
+            for (m, module) in modules.iter().enumerate() {
+                for assignment in &module.assignments {
+                    if let Assignment::Type(name, ty) = assignment {
+                        if generator.skipped.contains(&(m, name.clone())) {
+                            continue;
+                        }
+
+                        if let Err(reason) = generator.type_assignment(m, name, ty) {
+                            failed.push((m, name.clone(), reason));
+                        }
+                    }
+                }
+            }
+
+            if failed.is_empty() {
+                break;
+            }
+
+            for (m, name, reason) in failed {
+                generator.skip(m, &name, &reason);
+            }
+        }
+
+        generator
+    }
+
+    fn skip(&mut self, m: usize, name: &str, reason: &str) {
+        let module = &self.modules[m].name;
+        self.warnings
+            .push(format!("{}: skipped `{}`: {}", module, name, reason));
+        self.skipped.insert((m, name.into()));
+    }
+
+    /// The assignments which were skipped, and why.
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    /// Generate a Rust module for each of the ASN.1 modules.
+    pub fn module(&self) -> TokenStream {
+        let mut mods = TokenStream::default();
+
+        for (m, module) in self.modules.iter().enumerate() {
+            let name = field_name(&module.name);
+            let mut items = TokenStream::default();
+
+            for assignment in &module.assignments {
+                match assignment {
+                    Assignment::Type(name, ty) if !self.skipped.contains(&(m, name.clone())) => {
+                        // Errors were already reported when the types were
+                        // checked in `new()`.
+                        items.extend(self.type_assignment(m, name, ty).unwrap_or_default());
+                    }
+                    Assignment::ObjectIdentifier(name, _) | Assignment::Value(name, _, _) => {
+                        if let Some(oid) = self.oid(m, name, 0) {
+                            let symb = const_name(name);
+                            let doc = format!("`{}` as defined in `{}`.", name, module.name);
+                            items.extend(quote! {
+                                #[doc = #doc]
+                                pub const #symb: ::der::asn1::ObjectIdentifier =
+                                    ::der::asn1::ObjectIdentifier::new_unwrap(#oid);
+                            });
+                        }
+                    }
+                    Assignment::Integer(name, value) => {
+                        let symb = const_name(name);
+                        let doc = format!("`{}` as defined in `{}`.", name, module.name);
+                        let (ty, value) = match u32::try_from(*value) {
+                            Ok(n) => (quote!(u32), Literal::u32_unsuffixed(n)),
+                            Err(_) => (quote!(i128), Literal::i128_unsuffixed(*value)),
+                        };
+                        items.extend(quote! {
+                            #[doc = #doc]
+                            pub const #symb: #ty = #value;
+                        });
+                    }
+                    _ => (),
+                }
+            }
+
+            mods.extend(quote! {
+                pub mod #name {
+                    #items
+                }
+            });
+        }
+
+        quote! {
+            #![doc = "!! DO NOT EDIT !!: This file is auto-generated by asn1gen."]
+
+            #mods
+        }
+    }
+
+    /// Find the module defining a type, preferring `m`.
+    fn lookup(&self, m: usize, name: &str) -> Option<(usize, &'a Type)> {
+        let find = |m: usize| {
+            self.modules[m].assignments.iter().find_map(|a| match a {
+                Assignment::Type(n, ty) if n == name => Some((m, ty)),
+                _ => None,
+            })
+        };
+
+        find(m).or_else(|| (0..self.modules.len()).find_map(find))
+    }
+
+    /// Find a generated type, i.e. one which was not skipped.
+    fn resolve(&self, m: usize, name: &str) -> Option<(usize, &'a Type)> {
+        self.lookup(m, name)
+            .filter(|(m, _)| !self.skipped.contains(&(*m, name.into())))
+    }
+
+    /// Resolve the value of an `INTEGER`.
+    fn integer(&self, m: usize, name: &str) -> Option<i128> {
+        let find = |m: usize| {
+            self.modules[m].assignments.iter().find_map(|a| match a {
+                Assignment::Integer(n, value) if n == name => Some(*value),
+                _ => None,
+            })
+        };
+
+        find(m).or_else(|| (0..self.modules.len()).find_map(find))
+    }
+
+    /// Resolve the dotted form of an `OBJECT IDENTIFIER`.
+    fn oid(&self, m: usize, name: &str, depth: usize) -> Option<String> {
+        let find = |m: usize| {
+            self.modules[m].assignments.iter().find_map(|a| match a {
+                Assignment::ObjectIdentifier(n, arcs) if n == name => Some((m, arcs)),
+                Assignment::Value(n, ty, arcs) if n == name => {
+                    let (n, ty) = self.lookup(m, ty)?;
+                    match self.base(n, ty) {
+                        Some((_, Type::ObjectIdentifier)) => Some((m, arcs)),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            })
+        };
+
+        if depth > 32 {
+            return None;
+        }
+
+        let (m, arcs) = find(m).or_else(|| (0..self.modules.len()).find_map(find))?;
+        let mut oid = Vec::new();
+
+        for (i, arc) in arcs.iter().enumerate() {
+            match arc {
+                Arc::Number(n) => oid.push(n.to_string()),
+                Arc::Named(name) if i == 0 => match ROOTS.iter().find(|(r, _)| r == name) {
+                    Some((_, n)) => oid.push(n.to_string()),
+                    None => oid.push(self.oid(m, name, depth + 1)?),
+                },
+                Arc::Named(_) => return None,
+            }
+        }
+
+        Some(oid.join("."))
+    }
+
+    /// Follow references to the underlying type.
+    fn base(&self, m: usize, ty: &'a Type) -> Option<(usize, &'a Type)> {
+        let mut current = (m, ty);
+        for _ in 0..32 {
+            match current.1 {
+                Type::Reference(name) => current = self.resolve(current.0, name)?,
+                _ => return Some(current),
+            }
+        }
+
+        None
+    }
+
+    /// Does the Rust type of `ty` borrow from the input?
+    fn borrows(&self, m: usize, ty: &'a Type, visiting: &mut Vec<(usize, &'a str)>) -> bool {
+        match ty {
+            Type::Boolean
+            | Type::Null
+            | Type::Enumerated(_)
+            | Type::ObjectIdentifier
+            | Type::UtcTime
+            | Type::GeneralizedTime => false,
+            Type::Integer { named, range } => named.is_empty() && !self.is_small(m, range),
+            Type::BitString { .. } | Type::OctetString { .. } | Type::String { .. } => true,
+            Type::Any => true,
+            Type::Sequence(components) | Type::Choice(components) => {
+                components.iter().any(|c| self.borrows(m, &c.ty, visiting))
+            }
+            Type::SequenceOf { element, .. } | Type::SetOf { element, .. } => {
+                self.borrows(m, element, visiting)
+            }
+            Type::Tagged { inner, .. } => self.borrows(m, inner, visiting),
+            Type::Reference(name) => match self.resolve(m, name) {
+                Some(_) if visiting.contains(&(m, name)) => false,
+                Some((n, ty)) => {
+                    visiting.push((m, name));
+                    let borrows = self.borrows(n, ty, visiting);
+                    visiting.pop();
+                    borrows
+                }
+                // Generated as `AnyRef`
+                None => true,
+            },
+        }
+    }
+
+    /// Generic parameters of a type.
+    fn lifetime(&self, m: usize, ty: &'a Type) -> TokenStream {
+        match self.borrows(m, ty, &mut Vec::new()) {
+            true => quote!(<'a>),
+            false => TokenStream::default(),
+        }
+    }
+
+    /// Can an `INTEGER` with this range be represented as a `u32`?
+    fn is_small(&self, m: usize, range: &Option<Range>) -> bool {
+        let bound = |bound: &Bound| match bound {
+            Bound::Number(n) => Some(*n),
+            Bound::Value(name) => self.integer(m, name),
+            Bound::Max => None,
+        };
+
+        match range {
+            Some(range) => matches!(
+                (bound(&range.lower), bound(&range.upper)),
+                (Some(lower), Some(upper)) if lower >= 0 && u32::try_from(upper).is_ok()
+            ),
+            None => false,
+        }
+    }
+
+    /// Is `ty` a CHOICE or ANY, whose tags are always explicit?
+    fn is_untagged(&self, m: usize, ty: &'a Type) -> bool {
+        matches!(self.base(m, ty), Some((_, Type::Choice(_) | Type::Any)))
+    }
+
+    /// Does the Rust type of `ty` always have the same tag?
+    fn has_fixed_tag(&self, m: usize, ty: &'a Type) -> bool {
+        match self.base(m, ty) {
+            Some((_, Type::Choice(_) | Type::Any | Type::Reference(_))) | None => false,
+            Some((_, Type::String { name, .. })) => string_type(name).is_some(),
+            Some(_) => true,
+        }
+    }
+
+    /// Is the encoding of `ty` constructed?
+    fn is_constructed(&self, m: usize, ty: &'a Type) -> bool {
+        matches!(
+            self.base(m, ty),
+            Some((
+                _,
+                Type::Sequence(_) | Type::SequenceOf { .. } | Type::SetOf { .. }
+            ))
+        )
+    }
+
+    /// Generate the items for a type assignment.
+    fn type_assignment(&self, m: usize, name: &str, ty: &'a Type) -> Result<TokenStream> {
+        let ident = type_name(name);
+        let doc = format!("`{}` as defined in `{}`.", name, self.modules[m].name);
+        let mut items = TokenStream::default();
+
+        match ty {
+            Type::Sequence(_) | Type::Choice(_) | Type::Enumerated(_) | Type::Integer { .. }
+                if self.is_named(ty) =>
+            {
+                self.named_type(m, &ident, &doc, ty, &mut items)?
+            }
+            Type::Tagged { .. } => return Err("tagged type assignments are not supported".into()),
+            _ => {
+                let lifetime = self.lifetime(m, ty);
+                let rust = self.rust_type(m, ty, &ident, &mut items)?;
+                let doc = format!("{}{}", doc, constraint_doc(ty));
+                items.extend(quote! {
+                    #[doc = #doc]
+                    pub type #ident #lifetime = #rust;
+                });
+            }
+        }
+
+        Ok(items)
+    }
+
+    /// Is `ty` generated as a named item, rather than as an existing type?
+    fn is_named(&self, ty: &Type) -> bool {
+        match ty {
+            Type::Sequence(_) | Type::Choice(_) | Type::Enumerated(_) => true,
+            Type::Integer { named, .. } => !named.is_empty(),
+            _ => false,
+        }
+    }
+
+    /// Generate a `struct` or `enum` named `ident` for `ty`.
+    fn named_type(
+        &self,
+        m: usize,
+        ident: &Ident,
+        doc: &str,
+        ty: &'a Type,
+        items: &mut TokenStream,
+    ) -> Result<()> {
+        let lifetime = self.lifetime(m, ty);
+
+        match ty {
+            Type::Sequence(components) => {
+                let mut fields = TokenStream::default();
+                for (i, component) in components.iter().enumerate() {
+                    let (attrs, rust) = self.component(m, ident, components, i, false, items)?;
+                    let name = field_name(&component.name);
+                    let doc = format!("`{}`{}", component.name, constraint_doc(&component.ty));
+                    fields.extend(quote! {
+                        #[doc = #doc]
+                        #attrs
+                        pub #name: #rust,
+                    });
+                }
+
+                items.extend(quote! {
+                    #[doc = #doc]
+                    #[derive(Clone, Debug, Eq, PartialEq, ::der::Sequence, ::der::ValueOrd)]
+                    pub struct #ident #lifetime {
+                        #fields
+                    }
+                });
+            }
+            Type::Choice(components) => {
+                let mut variants = TokenStream::default();
+                for (i, component) in components.iter().enumerate() {
+                    let (attrs, rust) = self.component(m, ident, components, i, true, items)?;
+                    let name = type_name(&component.name);
+                    let doc = format!("`{}`{}", component.name, constraint_doc(&component.ty));
+                    variants.extend(quote! {
+                        #[doc = #doc]
+                        #attrs
+                        #name(#rust),
+                    });
+                }
+
+                items.extend(quote! {
+                    #[doc = #doc]
+                    #[derive(Clone, Debug, Eq, PartialEq, ::der::Choice)]
+                    pub enum #ident #lifetime {
+                        #variants
+                    }
+
+                    impl #lifetime ::der::ValueOrd for #ident #lifetime {
+                        fn value_cmp(&self, other: &Self) -> ::der::Result<::core::cmp::Ordering> {
+                            Ok(::der::Encode::to_vec(self)?.cmp(&::der::Encode::to_vec(other)?))
+                        }
+                    }
+                });
+            }
+            Type::Enumerated(named) | Type::Integer { named, .. } => {
+                let mut variants = TokenStream::default();
+                for (name, value) in named {
+                    let variant = type_name(name);
+                    let value = u32::try_from(*value)
+                        .map_err(|_| format!("`{}` is not a u32", name))
+                        .map(Literal::u32_unsuffixed)?;
+                    let doc = format!("`{}`", name);
+                    variants.extend(quote! {
+                        #[doc = #doc]
+                        #variant = #value,
+                    });
+                }
+
+                let asn1_type = match ty {
+                    Type::Integer { .. } => quote!(#[asn1(type = "INTEGER")]),
+                    _ => TokenStream::default(),
+                };
+
+                items.extend(quote! {
+                    #[doc = #doc]
+                    #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord, ::der::Enumerated)]
+                    #asn1_type
+                    #[repr(u32)]
+                    pub enum #ident {
+                        #variants
+                    }
+
+                    impl ::der::ValueOrd for #ident {
+                        fn value_cmp(&self, other: &Self) -> ::der::Result<::core::cmp::Ordering> {
+                            Ok(self.cmp(other))
+                        }
+                    }
+                });
+            }
+            _ => unreachable!("not a named type"),
+        }
+
+        Ok(())
+    }
+
+    /// Generate the attributes and type of a field or variant.
+    fn component(
+        &self,
+        m: usize,
+        parent: &Ident,
+        components: &'a [Component],
+        index: usize,
+        choice: bool,
+        items: &mut TokenStream,
+    ) -> Result<(TokenStream, TokenStream)> {
+        let component = &components[index];
+        let tag_default = self.modules[m].tag_default;
+        let automatic = tag_default == TagDefault::Automatic
+            && components
+                .iter()
+                .all(|c| !matches!(c.ty, Type::Tagged { .. }));
+
+        let (tag, inner) = match &component.ty {
+            Type::Tagged {
+                number,
+                mode,
+                inner,
+            } => (Some((*number, *mode)), &**inner),
+            ty if automatic => (Some((u32::try_from(index).unwrap_or(u32::MAX), None)), ty),
+            ty => (None, ty),
+        };
+
+        if matches!(inner, Type::Tagged { .. }) {
+            return Err("nested tags are not supported".into());
+        }
+
+        let context = Ident::new(
+            &format!("{}{}", parent, type_name(&component.name)),
+            Span::call_site(),
+        );
+        let rust = self.rust_type(m, inner, &context, items)?;
+        let mut attrs = Vec::new();
+
+        match tag {
+            Some((number, mode)) => {
+                let number = number.to_string();
+                attrs.push(quote!(context_specific = #number));
+
+                // Tags of CHOICE and ANY types are always explicit.
+                let implicit = match mode {
+                    Some(mode) => mode == TagMode::Implicit,
+                    None => tag_default != TagDefault::Explicit,
+                } && !self.is_untagged(m, inner);
+
+                if implicit {
+                    if !self.has_fixed_tag(m, inner) {
+                        return Err(format!(
+                            "`{}` is implicitly tagged, but its type is not supported",
+                            component.name
+                        ));
+                    }
+
+                    attrs.push(quote!(tag_mode = "IMPLICIT"));
+                    if self.is_constructed(m, inner) {
+                        attrs.push(quote!(constructed = "true"));
+                    }
+                } else if choice {
+                    // `Choice` matches on the whole tag, and explicit tags
+                    // are always constructed.
+                    attrs.push(quote!(constructed = "true"));
+                }
+            }
+            // Untagged alternatives are told apart by their tag.
+            None if choice && !self.has_fixed_tag(m, inner) => {
+                return Err(format!(
+                    "`{}` has no tag of its own and is not supported",
+                    component.name
+                ));
+            }
+            None => (),
+        }
+
+        let default = component
+            .default
+            .as_ref()
+            .and_then(|value| self.default(m, parent, component, inner, &rust, value));
+
+        let rust = match (&default, component.optional || component.default.is_some()) {
+            (Some((path, item)), _) => {
+                let path = path.to_string();
+                attrs.push(quote!(default = #path));
+                items.extend(item.clone());
+                rust
+            }
+            (None, true) => {
+                if tag.is_some() {
+                    attrs.push(quote!(optional = "true"));
+                }
+                quote!(Option<#rust>)
+            }
+            (None, false) => rust,
+        };
+
+        let attrs = match attrs.is_empty() {
+            true => TokenStream::default(),
+            false => quote!(#[asn1(#(#attrs),*)]),
+        };
+
+        Ok((attrs, rust))
+    }
+
+    /// Generate a function returning the `DEFAULT` value of a component.
+    ///
+    /// Returns `None` for values which cannot be generated, in which case the
+    /// component is treated as `OPTIONAL`.
+    fn default(
+        &self,
+        m: usize,
+        parent: &Ident,
+        component: &Component,
+        ty: &'a Type,
+        rust: &TokenStream,
+        value: &Value,
+    ) -> Option<(TokenStream, TokenStream)> {
+        let name = format!(
+            "{}_{}_default",
+            snake_name(&parent.to_string()),
+            snake_name(&component.name)
+        );
+        let ident = Ident::new(&name, Span::call_site());
+        let item = |ty: TokenStream, body: TokenStream| {
+            let lifetime = match ty.to_string().contains("'a") {
+                true => quote!(<'a>),
+                false => TokenStream::default(),
+            };
+
+            quote! {
+                fn #ident #lifetime () -> #ty {
+                    #body
+                }
+            }
+        };
+
+        let (_, base) = self.base(m, ty)?;
+        match (base, value) {
+            (Type::Boolean, Value::Boolean(false)) => {
+                Some((quote!(Default::default), TokenStream::default()))
+            }
+            (Type::Boolean, Value::Boolean(true)) => {
+                Some((quote!(#ident), item(quote!(bool), quote!(true))))
+            }
+            (Type::Integer { named, .. }, Value::Named(value))
+            | (Type::Enumerated(named), Value::Named(value))
+                if named.iter().any(|(n, _)| n == value) =>
+            {
+                let variant = type_name(value);
+                Some((quote!(#ident), item(rust.clone(), quote!(#rust::#variant))))
+            }
+            (Type::Integer { named, range }, value) if named.is_empty() => {
+                let value = match value {
+                    Value::Number(n) => *n,
+                    Value::Named(name) => self.integer(m, name)?,
+                    _ => return None,
+                };
+
+                match self.is_small(m, range) {
+                    true => {
+                        let value = Literal::u32_unsuffixed(u32::try_from(value).ok()?);
+                        Some((quote!(#ident), item(quote!(u32), quote!(#value))))
+                    }
+                    false => {
+                        let bytes = int_bytes(value).into_iter().map(Literal::u8_unsuffixed);
+                        Some((
+                            quote!(#ident),
+                            item(
+                                quote!(::der::asn1::IntRef<'a>),
+                                quote! {
+                                    ::der::asn1::IntRef::new(&[#(#bytes),*])
+                                        .expect("DEFAULT is a valid INTEGER")
+                                },
+                            ),
+                        ))
+                    }
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Generate the Rust type of `ty`, along with any items it needs, which
+    /// are named after `context`.
+    fn rust_type(
+        &self,
+        m: usize,
+        ty: &'a Type,
+        context: &Ident,
+        items: &mut TokenStream,
+    ) -> Result<TokenStream> {
+        let rust = match ty {
+            Type::Boolean => quote!(bool),
+            Type::Null => quote!(::der::asn1::Null),
+            Type::Integer { named, range } if named.is_empty() => match self.is_small(m, range) {
+                true => quote!(u32),
+                false => quote!(::der::asn1::IntRef<'a>),
+            },
+            Type::BitString { .. } => quote!(::der::asn1::BitStringRef<'a>),
+            Type::OctetString { .. } => quote!(::der::asn1::OctetStringRef<'a>),
+            Type::ObjectIdentifier => quote!(::der::asn1::ObjectIdentifier),
+            Type::String { name, .. } => {
+                string_type(name).unwrap_or_else(|| quote!(::der::asn1::AnyRef<'a>))
+            }
+            Type::UtcTime => quote!(::der::asn1::UtcTime),
+            Type::GeneralizedTime => quote!(::der::asn1::GeneralizedTime),
+            Type::Any => quote!(::der::asn1::AnyRef<'a>),
+            Type::Reference(name) => match self.resolve(m, name) {
+                Some((n, ty)) => {
+                    let ident = type_name(name);
+                    let lifetime = self.lifetime(n, ty);
+                    match n == m {
+                        true => quote!(#ident #lifetime),
+                        false => {
+                            let module = field_name(&self.modules[n].name);
+                            quote!(super::#module::#ident #lifetime)
+                        }
+                    }
+                }
+                None => quote!(::der::asn1::AnyRef<'a>),
+            },
+            Type::SequenceOf { element, .. } | Type::SetOf { element, .. } => {
+                if matches!(**element, Type::Tagged { .. }) {
+                    return Err("tagged elements are not supported".into());
+                }
+
+                let item = Ident::new(&format!("{}Item", context), Span::call_site());
+                let element = self.rust_type(m, element, &item, items)?;
+                match ty {
+                    Type::SequenceOf { .. } => quote!(Vec<#element>),
+                    _ => quote!(::der::asn1::SetOfVec<#element>),
+                }
+            }
+            Type::Tagged { .. } => return Err("tags are only supported on components".into()),
+            _ => {
+                let doc = format!("`{}` of `{}`.", context, self.modules[m].name);
+                self.named_type(m, context, &doc, ty, items)?;
+                let lifetime = self.lifetime(m, ty);
+                quote!(#context #lifetime)
+            }
+        };
+
+        Ok(rust)
+    }
+}
+
+/// The Rust type of a character string type, if `der` supports it.
+fn string_type(name: &str) -> Option<TokenStream> {
+    let rust = match name {
+        "BMPString" => quote!(::der::asn1::BmpStringRef<'a>),
+        "IA5String" => quote!(::der::asn1::Ia5StringRef<'a>),
+        "PrintableString" => quote!(::der::asn1::PrintableStringRef<'a>),
+        "T61String" | "TeletexString" => quote!(::der::asn1::TeletexStringRef<'a>),
+        "UniversalString" => quote!(::der::asn1::UniversalStringRef<'a>),
+        "UTF8String" => quote!(::der::asn1::Utf8StringRef<'a>),
+        _ => return None,
+    };
+
+    Some(rust)
+}
+
+/// Document the constraints of a type.
+fn constraint_doc(ty: &Type) -> String {
+    let (keyword, range) = match ty {
+        Type::BitString { size, .. }
+        | Type::OctetString { size }
+        | Type::String { size, .. }
+        | Type::SequenceOf { size, .. }
+        | Type::SetOf { size, .. } => ("SIZE ", size),
+        Type::Integer { range, .. } => ("", range),
+        Type::Tagged { inner, .. } => return constraint_doc(inner),
+        _ => return String::new(),
+    };
+
+    let bound = |bound: &Bound| match bound {
+        Bound::Number(n) => n.to_string(),
+        Bound::Value(name) => name.clone(),
+        Bound::Max => "MAX".into(),
+    };
+
+    match range {
+        Some(range) if range.lower == range.upper => {
+            format!(" ({}({}))", keyword, bound(&range.lower))
+        }
+        Some(range) => format!(
+            " ({}({}..{}))",
+            keyword,
+            bound(&range.lower),
+            bound(&range.upper)
+        ),
+        None => String::new(),
+    }
+}
+
+/// The minimal two's complement encoding of an `INTEGER`.
+fn int_bytes(value: i128) -> Vec<u8> {
+    let bytes = value.to_be_bytes();
+    let mut start = 0;
+
+    while start < bytes.len() - 1 {
+        let (byte, next) = (bytes[start], bytes[start + 1]);
+        match (byte, next & 0x80) {
+            (0x00, 0) | (0xFF, 0x80) => start += 1,
+            _ => break,
+        }
+    }
+
+    bytes[start..].to_vec()
+}
+
+#[test]
+fn test() {
+    let modules = crate::parse(
+        r"
+        Example DEFINITIONS IMPLICIT TAGS ::= BEGIN
+
+        id-example OBJECT IDENTIFIER ::= { iso(1) 2 }
+        id-example-foo OBJECT IDENTIFIER ::= { id-example 3 }
+
+        Example ::= SEQUENCE {
+            version     [0] EXPLICIT Version DEFAULT v1,
+            critical    BOOLEAN DEFAULT FALSE,
+            names       [1] SEQUENCE SIZE (1..MAX) OF UTF8String OPTIONAL }
+
+        Version ::= INTEGER { v1(0), v2(1) }
+
+        Broken ::= [APPLICATION 1] INTEGER
+
+        UsesBroken ::= CHOICE { a Broken, b UTF8String }
+
+        END
+        ",
+    );
+
+    let generator = Generator::new(&modules);
+    assert_eq!(
+        generator.warnings(),
+        [
+            "Example: skipped `Broken`: APPLICATION tags are not supported",
+            "Example: skipped `UsesBroken`: `a` has no tag of its own and is not supported",
+        ]
+    );
+
+    let code = generator.module().to_string();
+    for expected in [
+        r#"pub const ID_EXAMPLE_FOO : :: der :: asn1 :: ObjectIdentifier = :: der :: asn1 :: ObjectIdentifier :: new_unwrap ("1.2.3")"#,
+        r#"pub struct Example < 'a >"#,
+        r#"# [asn1 (context_specific = "0" , default = "example_version_default")] pub version : Version"#,
+        r#"# [asn1 (default = "Default :: default")] pub critical : bool"#,
+        r#"# [asn1 (context_specific = "1" , tag_mode = "IMPLICIT" , constructed = "true" , optional = "true")] pub names : Option < Vec < :: der :: asn1 :: Utf8StringRef < 'a > > >"#,
+        r#"fn example_version_default () -> Version { Version :: V1 }"#,
+        r#"# [asn1 (type = "INTEGER")] # [repr (u32)] pub enum Version"#,
+    ] {
+        assert!(code.contains(expected), "{}\n\n{}", expected, code);
+    }
+
+    assert_eq!(int_bytes(0), [0]);
+    assert_eq!(int_bytes(128), [0, 128]);
+    assert_eq!(int_bytes(-129), [0xFF, 0x7F]);
+}
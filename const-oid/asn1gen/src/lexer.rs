@@ -0,0 +1,183 @@
+/// A lexical item of ASN.1 notation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Token {
+    /// Keywords, references and identifiers, e.g. `SEQUENCE` or `id-ce`.
+    Word(String),
+    Number(u64),
+    /// Quoted strings, including bit and hex strings.
+    Str,
+    /// `::=`
+    Assign,
+    /// `..`
+    Range,
+    /// `...`
+    Ellipsis,
+    Punct(char),
+}
+
+impl Token {
+    pub fn is_word(&self, word: &str) -> bool {
+        matches!(self, Token::Word(w) if w == word)
+    }
+}
+
+/// Split ASN.1 notation into tokens, dropping comments.
+pub fn lex(text: &str) -> Vec<Token> {
+    let chars = text.chars().collect::<Vec<_>>();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+
+        match c {
+            // A comment runs until the end of the line or the next `--`.
+            '-' if next == Some('-') => {
+                i += 2;
+                while i < chars.len() && chars[i] != '\n' {
+                    if chars[i] == '-' && chars.get(i + 1) == Some(&'-') {
+                        i += 1;
+                        break;
+                    }
+                    i += 1;
+                }
+                i += 1;
+            }
+
+            // Block comments nest.
+            '/' if next == Some('*') => {
+                let mut depth = 0;
+                while i < chars.len() {
+                    match (chars[i], chars.get(i + 1)) {
+                        ('/', Some('*')) => {
+                            depth += 1;
+                            i += 2;
+                        }
+                        ('*', Some('/')) => {
+                            depth -= 1;
+                            i += 2;
+                            if depth == 0 {
+                                break;
+                            }
+                        }
+                        _ => i += 1,
+                    }
+                }
+            }
+
+            '"' | '\'' => {
+                i += 1;
+                while i < chars.len() && chars[i] != c {
+                    i += 1;
+                }
+                i += 1;
+
+                // The `B` or `H` suffix of bit and hex strings
+                if c == '\'' && matches!(chars.get(i), Some('B' | 'H')) {
+                    i += 1;
+                }
+                tokens.push(Token::Str);
+            }
+
+            ':' if chars[i..].starts_with(&[':', ':', '=']) => {
+                tokens.push(Token::Assign);
+                i += 3;
+            }
+
+            '.' if chars[i..].starts_with(&['.', '.', '.']) => {
+                tokens.push(Token::Ellipsis);
+                i += 3;
+            }
+
+            '.' if next == Some('.') => {
+                tokens.push(Token::Range);
+                i += 2;
+            }
+
+            c if c.is_ascii_digit() => {
+                let start = i;
+                while i < chars.len() && chars[i].is_ascii_digit() {
+                    i += 1;
+                }
+
+                let digits = chars[start..i].iter().collect::<String>();
+                match digits.parse() {
+                    Ok(n) => tokens.push(Token::Number(n)),
+                    Err(_) => tokens.push(Token::Str),
+                }
+            }
+
+            // Words may contain single hyphens, but not end with one.
+            c if c.is_ascii_alphabetic() || c == '&' => {
+                let start = i;
+                i += 1;
+                while i < chars.len() {
+                    match chars[i] {
+                        c if c.is_ascii_alphanumeric() => i += 1,
+                        '-' if matches!(chars.get(i + 1), Some(c) if c.is_ascii_alphanumeric()) => {
+                            i += 1
+                        }
+                        _ => break,
+                    }
+                }
+
+                tokens.push(Token::Word(chars[start..i].iter().collect()));
+            }
+
+            c if c.is_whitespace() => i += 1,
+
+            c => {
+                tokens.push(Token::Punct(c));
+                i += 1;
+            }
+        }
+    }
+
+    tokens
+}
+
+#[test]
+fn test() {
+    let tokens = lex(r"
+        id-ce OBJECT IDENTIFIER ::= { joint-iso-ccitt(2) ds(5) 29 } -- comment
+        Foo ::= SEQUENCE SIZE (1..MAX) OF Bar -- comment -- , ...
+        /* block /* nested */ comment */ '0101'B
+    ");
+
+    let word = |w: &str| Token::Word(w.into());
+    assert_eq!(
+        tokens,
+        vec![
+            word("id-ce"),
+            word("OBJECT"),
+            word("IDENTIFIER"),
+            Token::Assign,
+            Token::Punct('{'),
+            word("joint-iso-ccitt"),
+            Token::Punct('('),
+            Token::Number(2),
+            Token::Punct(')'),
+            word("ds"),
+            Token::Punct('('),
+            Token::Number(5),
+            Token::Punct(')'),
+            Token::Number(29),
+            Token::Punct('}'),
+            word("Foo"),
+            Token::Assign,
+            word("SEQUENCE"),
+            word("SIZE"),
+            Token::Punct('('),
+            Token::Number(1),
+            Token::Range,
+            word("MAX"),
+            Token::Punct(')'),
+            word("OF"),
+            word("Bar"),
+            Token::Punct(','),
+            Token::Ellipsis,
+            Token::Str,
+        ]
+    );
+}
@@ -0,0 +1,9 @@
+mod ast;
+mod gen;
+mod lexer;
+mod naming;
+mod parser;
+
+pub use ast::Module;
+pub use gen::Generator;
+pub use parser::parse;
@@ -0,0 +1,27 @@
+use asn1gen::{parse, Generator};
+use std::{env, fs, process};
+
+fn main() {
+    let paths = env::args().skip(1).collect::<Vec<_>>();
+    if paths.is_empty() {
+        eprintln!("usage: asn1gen <file>...");
+        process::exit(2);
+    }
+
+    let mut modules = Vec::new();
+    for path in &paths {
+        let text = fs::read_to_string(path).unwrap_or_else(|e| {
+            eprintln!("{}: {}", path, e);
+            process::exit(1);
+        });
+
+        modules.extend(parse(&text));
+    }
+
+    let generator = Generator::new(&modules);
+    for warning in generator.warnings() {
+        eprintln!("warning: {}", warning);
+    }
+
+    println!("{}", generator.module());
+}
@@ -0,0 +1,107 @@
+use proc_macro2::{Ident, Span};
+
+/// Split an ASN.1 name into its words.
+///
+/// Acronyms are kept together, including when preceded by a single lowercase
+/// letter: `dNSName` is split into `dNS` and `Name`.
+fn words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+
+    for part in name.split('-').filter(|p| !p.is_empty()) {
+        let chars = part.chars().collect::<Vec<_>>();
+        let mut word = String::new();
+
+        for (i, &c) in chars.iter().enumerate() {
+            if i > 0 && c.is_ascii_uppercase() {
+                let prev = chars[i - 1];
+                let next = chars.get(i + 1).copied();
+                let lone_prefix = i == 1 && prev.is_ascii_lowercase();
+
+                let boundary = !lone_prefix
+                    && (prev.is_ascii_lowercase()
+                        || prev.is_ascii_digit()
+                        || (prev.is_ascii_uppercase()
+                            && matches!(next, Some(n) if n.is_ascii_lowercase())));
+
+                if boundary {
+                    words.push(std::mem::take(&mut word));
+                }
+            }
+
+            word.push(c);
+        }
+
+        words.push(word);
+    }
+
+    words
+}
+
+/// Make an identifier, escaping keywords.
+fn ident(name: &str) -> Ident {
+    const KEYWORDS: &[&str] = &[
+        "as", "async", "await", "box", "break", "const", "continue", "crate", "dyn", "else",
+        "enum", "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod",
+        "move", "mut", "pub", "ref", "return", "self", "static", "struct", "super", "trait",
+        "true", "try", "type", "unsafe", "use", "where", "while", "yield",
+    ];
+
+    match KEYWORDS.contains(&name) {
+        true => Ident::new_raw(name, Span::call_site()),
+        false => Ident::new(name, Span::call_site()),
+    }
+}
+
+/// `UpperCamelCase` name of a type or variant.
+pub fn type_name(name: &str) -> Ident {
+    let name = words(name)
+        .iter()
+        .map(|w| {
+            let mut chars = w.chars();
+            let first = chars.next().map(|c| c.to_ascii_uppercase());
+            first
+                .into_iter()
+                .chain(chars.map(|c| c.to_ascii_lowercase()))
+                .collect::<String>()
+        })
+        .collect::<String>();
+
+    ident(&name)
+}
+
+/// `snake_case` name of a field or module.
+pub fn snake_name(name: &str) -> String {
+    words(name)
+        .iter()
+        .map(|w| w.to_ascii_lowercase())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// `snake_case` identifier of a field or module.
+pub fn field_name(name: &str) -> Ident {
+    ident(&snake_name(name))
+}
+
+/// `UPPER_SNAKE_CASE` name of a constant.
+pub fn const_name(name: &str) -> Ident {
+    ident(&snake_name(name).to_ascii_uppercase())
+}
+
+#[test]
+fn test() {
+    assert_eq!(field_name("tbsCertificate").to_string(), "tbs_certificate");
+    assert_eq!(field_name("dNSName").to_string(), "dns_name");
+    assert_eq!(field_name("iPAddress").to_string(), "ip_address");
+    assert_eq!(field_name("rfc822Name").to_string(), "rfc822_name");
+    assert_eq!(field_name("type").to_string(), "r#type");
+    assert_eq!(type_name("TBSCertificate").to_string(), "TbsCertificate");
+    assert_eq!(type_name("eDIPartyName").to_string(), "EdiPartyName");
+    assert_eq!(type_name("CRLReason").to_string(), "CrlReason");
+    assert_eq!(type_name("X520name").to_string(), "X520name");
+    assert_eq!(
+        const_name("id-pe-authorityInfoAccess").to_string(),
+        "ID_PE_AUTHORITY_INFO_ACCESS"
+    );
+    assert_eq!(snake_name("PKIX1Explicit88"), "pkix1_explicit88");
+}
@@ -0,0 +1,706 @@
+use crate::{
+    ast::{Arc, Assignment, Bound, Component, Module, Range, TagDefault, TagMode, Type, Value},
+    lexer::{lex, Token},
+};
+
+type Result<T> = std::result::Result<T, String>;
+
+/// Character string types, which all map onto the same [`Type`].
+const STRING_TYPES: &[&str] = &[
+    "BMPString",
+    "GeneralString",
+    "GraphicString",
+    "IA5String",
+    "ISO646String",
+    "NumericString",
+    "PrintableString",
+    "T61String",
+    "TeletexString",
+    "UniversalString",
+    "UTF8String",
+    "VideotexString",
+    "VisibleString",
+];
+
+/// Parse all of the ASN.1 modules found in `text`.
+///
+/// The text may be a plain ASN.1 file or an RFC, whose prose and page breaks
+/// are skipped.
+pub fn parse(text: &str) -> Vec<Module> {
+    // Drop the page breaks of RFCs, which may fall in the middle of modules.
+    let lines = text
+        .lines()
+        .filter(|l| !l.contains('\u{c}'))
+        .filter(|l| !(l.starts_with("RFC ") || l.trim_end().ends_with(']') && l.contains("[Page ")))
+        .collect::<Vec<_>>();
+
+    let mut modules = Vec::new();
+    let mut i = 0;
+
+    while i < lines.len() {
+        let is_header = lex(lines[i]).iter().any(|t| t.is_word("DEFINITIONS"));
+        let end = lines[i..].iter().position(|l| l.trim() == "END");
+
+        match end {
+            Some(end) if is_header => {
+                // The name of the module and its OID may precede `DEFINITIONS`.
+                let start = (i.saturating_sub(6)..=i)
+                    .rev()
+                    .find(|&j| is_module_reference(lines[j]))
+                    .unwrap_or(i);
+
+                let tokens = lex(&lines[start..=i + end].join("\n"));
+                if let Some(module) = Parser::new(&tokens).module() {
+                    modules.push(module);
+                }
+
+                i += end + 1;
+            }
+            _ => i += 1,
+        }
+    }
+
+    modules
+}
+
+/// Recursive descent parser over the tokens of a module.
+struct Parser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(tokens: &'a [Token]) -> Self {
+        Self { tokens, pos: 0 }
+    }
+
+    fn peek(&self) -> Option<&'a Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn peek_punct(&self, c: char) -> bool {
+        self.peek() == Some(&Token::Punct(c))
+    }
+
+    fn next(&mut self) -> Option<&'a Token> {
+        let token = self.tokens.get(self.pos);
+        self.pos += 1;
+        token
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        match self.peek() == Some(token) {
+            true => {
+                self.pos += 1;
+                true
+            }
+            false => false,
+        }
+    }
+
+    fn eat_word(&mut self, word: &str) -> bool {
+        self.eat(&Token::Word(word.into()))
+    }
+
+    fn eat_punct(&mut self, c: char) -> bool {
+        self.eat(&Token::Punct(c))
+    }
+
+    fn expect(&mut self, token: &Token) -> Result<()> {
+        match self.eat(token) {
+            true => Ok(()),
+            false => Err(format!("expected {:?}, found {:?}", token, self.peek())),
+        }
+    }
+
+    fn word(&mut self) -> Result<&'a str> {
+        match self.next() {
+            Some(Token::Word(w)) => Ok(w),
+            t => Err(format!("expected a name, found {:?}", t)),
+        }
+    }
+
+    fn number(&mut self) -> Result<i128> {
+        let negative = self.eat_punct('-');
+        match self.next() {
+            Some(Token::Number(n)) if negative => Ok(-i128::from(*n)),
+            Some(Token::Number(n)) => Ok(i128::from(*n)),
+            t => Err(format!("expected a number, found {:?}", t)),
+        }
+    }
+
+    /// Skip a bracketed group of tokens, e.g. `{ ... }`.
+    fn skip_group(&mut self) -> Result<()> {
+        let mut depth = 0;
+        loop {
+            match self.next() {
+                Some(Token::Punct('{' | '(' | '[')) => depth += 1,
+                Some(Token::Punct('}' | ')' | ']')) => depth -= 1,
+                Some(_) => (),
+                None => return Err("unbalanced brackets".into()),
+            }
+
+            if depth == 0 {
+                return Ok(());
+            }
+        }
+    }
+
+    fn module(&mut self) -> Option<Module> {
+        let name = self.word().ok()?.to_string();
+        if self.peek_punct('{') {
+            self.skip_group().ok()?;
+        }
+
+        if !self.eat_word("DEFINITIONS") {
+            return None;
+        }
+
+        let mut tag_default = TagDefault::Explicit;
+        while !self.eat(&Token::Assign) {
+            match self.next()? {
+                Token::Word(w) if w == "IMPLICIT" => tag_default = TagDefault::Implicit,
+                Token::Word(w) if w == "AUTOMATIC" => tag_default = TagDefault::Automatic,
+                _ => (),
+            }
+        }
+
+        if !self.eat_word("BEGIN") {
+            return None;
+        }
+
+        let end = self.tokens.iter().rposition(|t| t.is_word("END"))?;
+        let body = &self.tokens[self.pos..end];
+        let assignments = split_assignments(body)
+            .into_iter()
+            .filter_map(|tokens| Parser::new(tokens).assignment())
+            .collect();
+
+        Some(Module {
+            name,
+            tag_default,
+            assignments,
+        })
+    }
+
+    fn assignment(&mut self) -> Option<Assignment> {
+        let name = self.word().ok()?.to_string();
+        let unsupported = |reason: String| Some(Assignment::Unsupported(name.clone(), reason));
+
+        if self.eat(&Token::Assign) {
+            return match self.ty().and_then(|ty| self.end().map(|_| ty)) {
+                Ok(ty) => Some(Assignment::Type(name, ty)),
+                Err(reason) => unsupported(reason),
+            };
+        }
+
+        if self.eat_word("OBJECT") && self.eat_word("IDENTIFIER") && self.eat(&Token::Assign) {
+            return match self.arcs().and_then(|arcs| self.end().map(|_| arcs)) {
+                Ok(arcs) => Some(Assignment::ObjectIdentifier(name, arcs)),
+                Err(reason) => unsupported(reason),
+            };
+        }
+
+        if self.eat_word("INTEGER") && self.eat(&Token::Assign) {
+            return match self.number().and_then(|n| self.end().map(|_| n)) {
+                Ok(n) => Some(Assignment::Integer(name, n)),
+                Err(reason) => unsupported(reason),
+            };
+        }
+
+        // Values of other types, e.g. `id-at-name AttributeType ::= { id-at 41 }`
+        self.pos = 1;
+        if let (Ok(ty), true) = (self.word(), self.eat(&Token::Assign)) {
+            if let Ok(arcs) = self.arcs().and_then(|arcs| self.end().map(|_| arcs)) {
+                return Some(Assignment::Value(name, ty.into(), arcs));
+            }
+        }
+
+        unsupported("only type, OBJECT IDENTIFIER and INTEGER assignments are supported".into())
+    }
+
+    /// Ensure that all of the tokens of an assignment were parsed.
+    fn end(&self) -> Result<()> {
+        match self.peek() {
+            None => Ok(()),
+            Some(t) => Err(format!("unexpected {:?}", t)),
+        }
+    }
+
+    fn ty(&mut self) -> Result<Type> {
+        if self.eat_punct('[') {
+            if let Some(Token::Word(class)) = self.peek() {
+                return Err(format!("{} tags are not supported", class));
+            }
+
+            let number = u32::try_from(self.number()?).map_err(|e| e.to_string())?;
+            self.expect(&Token::Punct(']'))?;
+
+            let mode = match () {
+                _ if self.eat_word("IMPLICIT") => Some(TagMode::Implicit),
+                _ if self.eat_word("EXPLICIT") => Some(TagMode::Explicit),
+                _ => None,
+            };
+
+            let inner = Box::new(self.ty()?);
+            return Ok(Type::Tagged {
+                number,
+                mode,
+                inner,
+            });
+        }
+
+        let word = self.word()?;
+        let mut ty = match word {
+            "BOOLEAN" => Type::Boolean,
+            "NULL" => Type::Null,
+            "INTEGER" => Type::Integer {
+                named: match self.peek_punct('{') {
+                    true => self.named_numbers()?,
+                    false => Vec::new(),
+                },
+                range: None,
+            },
+            "ENUMERATED" => Type::Enumerated(self.named_numbers()?),
+            "BIT" => {
+                self.expect(&Token::Word("STRING".into()))?;
+                Type::BitString {
+                    named: match self.peek_punct('{') {
+                        true => self.named_numbers()?,
+                        false => Vec::new(),
+                    },
+                    size: None,
+                }
+            }
+            "OCTET" => {
+                self.expect(&Token::Word("STRING".into()))?;
+                Type::OctetString { size: None }
+            }
+            "OBJECT" => {
+                self.expect(&Token::Word("IDENTIFIER".into()))?;
+                Type::ObjectIdentifier
+            }
+            "SEQUENCE" | "SET" if self.peek_punct('{') => {
+                let components = self.components()?;
+                match word {
+                    "SEQUENCE" => Type::Sequence(components),
+                    _ => return Err("SET is not supported".into()),
+                }
+            }
+            "SEQUENCE" | "SET" => {
+                let size = match () {
+                    _ if self.eat_word("SIZE") => Some(self.range()?),
+                    _ if self.peek_punct('(') => self.constraint()?.and_then(Constraint::size),
+                    _ => None,
+                };
+
+                self.expect(&Token::Word("OF".into()))?;
+
+                // `SEQUENCE OF name Type`
+                if let (Some(Token::Word(w)), Some(Token::Word(_) | Token::Punct('['))) =
+                    (self.peek(), self.tokens.get(self.pos + 1))
+                {
+                    if w.starts_with(|c: char| c.is_ascii_lowercase()) {
+                        self.pos += 1;
+                    }
+                }
+
+                let element = Box::new(self.ty()?);
+                match word {
+                    "SEQUENCE" => Type::SequenceOf { element, size },
+                    _ => Type::SetOf { element, size },
+                }
+            }
+            "CHOICE" => Type::Choice(self.components()?),
+            "ANY" => {
+                if self.eat_word("DEFINED") {
+                    self.expect(&Token::Word("BY".into()))?;
+                    self.word()?;
+                }
+                Type::Any
+            }
+            "UTCTime" => Type::UtcTime,
+            "GeneralizedTime" => Type::GeneralizedTime,
+            s if STRING_TYPES.contains(&s) => Type::String {
+                name: s.into(),
+                size: None,
+            },
+            s if s.starts_with(|c: char| c.is_ascii_uppercase()) && has_lowercase(s) => {
+                match self.peek() {
+                    Some(Token::Punct('.')) => return Err("class fields are not supported".into()),
+                    Some(Token::Punct('{')) => {
+                        return Err("parameterized types are not supported".into())
+                    }
+                    _ => Type::Reference(s.into()),
+                }
+            }
+            s => return Err(format!("`{}` is not supported", s)),
+        };
+
+        while self.peek_punct('(') {
+            match (self.constraint()?, &mut ty) {
+                (
+                    Some(Constraint::Size(range)),
+                    Type::BitString { size, .. }
+                    | Type::OctetString { size }
+                    | Type::String { size, .. }
+                    | Type::SequenceOf { size, .. }
+                    | Type::SetOf { size, .. },
+                ) => *size = Some(range),
+                (Some(Constraint::Value(r)), Type::Integer { range, .. }) => *range = Some(r),
+                _ => (),
+            }
+        }
+
+        Ok(ty)
+    }
+
+    /// Parse a constraint, returning `None` for unsupported ones, which are
+    /// skipped.
+    fn constraint(&mut self) -> Result<Option<Constraint>> {
+        let start = self.pos;
+        self.expect(&Token::Punct('('))?;
+
+        let constraint = match self.eat_word("SIZE") {
+            true => self.range().map(Constraint::Size),
+            false => self.range_bounds().map(Constraint::Value),
+        };
+
+        match constraint {
+            Ok(constraint) if self.eat_punct(')') => Ok(Some(constraint)),
+            _ => {
+                self.pos = start;
+                self.skip_group()?;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Parse a parenthesized range, e.g. `(1..MAX)`.
+    fn range(&mut self) -> Result<Range> {
+        self.expect(&Token::Punct('('))?;
+        let range = self.range_bounds()?;
+        self.expect(&Token::Punct(')'))?;
+        Ok(range)
+    }
+
+    fn range_bounds(&mut self) -> Result<Range> {
+        let lower = self.bound()?;
+        let upper = match self.eat(&Token::Range) {
+            true => self.bound()?,
+            false => lower.clone(),
+        };
+
+        Ok(Range { lower, upper })
+    }
+
+    fn bound(&mut self) -> Result<Bound> {
+        match self.peek() {
+            Some(Token::Word(w)) if w == "MAX" => {
+                self.pos += 1;
+                Ok(Bound::Max)
+            }
+            Some(Token::Word(w)) if w.starts_with(|c: char| c.is_ascii_lowercase()) => {
+                self.pos += 1;
+                Ok(Bound::Value(w.clone()))
+            }
+            _ => self.number().map(Bound::Number),
+        }
+    }
+
+    /// Parse the named numbers of an `INTEGER`, `ENUMERATED` or `BIT STRING`.
+    ///
+    /// Numbers are assigned to enumerations without them in order.
+    fn named_numbers(&mut self) -> Result<Vec<(String, i128)>> {
+        self.expect(&Token::Punct('{'))?;
+        let mut named = Vec::<(String, i128)>::new();
+
+        loop {
+            if self.eat(&Token::Ellipsis) {
+                self.eat_punct(',');
+                continue;
+            }
+
+            let name = self.word()?.to_string();
+            let number = match self.eat_punct('(') {
+                true => {
+                    let n = self.number()?;
+                    self.expect(&Token::Punct(')'))?;
+                    n
+                }
+                false => (0..)
+                    .find(|n| named.iter().all(|(_, m)| m != n))
+                    .unwrap_or_default(),
+            };
+            named.push((name, number));
+
+            if self.eat_punct('}') {
+                return Ok(named);
+            }
+            self.expect(&Token::Punct(','))?;
+        }
+    }
+
+    /// Parse the components of a `SEQUENCE` or alternatives of a `CHOICE`.
+    fn components(&mut self) -> Result<Vec<Component>> {
+        self.expect(&Token::Punct('{'))?;
+        let mut components = Vec::new();
+
+        loop {
+            if self.eat_punct('}') {
+                return Ok(components);
+            }
+
+            // Extension markers are ignored, but not extension groups.
+            if self.eat(&Token::Ellipsis) {
+                self.eat_punct(',');
+                continue;
+            }
+
+            if self.peek_punct('[') && self.tokens.get(self.pos + 1) == Some(&Token::Punct('[')) {
+                return Err("extension addition groups are not supported".into());
+            }
+
+            let name = self.word()?.to_string();
+            if name == "COMPONENTS" {
+                return Err("COMPONENTS OF is not supported".into());
+            }
+
+            let ty = self.ty()?;
+            let optional = self.eat_word("OPTIONAL");
+            let default = match self.eat_word("DEFAULT") {
+                true => Some(self.value()?),
+                false => None,
+            };
+
+            components.push(Component {
+                name,
+                ty,
+                optional,
+                default,
+            });
+
+            if !self.peek_punct('}') {
+                self.expect(&Token::Punct(','))?;
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<Value> {
+        match self.peek() {
+            Some(Token::Word(w)) if w == "TRUE" || w == "FALSE" => {
+                self.pos += 1;
+                Ok(Value::Boolean(w == "TRUE"))
+            }
+            Some(Token::Word(w)) => {
+                self.pos += 1;
+                Ok(Value::Named(w.clone()))
+            }
+            Some(Token::Punct('{')) => self.skip_group().map(|_| Value::Other),
+            Some(Token::Str) => {
+                self.pos += 1;
+                Ok(Value::Other)
+            }
+            _ => self.number().map(Value::Number),
+        }
+    }
+
+    /// Parse the arcs of an `OBJECT IDENTIFIER` value.
+    fn arcs(&mut self) -> Result<Vec<Arc>> {
+        self.expect(&Token::Punct('{'))?;
+        let mut arcs = Vec::new();
+
+        while !self.eat_punct('}') {
+            match self.next() {
+                Some(Token::Number(n)) => arcs.push(Arc::Number(*n)),
+                Some(Token::Word(w)) => match self.eat_punct('(') {
+                    true => match self.next() {
+                        Some(Token::Number(n)) => {
+                            arcs.push(Arc::Number(*n));
+                            self.expect(&Token::Punct(')'))?;
+                        }
+                        t => return Err(format!("expected a number, found {:?}", t)),
+                    },
+                    false => arcs.push(Arc::Named(w.clone())),
+                },
+                t => return Err(format!("unexpected {:?} in OBJECT IDENTIFIER", t)),
+            }
+        }
+
+        Ok(arcs)
+    }
+}
+
+/// A supported constraint.
+enum Constraint {
+    Size(Range),
+    Value(Range),
+}
+
+impl Constraint {
+    fn size(self) -> Option<Range> {
+        match self {
+            Constraint::Size(range) => Some(range),
+            Constraint::Value(_) => None,
+        }
+    }
+}
+
+/// Split the body of a module into its assignments.
+///
+/// An assignment starts with `name ::=`, `name Type ::=` or `name { ... } ::=`
+/// outside of any brackets; `EXPORTS` and `IMPORTS` are skipped.
+fn split_assignments(body: &[Token]) -> Vec<&[Token]> {
+    let mut starts = Vec::new();
+    let mut depth = 0i32;
+    let mut i = 0;
+
+    while i < body.len() {
+        match &body[i] {
+            Token::Word(w) if depth == 0 && (w == "EXPORTS" || w == "IMPORTS") => {
+                match body[i..].iter().position(|t| *t == Token::Punct(';')) {
+                    Some(end) => i += end + 1,
+                    None => break,
+                }
+                continue;
+            }
+            Token::Word(w) if depth == 0 => {
+                let header = match &body[i + 1..] {
+                    [Token::Assign, ..] => Some(1),
+                    // Values and objects of classes, but not `Type Next ::=`
+                    [Token::Word(ty), Token::Assign, ..]
+                        if w.starts_with(|c: char| c.is_ascii_lowercase())
+                            || (has_lowercase(w) && !has_lowercase(ty)) =>
+                    {
+                        Some(2)
+                    }
+                    [Token::Word(o), Token::Word(i), Token::Assign, ..]
+                        if o == "OBJECT" && i == "IDENTIFIER" =>
+                    {
+                        Some(3)
+                    }
+                    [Token::Punct('{'), rest @ ..] => rest
+                        .iter()
+                        .position(|t| *t == Token::Punct('}'))
+                        .filter(|&close| rest.get(close + 1) == Some(&Token::Assign))
+                        .map(|close| close + 3),
+                    _ => None,
+                };
+
+                if let Some(len) = header {
+                    starts.push(i);
+                    i += len + 1;
+                    continue;
+                }
+            }
+            Token::Punct('{' | '(' | '[') => depth += 1,
+            Token::Punct('}' | ')' | ']') => depth -= 1,
+            _ => (),
+        }
+
+        i += 1;
+    }
+
+    starts
+        .iter()
+        .zip(starts.iter().skip(1).chain(Some(&body.len())))
+        .map(|(&start, &end)| &body[start..end])
+        .collect()
+}
+
+/// Does the line start with the name of a module?
+fn is_module_reference(line: &str) -> bool {
+    let tokens = lex(line);
+    match tokens.first() {
+        Some(Token::Word(name)) if name.starts_with(|c: char| c.is_ascii_uppercase()) => {
+            match tokens.get(1) {
+                None | Some(Token::Punct('{')) => true,
+                Some(token) => token.is_word("DEFINITIONS"),
+            }
+        }
+        _ => false,
+    }
+}
+
+fn has_lowercase(word: &str) -> bool {
+    word.chars().any(|c| c.is_ascii_lowercase())
+}
+
+#[test]
+fn test() {
+    let modules = parse(
+        r"
+        Example { iso(1) 2 }
+
+        DEFINITIONS IMPLICIT TAGS ::=
+
+        BEGIN
+
+        IMPORTS Name FROM Other { 1 2 3 } ;
+
+        ub-name INTEGER ::= 64
+        id-example OBJECT IDENTIFIER ::= { iso(1) member-body(2) 3 }
+
+        Example ::= SEQUENCE {
+            version     [0] EXPLICIT INTEGER { v1(0), v2(1) } DEFAULT v1,
+            names       SEQUENCE SIZE (1..MAX) OF Name,
+            label       UTF8String (SIZE (1..ub-name)) OPTIONAL,
+            ... }
+
+        Reason ::= ENUMERATED { good, bad(2), ugly }
+
+        Class ::= CLASS { &id OBJECT IDENTIFIER UNIQUE }
+
+        END
+        ",
+    );
+
+    assert_eq!(modules.len(), 1);
+    let module = &modules[0];
+    assert_eq!(module.name, "Example");
+    assert_eq!(module.tag_default, TagDefault::Implicit);
+    assert_eq!(
+        module.assignments[..2],
+        [
+            Assignment::Integer("ub-name".into(), 64),
+            Assignment::ObjectIdentifier(
+                "id-example".into(),
+                vec![Arc::Number(1), Arc::Number(2), Arc::Number(3)]
+            ),
+        ]
+    );
+
+    let components = match &module.assignments[2] {
+        Assignment::Type(name, Type::Sequence(components)) if name == "Example" => components,
+        a => panic!("unexpected assignment: {:?}", a),
+    };
+    assert_eq!(components.len(), 3);
+    assert_eq!(components[0].default, Some(Value::Named("v1".into())));
+    assert_eq!(
+        components[1].ty,
+        Type::SequenceOf {
+            element: Box::new(Type::Reference("Name".into())),
+            size: Some(Range {
+                lower: Bound::Number(1),
+                upper: Bound::Max
+            }),
+        }
+    );
+    assert!(components[2].optional);
+
+    assert_eq!(
+        module.assignments[3],
+        Assignment::Type(
+            "Reason".into(),
+            Type::Enumerated(vec![
+                ("good".into(), 0),
+                ("bad".into(), 2),
+                ("ugly".into(), 1)
+            ])
+        )
+    );
+    assert!(matches!(
+        &module.assignments[4],
+        Assignment::Unsupported(name, _) if name == "Class"
+    ));
+}
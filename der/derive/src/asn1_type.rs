@@ -11,6 +11,9 @@ pub(crate) enum Asn1Type {
     /// ASN.1 `BIT STRING`.
     BitString,
 
+    /// ASN.1 `BMPString`.
+    BmpString,
+
     /// ASN.1 `IA5String`.
     Ia5String,
 
@@ -23,6 +26,12 @@ pub(crate) enum Asn1Type {
     /// ASN.1 `PrintableString`.
     PrintableString,
 
+    /// ASN.1 `TeletexString`.
+    TeletexString,
+
+    /// ASN.1 `UniversalString`.
+    UniversalString,
+
     /// ASN.1 `UTCTime`.
     UtcTime,
 
@@ -35,10 +44,13 @@ impl Asn1Type {
     pub fn tag(self) -> TokenStream {
         match self {
             Asn1Type::BitString => quote!(::der::Tag::BitString),
+            Asn1Type::BmpString => quote!(::der::Tag::BmpString),
             Asn1Type::Ia5String => quote!(::der::Tag::Ia5String),
             Asn1Type::GeneralizedTime => quote!(::der::Tag::GeneralizedTime),
             Asn1Type::OctetString => quote!(::der::Tag::OctetString),
             Asn1Type::PrintableString => quote!(::der::Tag::PrintableString),
+            Asn1Type::TeletexString => quote!(::der::Tag::TeletexString),
+            Asn1Type::UniversalString => quote!(::der::Tag::UniversalString),
             Asn1Type::UtcTime => quote!(::der::Tag::UtcTime),
             Asn1Type::Utf8String => quote!(::der::Tag::Utf8String),
         }
@@ -48,10 +60,15 @@ impl Asn1Type {
     pub fn decoder(self) -> TokenStream {
         match self {
             Asn1Type::BitString => quote!(::der::asn1::BitStringRef::decode(reader)?),
+            Asn1Type::BmpString => quote!(::der::asn1::BmpStringRef::decode(reader)?),
             Asn1Type::Ia5String => quote!(::der::asn1::Ia5StringRef::decode(reader)?),
             Asn1Type::GeneralizedTime => quote!(::der::asn1::GeneralizedTime::decode(reader)?),
             Asn1Type::OctetString => quote!(::der::asn1::OctetStringRef::decode(reader)?),
             Asn1Type::PrintableString => quote!(::der::asn1::PrintableStringRef::decode(reader)?),
+            Asn1Type::TeletexString => quote!(::der::asn1::TeletexStringRef::decode(reader)?),
+            Asn1Type::UniversalString => {
+                quote!(::der::asn1::UniversalStringRef::decode(reader)?)
+            }
             Asn1Type::UtcTime => quote!(::der::asn1::UtcTime::decode(reader)?),
             Asn1Type::Utf8String => quote!(::der::asn1::Utf8StringRef::decode(reader)?),
         }
@@ -62,9 +79,12 @@ impl Asn1Type {
         let type_path = self.type_path();
 
         match self {
-            Asn1Type::Ia5String
+            Asn1Type::BmpString
+            | Asn1Type::Ia5String
             | Asn1Type::OctetString
             | Asn1Type::PrintableString
+            | Asn1Type::TeletexString
+            | Asn1Type::UniversalString
             | Asn1Type::Utf8String => quote!(#type_path::new(#binding)?),
             _ => quote!(#type_path::try_from(#binding)?),
         }
@@ -75,10 +95,13 @@ impl Asn1Type {
     pub fn type_path(self) -> TokenStream {
         match self {
             Asn1Type::BitString => quote!(::der::asn1::BitStringRef),
+            Asn1Type::BmpString => quote!(::der::asn1::BmpStringRef),
             Asn1Type::Ia5String => quote!(::der::asn1::Ia5StringRef),
             Asn1Type::GeneralizedTime => quote!(::der::asn1::GeneralizedTime),
             Asn1Type::OctetString => quote!(::der::asn1::OctetStringRef),
             Asn1Type::PrintableString => quote!(::der::asn1::PrintableStringRef),
+            Asn1Type::TeletexString => quote!(::der::asn1::TeletexStringRef),
+            Asn1Type::UniversalString => quote!(::der::asn1::UniversalStringRef),
             Asn1Type::UtcTime => quote!(::der::asn1::UtcTime),
             Asn1Type::Utf8String => quote!(::der::asn1::Utf8StringRef),
         }
@@ -91,10 +114,13 @@ impl FromStr for Asn1Type {
     fn from_str(s: &str) -> Result<Self, ParseError> {
         match s {
             "BIT STRING" => Ok(Self::BitString),
+            "BMPString" => Ok(Self::BmpString),
             "IA5String" => Ok(Self::Ia5String),
             "GeneralizedTime" => Ok(Self::GeneralizedTime),
             "OCTET STRING" => Ok(Self::OctetString),
             "PrintableString" => Ok(Self::PrintableString),
+            "TeletexString" => Ok(Self::TeletexString),
+            "UniversalString" => Ok(Self::UniversalString),
             "UTCTime" => Ok(Self::UtcTime),
             "UTF8String" => Ok(Self::Utf8String),
             _ => Err(ParseError),
@@ -106,10 +132,13 @@ impl fmt::Display for Asn1Type {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.write_str(match self {
             Asn1Type::BitString => "BIT STRING",
+            Asn1Type::BmpString => "BMPString",
             Asn1Type::Ia5String => "IA5String",
             Asn1Type::GeneralizedTime => "GeneralizedTime",
             Asn1Type::OctetString => "OCTET STRING",
             Asn1Type::PrintableString => "PrintableString",
+            Asn1Type::TeletexString => "TeletexString",
+            Asn1Type::UniversalString => "UniversalString",
             Asn1Type::UtcTime => "UTCTime",
             Asn1Type::Utf8String => "UTF8String",
         })
@@ -21,19 +21,34 @@ pub(crate) struct TypeAttrs {
     ///
     /// The default value is `EXPLICIT`.
     pub tag_mode: TagMode,
+
+    /// Are `CONTEXT-SPECIFIC` tags numbered automatically, supplied as
+    /// `#[asn1(tag_mode = "AUTOMATIC")]`?
+    ///
+    /// Automatic tags are `IMPLICIT`.
+    pub automatic: bool,
 }
 
 impl TypeAttrs {
     /// Parse attributes from a struct field or enum variant.
     pub fn parse(attrs: &[Attribute]) -> Self {
         let mut tag_mode = None;
+        let mut automatic = false;
 
         let mut parsed_attrs = Vec::new();
         AttrNameValue::from_attributes(attrs, &mut parsed_attrs);
 
         for attr in parsed_attrs {
+            // `tag_mode = "AUTOMATIC"` attribute
+            if attr.name.is_ident("tag_mode") && attr.value.value() == "AUTOMATIC" {
+                if tag_mode.is_some() {
+                    abort!(attr.name, "duplicate ASN.1 `tag_mode` attribute");
+                }
+
+                tag_mode = Some(TagMode::Implicit);
+                automatic = true;
             // `tag_mode = "..."` attribute
-            if let Some(mode) = attr.parse_value("tag_mode") {
+            } else if let Some(mode) = attr.parse_value("tag_mode") {
                 if tag_mode.is_some() {
                     abort!(attr.name, "duplicate ASN.1 `tag_mode` attribute");
                 }
@@ -49,8 +64,36 @@ impl TypeAttrs {
 
         Self {
             tag_mode: tag_mode.unwrap_or_default(),
+            automatic,
         }
     }
+
+    /// Parse the attributes of the fields or variants of this type.
+    ///
+    /// When tags are `AUTOMATIC` and none of the fields is `CONTEXT-SPECIFIC`,
+    /// the fields are numbered in order starting from 0.
+    pub fn parse_fields<'a>(
+        &self,
+        fields: impl IntoIterator<Item = &'a [Attribute]>,
+    ) -> Vec<FieldAttrs> {
+        let mut fields = fields
+            .into_iter()
+            .map(|attrs| FieldAttrs::parse(attrs, self))
+            .collect::<Vec<_>>();
+
+        if self.automatic && fields.iter().all(|field| field.context_specific.is_none()) {
+            for (number, field) in fields.iter_mut().enumerate() {
+                match u8::try_from(number) {
+                    Ok(number) if number <= TagNumber::MAX => {
+                        field.context_specific = Some(TagNumber(number))
+                    }
+                    _ => abort_call_site!("too many fields for `AUTOMATIC` tagging"),
+                }
+            }
+        }
+
+        fields
+    }
 }
 
 /// Field-level attributes.
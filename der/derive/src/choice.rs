@@ -42,10 +42,13 @@ impl DeriveChoice {
             .map(|lt| lt.lifetime.clone());
 
         let type_attrs = TypeAttrs::parse(&input.attrs);
+        let variant_attrs = type_attrs.parse_fields(data.variants.iter().map(|v| &v.attrs[..]));
+
         let variants = data
             .variants
             .iter()
-            .map(|variant| ChoiceVariant::new(variant, &type_attrs))
+            .zip(variant_attrs)
+            .map(|(variant, attrs)| ChoiceVariant::new(variant, attrs))
             .collect();
 
         Self {
@@ -1,6 +1,6 @@
 //! Choice variant IR and lowerings
 
-use crate::{FieldAttrs, Tag, TypeAttrs};
+use crate::{FieldAttrs, Tag};
 use proc_macro2::TokenStream;
 use proc_macro_error::abort;
 use quote::quote;
@@ -73,9 +73,8 @@ pub(super) struct ChoiceVariant {
 
 impl ChoiceVariant {
     /// Create a new [`ChoiceVariant`] from the input [`Variant`].
-    pub(super) fn new(input: &Variant, type_attrs: &TypeAttrs) -> Self {
+    pub(super) fn new(input: &Variant, attrs: FieldAttrs) -> Self {
         let ident = input.ident.clone();
-        let attrs = FieldAttrs::parse(&input.attrs, type_attrs);
 
         if attrs.extensible {
             abort!(&ident, "`extensible` is not allowed on CHOICE");
@@ -9,6 +9,7 @@
 //! - [`Choice`][`derive@Choice`]: map ASN.1 `CHOICE` to a Rust enum.
 //! - [`Enumerated`][`derive@Enumerated`]: map ASN.1 `ENUMERATED` to a C-like Rust enum.
 //! - [`Sequence`][`derive@Sequence`]: map ASN.1 `SEQUENCE` to a Rust struct.
+//! - [`Set`][`derive@Set`]: map ASN.1 `SET` to a Rust struct.
 //! - [`ValueOrd`][`derive@ValueOrd`]: determine DER ordering for ASN.1 `SET OF`.
 //!
 //! Note that this crate shouldn't be used directly, but instead accessed
@@ -33,9 +34,9 @@
 //! ## Toplevel attributes
 //!
 //! The following attributes can be added to an `enum` or `struct` when
-//! deriving either [`Choice`] or [`Sequence`] respectively:
+//! deriving either [`Choice`], or [`Sequence`] and [`Set`] respectively:
 //!
-//! ### `#[asn1(tag_mode = "...")]` attribute: `EXPLICIT` vs `IMPLICIT`
+//! ### `#[asn1(tag_mode = "...")]` attribute: `EXPLICIT` vs `IMPLICIT` vs `AUTOMATIC`
 //!
 //! This attribute can be used to declare the tagging mode used by a particular
 //! ASN.1 module.
@@ -43,7 +44,13 @@
 //! It's used when parsing `CONTEXT-SENSITIVE` fields.
 //!
 //! The default is `EXPLICIT`, so the attribute only needs to be added when
-//! a particular module is declared `IMPLICIT`.
+//! a particular module is declared `IMPLICIT` or `AUTOMATIC`.
+//!
+//! With `AUTOMATIC`, fields are numbered as `IMPLICIT` `CONTEXT-SPECIFIC`
+//! fields in order starting from 0, as if each had its own
+//! `context_specific` attribute, unless any of them already has one.
+//! Fields whose type is a `CHOICE` or `ANY` are `EXPLICIT` in ASN.1, and
+//! need a `tag_mode = "EXPLICIT"` attribute of their own.
 //!
 //! ## Field-level attributes
 //!
@@ -86,10 +93,13 @@
 //! listed below (use the ASN.1 type keywords as the `type`):
 //!
 //! - `BIT STRING`: performs an intermediate conversion to [`der::asn1::BitString`]
+//! - `BMPString`: performs an intermediate conversion to [`der::asn1::BmpString`]
 //! - `IA5String`: performs an intermediate conversion to [`der::asn1::IA5String`]
 //! - `GeneralizedTime`: performs an intermediate conversion to [`der::asn1::GeneralizedTime`]
 //! - `OCTET STRING`: performs an intermediate conversion to [`der::asn1::OctetString`]
 //! - `PrintableString`: performs an intermediate conversion to [`der::asn1::PrintableString`]
+//! - `TeletexString`: performs an intermediate conversion to [`der::asn1::TeletexString`]
+//! - `UniversalString`: performs an intermediate conversion to [`der::asn1::UniversalString`]
 //! - `UTCTime`: performs an intermediate conversion to [`der::asn1::UtcTime`]
 //! - `UTF8String`: performs an intermediate conversion to [`der::asn1::Utf8String`]
 //!
@@ -104,11 +114,15 @@
 //! [`der`]: https://docs.rs/der/
 //! [`Choice`]: derive@Choice
 //! [`Sequence`]: derive@Sequence
+//! [`Set`]: derive@Set
 //! [`der::asn1::BitString`]: https://docs.rs/der/latest/der/asn1/struct.BitString.html
+//! [`der::asn1::BmpString`]: https://docs.rs/der/latest/der/asn1/struct.BmpStringRef.html
 //! [`der::asn1::Ia5String`]: https://docs.rs/der/latest/der/asn1/struct.Ia5String.html
 //! [`der::asn1::GeneralizedTime`]: https://docs.rs/der/latest/der/asn1/struct.GeneralizedTime.html
 //! [`der::asn1::OctetString`]: https://docs.rs/der/latest/der/asn1/struct.OctetString.html
 //! [`der::asn1::PrintableString`]: https://docs.rs/der/latest/der/asn1/struct.PrintableString.html
+//! [`der::asn1::TeletexString`]: https://docs.rs/der/latest/der/asn1/struct.TeletexStringRef.html
+//! [`der::asn1::UniversalString`]: https://docs.rs/der/latest/der/asn1/struct.UniversalStringRef.html
 //! [`der::asn1::UtcTime`]: https://docs.rs/der/latest/der/asn1/struct.UtcTime.html
 //! [`der::asn1::Utf8String`]: https://docs.rs/der/latest/der/asn1/struct.Utf8String.html
 
@@ -126,6 +140,7 @@ mod attributes;
 mod choice;
 mod enumerated;
 mod sequence;
+mod set;
 mod tag;
 mod value_ord;
 
@@ -135,6 +150,7 @@ use crate::{
     choice::DeriveChoice,
     enumerated::DeriveEnumerated,
     sequence::DeriveSequence,
+    set::DeriveSet,
     tag::{Tag, TagMode, TagNumber},
     value_ord::DeriveValueOrd,
 };
@@ -270,6 +286,39 @@ pub fn derive_sequence(input: TokenStream) -> TokenStream {
     DeriveSequence::new(input).to_tokens().into()
 }
 
+/// Derive the [`Decode`][1] and [`Encode`][2] traits on a `struct`
+/// representing an ASN.1 `SET`.
+///
+/// Fields are encoded in the canonical order of their tags required by DER,
+/// regardless of the order in which they're declared, and may be decoded in
+/// any order. The tag of each field must be distinct.
+///
+/// Fields accept the same `#[asn1]` attributes as the fields of a
+/// [`Sequence`][3], except for `extensible`.
+///
+/// # Usage
+///
+/// ```ignore
+/// use der::{asn1::Utf8StringRef, Set};
+///
+/// #[derive(Set)]
+/// #[asn1(tag_mode = "AUTOMATIC")]
+/// pub struct Person<'a> {
+///     pub name: Utf8StringRef<'a>,
+///     pub age: Option<u8>,
+/// }
+/// ```
+///
+/// [1]: https://docs.rs/der/latest/der/trait.Decode.html
+/// [2]: https://docs.rs/der/latest/der/trait.Encode.html
+/// [3]: derive@Sequence
+#[proc_macro_derive(Set, attributes(asn1))]
+#[proc_macro_error]
+pub fn derive_set(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    DeriveSet::new(input).to_tokens().into()
+}
+
 /// Derive the [`ValueOrd`][1] trait on a `struct`.
 ///
 /// This trait is used in conjunction with ASN.1 `SET OF` types to determine
@@ -1,7 +1,7 @@
 //! Support for deriving the `Sequence` trait on structs for the purposes of
 //! decoding/encoding ASN.1 `SEQUENCE` types as mapped to struct fields.
 
-mod field;
+pub(crate) mod field;
 
 use crate::{default_lifetime, TypeAttrs};
 use field::SequenceField;
@@ -41,11 +41,13 @@ impl DeriveSequence {
             .map(|lt| lt.lifetime.clone());
 
         let type_attrs = TypeAttrs::parse(&input.attrs);
+        let field_attrs = type_attrs.parse_fields(data.fields.iter().map(|f| &f.attrs[..]));
 
         let fields = data
             .fields
             .iter()
-            .map(|field| SequenceField::new(field, &type_attrs))
+            .zip(field_attrs)
+            .map(|(field, attrs)| SequenceField::new(field, attrs))
             .collect();
 
         Self {
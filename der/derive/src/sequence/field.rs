@@ -1,26 +1,26 @@
 //! Sequence field IR and lowerings
 
-use crate::{Asn1Type, FieldAttrs, TagMode, TagNumber, TypeAttrs};
+use crate::{Asn1Type, FieldAttrs, TagMode, TagNumber};
 use proc_macro2::TokenStream;
 use proc_macro_error::abort;
 use quote::quote;
 use syn::{Field, Ident, Path, Type};
 
 /// "IR" for a field of a derived `Sequence`.
-pub(super) struct SequenceField {
+pub(crate) struct SequenceField {
     /// Variant name.
-    pub(super) ident: Ident,
+    pub(crate) ident: Ident,
 
     /// Field-level attributes.
-    pub(super) attrs: FieldAttrs,
+    pub(crate) attrs: FieldAttrs,
 
     /// Field type
-    pub(super) field_type: Type,
+    pub(crate) field_type: Type,
 }
 
 impl SequenceField {
     /// Create a new [`SequenceField`] from the input [`Field`].
-    pub(super) fn new(field: &Field, type_attrs: &TypeAttrs) -> Self {
+    pub(crate) fn new(field: &Field, attrs: FieldAttrs) -> Self {
         let ident = field.ident.as_ref().cloned().unwrap_or_else(|| {
             abort!(
                 field,
@@ -28,8 +28,6 @@ impl SequenceField {
             )
         });
 
-        let attrs = FieldAttrs::parse(&field.attrs, type_attrs);
-
         if attrs.asn1_type.is_some() && attrs.default.is_some() {
             abort!(
                 ident,
@@ -52,7 +50,7 @@ impl SequenceField {
     }
 
     /// Derive code for decoding a field of a sequence.
-    pub(super) fn to_decode_tokens(&self) -> TokenStream {
+    pub(crate) fn to_decode_tokens(&self) -> TokenStream {
         let mut lowerer = LowerFieldDecoder::new(&self.attrs);
 
         if self.attrs.asn1_type.is_some() {
@@ -76,7 +74,7 @@ impl SequenceField {
     }
 
     /// Derive code for encoding a field of a sequence.
-    pub(super) fn to_encode_tokens(&self) -> TokenStream {
+    pub(crate) fn to_encode_tokens(&self) -> TokenStream {
         let mut lowerer = LowerFieldEncoder::new(&self.ident);
         let attrs = &self.attrs;
 
@@ -0,0 +1,328 @@
+//! Support for deriving the `Decode` and `Encode` traits on structs for the
+//! purposes of decoding/encoding ASN.1 `SET` types as mapped to struct fields.
+
+use crate::{default_lifetime, sequence::field::SequenceField, Tag, TagMode, TypeAttrs};
+use proc_macro2::TokenStream;
+use proc_macro_error::abort;
+use quote::quote;
+use syn::{DeriveInput, GenericArgument, Ident, Lifetime, PathArguments, Type};
+
+/// Derive the `Decode` and `Encode` traits for a struct representing a `SET`.
+pub(crate) struct DeriveSet {
+    /// Name of the set struct.
+    ident: Ident,
+
+    /// Lifetime of the struct.
+    lifetime: Option<Lifetime>,
+
+    /// Fields of the struct.
+    fields: Vec<SequenceField>,
+}
+
+impl DeriveSet {
+    /// Parse [`DeriveInput`].
+    pub fn new(input: DeriveInput) -> Self {
+        let data = match input.data {
+            syn::Data::Struct(data) => data,
+            _ => abort!(
+                input.ident,
+                "can't derive `Set` on this type: only `struct` types are allowed",
+            ),
+        };
+
+        // Only the first lifetime is used; structs with several aren't supported
+        let lifetime = input
+            .generics
+            .lifetimes()
+            .next()
+            .map(|lt| lt.lifetime.clone());
+
+        let type_attrs = TypeAttrs::parse(&input.attrs);
+        let field_attrs = type_attrs.parse_fields(data.fields.iter().map(|f| &f.attrs[..]));
+
+        let fields = data
+            .fields
+            .iter()
+            .zip(field_attrs)
+            .map(|(field, attrs)| {
+                if attrs.extensible {
+                    abort!(field, "`extensible` is not allowed on SET");
+                }
+
+                SequenceField::new(field, attrs)
+            })
+            .collect();
+
+        Self {
+            ident: input.ident,
+            lifetime,
+            fields,
+        }
+    }
+
+    /// Lower the derived output into a [`TokenStream`].
+    pub fn to_tokens(&self) -> TokenStream {
+        let ident = &self.ident;
+
+        let lifetime = match self.lifetime {
+            Some(ref lifetime) => quote!(#lifetime),
+            None => default_lifetime(),
+        };
+
+        // Lifetime parameters
+        // At most one lifetime parameter, as above
+        let lt_params = self
+            .lifetime
+            .as_ref()
+            .map(|_| lifetime.clone())
+            .unwrap_or_default();
+
+        let mut decode_vars = Vec::new();
+        let mut decode_body = Vec::new();
+        let mut decode_result = Vec::new();
+        let mut encode_body = Vec::new();
+        let mut encode_tags = Vec::new();
+
+        for field in &self.fields {
+            let field_ident = &field.ident;
+            decode_vars.push(quote!(let mut #field_ident = None;));
+            decode_body.push(to_decode_tokens(field, &lifetime));
+            decode_result.push(to_decode_result_tokens(field));
+            encode_body.push(field.to_encode_tokens());
+            encode_tags.push(to_tag_tokens(field));
+        }
+
+        let field_count = self.fields.len();
+
+        quote! {
+            impl<#lifetime> ::der::DecodeValue<#lifetime> for #ident<#lt_params> {
+                fn decode_value<R: ::der::Reader<#lifetime>>(
+                    reader: &mut R,
+                    header: ::der::Header,
+                ) -> ::der::Result<Self> {
+                    use ::der::{Decode as _, DecodeValue as _, Reader as _};
+
+                    reader.read_nested(header.length, |reader| {
+                        #(#decode_vars)*
+
+                        // Fields may appear in any order
+                        while !reader.is_finished() {
+                            let tag = reader.peek_tag()?;
+
+                            #(#decode_body else)* {
+                                return Err(tag.unexpected_error(None));
+                            }
+                        }
+
+                        Ok(Self {
+                            #(#decode_result),*
+                        })
+                    })
+                }
+            }
+
+            impl<#lt_params> ::der::EncodeValue for #ident<#lt_params> {
+                fn value_len(&self) -> ::der::Result<::der::Length> {
+                    let fields: [&dyn ::der::Encode; #field_count] = [
+                        #(#encode_body),*
+                    ];
+
+                    fields
+                        .iter()
+                        .try_fold(::der::Length::ZERO, |acc, field| acc + field.encoded_len()?)
+                }
+
+                fn encode_value(&self, writer: &mut dyn ::der::Writer) -> ::der::Result<()> {
+                    let mut fields: [(Option<::der::Tag>, &dyn ::der::Encode); #field_count] = [
+                        #((#encode_tags, #encode_body)),*
+                    ];
+
+                    // DER requires the fields of a `SET` to be sorted by tag.
+                    // Absent fields have no tag, and encode as nothing.
+                    fields.sort_unstable_by_key(|(tag, _)| tag.map(|tag| (tag.class(), tag.number())));
+
+                    for (_, field) in &fields {
+                        field.encode(writer)?;
+                    }
+
+                    Ok(())
+                }
+            }
+
+            impl<#lt_params> ::der::FixedTag for #ident<#lt_params> {
+                const TAG: ::der::Tag = ::der::Tag::Set;
+            }
+        }
+    }
+}
+
+/// Get the inner type of an `Option<T>`.
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let segment = match ty {
+        Type::Path(path) => path.path.segments.last()?,
+        _ => return None,
+    };
+
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) if segment.ident == "Option" => {
+            match args.args.first() {
+                Some(GenericArgument::Type(ty)) => Some(ty),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Get the tag of a field, if it is known ahead of time.
+fn static_tag(field: &SequenceField) -> Option<TokenStream> {
+    match (field.attrs.context_specific, field.attrs.asn1_type) {
+        (Some(number), _) => Some(
+            Tag::ContextSpecific {
+                constructed: field.attrs.constructed,
+                number,
+            }
+            .to_tokens(),
+        ),
+        (None, Some(ty)) => Some(ty.tag()),
+        (None, None) => None,
+    }
+}
+
+/// Derive an expression for the tag of a field when encoding, which is `None`
+/// if the field is absent.
+fn to_tag_tokens(field: &SequenceField) -> TokenStream {
+    let ident = &field.ident;
+
+    match static_tag(field) {
+        Some(tag) => quote!(Some(#tag)),
+        None if option_inner(&field.field_type).is_some() => {
+            quote!(self.#ident.as_ref().map(::der::Tagged::tag))
+        }
+        None => quote!(Some(::der::Tagged::tag(&self.#ident))),
+    }
+}
+
+/// Derive a branch which decodes a field if `tag` is the tag of the field.
+fn to_decode_tokens(field: &SequenceField, lifetime: &TokenStream) -> TokenStream {
+    let ident = &field.ident;
+    let attrs = &field.attrs;
+    let type_params = attrs.asn1_type.map(|ty| ty.type_path()).unwrap_or_default();
+
+    let (matches, decoder) = match (attrs.context_specific, attrs.asn1_type) {
+        (Some(tag_number), _) => {
+            let tag_number = tag_number.to_tokens();
+            let decoder = match attrs.tag_mode {
+                TagMode::Explicit => quote! {
+                    ::der::asn1::ContextSpecific::<#type_params>::decode(reader)?.value
+                },
+                TagMode::Implicit => quote! {
+                    ::der::asn1::ContextSpecific::<#type_params>::decode_implicit(
+                        reader,
+                        #tag_number
+                    )?
+                    .ok_or_else(|| tag.value_error())?
+                    .value
+                },
+            };
+
+            (
+                quote!(tag.is_context_specific() && tag.number() == #tag_number),
+                decoder,
+            )
+        }
+        (None, Some(ty)) => {
+            let tag = ty.tag();
+            (quote!(tag == #tag), ty.decoder())
+        }
+        (None, None) => {
+            let ty = option_inner(&field.field_type).unwrap_or(&field.field_type);
+            (
+                quote!(<#ty as ::der::Choice<#lifetime>>::can_decode(tag)),
+                quote!(reader.decode()?),
+            )
+        }
+    };
+
+    let decoder = match attrs.asn1_type {
+        Some(_) => quote!(#decoder.try_into()?),
+        None => decoder,
+    };
+
+    quote! {
+        if #matches {
+            if #ident.is_some() {
+                return Err(::der::Tag::Set.value_error());
+            }
+
+            #ident = Some(#decoder);
+        }
+    }
+}
+
+/// Derive the value of a field once all of the fields have been decoded.
+fn to_decode_result_tokens(field: &SequenceField) -> TokenStream {
+    let ident = &field.ident;
+
+    if let Some(default) = &field.attrs.default {
+        quote!(#ident: #ident.unwrap_or_else(#default))
+    } else if field.attrs.optional || option_inner(&field.field_type).is_some() {
+        quote!(#ident)
+    } else {
+        quote!(#ident: #ident.ok_or_else(|| ::der::Tag::Set.value_error())?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DeriveSet;
+    use crate::{Asn1Type, TagMode, TagNumber};
+    use syn::parse_quote;
+
+    /// `AUTOMATIC` tagged example
+    #[test]
+    fn automatic_example() {
+        let input = parse_quote! {
+            #[asn1(tag_mode = "AUTOMATIC")]
+            pub struct AutomaticSet<'a> {
+                version: u8,
+
+                #[asn1(type = "UTF8String")]
+                name: String,
+
+                label: Option<Utf8StringRef<'a>>,
+            }
+        };
+
+        let ir = DeriveSet::new(input);
+        assert_eq!(ir.ident, "AutomaticSet");
+        assert_eq!(ir.lifetime.unwrap().to_string(), "'a");
+        assert_eq!(ir.fields.len(), 3);
+
+        for (number, field) in ir.fields.iter().enumerate() {
+            assert_eq!(field.attrs.context_specific, Some(TagNumber(number as u8)));
+            assert_eq!(field.attrs.tag_mode, TagMode::Implicit);
+        }
+
+        assert_eq!(ir.fields[1].attrs.asn1_type, Some(Asn1Type::Utf8String));
+    }
+
+    /// Fields which are already tagged aren't numbered automatically.
+    #[test]
+    fn automatic_tagged_example() {
+        let input = parse_quote! {
+            #[asn1(tag_mode = "AUTOMATIC")]
+            pub struct TaggedSet {
+                #[asn1(context_specific = "5")]
+                version: u8,
+
+                serial: u32,
+            }
+        };
+
+        let ir = DeriveSet::new(input);
+        assert_eq!(ir.fields[0].attrs.context_specific, Some(TagNumber(5)));
+        assert_eq!(ir.fields[0].attrs.tag_mode, TagMode::Implicit);
+        assert_eq!(ir.fields[1].attrs.context_specific, None);
+    }
+}
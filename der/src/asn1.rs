@@ -3,6 +3,7 @@
 
 mod any;
 mod bit_string;
+mod bmp_string;
 mod boolean;
 mod choice;
 mod context_specific;
@@ -20,30 +21,41 @@ mod real;
 mod sequence;
 mod sequence_of;
 mod set_of;
+mod teletex_string;
+mod universal_string;
 mod utc_time;
 mod utf8_string;
 
 pub use self::{
     any::AnyRef,
     bit_string::{BitStringIter, BitStringRef},
+    bmp_string::BmpStringRef,
     choice::Choice,
     context_specific::{ContextSpecific, ContextSpecificRef},
-    generalized_time::GeneralizedTime,
+    generalized_time::{GeneralizedTime, GeneralizedTimeNanos},
     ia5_string::Ia5StringRef,
-    integer::bigint::UIntRef,
+    integer::bigint::{IntRef, UIntRef},
     null::Null,
     octet_string::OctetStringRef,
     printable_string::PrintableStringRef,
     sequence::{Sequence, SequenceRef},
     sequence_of::{SequenceOf, SequenceOfIter},
     set_of::{SetOf, SetOfIter},
+    teletex_string::TeletexStringRef,
+    universal_string::UniversalStringRef,
     utc_time::UtcTime,
     utf8_string::Utf8StringRef,
 };
 
 #[cfg(feature = "alloc")]
 #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
-pub use self::{any::Any, bit_string::BitString, octet_string::OctetString, set_of::SetOfVec};
+pub use self::{
+    any::Any,
+    bit_string::BitString,
+    integer::bigint::{Int, UInt},
+    octet_string::OctetString,
+    set_of::SetOfVec,
+};
 
 #[cfg(feature = "oid")]
 #[cfg_attr(docsrs, doc(cfg(feature = "oid")))]
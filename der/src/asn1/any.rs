@@ -81,6 +81,11 @@ impl<'a> AnyRef<'a> {
         self.try_into()
     }
 
+    /// Attempt to decode an ASN.1 `BMPString`.
+    pub fn bmp_string(self) -> Result<BmpStringRef<'a>> {
+        self.try_into()
+    }
+
     /// Attempt to decode an ASN.1 `CONTEXT-SPECIFIC` field.
     pub fn context_specific<T>(self) -> Result<ContextSpecific<T>>
     where
@@ -140,6 +145,16 @@ impl<'a> AnyRef<'a> {
         reader.finish(result)
     }
 
+    /// Attempt to decode an ASN.1 `TeletexString`.
+    pub fn teletex_string(self) -> Result<TeletexStringRef<'a>> {
+        self.try_into()
+    }
+
+    /// Attempt to decode an ASN.1 `UniversalString`.
+    pub fn universal_string(self) -> Result<UniversalStringRef<'a>> {
+        self.try_into()
+    }
+
     /// Attempt to decode an ASN.1 `UTCTime`.
     pub fn utc_time(self) -> Result<UtcTime> {
         self.try_into()
@@ -258,6 +273,16 @@ impl EncodeValue for Any {
     }
 }
 
+#[cfg(feature = "alloc")]
+impl From<AnyRef<'_>> for Any {
+    fn from(any: AnyRef<'_>) -> Any {
+        Any {
+            tag: any.tag,
+            value: any.value().into(),
+        }
+    }
+}
+
 #[cfg(feature = "alloc")]
 impl<'a> From<&'a Any> for AnyRef<'a> {
     fn from(any: &'a Any) -> AnyRef<'a> {
@@ -272,3 +297,10 @@ impl Tagged for Any {
         self.tag
     }
 }
+
+#[cfg(feature = "alloc")]
+impl ValueOrd for Any {
+    fn value_cmp(&self, other: &Self) -> Result<Ordering> {
+        AnyRef::from(self).value_cmp(&AnyRef::from(other))
+    }
+}
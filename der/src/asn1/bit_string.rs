@@ -319,6 +319,17 @@ impl FixedTag for BitString {
     const TAG: Tag = Tag::BitString;
 }
 
+#[cfg(feature = "alloc")]
+impl From<BitStringRef<'_>> for BitString {
+    fn from(bit_string: BitStringRef<'_>) -> BitString {
+        BitString {
+            unused_bits: bit_string.unused_bits,
+            bit_length: bit_string.bit_length,
+            inner: bit_string.raw_bytes().into(),
+        }
+    }
+}
+
 #[cfg(feature = "alloc")]
 impl<'a> From<&'a BitString> for BitStringRef<'a> {
     fn from(bit_string: &'a BitString) -> BitStringRef<'a> {
@@ -0,0 +1,172 @@
+//! ASN.1 `BMPString` support.
+
+use crate::{
+    asn1::AnyRef, ord::OrdIsValueOrd, ByteSlice, DecodeValue, EncodeValue, Error, FixedTag, Header,
+    Length, Reader, Result, Tag, Writer,
+};
+use core::{char, fmt};
+
+/// ASN.1 `BMPString` type.
+///
+/// Encodes characters of the Basic Multilingual Plane of ISO 10646 as
+/// UCS-2, i.e. as two bytes in big endian order per character.
+///
+/// For UTF-8, use [`Utf8StringRef`][`crate::asn1::Utf8StringRef`] instead.
+///
+/// This is a zero-copy reference type which borrows from the input data. As
+/// the encoding is not UTF-8, the string can't be borrowed as a `str`: use
+/// [`BmpStringRef::chars`] or the [`Display`][`fmt::Display`] impl instead.
+#[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Ord)]
+pub struct BmpStringRef<'a> {
+    /// Inner value
+    inner: ByteSlice<'a>,
+}
+
+impl<'a> BmpStringRef<'a> {
+    /// Create a new ASN.1 `BMPString` from its UCS-2 encoding.
+    pub fn new<T>(input: &'a T) -> Result<Self>
+    where
+        T: AsRef<[u8]> + ?Sized,
+    {
+        let input = input.as_ref();
+
+        // Surrogates are not characters, and UCS-2 has no means of pairing them
+        if input.len() % 2 != 0 || decode(input).any(|c| c.is_err()) {
+            return Err(Self::TAG.value_error());
+        }
+
+        ByteSlice::new(input)
+            .map(|inner| Self { inner })
+            .map_err(|_| Self::TAG.length_error())
+    }
+
+    /// Borrow the UCS-2 encoding of the string.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.inner.as_slice()
+    }
+
+    /// Iterate over the characters of the string.
+    pub fn chars(&self) -> impl 'a + Iterator<Item = char> {
+        // Ensured to be valid in constructor
+        decode(self.as_bytes()).filter_map(|c| c.ok())
+    }
+
+    /// Get the length of the inner byte slice.
+    pub fn len(&self) -> Length {
+        self.inner.len()
+    }
+
+    /// Is the inner string empty?
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+}
+
+fn decode(bytes: &[u8]) -> impl '_ + Iterator<Item = core::result::Result<char, u16>> {
+    bytes
+        .chunks_exact(2)
+        .map(|unit| u16::from_be_bytes([unit[0], unit[1]]))
+        .map(|unit| char::from_u32(unit.into()).ok_or(unit))
+}
+
+impl AsRef<[u8]> for BmpStringRef<'_> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl<'a> DecodeValue<'a> for BmpStringRef<'a> {
+    fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> Result<Self> {
+        Self::new(ByteSlice::decode_value(reader, header)?.as_slice())
+    }
+}
+
+impl<'a> EncodeValue for BmpStringRef<'a> {
+    fn value_len(&self) -> Result<Length> {
+        self.inner.value_len()
+    }
+
+    fn encode_value(&self, writer: &mut dyn Writer) -> Result<()> {
+        self.inner.encode_value(writer)
+    }
+}
+
+impl FixedTag for BmpStringRef<'_> {
+    const TAG: Tag = Tag::BmpString;
+}
+
+impl OrdIsValueOrd for BmpStringRef<'_> {}
+
+impl<'a> From<&BmpStringRef<'a>> for BmpStringRef<'a> {
+    fn from(value: &BmpStringRef<'a>) -> BmpStringRef<'a> {
+        *value
+    }
+}
+
+impl<'a> TryFrom<AnyRef<'a>> for BmpStringRef<'a> {
+    type Error = Error;
+
+    fn try_from(any: AnyRef<'a>) -> Result<BmpStringRef<'a>> {
+        any.decode_into()
+    }
+}
+
+impl<'a> From<BmpStringRef<'a>> for AnyRef<'a> {
+    fn from(bmp_string: BmpStringRef<'a>) -> AnyRef<'a> {
+        AnyRef::from_tag_and_value(Tag::BmpString, bmp_string.inner)
+    }
+}
+
+impl<'a> From<BmpStringRef<'a>> for &'a [u8] {
+    fn from(bmp_string: BmpStringRef<'a>) -> &'a [u8] {
+        bmp_string.as_bytes()
+    }
+}
+
+impl<'a> fmt::Display for BmpStringRef<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.chars().try_for_each(|c| fmt::Write::write_char(f, c))
+    }
+}
+
+impl<'a> fmt::Debug for BmpStringRef<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("BmpString(\"")?;
+        for c in self.chars().flat_map(char::escape_debug) {
+            fmt::Write::write_char(f, c)?;
+        }
+        f.write_str("\")")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BmpStringRef;
+    use crate::{Decode, Encode, Tag};
+    use hex_literal::hex;
+
+    #[test]
+    fn parse_bytes() {
+        let example_bytes = hex!("1e 0c 0054 0065 0073 0074 00e9 20ac");
+
+        let bmp_string = BmpStringRef::from_der(&example_bytes).unwrap();
+        assert!(bmp_string.chars().eq("Testé€".chars()));
+        assert_eq!(bmp_string.as_bytes(), &example_bytes[2..]);
+
+        let mut buf = [0u8; 16];
+        assert_eq!(
+            bmp_string.encode_to_slice(&mut buf).unwrap(),
+            &example_bytes
+        );
+    }
+
+    #[test]
+    fn reject_invalid() {
+        // odd length
+        assert!(BmpStringRef::new(&[0x00, 0x54, 0x00]).is_err());
+
+        // surrogate
+        let err = BmpStringRef::new(&hex!("d83d de00")).unwrap_err();
+        assert_eq!(err.kind(), Tag::BmpString.value_error().kind());
+    }
+}
@@ -13,7 +13,10 @@ use core::time::Duration;
 use std::time::SystemTime;
 
 #[cfg(feature = "time")]
-use time::PrimitiveDateTime;
+use time::{OffsetDateTime, PrimitiveDateTime};
+
+#[cfg(feature = "chrono")]
+use chrono::Utc;
 
 /// ASN.1 `GeneralizedTime` type.
 ///
@@ -33,8 +36,14 @@ impl GeneralizedTime {
     /// Length of an RFC 5280-flavored ASN.1 DER-encoded [`GeneralizedTime`].
     const LENGTH: usize = 15;
 
+    /// The `99991231235959Z` value used by [RFC 5280 Section 4.1.2.5] for
+    /// certificates which have no well-defined expiration date.
+    ///
+    /// [RFC 5280 Section 4.1.2.5]: https://datatracker.ietf.org/doc/html/rfc5280#section-4.1.2.5
+    pub const INFINITY: GeneralizedTime = GeneralizedTime(DateTime::INFINITY);
+
     /// Create a [`GeneralizedTime`] from a [`DateTime`].
-    pub fn from_date_time(datetime: DateTime) -> Self {
+    pub const fn from_date_time(datetime: DateTime) -> Self {
         Self(datetime)
     }
 
@@ -51,7 +60,8 @@ impl GeneralizedTime {
             .map_err(|_| Self::TAG.value_error())
     }
 
-    /// Get the duration of this timestamp since `UNIX_EPOCH`.
+    /// Get the duration of this timestamp since `UNIX_EPOCH`, which is zero
+    /// for times before it, as described in [`DateTime::unix_duration`].
     pub fn to_unix_duration(&self) -> Duration {
         self.0.unix_duration()
     }
@@ -84,23 +94,7 @@ impl<'a> DecodeValue<'a> for GeneralizedTime {
 
         match bytes {
             // RFC 5280 requires mandatory seconds and Z-normalized time zone
-            [y1, y2, y3, y4, mon1, mon2, day1, day2, hour1, hour2, min1, min2, sec1, sec2, b'Z'] => {
-                let year = u16::from(datetime::decode_decimal(Self::TAG, y1, y2)?)
-                    .checked_mul(100)
-                    .and_then(|y| {
-                        y.checked_add(datetime::decode_decimal(Self::TAG, y3, y4).ok()?.into())
-                    })
-                    .ok_or(ErrorKind::DateTime)?;
-                let month = datetime::decode_decimal(Self::TAG, mon1, mon2)?;
-                let day = datetime::decode_decimal(Self::TAG, day1, day2)?;
-                let hour = datetime::decode_decimal(Self::TAG, hour1, hour2)?;
-                let minute = datetime::decode_decimal(Self::TAG, min1, min2)?;
-                let second = datetime::decode_decimal(Self::TAG, sec1, sec2)?;
-
-                DateTime::new(year, month, day, hour, minute, second)
-                    .map_err(|_| Self::TAG.value_error())
-                    .and_then(|dt| Self::from_unix_duration(dt.unix_duration()))
-            }
+            [datetime @ .., b'Z'] => decode_date_time(&datetime).map(Self::from_date_time),
             _ => Err(Self::TAG.value_error()),
         }
     }
@@ -112,16 +106,7 @@ impl EncodeValue for GeneralizedTime {
     }
 
     fn encode_value(&self, writer: &mut dyn Writer) -> Result<()> {
-        let year_hi = u8::try_from(self.0.year() / 100)?;
-        let year_lo = u8::try_from(self.0.year() % 100)?;
-
-        datetime::encode_decimal(writer, Self::TAG, year_hi)?;
-        datetime::encode_decimal(writer, Self::TAG, year_lo)?;
-        datetime::encode_decimal(writer, Self::TAG, self.0.month())?;
-        datetime::encode_decimal(writer, Self::TAG, self.0.day())?;
-        datetime::encode_decimal(writer, Self::TAG, self.0.hour())?;
-        datetime::encode_decimal(writer, Self::TAG, self.0.minutes())?;
-        datetime::encode_decimal(writer, Self::TAG, self.0.seconds())?;
+        encode_date_time(writer, &self.0)?;
         writer.write_byte(b'Z')
     }
 }
@@ -328,10 +313,349 @@ impl TryFrom<GeneralizedTime> for PrimitiveDateTime {
     }
 }
 
+#[cfg(feature = "time")]
+#[cfg_attr(docsrs, doc(cfg(feature = "time")))]
+impl TryFrom<OffsetDateTime> for GeneralizedTime {
+    type Error = Error;
+
+    fn try_from(time: OffsetDateTime) -> Result<GeneralizedTime> {
+        Ok(GeneralizedTime::from_date_time(DateTime::try_from(time)?))
+    }
+}
+
+#[cfg(feature = "time")]
+#[cfg_attr(docsrs, doc(cfg(feature = "time")))]
+impl TryFrom<GeneralizedTime> for OffsetDateTime {
+    type Error = Error;
+
+    fn try_from(time: GeneralizedTime) -> Result<OffsetDateTime> {
+        time.to_date_time().try_into()
+    }
+}
+
+#[cfg(feature = "chrono")]
+#[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+impl<Tz: chrono::TimeZone> TryFrom<chrono::DateTime<Tz>> for GeneralizedTime {
+    type Error = Error;
+
+    fn try_from(time: chrono::DateTime<Tz>) -> Result<GeneralizedTime> {
+        Ok(GeneralizedTime::from_date_time(DateTime::try_from(time)?))
+    }
+}
+
+#[cfg(feature = "chrono")]
+#[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+impl TryFrom<GeneralizedTime> for chrono::DateTime<Utc> {
+    type Error = Error;
+
+    fn try_from(time: GeneralizedTime) -> Result<chrono::DateTime<Utc>> {
+        time.to_date_time().try_into()
+    }
+}
+
+/// ASN.1 `GeneralizedTime` type with optional fractional seconds.
+///
+/// Unlike [`GeneralizedTime`], which implements the RFC 5280 profile, this
+/// type supports fractional seconds with up to nanosecond precision, as found
+/// in e.g. the `genTime` of [RFC 3161] time-stamp tokens.
+///
+/// Values are always Z-normalized and encoded according to the DER rules of
+/// [X.690 Section 11.7]: the decimal point is a `.`, the fraction has no
+/// trailing zeros, and it is omitted altogether when it is zero.
+///
+/// [RFC 3161]: https://datatracker.ietf.org/doc/html/rfc3161#section-2.4.2
+/// [X.690 Section 11.7]: https://www.itu.int/rec/T-REC-X.690
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+pub struct GeneralizedTimeNanos {
+    /// Whole seconds
+    datetime: DateTime,
+
+    /// Fractional seconds, in nanoseconds
+    nanos: u32,
+}
+
+impl GeneralizedTimeNanos {
+    /// Maximum length of a DER-encoded [`GeneralizedTimeNanos`].
+    const MAX_LENGTH: usize = GeneralizedTime::LENGTH + 10;
+
+    /// Number of nanoseconds in a second.
+    const NANOS_PER_SEC: u32 = 1_000_000_000;
+
+    /// Create a [`GeneralizedTimeNanos`] from a [`DateTime`] and a number of
+    /// nanoseconds, which must be less than a second.
+    pub fn new(datetime: DateTime, nanos: u32) -> Result<Self> {
+        if nanos >= Self::NANOS_PER_SEC {
+            return Err(ErrorKind::DateTime.into());
+        }
+
+        Ok(Self { datetime, nanos })
+    }
+
+    /// Get the whole seconds of this timestamp as a [`DateTime`].
+    pub fn to_date_time(&self) -> DateTime {
+        self.datetime
+    }
+
+    /// Get the fractional seconds of this timestamp in nanoseconds.
+    pub fn nanos(&self) -> u32 {
+        self.nanos
+    }
+
+    /// Create a new [`GeneralizedTimeNanos`] given a [`Duration`] since
+    /// `UNIX_EPOCH` (a.k.a. "Unix time")
+    pub fn from_unix_duration(unix_duration: Duration) -> Result<Self> {
+        DateTime::from_unix_duration(Duration::from_secs(unix_duration.as_secs()))
+            .and_then(|datetime| Self::new(datetime, unix_duration.subsec_nanos()))
+            .map_err(|_| Self::TAG.value_error())
+    }
+
+    /// Get the duration of this timestamp since `UNIX_EPOCH`, which is zero
+    /// for times before it, as described in [`DateTime::unix_duration`].
+    pub fn to_unix_duration(&self) -> Duration {
+        Duration::new(self.datetime.unix_duration().as_secs(), self.nanos)
+    }
+
+    /// Instantiate from [`SystemTime`].
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn from_system_time(time: SystemTime) -> Result<Self> {
+        let datetime = DateTime::from_system_time(time).map_err(|_| Self::TAG.value_error())?;
+        let nanos = time
+            .duration_since(datetime.to_system_time())
+            .map_err(|_| Self::TAG.value_error())?
+            .subsec_nanos();
+
+        Self::new(datetime, nanos)
+    }
+
+    /// Convert to [`SystemTime`].
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn to_system_time(&self) -> SystemTime {
+        self.datetime.to_system_time() + Duration::from_nanos(self.nanos.into())
+    }
+
+    /// Get the decimal digits of the fractional seconds, without trailing
+    /// zeros.
+    #[allow(clippy::integer_arithmetic)]
+    fn fraction(&self) -> ([u8; 9], usize) {
+        let mut digits = [b'0'; 9];
+        let mut nanos = self.nanos;
+        let mut len = 0;
+
+        for (i, digit) in digits.iter_mut().enumerate().rev() {
+            // `nanos % 10` always fits in a digit
+            *digit = b'0' + (nanos % 10) as u8;
+            nanos /= 10;
+
+            if len == 0 && *digit != b'0' {
+                len = i + 1;
+            }
+        }
+
+        (digits, len)
+    }
+}
+
+impl<'a> DecodeValue<'a> for GeneralizedTimeNanos {
+    #[allow(clippy::integer_arithmetic)]
+    fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> Result<Self> {
+        let length = usize::try_from(header.length)?;
+        if !(GeneralizedTime::LENGTH..=Self::MAX_LENGTH).contains(&length) {
+            return Err(Self::TAG.value_error());
+        }
+
+        let mut buf = [0u8; Self::MAX_LENGTH];
+        let bytes = reader.read_into(&mut buf[..length])?;
+        let (datetime, rest) = bytes.split_at(GeneralizedTime::LENGTH - 1);
+        let datetime = decode_date_time(datetime)?;
+
+        let nanos = match rest {
+            [b'Z'] => 0,
+            // DER forbids trailing zeros in the fraction
+            [b'.', fraction @ .., last, b'Z'] if *last != b'0' => {
+                let mut nanos = 0u32;
+                let mut scale = Self::NANOS_PER_SEC;
+
+                for &digit in fraction.iter().chain(core::iter::once(last)) {
+                    if !digit.is_ascii_digit() {
+                        return Err(Self::TAG.value_error());
+                    }
+
+                    scale /= 10;
+                    nanos += u32::from(digit - b'0') * scale;
+                }
+
+                nanos
+            }
+            _ => return Err(Self::TAG.value_error()),
+        };
+
+        Self::new(datetime, nanos)
+    }
+}
+
+impl EncodeValue for GeneralizedTimeNanos {
+    #[allow(clippy::integer_arithmetic)]
+    fn value_len(&self) -> Result<Length> {
+        match self.fraction() {
+            (_, 0) => GeneralizedTime::LENGTH.try_into(),
+            (_, len) => (GeneralizedTime::LENGTH + 1 + len).try_into(),
+        }
+    }
+
+    fn encode_value(&self, writer: &mut dyn Writer) -> Result<()> {
+        encode_date_time(writer, &self.datetime)?;
+
+        if let (digits, len @ 1..=9) = self.fraction() {
+            writer.write_byte(b'.')?;
+            writer.write(&digits[..len])?;
+        }
+
+        writer.write_byte(b'Z')
+    }
+}
+
+impl FixedTag for GeneralizedTimeNanos {
+    const TAG: Tag = Tag::GeneralizedTime;
+}
+
+impl OrdIsValueOrd for GeneralizedTimeNanos {}
+
+impl From<GeneralizedTime> for GeneralizedTimeNanos {
+    fn from(time: GeneralizedTime) -> GeneralizedTimeNanos {
+        time.to_date_time().into()
+    }
+}
+
+impl From<DateTime> for GeneralizedTimeNanos {
+    fn from(datetime: DateTime) -> GeneralizedTimeNanos {
+        GeneralizedTimeNanos { datetime, nanos: 0 }
+    }
+}
+
+impl TryFrom<GeneralizedTimeNanos> for GeneralizedTime {
+    type Error = Error;
+
+    /// Fails if the time has fractional seconds, which RFC 5280 forbids.
+    fn try_from(time: GeneralizedTimeNanos) -> Result<GeneralizedTime> {
+        match time.nanos {
+            0 => Ok(GeneralizedTime::from_date_time(time.datetime)),
+            _ => Err(Self::TAG.value_error()),
+        }
+    }
+}
+
+impl TryFrom<AnyRef<'_>> for GeneralizedTimeNanos {
+    type Error = Error;
+
+    fn try_from(any: AnyRef<'_>) -> Result<GeneralizedTimeNanos> {
+        any.decode_into()
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl From<GeneralizedTimeNanos> for SystemTime {
+    fn from(time: GeneralizedTimeNanos) -> SystemTime {
+        time.to_system_time()
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl TryFrom<SystemTime> for GeneralizedTimeNanos {
+    type Error = Error;
+
+    fn try_from(time: SystemTime) -> Result<GeneralizedTimeNanos> {
+        GeneralizedTimeNanos::from_system_time(time)
+    }
+}
+
+#[cfg(feature = "time")]
+#[cfg_attr(docsrs, doc(cfg(feature = "time")))]
+impl TryFrom<PrimitiveDateTime> for GeneralizedTimeNanos {
+    type Error = Error;
+
+    fn try_from(time: PrimitiveDateTime) -> Result<GeneralizedTimeNanos> {
+        GeneralizedTimeNanos::new(DateTime::try_from(time)?, time.nanosecond())
+    }
+}
+
+#[cfg(feature = "time")]
+#[cfg_attr(docsrs, doc(cfg(feature = "time")))]
+impl TryFrom<GeneralizedTimeNanos> for PrimitiveDateTime {
+    type Error = Error;
+
+    fn try_from(time: GeneralizedTimeNanos) -> Result<PrimitiveDateTime> {
+        let datetime = PrimitiveDateTime::try_from(time.datetime)?;
+        Ok(datetime.replace_time(datetime.time().replace_nanosecond(time.nanos)?))
+    }
+}
+
+#[cfg(feature = "time")]
+#[cfg_attr(docsrs, doc(cfg(feature = "time")))]
+impl TryFrom<OffsetDateTime> for GeneralizedTimeNanos {
+    type Error = Error;
+
+    fn try_from(time: OffsetDateTime) -> Result<GeneralizedTimeNanos> {
+        let time = time.to_offset(time::UtcOffset::UTC);
+        PrimitiveDateTime::new(time.date(), time.time()).try_into()
+    }
+}
+
+#[cfg(feature = "time")]
+#[cfg_attr(docsrs, doc(cfg(feature = "time")))]
+impl TryFrom<GeneralizedTimeNanos> for OffsetDateTime {
+    type Error = Error;
+
+    fn try_from(time: GeneralizedTimeNanos) -> Result<OffsetDateTime> {
+        Ok(PrimitiveDateTime::try_from(time)?.assume_utc())
+    }
+}
+
+/// Decode the `YYYYMMDDHHMMSS` components of a `GeneralizedTime`.
+fn decode_date_time(bytes: &[u8]) -> Result<DateTime> {
+    let tag = Tag::GeneralizedTime;
+
+    match *bytes {
+        [y1, y2, y3, y4, mon1, mon2, day1, day2, hour1, hour2, min1, min2, sec1, sec2] => {
+            let year = u16::from(datetime::decode_decimal(tag, y1, y2)?)
+                .checked_mul(100)
+                .and_then(|y| y.checked_add(datetime::decode_decimal(tag, y3, y4).ok()?.into()))
+                .ok_or(ErrorKind::DateTime)?;
+            let month = datetime::decode_decimal(tag, mon1, mon2)?;
+            let day = datetime::decode_decimal(tag, day1, day2)?;
+            let hour = datetime::decode_decimal(tag, hour1, hour2)?;
+            let minute = datetime::decode_decimal(tag, min1, min2)?;
+            let second = datetime::decode_decimal(tag, sec1, sec2)?;
+
+            DateTime::new(year, month, day, hour, minute, second).map_err(|_| tag.value_error())
+        }
+        _ => Err(tag.value_error()),
+    }
+}
+
+/// Encode the `YYYYMMDDHHMMSS` components of a `GeneralizedTime`.
+fn encode_date_time(writer: &mut dyn Writer, datetime: &DateTime) -> Result<()> {
+    let tag = Tag::GeneralizedTime;
+    let year_hi = u8::try_from(datetime.year() / 100)?;
+    let year_lo = u8::try_from(datetime.year() % 100)?;
+
+    datetime::encode_decimal(writer, tag, year_hi)?;
+    datetime::encode_decimal(writer, tag, year_lo)?;
+    datetime::encode_decimal(writer, tag, datetime.month())?;
+    datetime::encode_decimal(writer, tag, datetime.day())?;
+    datetime::encode_decimal(writer, tag, datetime.hour())?;
+    datetime::encode_decimal(writer, tag, datetime.minutes())?;
+    datetime::encode_decimal(writer, tag, datetime.seconds())
+}
+
 #[cfg(test)]
 mod tests {
-    use super::GeneralizedTime;
-    use crate::{Decode, Encode, SliceWriter};
+    use super::{GeneralizedTime, GeneralizedTimeNanos};
+    use crate::{asn1::AnyRef, DateTime, Decode, Encode, SliceWriter, Tag};
+    use core::time::Duration;
     use hex_literal::hex;
 
     #[test]
@@ -345,4 +669,81 @@ mod tests {
         utc_time.encode(&mut encoder).unwrap();
         assert_eq!(example_bytes, encoder.finish().unwrap());
     }
+
+    #[test]
+    fn infinity() {
+        let time = GeneralizedTime::from_der(b"\x18\x0f99991231235959Z").unwrap();
+        assert_eq!(time, GeneralizedTime::INFINITY);
+        assert_eq!(time.to_date_time(), DateTime::INFINITY);
+    }
+
+    #[test]
+    fn fractional_seconds() {
+        let time = GeneralizedTimeNanos::from_der(b"\x18\x1319910506234540.125Z").unwrap();
+        assert_eq!(time.to_date_time().seconds(), 40);
+        assert_eq!(time.nanos(), 125_000_000);
+        assert_eq!(
+            time.to_unix_duration(),
+            Duration::new(673573540, 125_000_000)
+        );
+
+        let mut buf = [0u8; 128];
+        let mut encoder = SliceWriter::new(&mut buf);
+        time.encode(&mut encoder).unwrap();
+        assert_eq!(encoder.finish().unwrap(), b"\x18\x1319910506234540.125Z");
+
+        // the fraction is omitted when zero
+        let time = GeneralizedTimeNanos::from_unix_duration(Duration::new(673573540, 0)).unwrap();
+        let mut buf = [0u8; 128];
+        let mut encoder = SliceWriter::new(&mut buf);
+        time.encode(&mut encoder).unwrap();
+        assert_eq!(encoder.finish().unwrap(), b"\x18\x0f19910506234540Z");
+        assert!(GeneralizedTime::try_from(time).is_ok());
+
+        let time = GeneralizedTimeNanos::from_unix_duration(Duration::new(0, 1)).unwrap();
+        let mut buf = [0u8; 128];
+        let mut encoder = SliceWriter::new(&mut buf);
+        time.encode(&mut encoder).unwrap();
+        assert_eq!(
+            encoder.finish().unwrap(),
+            b"\x18\x1919700101000000.000000001Z"
+        );
+        assert!(GeneralizedTime::try_from(time).is_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn system_time_before_unix_epoch() {
+        let system_time = std::time::UNIX_EPOCH - Duration::new(1, 250_000_000);
+        let time = GeneralizedTimeNanos::from_system_time(system_time).unwrap();
+        assert_eq!(time.to_date_time().unix_timestamp(), -2);
+        assert_eq!(time.nanos(), 750_000_000);
+        assert_eq!(time.to_system_time(), system_time);
+
+        let time = GeneralizedTime::from_der(b"\x18\x0f19500101000000Z").unwrap();
+        assert_eq!(time.to_date_time().unix_timestamp(), -631152000);
+        assert_eq!(
+            GeneralizedTime::from_system_time(time.to_system_time()).unwrap(),
+            time
+        );
+    }
+
+    #[test]
+    fn reject_non_canonical_fraction() {
+        for value in [
+            &b"19910506234540.120Z"[..],
+            b"19910506234540.Z",
+            b"19910506234540,125Z",
+            b"19910506234540.1234567891Z",
+            b"19910506234540.12a5Z",
+            b"19910506234540.125",
+        ] {
+            let any = AnyRef::new(Tag::GeneralizedTime, value).unwrap();
+            assert!(GeneralizedTimeNanos::try_from(any).is_err());
+        }
+
+        // RFC 5280 times never have fractional seconds
+        let any = AnyRef::new(Tag::GeneralizedTime, b"19910506234540.125Z").unwrap();
+        assert!(GeneralizedTime::try_from(any).is_err());
+    }
 }
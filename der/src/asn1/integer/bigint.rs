@@ -1,10 +1,14 @@
 //! "Big" ASN.1 `INTEGER` types.
 
-use super::uint;
+use super::{int, uint};
 use crate::{
     asn1::AnyRef, ByteSlice, DecodeValue, EncodeValue, Error, ErrorKind, FixedTag, Header, Length,
-    Reader, Result, Tag, Writer,
+    Reader, Result, Tag, ValueOrd, Writer,
 };
+use core::cmp::Ordering;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 
 /// "Big" unsigned ASN.1 `INTEGER` type.
 ///
@@ -92,13 +96,344 @@ impl<'a> FixedTag for UIntRef<'a> {
     const TAG: Tag = Tag::Integer;
 }
 
+/// Owned form of the "big" unsigned ASN.1 `INTEGER` type.
+///
+/// This type provides the same functionality as [`UIntRef`] but owns the
+/// backing data.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+pub struct UInt {
+    /// Inner value
+    inner: Vec<u8>,
+}
+
+#[cfg(feature = "alloc")]
+impl UInt {
+    /// Create a new [`UInt`] from a byte slice.
+    pub fn new(bytes: &[u8]) -> Result<Self> {
+        Ok(UIntRef::new(bytes)?.into())
+    }
+
+    /// Borrow the inner byte slice which contains the least significant bytes
+    /// of a big endian integer value with all leading zeros stripped.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.inner.as_slice()
+    }
+
+    /// Get the length of this [`UInt`] in bytes.
+    pub fn len(&self) -> Length {
+        UIntRef::from(self).len()
+    }
+
+    /// Is the inner byte slice empty?
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> DecodeValue<'a> for UInt {
+    fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> Result<Self> {
+        let bytes = reader.read_vec(header.length)?;
+        let result = Self::new(uint::decode_to_slice(&bytes)?)?;
+
+        // Ensure we compute the same encoded length as the original any value.
+        if result.value_len()? != header.length {
+            return Err(Self::TAG.non_canonical_error());
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl EncodeValue for UInt {
+    fn value_len(&self) -> Result<Length> {
+        UIntRef::from(self).value_len()
+    }
+
+    fn encode_value(&self, writer: &mut dyn Writer) -> Result<()> {
+        UIntRef::from(self).encode_value(writer)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl FixedTag for UInt {
+    const TAG: Tag = Tag::Integer;
+}
+
+#[cfg(feature = "alloc")]
+impl From<UIntRef<'_>> for UInt {
+    fn from(uint: UIntRef<'_>) -> UInt {
+        UInt {
+            inner: uint.as_bytes().into(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&'a UInt> for UIntRef<'a> {
+    fn from(uint: &'a UInt) -> UIntRef<'a> {
+        // Ensured to parse successfully in constructor
+        UIntRef::new(&uint.inner).expect("invalid INTEGER")
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl TryFrom<AnyRef<'_>> for UInt {
+    type Error = Error;
+
+    fn try_from(any: AnyRef<'_>) -> Result<UInt> {
+        any.decode_into()
+    }
+}
+
+/// "Big" signed ASN.1 `INTEGER` type.
+///
+/// Provides direct access to the underlying big endian bytes which comprise
+/// the two's complement encoding of a signed integer value, e.g. negative
+/// serial numbers, which [RFC 5280 Section 4.1.2.2] recommends tolerating.
+///
+/// [RFC 5280 Section 4.1.2.2]: https://datatracker.ietf.org/doc/html/rfc5280#section-4.1.2.2
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct IntRef<'a> {
+    /// Inner value
+    inner: ByteSlice<'a>,
+}
+
+impl<'a> IntRef<'a> {
+    /// Create a new [`IntRef`] from the big endian two's complement bytes of
+    /// an integer.
+    ///
+    /// Leading bytes which only extend the sign of the value are ignored.
+    pub fn new(bytes: &'a [u8]) -> Result<Self> {
+        let bytes = int::strip_sign_extension(bytes);
+        if bytes.is_empty() {
+            return Err(Self::TAG.length_error());
+        }
+
+        let inner = ByteSlice::new(bytes).map_err(|_| ErrorKind::Length { tag: Self::TAG })?;
+        Ok(Self { inner })
+    }
+
+    /// Borrow the inner byte slice which contains the big endian two's
+    /// complement bytes of the integer, with the minimum number of octets.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.inner.as_slice()
+    }
+
+    /// Is this integer negative?
+    pub fn is_negative(&self) -> bool {
+        super::is_highest_bit_set(self.as_bytes())
+    }
+
+    /// Get the length of this [`IntRef`] in bytes.
+    pub fn len(&self) -> Length {
+        self.inner.len()
+    }
+
+    /// Is the inner byte slice empty?
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+}
+
+impl<'a> DecodeValue<'a> for IntRef<'a> {
+    fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> Result<Self> {
+        let bytes = ByteSlice::decode_value(reader, header)?.as_slice();
+        Self::new(int::decode_to_slice(bytes)?)
+    }
+}
+
+impl<'a> EncodeValue for IntRef<'a> {
+    fn value_len(&self) -> Result<Length> {
+        Ok(self.len())
+    }
+
+    fn encode_value(&self, writer: &mut dyn Writer) -> Result<()> {
+        writer.write(self.as_bytes())
+    }
+}
+
+impl<'a> From<&IntRef<'a>> for IntRef<'a> {
+    fn from(value: &IntRef<'a>) -> IntRef<'a> {
+        *value
+    }
+}
+
+impl<'a> TryFrom<AnyRef<'a>> for IntRef<'a> {
+    type Error = Error;
+
+    fn try_from(any: AnyRef<'a>) -> Result<IntRef<'a>> {
+        any.decode_into()
+    }
+}
+
+impl<'a> TryFrom<IntRef<'a>> for i128 {
+    type Error = Error;
+
+    fn try_from(int: IntRef<'a>) -> Result<i128> {
+        let bytes = int.as_bytes();
+        let offset = 16usize
+            .checked_sub(bytes.len())
+            .ok_or(ErrorKind::Overflow)?;
+
+        let mut output = [if int.is_negative() { 0xFF } else { 0 }; 16];
+        output[offset..].copy_from_slice(bytes);
+        Ok(i128::from_be_bytes(output))
+    }
+}
+
+impl<'a> FixedTag for IntRef<'a> {
+    const TAG: Tag = Tag::Integer;
+}
+
+impl<'a> ValueOrd for IntRef<'a> {
+    fn value_cmp(&self, other: &Self) -> Result<Ordering> {
+        Ok(self.as_bytes().cmp(other.as_bytes()))
+    }
+}
+
+/// Owned form of the "big" signed ASN.1 `INTEGER` type.
+///
+/// This type provides the same functionality as [`IntRef`] but owns the
+/// backing data.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Int {
+    /// Inner value
+    inner: Vec<u8>,
+}
+
+#[cfg(feature = "alloc")]
+impl Int {
+    /// Create a new [`Int`] from the big endian two's complement bytes of an
+    /// integer.
+    pub fn new(bytes: &[u8]) -> Result<Self> {
+        Ok(IntRef::new(bytes)?.into())
+    }
+
+    /// Borrow the inner byte slice which contains the big endian two's
+    /// complement bytes of the integer, with the minimum number of octets.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.inner.as_slice()
+    }
+
+    /// Is this integer negative?
+    pub fn is_negative(&self) -> bool {
+        IntRef::from(self).is_negative()
+    }
+
+    /// Get the length of this [`Int`] in bytes.
+    pub fn len(&self) -> Length {
+        IntRef::from(self).len()
+    }
+
+    /// Is the inner byte slice empty?
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> DecodeValue<'a> for Int {
+    fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> Result<Self> {
+        IntRef::decode_value(reader, header).map(Into::into)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl EncodeValue for Int {
+    fn value_len(&self) -> Result<Length> {
+        IntRef::from(self).value_len()
+    }
+
+    fn encode_value(&self, writer: &mut dyn Writer) -> Result<()> {
+        IntRef::from(self).encode_value(writer)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl FixedTag for Int {
+    const TAG: Tag = Tag::Integer;
+}
+
+#[cfg(feature = "alloc")]
+impl ValueOrd for Int {
+    fn value_cmp(&self, other: &Self) -> Result<Ordering> {
+        IntRef::from(self).value_cmp(&other.into())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<IntRef<'_>> for Int {
+    fn from(int: IntRef<'_>) -> Int {
+        Int {
+            inner: int.as_bytes().into(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&'a Int> for IntRef<'a> {
+    fn from(int: &'a Int) -> IntRef<'a> {
+        // Ensured to parse successfully in constructor
+        IntRef::new(&int.inner).expect("invalid INTEGER")
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<UIntRef<'_>> for Int {
+    fn from(uint: UIntRef<'_>) -> Int {
+        let mut inner = Vec::new();
+        if super::is_highest_bit_set(uint.as_bytes()) {
+            inner.push(0);
+        }
+        inner.extend_from_slice(uint.as_bytes());
+
+        // An empty `UIntRef` is zero
+        Int::new(&inner).unwrap_or_else(|_| Int {
+            inner: alloc::vec![0],
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<i128> for Int {
+    fn from(value: i128) -> Int {
+        // The bytes are never empty
+        Int::new(&value.to_be_bytes()).expect("invalid INTEGER")
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl TryFrom<&Int> for i128 {
+    type Error = Error;
+
+    fn try_from(int: &Int) -> Result<i128> {
+        IntRef::from(int).try_into()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl TryFrom<AnyRef<'_>> for Int {
+    type Error = Error;
+
+    fn try_from(any: AnyRef<'_>) -> Result<Int> {
+        any.decode_into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::UIntRef;
+    use super::{IntRef, UIntRef};
     use crate::{
         asn1::{integer::tests::*, AnyRef},
-        Decode, Encode, ErrorKind, SliceWriter, Tag,
+        Decode, DerOrd, Encode, ErrorKind, SliceWriter, Tag, ValueOrd,
     };
+    use core::cmp::Ordering;
 
     #[test]
     fn decode_uint_bytes() {
@@ -139,6 +474,22 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn owned_uint_round_trip() {
+        use super::UInt;
+
+        for &example in &[I0_BYTES, I128_BYTES, I255_BYTES, I256_BYTES, I32767_BYTES] {
+            let uint = UInt::from_der(example).unwrap();
+            assert_eq!(
+                uint.as_bytes(),
+                UIntRef::from_der(example).unwrap().as_bytes()
+            );
+            assert_eq!(UIntRef::from(&uint), UIntRef::from_der(example).unwrap());
+            assert_eq!(uint.to_vec().unwrap(), example);
+        }
+    }
+
     #[test]
     fn reject_oversize_without_extra_zero() {
         let err = UIntRef::try_from(AnyRef::new(Tag::Integer, &[0x81]).unwrap())
@@ -147,4 +498,108 @@ mod tests {
 
         assert_eq!(err.kind(), ErrorKind::Value { tag: Tag::Integer });
     }
+
+    #[test]
+    fn decode_int_bytes() {
+        assert_eq!(&[0], IntRef::from_der(I0_BYTES).unwrap().as_bytes());
+        assert_eq!(
+            &[0x00, 0x80],
+            IntRef::from_der(I128_BYTES).unwrap().as_bytes()
+        );
+        assert_eq!(&[0x80], IntRef::from_der(INEG128_BYTES).unwrap().as_bytes());
+        assert_eq!(
+            &[0xFF, 0x7F],
+            IntRef::from_der(INEG129_BYTES).unwrap().as_bytes()
+        );
+        assert!(IntRef::from_der(INEG128_BYTES).unwrap().is_negative());
+        assert!(!IntRef::from_der(I255_BYTES).unwrap().is_negative());
+    }
+
+    #[test]
+    fn encode_int_bytes() {
+        for &example in &[
+            I0_BYTES,
+            I127_BYTES,
+            I128_BYTES,
+            I256_BYTES,
+            INEG128_BYTES,
+            INEG129_BYTES,
+            I32767_BYTES,
+            INEG32768_BYTES,
+        ] {
+            let int = IntRef::from_der(example).unwrap();
+
+            let mut buf = [0u8; 128];
+            let mut encoder = SliceWriter::new(&mut buf);
+            int.encode(&mut encoder).unwrap();
+
+            let result = encoder.finish().unwrap();
+            assert_eq!(example, result);
+        }
+    }
+
+    #[test]
+    fn reject_non_canonical_int() {
+        for bytes in [&[][..], &[0x00, 0x7F], &[0xFF, 0x80]] {
+            assert!(IntRef::try_from(AnyRef::new(Tag::Integer, bytes).unwrap()).is_err());
+        }
+
+        // redundant sign extension is stripped when constructed directly
+        assert_eq!(
+            IntRef::new(&[0xFF, 0xFF, 0x80]).unwrap().as_bytes(),
+            &[0x80]
+        );
+        assert!(IntRef::new(&[]).is_err());
+    }
+
+    #[test]
+    fn int_to_i128() {
+        let value = |bytes| i128::try_from(IntRef::from_der(bytes).unwrap()).unwrap();
+        assert_eq!(value(I0_BYTES), 0);
+        assert_eq!(value(I255_BYTES), 255);
+        assert_eq!(value(INEG129_BYTES), -129);
+        assert_eq!(value(INEG32768_BYTES), -32768);
+
+        let too_big = IntRef::new(&[0x01; 17]).unwrap();
+        assert_eq!(
+            i128::try_from(too_big).err().unwrap().kind(),
+            ErrorKind::Overflow
+        );
+    }
+
+    #[test]
+    fn int_value_cmp() {
+        let int = |bytes| IntRef::from_der(bytes).unwrap();
+        assert_eq!(
+            int(I256_BYTES).value_cmp(&int(I32767_BYTES)).unwrap(),
+            Ordering::Less
+        );
+        assert_eq!(
+            int(I256_BYTES).value_cmp(&int(I256_BYTES)).unwrap(),
+            Ordering::Equal
+        );
+
+        // DER ordering compares the length of the encoding first
+        assert_eq!(
+            int(I127_BYTES).der_cmp(&int(I128_BYTES)).unwrap(),
+            Ordering::Less
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn owned_int_round_trip() {
+        use super::{Int, UInt};
+
+        for value in [0, 1, -1, 127, 128, -128, -129, i128::MAX, i128::MIN] {
+            let int = Int::from(value);
+            assert_eq!(i128::try_from(&int).unwrap(), value);
+            assert_eq!(Int::from_der(&int.to_vec().unwrap()).unwrap(), int);
+        }
+
+        assert_eq!(Int::from(-129).as_bytes(), &[0xFF, 0x7F]);
+
+        let uint = UInt::from_der(I128_BYTES).unwrap();
+        assert_eq!(Int::from(UIntRef::from(&uint)).as_bytes(), &[0x00, 0x80]);
+    }
 }
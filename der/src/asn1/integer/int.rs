@@ -1,7 +1,20 @@
 //! Support for encoding negative integers
 
 use super::is_highest_bit_set;
-use crate::{ErrorKind, Length, Result, Writer};
+use crate::{ErrorKind, Length, Result, Tag, Writer};
+
+/// Decode a signed integer into a big endian byte slice, ensuring it is
+/// encoded with the minimum number of octets.
+pub(super) fn decode_to_slice(bytes: &[u8]) -> Result<&[u8]> {
+    // The first nine bits of the two's complement encoding must not be all
+    // zeroes or all ones.
+    match bytes {
+        [] => Err(Tag::Integer.non_canonical_error()),
+        [0x00, byte, ..] if *byte < 0x80 => Err(Tag::Integer.non_canonical_error()),
+        [0xFF, byte, ..] if *byte >= 0x80 => Err(Tag::Integer.non_canonical_error()),
+        _ => Ok(bytes),
+    }
+}
 
 /// Decode an unsigned integer of the specified size.
 ///
@@ -40,6 +53,26 @@ pub(super) fn encoded_len(bytes: &[u8]) -> Result<Length> {
     Length::try_from(strip_leading_ones(bytes).len())
 }
 
+/// Strip the leading bytes which only extend the sign of the given two's
+/// complement big endian bytes.
+pub(super) fn strip_sign_extension(mut bytes: &[u8]) -> &[u8] {
+    while let Some((byte, rest)) = bytes.split_first() {
+        let redundant = match *byte {
+            0x00 => !rest.is_empty() && !is_highest_bit_set(rest),
+            0xFF => is_highest_bit_set(rest),
+            _ => false,
+        };
+
+        if !redundant {
+            break;
+        }
+
+        bytes = rest;
+    }
+
+    bytes
+}
+
 /// Strip the leading all-ones bytes from the given byte slice.
 fn strip_leading_ones(mut bytes: &[u8]) -> &[u8] {
     while let Some((byte, rest)) = bytes.split_first() {
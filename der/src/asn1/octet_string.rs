@@ -170,6 +170,15 @@ impl FixedTag for OctetString {
     const TAG: Tag = Tag::OctetString;
 }
 
+#[cfg(feature = "alloc")]
+impl From<OctetStringRef<'_>> for OctetString {
+    fn from(octet_string: OctetStringRef<'_>) -> OctetString {
+        OctetString {
+            inner: octet_string.as_bytes().into(),
+        }
+    }
+}
+
 #[cfg(feature = "alloc")]
 impl<'a> From<&'a OctetString> for OctetStringRef<'a> {
     fn from(octet_string: &'a OctetString) -> OctetStringRef<'a> {
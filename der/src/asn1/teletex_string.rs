@@ -0,0 +1,170 @@
+//! ASN.1 `TeletexString` support.
+
+use crate::{
+    asn1::AnyRef, ord::OrdIsValueOrd, ByteSlice, DecodeValue, EncodeValue, Error, FixedTag, Header,
+    Length, Reader, Result, Tag, Writer,
+};
+use core::fmt;
+
+/// ASN.1 `TeletexString` type.
+///
+/// For UTF-8, use [`Utf8StringRef`][`crate::asn1::Utf8StringRef`] instead.
+///
+/// This is a zero-copy reference type which borrows from the input data. As
+/// the encoding is not UTF-8, the string can't be borrowed as a `str`: use
+/// [`TeletexStringRef::chars`] or the [`Display`][`fmt::Display`] impl
+/// instead.
+///
+/// # Supported characters
+///
+/// The standard defines a complex character set allowed in this type. However, quoting the ASN.1
+/// mailing list, "a sizable volume of software in the world treats TeletexString (T61String) as a
+/// simple 8-bit string with mostly Windows Latin 1 (superset of iso-8859-1) encoding".
+///
+/// Accordingly, any byte is accepted, and bytes are decoded as ISO 8859-1
+/// (Latin-1) characters.
+#[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Ord)]
+pub struct TeletexStringRef<'a> {
+    /// Inner value
+    inner: ByteSlice<'a>,
+}
+
+impl<'a> TeletexStringRef<'a> {
+    /// Create a new ASN.1 `TeletexString`.
+    pub fn new<T>(input: &'a T) -> Result<Self>
+    where
+        T: AsRef<[u8]> + ?Sized,
+    {
+        ByteSlice::new(input.as_ref())
+            .map(|inner| Self { inner })
+            .map_err(|_| Self::TAG.length_error())
+    }
+
+    /// Borrow the string as bytes.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.inner.as_slice()
+    }
+
+    /// Iterate over the characters of the string, decoding each byte as a
+    /// Latin-1 character.
+    pub fn chars(&self) -> impl 'a + Iterator<Item = char> {
+        self.as_bytes().iter().map(|&b| char::from(b))
+    }
+
+    /// Get the length of the inner byte slice.
+    pub fn len(&self) -> Length {
+        self.inner.len()
+    }
+
+    /// Is the inner string empty?
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+}
+
+impl AsRef<[u8]> for TeletexStringRef<'_> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl<'a> DecodeValue<'a> for TeletexStringRef<'a> {
+    fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> Result<Self> {
+        Self::new(ByteSlice::decode_value(reader, header)?.as_slice())
+    }
+}
+
+impl<'a> EncodeValue for TeletexStringRef<'a> {
+    fn value_len(&self) -> Result<Length> {
+        self.inner.value_len()
+    }
+
+    fn encode_value(&self, writer: &mut dyn Writer) -> Result<()> {
+        self.inner.encode_value(writer)
+    }
+}
+
+impl FixedTag for TeletexStringRef<'_> {
+    const TAG: Tag = Tag::TeletexString;
+}
+
+impl OrdIsValueOrd for TeletexStringRef<'_> {}
+
+impl<'a> From<&TeletexStringRef<'a>> for TeletexStringRef<'a> {
+    fn from(value: &TeletexStringRef<'a>) -> TeletexStringRef<'a> {
+        *value
+    }
+}
+
+impl<'a> TryFrom<AnyRef<'a>> for TeletexStringRef<'a> {
+    type Error = Error;
+
+    fn try_from(any: AnyRef<'a>) -> Result<TeletexStringRef<'a>> {
+        any.decode_into()
+    }
+}
+
+impl<'a> From<TeletexStringRef<'a>> for AnyRef<'a> {
+    fn from(teletex_string: TeletexStringRef<'a>) -> AnyRef<'a> {
+        AnyRef::from_tag_and_value(Tag::TeletexString, teletex_string.inner)
+    }
+}
+
+impl<'a> From<TeletexStringRef<'a>> for &'a [u8] {
+    fn from(teletex_string: TeletexStringRef<'a>) -> &'a [u8] {
+        teletex_string.as_bytes()
+    }
+}
+
+impl<'a> fmt::Display for TeletexStringRef<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.chars().try_for_each(|c| fmt::Write::write_char(f, c))
+    }
+}
+
+impl<'a> fmt::Debug for TeletexStringRef<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("TeletexString(\"")?;
+        for c in self.chars().flat_map(char::escape_debug) {
+            fmt::Write::write_char(f, c)?;
+        }
+        f.write_str("\")")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TeletexStringRef;
+    use crate::{Decode, Encode, SliceWriter};
+    use hex_literal::hex;
+
+    #[test]
+    fn parse_bytes() {
+        let example_bytes = &[
+            0x14, 0x0b, 0x54, 0x65, 0x73, 0x74, 0x20, 0x55, 0x73, 0x65, 0x72, 0x20, 0x31,
+        ];
+
+        let teletex_string = TeletexStringRef::from_der(example_bytes).unwrap();
+        assert!(teletex_string.chars().eq("Test User 1".chars()));
+        let mut out = [0_u8; 30];
+        let mut writer = SliceWriter::new(&mut out);
+        writer.encode(&teletex_string).unwrap();
+        let encoded = writer.finish().unwrap();
+        assert_eq!(encoded, example_bytes);
+    }
+
+    #[test]
+    fn parse_latin1() {
+        let example_bytes = hex!("14 07 4d fc 6c 6c 65 72 a9");
+
+        let teletex_string = TeletexStringRef::from_der(&example_bytes).unwrap();
+        assert_eq!(teletex_string.as_bytes(), &example_bytes[2..]);
+        assert!(teletex_string.chars().eq("Müller©".chars()));
+
+        let mut buf = [0u8; 16];
+        assert_eq!(
+            teletex_string.encode_to_slice(&mut buf).unwrap(),
+            &example_bytes
+        );
+    }
+}
@@ -0,0 +1,171 @@
+//! ASN.1 `UniversalString` support.
+
+use crate::{
+    asn1::AnyRef, ord::OrdIsValueOrd, ByteSlice, DecodeValue, EncodeValue, Error, FixedTag, Header,
+    Length, Reader, Result, Tag, Writer,
+};
+use core::{char, fmt};
+
+/// ASN.1 `UniversalString` type.
+///
+/// Encodes characters of ISO 10646 as UCS-4, i.e. as four bytes in big
+/// endian order per character.
+///
+/// For UTF-8, use [`Utf8StringRef`][`crate::asn1::Utf8StringRef`] instead.
+///
+/// This is a zero-copy reference type which borrows from the input data. As
+/// the encoding is not UTF-8, the string can't be borrowed as a `str`: use
+/// [`UniversalStringRef::chars`] or the [`Display`][`fmt::Display`] impl instead.
+#[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Ord)]
+pub struct UniversalStringRef<'a> {
+    /// Inner value
+    inner: ByteSlice<'a>,
+}
+
+impl<'a> UniversalStringRef<'a> {
+    /// Create a new ASN.1 `UniversalString` from its UCS-4 encoding.
+    pub fn new<T>(input: &'a T) -> Result<Self>
+    where
+        T: AsRef<[u8]> + ?Sized,
+    {
+        let input = input.as_ref();
+
+        if input.len() % 4 != 0 || decode(input).any(|c| c.is_err()) {
+            return Err(Self::TAG.value_error());
+        }
+
+        ByteSlice::new(input)
+            .map(|inner| Self { inner })
+            .map_err(|_| Self::TAG.length_error())
+    }
+
+    /// Borrow the UCS-4 encoding of the string.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.inner.as_slice()
+    }
+
+    /// Iterate over the characters of the string.
+    pub fn chars(&self) -> impl 'a + Iterator<Item = char> {
+        // Ensured to be valid in constructor
+        decode(self.as_bytes()).filter_map(|c| c.ok())
+    }
+
+    /// Get the length of the inner byte slice.
+    pub fn len(&self) -> Length {
+        self.inner.len()
+    }
+
+    /// Is the inner string empty?
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+}
+
+fn decode(bytes: &[u8]) -> impl '_ + Iterator<Item = core::result::Result<char, u32>> {
+    bytes
+        .chunks_exact(4)
+        .map(|unit| u32::from_be_bytes([unit[0], unit[1], unit[2], unit[3]]))
+        .map(|unit| char::from_u32(unit).ok_or(unit))
+}
+
+impl AsRef<[u8]> for UniversalStringRef<'_> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl<'a> DecodeValue<'a> for UniversalStringRef<'a> {
+    fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> Result<Self> {
+        Self::new(ByteSlice::decode_value(reader, header)?.as_slice())
+    }
+}
+
+impl<'a> EncodeValue for UniversalStringRef<'a> {
+    fn value_len(&self) -> Result<Length> {
+        self.inner.value_len()
+    }
+
+    fn encode_value(&self, writer: &mut dyn Writer) -> Result<()> {
+        self.inner.encode_value(writer)
+    }
+}
+
+impl FixedTag for UniversalStringRef<'_> {
+    const TAG: Tag = Tag::UniversalString;
+}
+
+impl OrdIsValueOrd for UniversalStringRef<'_> {}
+
+impl<'a> From<&UniversalStringRef<'a>> for UniversalStringRef<'a> {
+    fn from(value: &UniversalStringRef<'a>) -> UniversalStringRef<'a> {
+        *value
+    }
+}
+
+impl<'a> TryFrom<AnyRef<'a>> for UniversalStringRef<'a> {
+    type Error = Error;
+
+    fn try_from(any: AnyRef<'a>) -> Result<UniversalStringRef<'a>> {
+        any.decode_into()
+    }
+}
+
+impl<'a> From<UniversalStringRef<'a>> for AnyRef<'a> {
+    fn from(universal_string: UniversalStringRef<'a>) -> AnyRef<'a> {
+        AnyRef::from_tag_and_value(Tag::UniversalString, universal_string.inner)
+    }
+}
+
+impl<'a> From<UniversalStringRef<'a>> for &'a [u8] {
+    fn from(universal_string: UniversalStringRef<'a>) -> &'a [u8] {
+        universal_string.as_bytes()
+    }
+}
+
+impl<'a> fmt::Display for UniversalStringRef<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.chars().try_for_each(|c| fmt::Write::write_char(f, c))
+    }
+}
+
+impl<'a> fmt::Debug for UniversalStringRef<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("UniversalString(\"")?;
+        for c in self.chars().flat_map(char::escape_debug) {
+            fmt::Write::write_char(f, c)?;
+        }
+        f.write_str("\")")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UniversalStringRef;
+    use crate::{Decode, Encode, Tag};
+    use hex_literal::hex;
+
+    #[test]
+    fn parse_bytes() {
+        let example_bytes = hex!("1c 10 00000054 000000e9 000020ac 0001f600");
+
+        let universal_string = UniversalStringRef::from_der(&example_bytes).unwrap();
+        assert!(universal_string.chars().eq("Té€😀".chars()));
+        assert_eq!(universal_string.as_bytes(), &example_bytes[2..]);
+
+        let mut buf = [0u8; 18];
+        assert_eq!(
+            universal_string.encode_to_slice(&mut buf).unwrap(),
+            &example_bytes
+        );
+    }
+
+    #[test]
+    fn reject_invalid() {
+        // truncated
+        assert!(UniversalStringRef::new(&[0x00, 0x00, 0x54]).is_err());
+
+        // beyond the last code point
+        let err = UniversalStringRef::new(&hex!("00110000")).unwrap_err();
+        assert_eq!(err.kind(), Tag::UniversalString.value_error().kind());
+    }
+}
@@ -12,6 +12,9 @@ use core::time::Duration;
 #[cfg(feature = "std")]
 use std::time::SystemTime;
 
+/// Minimum year that can be represented as a `UTCTime`.
+pub const MIN_YEAR: u16 = 1950;
+
 /// Maximum year that can be represented as a `UTCTime`.
 pub const MAX_YEAR: u16 = 2049;
 
@@ -39,7 +42,7 @@ impl UtcTime {
 
     /// Create a [`UtcTime`] from a [`DateTime`].
     pub fn from_date_time(datetime: DateTime) -> Result<Self> {
-        if datetime.year() <= MAX_YEAR {
+        if (MIN_YEAR..=MAX_YEAR).contains(&datetime.year()) {
             Ok(Self(datetime))
         } else {
             Err(Self::TAG.value_error())
@@ -57,7 +60,8 @@ impl UtcTime {
         DateTime::from_unix_duration(unix_duration)?.try_into()
     }
 
-    /// Get the duration of this timestamp since `UNIX_EPOCH`.
+    /// Get the duration of this timestamp since `UNIX_EPOCH`, which is zero
+    /// for times before it, as described in [`DateTime::unix_duration`].
     pub fn to_unix_duration(&self) -> Duration {
         self.0.unix_duration()
     }
@@ -108,7 +112,7 @@ impl<'a> DecodeValue<'a> for UtcTime {
 
                 DateTime::new(year, month, day, hour, minute, second)
                     .map_err(|_| Self::TAG.value_error())
-                    .and_then(|dt| Self::from_unix_duration(dt.unix_duration()))
+                    .and_then(Self::from_date_time)
             }
             _ => Err(Self::TAG.value_error()),
         }
@@ -212,4 +216,19 @@ mod tests {
         utc_time.encode(&mut encoder).unwrap();
         assert_eq!(example_bytes, encoder.finish().unwrap());
     }
+
+    #[test]
+    fn before_unix_epoch() {
+        let example_bytes = hex!("17 0d 35 30 30 31 30 31 30 30 30 30 30 30 5a");
+        let utc_time = UtcTime::from_der(&example_bytes).unwrap();
+        assert_eq!(utc_time.to_date_time().year(), 1950);
+        assert_eq!(utc_time.to_date_time().unix_timestamp(), -631152000);
+
+        let mut buf = [0u8; 128];
+        assert_eq!(utc_time.encode_to_slice(&mut buf).unwrap(), example_bytes);
+
+        let example_bytes = hex!("17 0d 36 39 31 32 33 31 32 33 35 39 35 39 5a");
+        let utc_time = UtcTime::from_der(&example_bytes).unwrap();
+        assert_eq!(utc_time.to_date_time().unix_timestamp(), -1);
+    }
 }
@@ -0,0 +1,696 @@
+//! Basic Encoding Rules (BER) support.
+//!
+//! This crate decodes DER, which is a restricted form of BER. Some encoders,
+//! notably streaming CMS implementations and legacy certificate issuers,
+//! produce BER instead, using:
+//!
+//! - indefinite lengths terminated by end-of-contents octets
+//! - length encodings which use more octets than necessary
+//! - constructed encodings of strings, split into several segments
+//!
+//! [`BerReader`] reads such input element by element, and [`to_der`] (or
+//! [`Document::from_ber`]) re-encodes it as DER so that it can be decoded
+//! with the types in this crate:
+//!
+//! ```
+//! # #[cfg(feature = "alloc")]
+//! # {
+//! use der::{asn1::OctetStringRef, Document};
+//!
+//! // SEQUENCE { OCTET STRING } with an indefinite length and a constructed
+//! // OCTET STRING made of two segments
+//! let ber = [
+//!     0x30, 0x80, 0x24, 0x80, 0x04, 0x01, 0x01, 0x04, 0x01, 0x02, 0x00, 0x00, 0x00, 0x00,
+//! ];
+//!
+//! let doc = Document::from_ber(&ber).unwrap();
+//! assert_eq!(doc.as_bytes(), [0x30, 0x04, 0x04, 0x02, 0x01, 0x02]);
+//!
+//! let strings: Vec<OctetStringRef<'_>> = doc.decode_msg().unwrap();
+//! assert_eq!(strings[0].as_bytes(), [0x01, 0x02]);
+//! # }
+//! ```
+//!
+//! The conversion is done without knowledge of the ASN.1 schema, so it can
+//! only apply the rules of [X.690 Section 10] and [Section 11] which do not
+//! depend on it: besides the above, `BOOLEAN` values are normalized, unused
+//! bits of `BIT STRING`s are cleared, and the elements of `SET`s are sorted.
+//! Constructed strings with an `IMPLICIT` context-specific tag and
+//! components equal to their `DEFAULT` value are left as they are.
+//!
+//! [X.690 Section 10]: https://www.itu.int/rec/T-REC-X.690
+//! [Section 11]: https://www.itu.int/rec/T-REC-X.690
+
+use crate::{Error, ErrorKind, Length, Result, Tag};
+
+#[cfg(feature = "alloc")]
+use {
+    crate::{Document, Encode},
+    alloc::vec::Vec,
+};
+
+/// Bit which marks an identifier octet as constructed.
+const CONSTRUCTED_FLAG: u8 = 0x20;
+
+/// Bits of an identifier octet encoding the class.
+const CLASS_MASK: u8 = 0xC0;
+
+/// Bits of an identifier octet encoding the tag number.
+const NUMBER_MASK: u8 = 0x1F;
+
+/// Maximum nesting depth of constructed encodings.
+const MAX_DEPTH: usize = 64;
+
+/// Universal tag number of `BOOLEAN`.
+#[cfg(feature = "alloc")]
+const BOOLEAN: u8 = 1;
+
+/// Universal tag number of `BIT STRING`.
+#[cfg(feature = "alloc")]
+const BIT_STRING: u8 = 3;
+
+/// Universal tag number of `SEQUENCE` and `SEQUENCE OF`.
+#[cfg(feature = "alloc")]
+const SEQUENCE: u8 = 16;
+
+/// Universal tag number of `SET` and `SET OF`.
+#[cfg(feature = "alloc")]
+const SET: u8 = 17;
+
+/// Reader for BER-encoded input.
+///
+/// Reads one complete element (tag, length and contents) at a time.
+#[derive(Clone, Debug)]
+pub struct BerReader<'a> {
+    /// Input being read.
+    bytes: &'a [u8],
+
+    /// Position within `bytes`.
+    position: usize,
+
+    /// Offset of `bytes` within the outermost input, for error reporting.
+    offset: usize,
+
+    /// Nesting depth of `bytes` within the outermost input.
+    depth: usize,
+}
+
+impl<'a> BerReader<'a> {
+    /// Create a new reader for the given byte slice.
+    pub fn new(bytes: &'a [u8]) -> Result<Self> {
+        Length::try_from(bytes.len())?;
+
+        Ok(Self {
+            bytes,
+            position: 0,
+            offset: 0,
+            depth: 0,
+        })
+    }
+
+    /// Have we read all of the input data?
+    pub fn is_finished(&self) -> bool {
+        self.position == self.bytes.len()
+    }
+
+    /// Get the position within the input.
+    pub fn position(&self) -> Length {
+        Length::try_from(self.offset.saturating_add(self.position)).unwrap_or(Length::MAX)
+    }
+
+    /// Read the next element.
+    pub fn read_element(&mut self) -> Result<BerElement<'a>> {
+        let start = self.position();
+        let identifier = self.read_byte()?;
+
+        if identifier & NUMBER_MASK == NUMBER_MASK {
+            return Err(ErrorKind::TagNumberInvalid.at(start));
+        }
+
+        if identifier == 0 {
+            return Err(ErrorKind::TagUnknown { byte: identifier }.at(start));
+        }
+
+        let constructed = identifier & CONSTRUCTED_FLAG != 0;
+        let contents_offset;
+
+        let contents = match self.read_length()? {
+            Some(length) => {
+                contents_offset = self.offset.saturating_add(self.position);
+                self.read_slice(length)?
+            }
+            None if !constructed => return Err(ErrorKind::IndefiniteLength.at(start)),
+            None => {
+                contents_offset = self.offset.saturating_add(self.position);
+                let mut nested = self.nested(&self.bytes[self.position..])?;
+
+                // Skip over the nested elements to find the end-of-contents
+                loop {
+                    if nested.bytes[nested.position..].starts_with(&[0, 0]) {
+                        break;
+                    }
+
+                    if nested.is_finished() {
+                        return Err(ErrorKind::IndefiniteLength.at(start));
+                    }
+
+                    nested.read_element()?;
+                }
+
+                let contents = &self.bytes[self.position..][..nested.position];
+                self.position = self
+                    .position
+                    .saturating_add(nested.position)
+                    .saturating_add(2);
+                contents
+            }
+        };
+
+        Ok(BerElement {
+            identifier,
+            contents,
+            offset: start,
+            contents_offset,
+            depth: self.depth,
+        })
+    }
+
+    /// Create a reader for input nested one level deeper than this one.
+    fn nested(&self, bytes: &'a [u8]) -> Result<Self> {
+        if self.depth == MAX_DEPTH {
+            return Err(ErrorKind::Overlength.at(self.position()));
+        }
+
+        Ok(Self {
+            bytes,
+            position: 0,
+            offset: self.offset.saturating_add(self.position),
+            depth: self.depth.saturating_add(1),
+        })
+    }
+
+    /// Read a single byte.
+    fn read_byte(&mut self) -> Result<u8> {
+        match self.bytes.get(self.position) {
+            Some(&byte) => {
+                self.position = self.position.saturating_add(1);
+                Ok(byte)
+            }
+            None => Err(Error::incomplete(self.position())),
+        }
+    }
+
+    /// Read the given number of bytes.
+    fn read_slice(&mut self, length: Length) -> Result<&'a [u8]> {
+        let length = usize::try_from(length)?;
+
+        match self
+            .bytes
+            .get(self.position..)
+            .and_then(|b| b.get(..length))
+        {
+            Some(slice) => {
+                self.position = self.position.saturating_add(length);
+                Ok(slice)
+            }
+            None => Err(ErrorKind::Incomplete {
+                expected_len: (self.position() + Length::try_from(length)?)?,
+                actual_len: Length::try_from(self.offset.saturating_add(self.bytes.len()))?,
+            }
+            .at(self.position())),
+        }
+    }
+
+    /// Read length octets, returning `None` for an indefinite length.
+    ///
+    /// Unlike DER, BER allows the long form to be used for any length, and
+    /// with leading zero octets.
+    fn read_length(&mut self) -> Result<Option<Length>> {
+        let start = self.position();
+
+        match self.read_byte()? {
+            len if len < 0x80 => Ok(Some(len.into())),
+            0x80 => Ok(None),
+            // X.690 Section 8.1.3.5: the value 0xFF is reserved
+            0xFF => Err(ErrorKind::Overlength.at(start)),
+            initial => {
+                let mut length = 0u32;
+
+                for _ in 0..(initial & 0x7F) {
+                    length = length
+                        .checked_mul(0x100)
+                        .ok_or_else(|| ErrorKind::Overlength.at(start))?
+                        | u32::from(self.read_byte()?);
+                }
+
+                Length::try_from(length)
+                    .map(Some)
+                    .map_err(|_| ErrorKind::Overlength.at(start))
+            }
+        }
+    }
+}
+
+/// BER-encoded element.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct BerElement<'a> {
+    /// Identifier octet.
+    identifier: u8,
+
+    /// Contents octets, excluding any end-of-contents octets.
+    contents: &'a [u8],
+
+    /// Offset of the element within the outermost input.
+    offset: Length,
+
+    /// Offset of the contents within the outermost input.
+    contents_offset: usize,
+
+    /// Nesting depth of the element.
+    depth: usize,
+}
+
+impl<'a> BerElement<'a> {
+    /// Get the identifier octet of this element.
+    pub fn identifier(&self) -> u8 {
+        self.identifier
+    }
+
+    /// Get the [`Tag`] of the DER encoding of this element.
+    ///
+    /// This is the tag of the identifier octet, except that the constructed
+    /// encodings of universal string types map to their primitive tag.
+    pub fn tag(&self) -> Result<Tag> {
+        if self.is_universal_string() {
+            Tag::try_from(self.identifier & !CONSTRUCTED_FLAG)
+        } else {
+            Tag::try_from(self.identifier)
+        }
+    }
+
+    /// Is this element constructed? (vs primitive).
+    pub fn is_constructed(&self) -> bool {
+        self.identifier & CONSTRUCTED_FLAG != 0
+    }
+
+    /// Get the contents octets of this element.
+    ///
+    /// For constructed elements, these are the BER encodings of the nested
+    /// elements.
+    pub fn contents(&self) -> &'a [u8] {
+        self.contents
+    }
+
+    /// Read the elements nested in this constructed element.
+    pub fn elements(&self) -> Result<BerReader<'a>> {
+        if !self.is_constructed() {
+            return Err(ErrorKind::TagUnknown {
+                byte: self.identifier,
+            }
+            .at(self.position()));
+        }
+
+        if self.depth == MAX_DEPTH {
+            return Err(ErrorKind::Overlength.at(self.position()));
+        }
+
+        Ok(BerReader {
+            bytes: self.contents,
+            position: 0,
+            offset: self.contents_offset,
+            depth: self.depth.saturating_add(1),
+        })
+    }
+
+    /// Get the DER encoding of this element.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn to_der(&self) -> Result<Vec<u8>> {
+        let mut der = Vec::new();
+        self.encode_der(&mut der)?;
+        Ok(der)
+    }
+
+    /// Get the contents octets of the DER encoding of a universal string
+    /// type, concatenating the segments of a constructed encoding.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn string_contents(&self) -> Result<Vec<u8>> {
+        if !self.is_universal_string() {
+            return Err(ErrorKind::TagUnknown {
+                byte: self.identifier,
+            }
+            .at(self.position()));
+        }
+
+        let number = self.identifier & NUMBER_MASK;
+        let mut contents = Vec::new();
+
+        if number == BIT_STRING {
+            let mut unused_bits = 0;
+            contents.push(0);
+            self.collect_segments(number, &mut contents, &mut unused_bits)?;
+
+            if unused_bits != 0 {
+                // X.690 Section 11.2.1: unused bits are set to zero
+                if let Some(last) = contents.last_mut() {
+                    *last &= u8::MAX.checked_shl(unused_bits.into()).unwrap_or(0);
+                }
+
+                contents[0] = unused_bits;
+            }
+        } else {
+            self.collect_segments(number, &mut contents, &mut 0)?;
+        }
+
+        Ok(contents)
+    }
+
+    /// Append the DER encoding of this element to `der`.
+    #[cfg(feature = "alloc")]
+    fn encode_der(&self, der: &mut Vec<u8>) -> Result<()> {
+        let universal = self.identifier & CLASS_MASK == 0;
+        let number = self.identifier & NUMBER_MASK;
+
+        if self.is_universal_string() {
+            let contents = self.string_contents()?;
+            return write_element(der, self.identifier & !CONSTRUCTED_FLAG, &contents);
+        }
+
+        if !self.is_constructed() {
+            if universal && number == BOOLEAN {
+                // X.690 Section 11.1: TRUE is encoded as 0xFF
+                return match self.contents {
+                    [0] => write_element(der, self.identifier, &[0]),
+                    [_] => write_element(der, self.identifier, &[0xFF]),
+                    _ => Err(ErrorKind::Length { tag: Tag::Boolean }.at(self.position())),
+                };
+            }
+
+            return write_element(der, self.identifier, self.contents);
+        }
+
+        // Constructed universal types other than strings, e.g. `INTEGER`,
+        // have no constructed encoding
+        if universal && !matches!(number, SEQUENCE | SET | 8 | 11) {
+            return Err(ErrorKind::TagUnknown {
+                byte: self.identifier,
+            }
+            .at(self.position()));
+        }
+
+        let mut reader = self.elements()?;
+        let mut elements = Vec::new();
+        while !reader.is_finished() {
+            elements.push(reader.read_element()?.to_der()?);
+        }
+
+        if universal && number == SET {
+            // X.690 Sections 10.3 and 11.6: order `SET` components by tag,
+            // and `SET OF` components by their encoding
+            elements.sort_by(|a, b| {
+                (a[0] & CLASS_MASK, a[0] & NUMBER_MASK, a).cmp(&(
+                    b[0] & CLASS_MASK,
+                    b[0] & NUMBER_MASK,
+                    b,
+                ))
+            });
+        }
+
+        let contents = elements.concat();
+        write_element(der, self.identifier, &contents)
+    }
+
+    /// Append the contents of the segments of a string to `contents`.
+    ///
+    /// For `BIT STRING`s, the initial octet of each segment is stripped and
+    /// its number of unused bits stored in `unused_bits`.
+    #[cfg(feature = "alloc")]
+    fn collect_segments(
+        &self,
+        number: u8,
+        contents: &mut Vec<u8>,
+        unused_bits: &mut u8,
+    ) -> Result<()> {
+        // X.690 Section 8.23.6: segments have the type of the string
+        if self.identifier & !CONSTRUCTED_FLAG != number {
+            return Err(ErrorKind::TagUnknown {
+                byte: self.identifier,
+            }
+            .at(self.position()));
+        }
+
+        if self.is_constructed() {
+            let mut reader = self.elements()?;
+            while !reader.is_finished() {
+                reader
+                    .read_element()?
+                    .collect_segments(number, contents, unused_bits)?;
+            }
+
+            return Ok(());
+        }
+
+        if number != BIT_STRING {
+            contents.extend_from_slice(self.contents);
+            return Ok(());
+        }
+
+        // X.690 Section 8.6.4: only the final segment may have unused bits
+        match self.contents.split_first() {
+            Some((&unused, bits)) if *unused_bits == 0 && unused < 8 => {
+                if bits.is_empty() && unused != 0 {
+                    return Err(self.bit_string_error());
+                }
+
+                contents.extend_from_slice(bits);
+                *unused_bits = unused;
+                Ok(())
+            }
+            _ => Err(self.bit_string_error()),
+        }
+    }
+
+    /// Is this element of a universal string type?
+    fn is_universal_string(&self) -> bool {
+        self.identifier & CLASS_MASK == 0
+            && matches!(
+                self.identifier & NUMBER_MASK,
+                // BIT STRING, OCTET STRING, ObjectDescriptor, UTF8String,
+                // NumericString through GeneralString, UniversalString
+                // and BMPString
+                3 | 4 | 7 | 12 | 18..=28 | 30
+            )
+    }
+
+    /// Get the position of the element within the outermost input.
+    fn position(&self) -> Length {
+        self.offset
+    }
+
+    /// Return an error for an invalid `BIT STRING` segment.
+    #[cfg(feature = "alloc")]
+    fn bit_string_error(&self) -> Error {
+        ErrorKind::Value {
+            tag: Tag::BitString,
+        }
+        .at(self.position())
+    }
+}
+
+/// Append an element with the given identifier octet and contents to `der`.
+#[cfg(feature = "alloc")]
+fn write_element(der: &mut Vec<u8>, identifier: u8, contents: &[u8]) -> Result<()> {
+    der.push(identifier);
+    der.extend_from_slice(&Length::try_from(contents.len())?.to_vec()?);
+    der.extend_from_slice(contents);
+    Ok(())
+}
+
+/// Re-encode a single BER-encoded element as DER.
+///
+/// See the [module documentation](self) for the rules which are applied.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub fn to_der(ber: &[u8]) -> Result<Vec<u8>> {
+    let mut reader = BerReader::new(ber)?;
+    let element = reader.read_element()?;
+
+    if !reader.is_finished() {
+        let decoded = reader.position();
+        return Err(ErrorKind::TrailingData {
+            decoded,
+            remaining: Length::try_from(ber.len().saturating_sub(reader.position))?,
+        }
+        .at(decoded));
+    }
+
+    element.to_der()
+}
+
+#[cfg(feature = "alloc")]
+impl Document {
+    /// Re-encode a BER-encoded `SEQUENCE` as DER, storing the result as a
+    /// [`Document`].
+    ///
+    /// See [`ber::to_der`](to_der) for the rules which are applied.
+    pub fn from_ber(ber: &[u8]) -> Result<Self> {
+        to_der(ber)?.try_into()
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::{to_der, BerReader};
+    use crate::{ErrorKind, Length, Tag};
+    use hex_literal::hex;
+
+    #[test]
+    fn indefinite_length() {
+        assert_eq!(
+            to_der(&hex!("3080 020105 0000")).unwrap(),
+            hex!("3003 020105")
+        );
+        assert_eq!(
+            to_der(&hex!("3080 3080 0500 0000 020105 0000")).unwrap(),
+            hex!("3007 3002 0500 020105")
+        );
+        assert_eq!(to_der(&hex!("a080 0000")).unwrap(), hex!("a000"));
+    }
+
+    #[test]
+    fn non_minimal_length() {
+        assert_eq!(to_der(&hex!("0481 02 aabb")).unwrap(), hex!("0402 aabb"));
+        assert_eq!(
+            to_der(&hex!("3084 00000005 02820001 05")).unwrap(),
+            hex!("3003 020105")
+        );
+    }
+
+    #[test]
+    fn constructed_strings() {
+        assert_eq!(
+            to_der(&hex!("2480 040201 02 2480 040103 0000 0000")).unwrap(),
+            hex!("0403 010203")
+        );
+        assert_eq!(
+            to_der(&hex!("0c80 0000")).unwrap_err().kind(),
+            ErrorKind::IndefiniteLength
+        );
+        assert_eq!(
+            to_der(&hex!("2c06 0c0168 0c0169")).unwrap(),
+            hex!("0c02 6869")
+        );
+
+        // segments must have the type of the string
+        assert_eq!(
+            to_der(&hex!("2406 040101 0c0102")).unwrap_err(),
+            ErrorKind::TagUnknown { byte: 0x0c }.at(Length::new(5))
+        );
+    }
+
+    #[test]
+    fn constructed_bit_strings() {
+        assert_eq!(
+            to_der(&hex!("2309 0303000a3b 0302045f")).unwrap(),
+            hex!("0304 040a3b50")
+        );
+        assert_eq!(to_der(&hex!("2300")).unwrap(), hex!("0301 00"));
+
+        // only the final segment may have unused bits
+        assert_eq!(
+            to_der(&hex!("2309 0303040a3b 0302005f"))
+                .unwrap_err()
+                .kind(),
+            ErrorKind::Value {
+                tag: Tag::BitString
+            }
+        );
+    }
+
+    #[test]
+    fn canonical_values() {
+        assert_eq!(to_der(&hex!("010101")).unwrap(), hex!("0101ff"));
+        assert_eq!(to_der(&hex!("010100")).unwrap(), hex!("010100"));
+        assert_eq!(to_der(&hex!("0302 0401")).unwrap(), hex!("0302 0400"));
+        assert_eq!(
+            to_der(&hex!("3106 020102 020101")).unwrap(),
+            hex!("3106 020101 020102")
+        );
+        assert_eq!(
+            to_der(&hex!("3105 a100 800100")).unwrap(),
+            hex!("3105 800100 a100")
+        );
+    }
+
+    #[test]
+    fn invalid_encodings() {
+        // primitive with indefinite length
+        assert_eq!(
+            to_der(&hex!("0480 0000")).unwrap_err(),
+            ErrorKind::IndefiniteLength.at(Length::ZERO)
+        );
+
+        // missing end-of-contents
+        assert_eq!(
+            to_der(&hex!("3080 020105")).unwrap_err(),
+            ErrorKind::IndefiniteLength.at(Length::ZERO)
+        );
+
+        // constructed INTEGER
+        assert_eq!(
+            to_der(&hex!("2203 020105")).unwrap_err().kind(),
+            ErrorKind::TagUnknown { byte: 0x22 }
+        );
+
+        assert_eq!(
+            to_der(&hex!("1f2001 00")).unwrap_err().kind(),
+            ErrorKind::TagNumberInvalid
+        );
+        assert_eq!(
+            to_der(&hex!("04ff")).unwrap_err().kind(),
+            ErrorKind::Overlength
+        );
+        assert_eq!(
+            to_der(&hex!("0403 0102")).unwrap_err().kind(),
+            ErrorKind::Incomplete {
+                expected_len: Length::new(5),
+                actual_len: Length::new(4)
+            }
+        );
+        assert_eq!(
+            to_der(&hex!("0500 0500")).unwrap_err().kind(),
+            ErrorKind::TrailingData {
+                decoded: Length::new(2),
+                remaining: Length::new(2)
+            }
+        );
+    }
+
+    #[test]
+    fn nesting_limit() {
+        let mut ber = [0x30u8, 0x80].repeat(100);
+        ber.extend([0u8; 200]);
+        assert_eq!(to_der(&ber).unwrap_err().kind(), ErrorKind::Overlength);
+    }
+
+    #[test]
+    fn read_elements() {
+        let ber = hex!("3080 020105 0000 0481 01 ff");
+        let mut reader = BerReader::new(&ber).unwrap();
+
+        let sequence = reader.read_element().unwrap();
+        assert_eq!(sequence.tag().unwrap(), Tag::Sequence);
+        assert_eq!(sequence.contents(), hex!("020105"));
+        let mut elements = sequence.elements().unwrap();
+        let integer = elements.read_element().unwrap();
+        assert_eq!(integer.tag().unwrap(), Tag::Integer);
+        assert_eq!(integer.contents(), [5]);
+        assert!(elements.is_finished());
+
+        let string = reader.read_element().unwrap();
+        assert_eq!(string.tag().unwrap(), Tag::OctetString);
+        assert_eq!(string.string_contents().unwrap(), [0xff]);
+        assert!(reader.is_finished());
+    }
+}
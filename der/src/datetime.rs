@@ -12,16 +12,25 @@ use core::{fmt, str::FromStr, time::Duration};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 #[cfg(feature = "time")]
-use time::PrimitiveDateTime;
+use time::{OffsetDateTime, PrimitiveDateTime};
 
-/// Minimum year allowed in [`DateTime`] values.
-const MIN_YEAR: u16 = 1970;
+#[cfg(feature = "chrono")]
+use chrono::{Datelike, NaiveDateTime, TimeZone, Timelike, Utc};
 
-/// Maximum duration since `UNIX_EPOCH` which can be represented as a
-/// [`DateTime`] (non-inclusive).
+/// Maximum year allowed in [`DateTime`] values.
+const MAX_YEAR: u16 = 9999;
+
+/// Minimum number of seconds since `UNIX_EPOCH` which can be represented as
+/// a [`DateTime`] (inclusive).
+///
+/// This corresponds to: 0000-01-01T00:00:00Z
+const MIN_UNIX_TIMESTAMP: i64 = -62_167_219_200;
+
+/// Maximum number of seconds since `UNIX_EPOCH` which can be represented as
+/// a [`DateTime`] (inclusive).
 ///
 /// This corresponds to: 9999-12-31T23:59:59Z
-const MAX_UNIX_DURATION: Duration = Duration::from_secs(253_402_300_799);
+const MAX_UNIX_TIMESTAMP: i64 = 253_402_300_799;
 
 /// Date-and-time type shared by multiple ASN.1 types
 /// (e.g. `GeneralizedTime`, `UTCTime`).
@@ -29,11 +38,13 @@ const MAX_UNIX_DURATION: Duration = Duration::from_secs(253_402_300_799);
 /// Following conventions from RFC 5280, this type is always Z-normalized
 /// (i.e. represents a UTC time). However, it isn't named "UTC time" in order
 /// to prevent confusion with ASN.1 `UTCTime`.
+///
+/// Years from 0000 through 9999, i.e. those which can be encoded as a
+/// `GeneralizedTime`, are supported. Times before the Unix epoch have a
+/// negative [`DateTime::unix_timestamp`].
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 pub struct DateTime {
     /// Full year (e.g. 2000).
-    ///
-    /// Must be >=1970 to permit positive conversions to Unix time.
     year: u16,
 
     /// Month (1-12)
@@ -51,17 +62,33 @@ pub struct DateTime {
     /// Seconds (0-59)
     seconds: u8,
 
-    /// [`Duration`] since the Unix epoch.
-    unix_duration: Duration,
+    /// Seconds since the Unix epoch, negative for earlier times.
+    unix_timestamp: i64,
 }
 
 impl DateTime {
+    /// The latest [`DateTime`] which can be represented: 9999-12-31T23:59:59Z.
+    ///
+    /// This is the `GeneralizedTime` value which [RFC 5280 Section 4.1.2.5]
+    /// assigns to certificates which have no well-defined expiration date.
+    ///
+    /// [RFC 5280 Section 4.1.2.5]: https://datatracker.ietf.org/doc/html/rfc5280#section-4.1.2.5
+    pub const INFINITY: DateTime = DateTime {
+        year: 9999,
+        month: 12,
+        day: 31,
+        hour: 23,
+        minutes: 59,
+        seconds: 59,
+        unix_timestamp: MAX_UNIX_TIMESTAMP,
+    };
+
     /// Create a new [`DateTime`] from the given UTC time components.
     // TODO(tarcieri): checked arithmetic
     #[allow(clippy::integer_arithmetic)]
     pub fn new(year: u16, month: u8, day: u8, hour: u8, minutes: u8, seconds: u8) -> Result<Self> {
         // Basic validation of the components.
-        if year < MIN_YEAR
+        if year > MAX_YEAR
             || !(1..=12).contains(&month)
             || !(1..=31).contains(&day)
             || !(0..=23).contains(&hour)
@@ -71,9 +98,6 @@ impl DateTime {
             return Err(ErrorKind::DateTime.into());
         }
 
-        let leap_years =
-            ((year - 1) - 1968) / 4 - ((year - 1) - 1900) / 100 + ((year - 1) - 1600) / 400;
-
         let is_leap_year = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
 
         let (mut ydays, mdays): (u16, u8) = match month {
@@ -103,13 +127,16 @@ impl DateTime {
             ydays += 1;
         }
 
-        let days = u64::from(year - 1970) * 365 + u64::from(leap_years) + u64::from(ydays);
-        let time = u64::from(seconds) + (u64::from(minutes) * 60) + (u64::from(hour) * 3600);
-        let unix_duration = Duration::from_secs(time + days * 86400);
+        // Leap days from 1970 up to the start of the year, which are
+        // negative for earlier years
+        let prev_year = i64::from(year) - 1;
+        let leap_days = |cycle: i64| prev_year.div_euclid(cycle) - 1969 / cycle;
 
-        if unix_duration > MAX_UNIX_DURATION {
-            return Err(ErrorKind::DateTime.into());
-        }
+        let days = (prev_year - 1969) * 365 + leap_days(4) - leap_days(100)
+            + leap_days(400)
+            + i64::from(ydays);
+        let time = i64::from(seconds) + (i64::from(minutes) * 60) + (i64::from(hour) * 3600);
+        let unix_timestamp = time + days * 86400;
 
         Ok(Self {
             year,
@@ -118,30 +145,38 @@ impl DateTime {
             hour,
             minutes,
             seconds,
-            unix_duration,
+            unix_timestamp,
         })
     }
 
     /// Compute a [`DateTime`] from the given [`Duration`] since the `UNIX_EPOCH`.
     ///
-    /// Returns `None` if the value is outside the supported date range.
+    /// Returns an error if the value is outside the supported date range.
+    pub fn from_unix_duration(unix_duration: Duration) -> Result<Self> {
+        i64::try_from(unix_duration.as_secs())
+            .map_err(|_| ErrorKind::DateTime.into())
+            .and_then(Self::from_unix_timestamp)
+    }
+
+    /// Compute a [`DateTime`] from the given number of seconds since the
+    /// `UNIX_EPOCH`, which is negative for earlier times.
+    ///
+    /// Returns an error if the value is outside the supported date range.
     // TODO(tarcieri): checked arithmetic
     #[allow(clippy::integer_arithmetic)]
-    pub fn from_unix_duration(unix_duration: Duration) -> Result<Self> {
-        if unix_duration > MAX_UNIX_DURATION {
+    pub fn from_unix_timestamp(unix_timestamp: i64) -> Result<Self> {
+        if !(MIN_UNIX_TIMESTAMP..=MAX_UNIX_TIMESTAMP).contains(&unix_timestamp) {
             return Err(ErrorKind::DateTime.into());
         }
 
-        let secs_since_epoch = unix_duration.as_secs();
-
         /// 2000-03-01 (mod 400 year, immediately after Feb 29)
         const LEAPOCH: i64 = 11017;
         const DAYS_PER_400Y: i64 = 365 * 400 + 97;
         const DAYS_PER_100Y: i64 = 365 * 100 + 24;
         const DAYS_PER_4Y: i64 = 365 * 4 + 1;
 
-        let days = i64::try_from(secs_since_epoch / 86400)? - LEAPOCH;
-        let secs_of_day = secs_since_epoch % 86400;
+        let days = unix_timestamp.div_euclid(86400) - LEAPOCH;
+        let secs_of_day = unix_timestamp.rem_euclid(86400);
 
         let mut qc_cycles = days / DAYS_PER_400Y;
         let mut remdays = days % DAYS_PER_400Y;
@@ -234,24 +269,49 @@ impl DateTime {
     }
 
     /// Compute [`Duration`] since `UNIX_EPOCH` from the given calendar date.
+    ///
+    /// A [`Duration`] can't be negative, so this is zero for times before
+    /// the epoch. Use [`DateTime::unix_timestamp`] for those.
     pub fn unix_duration(&self) -> Duration {
-        self.unix_duration
+        Duration::from_secs(self.unix_timestamp.try_into().unwrap_or(0))
+    }
+
+    /// Compute the number of seconds since `UNIX_EPOCH` from the given
+    /// calendar date, which is negative for earlier times.
+    pub fn unix_timestamp(&self) -> i64 {
+        self.unix_timestamp
     }
 
     /// Instantiate from [`SystemTime`].
     #[cfg(feature = "std")]
     #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
     pub fn from_system_time(time: SystemTime) -> Result<Self> {
-        time.duration_since(UNIX_EPOCH)
-            .map_err(|_| ErrorKind::DateTime.into())
-            .and_then(Self::from_unix_duration)
+        match time.duration_since(UNIX_EPOCH) {
+            Ok(duration) => Self::from_unix_duration(duration),
+            Err(err) => {
+                // Round down to whole seconds, i.e. away from the epoch
+                let duration = err.duration();
+                i64::try_from(duration.as_secs())
+                    .ok()
+                    .and_then(|secs| secs.checked_add(i64::from(duration.subsec_nanos() > 0)))
+                    .and_then(i64::checked_neg)
+                    .ok_or_else(|| ErrorKind::DateTime.into())
+                    .and_then(Self::from_unix_timestamp)
+            }
+        }
     }
 
     /// Convert to [`SystemTime`].
     #[cfg(feature = "std")]
     #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
     pub fn to_system_time(&self) -> SystemTime {
-        UNIX_EPOCH + self.unix_duration()
+        let secs = Duration::from_secs(self.unix_timestamp.unsigned_abs());
+
+        if self.unix_timestamp < 0 {
+            UNIX_EPOCH - secs
+        } else {
+            UNIX_EPOCH + secs
+        }
     }
 }
 
@@ -360,6 +420,88 @@ impl TryFrom<PrimitiveDateTime> for DateTime {
     }
 }
 
+#[cfg(feature = "time")]
+#[cfg_attr(docsrs, doc(cfg(feature = "time")))]
+impl TryFrom<DateTime> for OffsetDateTime {
+    type Error = Error;
+
+    fn try_from(time: DateTime) -> Result<OffsetDateTime> {
+        Ok(PrimitiveDateTime::try_from(time)?.assume_utc())
+    }
+}
+
+#[cfg(feature = "time")]
+#[cfg_attr(docsrs, doc(cfg(feature = "time")))]
+impl TryFrom<OffsetDateTime> for DateTime {
+    type Error = Error;
+
+    fn try_from(time: OffsetDateTime) -> Result<DateTime> {
+        let time = time.to_offset(time::UtcOffset::UTC);
+        PrimitiveDateTime::new(time.date(), time.time()).try_into()
+    }
+}
+
+#[cfg(feature = "chrono")]
+#[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+impl TryFrom<DateTime> for NaiveDateTime {
+    type Error = Error;
+
+    fn try_from(time: DateTime) -> Result<NaiveDateTime> {
+        chrono::NaiveDate::from_ymd_opt(
+            i32::from(time.year()),
+            u32::from(time.month()),
+            u32::from(time.day()),
+        )
+        .and_then(|date| {
+            date.and_hms_opt(
+                u32::from(time.hour()),
+                u32::from(time.minutes()),
+                u32::from(time.seconds()),
+            )
+        })
+        .ok_or_else(|| ErrorKind::DateTime.into())
+    }
+}
+
+#[cfg(feature = "chrono")]
+#[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+impl TryFrom<NaiveDateTime> for DateTime {
+    type Error = Error;
+
+    fn try_from(time: NaiveDateTime) -> Result<DateTime> {
+        let component = |value: u32| u8::try_from(value).map_err(|_| ErrorKind::DateTime);
+
+        DateTime::new(
+            time.year().try_into().map_err(|_| ErrorKind::DateTime)?,
+            component(time.month())?,
+            component(time.day())?,
+            component(time.hour())?,
+            component(time.minute())?,
+            component(time.second())?,
+        )
+    }
+}
+
+#[cfg(feature = "chrono")]
+#[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+impl TryFrom<DateTime> for chrono::DateTime<Utc> {
+    type Error = Error;
+
+    fn try_from(time: DateTime) -> Result<chrono::DateTime<Utc>> {
+        Ok(Utc.from_utc_datetime(&NaiveDateTime::try_from(time)?))
+    }
+}
+
+#[cfg(feature = "chrono")]
+#[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+impl<Tz: TimeZone> TryFrom<chrono::DateTime<Tz>> for DateTime {
+    type Error = Error;
+
+    fn try_from(time: chrono::DateTime<Tz>) -> Result<DateTime> {
+        time.naive_utc().try_into()
+    }
+}
+
 /// Decode 2-digit decimal value
 // TODO(tarcieri): checked arithmetic
 #[allow(clippy::integer_arithmetic)]
@@ -402,6 +544,43 @@ mod tests {
         assert!(!is_date_valid(2100, 2, 29, 0, 0, 0));
     }
 
+    #[test]
+    fn infinity() {
+        assert_eq!(
+            DateTime::new(9999, 12, 31, 23, 59, 59).unwrap(),
+            DateTime::INFINITY
+        );
+        assert_eq!(
+            DateTime::from_unix_duration(DateTime::INFINITY.unix_duration()).unwrap(),
+            DateTime::INFINITY
+        );
+    }
+
+    #[test]
+    fn before_unix_epoch() {
+        let datetime = DateTime::new(1950, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(datetime.unix_timestamp(), -631152000);
+        assert_eq!(datetime.unix_duration().as_secs(), 0);
+        assert_eq!(DateTime::from_unix_timestamp(-631152000).unwrap(), datetime);
+
+        let datetime = DateTime::new(0, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(datetime.unix_timestamp(), -62167219200);
+        assert!(DateTime::from_unix_timestamp(-62167219201).is_err());
+        assert!(datetime < DateTime::new(1969, 12, 31, 23, 59, 59).unwrap());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_roundtrip() {
+        use chrono::{TimeZone, Utc};
+
+        let datetime = DateTime::new(1955, 6, 7, 8, 9, 10).unwrap();
+        let chrono = chrono::DateTime::<Utc>::try_from(datetime).unwrap();
+        assert_eq!(chrono, Utc.with_ymd_and_hms(1955, 6, 7, 8, 9, 10).unwrap());
+        assert_eq!(chrono.timestamp(), datetime.unix_timestamp());
+        assert_eq!(DateTime::try_from(chrono).unwrap(), datetime);
+    }
+
     #[test]
     fn from_str() {
         let datetime = "2001-01-02T12:13:14Z".parse::<DateTime>().unwrap();
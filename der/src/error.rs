@@ -192,6 +192,10 @@ pub enum ErrorKind {
         actual_len: Length,
     },
 
+    /// Indefinite length used with a primitive encoding, or without
+    /// end-of-contents octets (BER only).
+    IndefiniteLength,
+
     /// I/O errors.
     #[cfg(feature = "std")]
     #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
@@ -319,6 +323,7 @@ impl fmt::Display for ErrorKind {
                 "ASN.1 DER message is incomplete: expected {}, actual {}",
                 expected_len, actual_len
             ),
+            ErrorKind::IndefiniteLength => write!(f, "invalid BER indefinite length"),
             #[cfg(feature = "std")]
             ErrorKind::Io(err) => write!(f, "I/O error: {:?}", err),
             ErrorKind::Length { tag } => write!(f, "incorrect length for {}", tag),
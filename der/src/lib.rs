@@ -47,6 +47,7 @@
 //! - [`Any`], [`AnyRef`]: ASN.1 `ANY`.
 //! - [`BitString`], [`BitStringRef`]: ASN.1 `BIT STRING`
 //! - [`GeneralizedTime`]: ASN.1 `GeneralizedTime`.
+//! - [`GeneralizedTimeNanos`]: ASN.1 `GeneralizedTime` with fractional seconds.
 //! - [`Ia5StringRef`]: ASN.1 `IA5String`.
 //! - [`Null`]: ASN.1 `NULL`.
 //! - [`ObjectIdentifier`]: ASN.1 `OBJECT IDENTIFIER`.
@@ -220,6 +221,7 @@
 //! - [`Choice`]: derive for `CHOICE` enum (see [`der_derive::Choice`])
 //! - [`Enumerated`]: derive for `ENUMERATED` enum (see [`der_derive::Enumerated`])
 //! - [`Sequence`]: derive for `SEQUENCE` struct (see [`der_derive::Sequence`])
+//! - [`Set`]: derive for `SET` struct (see [`der_derive::Set`])
 //!
 //! ### Derive [`Sequence`] for struct
 //! The following is a code example of how to use the [`Sequence`] custom derive:
@@ -315,6 +317,7 @@
 //! [`BitString`]: asn1::BitStringRef
 //! [`BitStringRef`]: asn1::BitStringRef
 //! [`GeneralizedTime`]: asn1::GeneralizedTime
+//! [`GeneralizedTimeNanos`]: asn1::GeneralizedTimeNanos
 //! [`Ia5StringRef`]: asn1::Ia5StringRef
 //! [`Null`]: asn1::Null
 //! [`ObjectIdentifier`]: asn1::ObjectIdentifier
@@ -336,6 +339,7 @@ extern crate alloc;
 extern crate std;
 
 pub mod asn1;
+pub mod ber;
 
 pub(crate) mod arrayvec;
 mod byte_slice;
@@ -379,7 +383,7 @@ pub use crypto_bigint as bigint;
 
 #[cfg(feature = "derive")]
 #[cfg_attr(docsrs, doc(cfg(feature = "derive")))]
-pub use der_derive::{Choice, Enumerated, Sequence, ValueOrd};
+pub use der_derive::{Choice, Enumerated, Sequence, Set, ValueOrd};
 
 #[cfg(feature = "oid")]
 #[cfg_attr(docsrs, doc(cfg(feature = "oid")))]
@@ -392,6 +396,14 @@ pub use {
     pem_rfc7468 as pem,
 };
 
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use crate::reader::io::IoReader;
+
+#[cfg(feature = "chrono")]
+#[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+pub use chrono;
+
 #[cfg(feature = "time")]
 #[cfg_attr(docsrs, doc(cfg(feature = "time")))]
 pub use time;
@@ -1,5 +1,7 @@
 //! Reader trait.
 
+#[cfg(feature = "std")]
+pub(crate) mod io;
 mod nested;
 #[cfg(feature = "pem")]
 pub(crate) mod pem;
@@ -0,0 +1,325 @@
+//! Streaming reader over [`std::io::Read`].
+
+use crate::{Decode, DecodeOwned, Encode, Error, ErrorKind, Header, Length, Result, Tag};
+use alloc::vec::Vec;
+use std::io::{self, Read};
+
+/// Maximum length of an encoded [`Header`]: one identifier octet, the initial
+/// length octet, and up to four subsequent length octets.
+const MAX_HEADER_LEN: usize = 6;
+
+/// Pull-style reader which decodes DER incrementally from an
+/// [`io::Read`] source.
+///
+/// Unlike the [`Reader`][`crate::Reader`] implementations, which require the
+/// whole message to be in memory, this reader only buffers the header of the
+/// next element. Callers either read an element in full with
+/// [`IoReader::decode`] or [`IoReader::read_tlv`], skip it with
+/// [`IoReader::skip`], or descend into a constructed element with
+/// [`IoReader::read_header`] and read its components one at a time.
+///
+/// Nothing tracks where a constructed element ends: after descending into
+/// one, callers are responsible for counting the lengths of its components.
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[derive(Debug)]
+pub struct IoReader<R> {
+    /// Inner source.
+    inner: R,
+
+    /// Number of bytes read from the source.
+    position: Length,
+
+    /// Header which was read from the source but not consumed yet, along
+    /// with its position.
+    peeked: Option<(Header, Length)>,
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl<R: Read> IoReader<R> {
+    /// Create a new reader over the given source.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            position: Length::ZERO,
+            peeked: None,
+        }
+    }
+
+    /// Get the inner source back.
+    ///
+    /// A peeked header has already been read from the source, and is lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Get the position of the next element in the input.
+    pub fn position(&self) -> Length {
+        match self.peeked {
+            Some((_, position)) => position,
+            None => self.position,
+        }
+    }
+
+    /// Have we reached the end of the input?
+    ///
+    /// This needs to read from the source: an element is only known to
+    /// follow once its header has been read.
+    pub fn is_finished(&mut self) -> Result<bool> {
+        Ok(self.fill()?.is_none())
+    }
+
+    /// Peek at the [`Header`] of the next element, without consuming it.
+    pub fn peek_header(&mut self) -> Result<Header> {
+        self.fill()?.ok_or_else(|| Error::incomplete(self.position))
+    }
+
+    /// Peek at the [`Tag`] of the next element, without consuming it.
+    pub fn peek_tag(&mut self) -> Result<Tag> {
+        Ok(self.peek_header()?.tag)
+    }
+
+    /// Consume the [`Header`] of the next element.
+    ///
+    /// The reader is then positioned at the start of the value: if the
+    /// element is constructed, the next element read is its first component.
+    pub fn read_header(&mut self) -> Result<Header> {
+        let header = self.peek_header()?;
+        self.peeked = None;
+        Ok(header)
+    }
+
+    /// Read the next element, returning its [`Header`] and its value.
+    pub fn read_value(&mut self) -> Result<(Header, Vec<u8>)> {
+        let header = self.read_header()?;
+        let mut value = Vec::new();
+        self.read_to_end(header.length, &mut value)?;
+        Ok((header, value))
+    }
+
+    /// Read the next element in full, including its header, i.e. its
+    /// tag-length-value encoding.
+    pub fn read_tlv(&mut self) -> Result<Vec<u8>> {
+        let header = self.read_header()?;
+        let mut tlv = Vec::new();
+        header.encode_to_vec(&mut tlv)?;
+        self.read_to_end(header.length, &mut tlv)?;
+        Ok(tlv)
+    }
+
+    /// Skip the next element.
+    pub fn skip(&mut self) -> Result<Header> {
+        let header = self.read_header()?;
+        let len = u64::from(u32::from(header.length));
+        let skipped = io::copy(&mut (&mut self.inner).take(len), &mut io::sink())
+            .map_err(|err| Error::from(err).kind().at(self.position))?;
+
+        self.advance(skipped, header.length)?;
+        Ok(header)
+    }
+
+    /// Decode the next element as a value of type `T`.
+    ///
+    /// Only the encoding of that element is held in memory.
+    pub fn decode<T: DecodeOwned>(&mut self) -> Result<T> {
+        let position = self.position();
+        T::from_der(&self.read_tlv()?).map_err(|err| err.nested(position))
+    }
+
+    /// Read the header of the next element if it isn't buffered yet, and
+    /// return it, or `None` at the end of the input.
+    fn fill(&mut self) -> Result<Option<Header>> {
+        if let Some((header, _)) = self.peeked {
+            return Ok(Some(header));
+        }
+
+        let position = self.position;
+        let mut buf = [0u8; MAX_HEADER_LEN];
+
+        if !self.read_first(&mut buf[0])? {
+            return Ok(None);
+        }
+
+        self.read_exact(&mut buf[1..2])?;
+
+        // Long form lengths are followed by their octets: any other form
+        // is invalid in DER, and is rejected when decoding the header.
+        let header_len = match buf[1] {
+            0x81..=0x84 => 2 + usize::from(buf[1] & 0x7F),
+            _ => 2,
+        };
+
+        self.read_exact(&mut buf[2..header_len])?;
+
+        let header = Header::from_der(&buf[..header_len]).map_err(|err| err.nested(position))?;
+        self.peeked = Some((header, position));
+        Ok(Some(header))
+    }
+
+    /// Read the first byte of an element, returning `false` if the end of the
+    /// input is reached instead.
+    fn read_first(&mut self, byte: &mut u8) -> Result<bool> {
+        loop {
+            match self.inner.read(core::slice::from_mut(byte)) {
+                Ok(0) => return Ok(false),
+                Ok(_) => {
+                    self.position = (self.position + Length::ONE)?;
+                    return Ok(true);
+                }
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(Error::from(err).kind().at(self.position)),
+            }
+        }
+    }
+
+    /// Fill `buf` from the source.
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<()> {
+        let mut filled = 0;
+
+        while filled < buf.len() {
+            match self.inner.read(&mut buf[filled..]) {
+                Ok(0) => return Err(Error::incomplete(self.position)),
+                Ok(n) => {
+                    filled = filled.saturating_add(n);
+                    self.position = (self.position + n)?;
+                }
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(Error::from(err).kind().at(self.position)),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Append `len` bytes from the source to `buf`.
+    ///
+    /// The buffer grows as data arrives, rather than being allocated ahead of
+    /// time from a length which may be bogus.
+    fn read_to_end(&mut self, len: Length, buf: &mut Vec<u8>) -> Result<()> {
+        let read = (&mut self.inner)
+            .take(u64::from(u32::from(len)))
+            .read_to_end(buf)
+            .map_err(|err| Error::from(err).kind().at(self.position))?;
+
+        self.advance(read as u64, len)
+    }
+
+    /// Record that `read` bytes of the `expected` ones were read.
+    fn advance(&mut self, read: u64, expected: Length) -> Result<()> {
+        let read = u32::try_from(read)
+            .map_err(|_| Error::from(ErrorKind::Overflow))
+            .and_then(Length::try_from)?;
+
+        self.position = (self.position + read)?;
+
+        if read < expected {
+            return Err(ErrorKind::Incomplete {
+                expected_len: (self.position + (expected - read)?)?,
+                actual_len: self.position,
+            }
+            .at(self.position));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IoReader;
+    use crate::{asn1::OctetString, ErrorKind, Length, Tag};
+    use hex_literal::hex;
+
+    // SEQUENCE { INTEGER 42, OCTET STRING 0x0102 }
+    const EXAMPLE_MSG: &[u8] = &hex!("300702012A04020102");
+
+    #[test]
+    fn decode() {
+        let mut reader = IoReader::new(EXAMPLE_MSG);
+        let header = reader.read_header().unwrap();
+        assert_eq!(header.tag, Tag::Sequence);
+        assert_eq!(header.length, Length::new(7));
+
+        assert_eq!(reader.decode::<u8>().unwrap(), 42);
+        assert_eq!(reader.position(), Length::new(5));
+        assert_eq!(reader.peek_tag().unwrap(), Tag::OctetString);
+        assert_eq!(reader.position(), Length::new(5));
+
+        let octets = reader.decode::<OctetString>().unwrap();
+        assert_eq!(octets.as_bytes(), [1, 2]);
+        assert!(reader.is_finished().unwrap());
+    }
+
+    #[test]
+    fn read_value() {
+        let mut reader = IoReader::new(EXAMPLE_MSG);
+        let (header, value) = reader.read_value().unwrap();
+        assert_eq!(header.tag, Tag::Sequence);
+        assert_eq!(value, &EXAMPLE_MSG[2..]);
+        assert!(reader.is_finished().unwrap());
+    }
+
+    #[test]
+    fn read_tlv() {
+        let mut reader = IoReader::new(EXAMPLE_MSG);
+        assert_eq!(reader.read_tlv().unwrap(), EXAMPLE_MSG);
+        assert!(reader.is_finished().unwrap());
+    }
+
+    #[test]
+    fn skip() {
+        let mut reader = IoReader::new(EXAMPLE_MSG);
+        reader.read_header().unwrap();
+        assert_eq!(reader.skip().unwrap().tag, Tag::Integer);
+        assert_eq!(reader.decode::<OctetString>().unwrap().as_bytes(), [1, 2]);
+    }
+
+    #[test]
+    fn long_form_length() {
+        let mut msg = hex!("04820100").to_vec();
+        msg.extend([0xAA; 256]);
+
+        let mut reader = IoReader::new(&msg[..]);
+        assert_eq!(reader.peek_header().unwrap().length, Length::new(256));
+        assert_eq!(
+            reader.decode::<OctetString>().unwrap().as_bytes(),
+            [0xAA; 256]
+        );
+        assert!(reader.is_finished().unwrap());
+    }
+
+    #[test]
+    fn truncated() {
+        let mut reader = IoReader::new(&EXAMPLE_MSG[..5]);
+        let err = reader.read_value().err().unwrap();
+        assert_eq!(
+            err.kind(),
+            ErrorKind::Incomplete {
+                expected_len: Length::new(9),
+                actual_len: Length::new(5)
+            }
+        );
+        assert_eq!(err.position(), Some(Length::new(5)));
+
+        let mut reader = IoReader::new(&EXAMPLE_MSG[..1]);
+        let err = reader.peek_header().err().unwrap();
+        assert_eq!(err.position(), Some(Length::new(1)));
+    }
+
+    #[test]
+    fn indefinite_length() {
+        // Indefinite lengths are BER-only
+        let mut reader = IoReader::new(&hex!("30800000")[..]);
+        let err = reader.peek_header().err().unwrap();
+        assert_eq!(err.kind(), ErrorKind::Length { tag: Tag::Sequence });
+    }
+
+    #[test]
+    fn nested_error_position() {
+        // SEQUENCE { INTEGER with a non-minimal encoding }
+        let mut reader = IoReader::new(&hex!("30040202002A")[..]);
+        reader.read_header().unwrap();
+        let err = reader.decode::<u8>().err().unwrap();
+        assert_eq!(err.kind(), ErrorKind::Noncanonical { tag: Tag::Integer });
+        assert!(err.position().unwrap() >= Length::new(2));
+    }
+}
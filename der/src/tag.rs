@@ -88,6 +88,9 @@ pub enum Tag {
     /// `PrintableString` tag: `19`.
     PrintableString,
 
+    /// `TeletexString` tag: `20`.
+    TeletexString,
+
     /// `IA5String` tag: `22`.
     Ia5String,
 
@@ -100,6 +103,9 @@ pub enum Tag {
     /// `VisibleString` tag: `26`.
     VisibleString,
 
+    /// `UniversalString` tag: `28`.
+    UniversalString,
+
     /// `BMPString` tag: `30`.
     BmpString,
 
@@ -199,11 +205,13 @@ impl Tag {
             Tag::Set => 0x11 | CONSTRUCTED_FLAG,
             Tag::NumericString => 0x12,
             Tag::PrintableString => 0x13,
+            Tag::TeletexString => 0x14,
             Tag::Ia5String => 0x16,
             Tag::UtcTime => 0x17,
             Tag::GeneralizedTime => 0x18,
             Tag::VisibleString => 0x1A,
-            Tag::BmpString => 0x1D,
+            Tag::UniversalString => 0x1C,
+            Tag::BmpString => 0x1E,
             Tag::Application {
                 constructed,
                 number,
@@ -266,11 +274,13 @@ impl TryFrom<u8> for Tag {
             0x0C => Ok(Tag::Utf8String),
             0x12 => Ok(Tag::NumericString),
             0x13 => Ok(Tag::PrintableString),
+            0x14 => Ok(Tag::TeletexString),
             0x16 => Ok(Tag::Ia5String),
             0x17 => Ok(Tag::UtcTime),
             0x18 => Ok(Tag::GeneralizedTime),
             0x1A => Ok(Tag::VisibleString),
-            0x1d => Ok(Tag::BmpString),
+            0x1C => Ok(Tag::UniversalString),
+            0x1E => Ok(Tag::BmpString),
             0x30 => Ok(Tag::Sequence), // constructed
             0x31 => Ok(Tag::Set),      // constructed
             0x40..=0x7E => Ok(Tag::Application {
@@ -341,10 +351,12 @@ impl fmt::Display for Tag {
             Tag::Set => f.write_str("SET"),
             Tag::NumericString => f.write_str("NumericString"),
             Tag::PrintableString => f.write_str("PrintableString"),
+            Tag::TeletexString => f.write_str("TeletexString"),
             Tag::Ia5String => f.write_str("IA5String"),
             Tag::UtcTime => f.write_str("UTCTime"),
             Tag::GeneralizedTime => f.write_str("GeneralizedTime"),
             Tag::VisibleString => f.write_str("VisibleString"),
+            Tag::UniversalString => f.write_str("UniversalString"),
             Tag::BmpString => f.write_str("BMPString"),
             Tag::Sequence => f.write_str("SEQUENCE"),
             Tag::Application {
@@ -403,9 +415,12 @@ mod tests {
         assert_eq!(Tag::Set.class(), Class::Universal);
         assert_eq!(Tag::NumericString.class(), Class::Universal);
         assert_eq!(Tag::PrintableString.class(), Class::Universal);
+        assert_eq!(Tag::TeletexString.class(), Class::Universal);
         assert_eq!(Tag::Ia5String.class(), Class::Universal);
         assert_eq!(Tag::UtcTime.class(), Class::Universal);
         assert_eq!(Tag::GeneralizedTime.class(), Class::Universal);
+        assert_eq!(Tag::UniversalString.class(), Class::Universal);
+        assert_eq!(Tag::BmpString.class(), Class::Universal);
         assert_eq!(Tag::Sequence.class(), Class::Universal);
 
         for num in 0..=30 {
@@ -441,4 +456,16 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn string_tag_octets() {
+        for (tag, octet) in [
+            (Tag::TeletexString, 0x14),
+            (Tag::UniversalString, 0x1C),
+            (Tag::BmpString, 0x1E),
+        ] {
+            assert_eq!(tag.octet(), octet);
+            assert_eq!(Tag::try_from(octet).unwrap(), tag);
+        }
+    }
 }
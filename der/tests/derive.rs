@@ -149,6 +149,45 @@ mod choice {
             assert_eq!(TIME_DER, encoder.finish().unwrap());
         }
     }
+
+    /// `Choice` with `AUTOMATIC` tagging.
+    mod automatic {
+        use der::{asn1::Utf8StringRef, Choice, Decode, Encode};
+        use hex_literal::hex;
+
+        /// ```text
+        /// Identifier ::= CHOICE {
+        ///     name    UTF8String,
+        ///     number  INTEGER }
+        /// ```
+        #[derive(Choice, Debug, Eq, PartialEq)]
+        #[asn1(tag_mode = "AUTOMATIC")]
+        pub enum Identifier<'a> {
+            Name(Utf8StringRef<'a>),
+            Number(u8),
+        }
+
+        const NAME_DER: &'static [u8] = &hex!("80 03 61 62 63");
+        const NUMBER_DER: &'static [u8] = &hex!("81 01 2a");
+
+        #[test]
+        fn decode() {
+            let name = Identifier::from_der(NAME_DER).unwrap();
+            assert_eq!(name, Identifier::Name(Utf8StringRef::new("abc").unwrap()));
+
+            let number = Identifier::from_der(NUMBER_DER).unwrap();
+            assert_eq!(number, Identifier::Number(42));
+        }
+
+        #[test]
+        fn encode() {
+            let name = Identifier::Name(Utf8StringRef::new("abc").unwrap());
+            assert_eq!(NAME_DER, name.to_vec().unwrap());
+
+            let number = Identifier::Number(42);
+            assert_eq!(NUMBER_DER, number.to_vec().unwrap());
+        }
+    }
 }
 
 /// Custom derive test cases for the `Enumerated` macro.
@@ -456,4 +495,145 @@ mod sequence {
             algorithm_identifier.to_vec().unwrap()
         );
     }
+
+    /// `Sequence` with `AUTOMATIC` tagging.
+    mod automatic {
+        use der::{asn1::Utf8StringRef, Decode, Encode, Sequence};
+        use hex_literal::hex;
+
+        /// ```text
+        /// Person ::= SEQUENCE {
+        ///     name    UTF8String,
+        ///     age     INTEGER OPTIONAL }
+        /// ```
+        #[derive(Sequence, Debug, Eq, PartialEq)]
+        #[asn1(tag_mode = "AUTOMATIC")]
+        pub struct Person<'a> {
+            pub name: Utf8StringRef<'a>,
+
+            #[asn1(optional = "true")]
+            pub age: Option<u8>,
+        }
+
+        const PERSON_DER: &'static [u8] = &hex!("30 08 80 03 61 62 63 81 01 2a");
+
+        #[test]
+        fn round_trip() {
+            let person = Person::from_der(PERSON_DER).unwrap();
+            assert_eq!(person.name.as_str(), "abc");
+            assert_eq!(person.age, Some(42));
+            assert_eq!(PERSON_DER, person.to_vec().unwrap());
+        }
+    }
+}
+
+/// Custom derive test cases for the `Set` macro.
+mod set {
+    use der::{asn1::Utf8StringRef, Decode, Encode, Set};
+    use hex_literal::hex;
+
+    /// ```text
+    /// Example ::= SET {
+    ///     flag        [0] IMPLICIT BOOLEAN OPTIONAL,
+    ///     name        UTF8String,
+    ///     version     INTEGER }
+    /// ```
+    #[derive(Set, Debug, Eq, PartialEq)]
+    pub struct Example<'a> {
+        #[asn1(context_specific = "0", tag_mode = "IMPLICIT", optional = "true")]
+        pub flag: Option<bool>,
+
+        pub name: Utf8StringRef<'a>,
+
+        pub version: u8,
+    }
+
+    /// Fields in the canonical order of their tags.
+    const EXAMPLE_DER: &'static [u8] = &hex!("31 0b 02 01 01 0c 03 61 62 63 80 01 ff");
+
+    /// Fields in declaration order, which isn't valid DER.
+    const UNORDERED_DER: &'static [u8] = &hex!("31 0b 80 01 ff 0c 03 61 62 63 02 01 01");
+
+    fn example() -> Example<'static> {
+        Example {
+            flag: Some(true),
+            name: Utf8StringRef::new("abc").unwrap(),
+            version: 1,
+        }
+    }
+
+    #[test]
+    fn decode() {
+        assert_eq!(Example::from_der(EXAMPLE_DER).unwrap(), example());
+        assert_eq!(Example::from_der(UNORDERED_DER).unwrap(), example());
+    }
+
+    #[test]
+    fn decode_optional() {
+        let example = Example::from_der(&hex!("31 08 0c 03 61 62 63 02 01 01")).unwrap();
+        assert_eq!(example.flag, None);
+        assert_eq!(example.version, 1);
+    }
+
+    #[test]
+    fn decode_missing() {
+        assert!(Example::from_der(&hex!("31 06 80 01 ff 02 01 01")).is_err());
+    }
+
+    #[test]
+    fn decode_duplicate() {
+        assert!(Example::from_der(&hex!("31 09 02 01 01 0c 01 61 02 01 01")).is_err());
+    }
+
+    #[test]
+    fn decode_unknown() {
+        assert!(Example::from_der(&hex!("31 0b 02 01 01 0c 03 61 62 63 81 01 ff")).is_err());
+    }
+
+    #[test]
+    fn encode() {
+        assert_eq!(EXAMPLE_DER, example().to_vec().unwrap());
+
+        let example = Example {
+            flag: None,
+            ..example()
+        };
+        assert_eq!(
+            &hex!("31 08 02 01 01 0c 03 61 62 63"),
+            example.to_vec().unwrap().as_slice()
+        );
+    }
+
+    /// `Set` with `AUTOMATIC` tagging.
+    mod automatic {
+        use der::{asn1::Utf8StringRef, Decode, Encode, Set};
+        use hex_literal::hex;
+
+        /// ```text
+        /// Person ::= SET {
+        ///     name    UTF8String,
+        ///     age     INTEGER OPTIONAL }
+        /// ```
+        #[derive(Set, Debug, Eq, PartialEq)]
+        #[asn1(tag_mode = "AUTOMATIC")]
+        pub struct Person<'a> {
+            pub name: Utf8StringRef<'a>,
+
+            #[asn1(optional = "true")]
+            pub age: Option<u8>,
+        }
+
+        const PERSON_DER: &'static [u8] = &hex!("31 08 80 03 61 62 63 81 01 2a");
+
+        #[test]
+        fn round_trip() {
+            let person = Person::from_der(PERSON_DER).unwrap();
+            assert_eq!(person.name.as_str(), "abc");
+            assert_eq!(person.age, Some(42));
+            assert_eq!(PERSON_DER, person.to_vec().unwrap());
+
+            let person = Person::from_der(&hex!("31 08 81 01 2a 80 03 61 62 63")).unwrap();
+            assert_eq!(PERSON_DER, person.to_vec().unwrap());
+        }
+    }
 }
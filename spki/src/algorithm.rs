@@ -5,6 +5,9 @@ use core::cmp::Ordering;
 use der::asn1::{AnyRef, ObjectIdentifier};
 use der::{Decode, DecodeValue, DerOrd, Encode, Header, Reader, Sequence, ValueOrd};
 
+#[cfg(feature = "alloc")]
+use der::asn1::Any;
+
 /// X.509 `AlgorithmIdentifier` as defined in [RFC 5280 Section 4.1.1.2].
 ///
 /// ```text
@@ -130,3 +133,77 @@ impl ValueOrd for AlgorithmIdentifier<'_> {
         }
     }
 }
+
+/// Owned form of [`AlgorithmIdentifier`].
+///
+/// This type provides the same functionality as [`AlgorithmIdentifier`] but
+/// owns its `parameters`, so it can be stored independently of the input
+/// data.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+pub struct AlgorithmIdentifierOwned {
+    /// Algorithm OID, i.e. the `algorithm` field in the `AlgorithmIdentifier`
+    /// ASN.1 schema.
+    pub oid: ObjectIdentifier,
+
+    /// Algorithm `parameters`.
+    pub parameters: Option<Any>,
+}
+
+#[cfg(feature = "alloc")]
+impl AlgorithmIdentifierOwned {
+    /// Borrow this algorithm identifier as an [`AlgorithmIdentifier`].
+    pub fn as_ref(&self) -> AlgorithmIdentifier<'_> {
+        self.into()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> DecodeValue<'a> for AlgorithmIdentifierOwned {
+    fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> der::Result<Self> {
+        reader.read_nested(header.length, |reader| {
+            Ok(Self {
+                oid: reader.decode()?,
+                parameters: reader.decode()?,
+            })
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Sequence<'_> for AlgorithmIdentifierOwned {
+    fn fields<F, T>(&self, f: F) -> der::Result<T>
+    where
+        F: FnOnce(&[&dyn Encode]) -> der::Result<T>,
+    {
+        f(&[&self.oid, &self.parameters])
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl ValueOrd for AlgorithmIdentifierOwned {
+    fn value_cmp(&self, other: &Self) -> der::Result<Ordering> {
+        self.as_ref().value_cmp(&other.as_ref())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<&AlgorithmIdentifier<'_>> for AlgorithmIdentifierOwned {
+    fn from(alg: &AlgorithmIdentifier<'_>) -> Self {
+        Self {
+            oid: alg.oid,
+            parameters: alg.parameters.map(Any::from),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&'a AlgorithmIdentifierOwned> for AlgorithmIdentifier<'a> {
+    fn from(alg: &'a AlgorithmIdentifierOwned) -> Self {
+        Self {
+            oid: alg.oid,
+            parameters: alg.parameters.as_ref().map(AnyRef::from),
+        }
+    }
+}
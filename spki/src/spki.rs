@@ -7,7 +7,10 @@ use der::{
 };
 
 #[cfg(feature = "alloc")]
-use der::Document;
+use {
+    crate::AlgorithmIdentifierOwned,
+    der::{asn1::BitString, Document},
+};
 
 #[cfg(feature = "fingerprint")]
 use crate::{fingerprint, FingerprintBytes};
@@ -139,3 +142,92 @@ impl TryFrom<&SubjectPublicKeyInfo<'_>> for Document {
 impl PemLabel for SubjectPublicKeyInfo<'_> {
     const PEM_LABEL: &'static str = "PUBLIC KEY";
 }
+
+/// Owned form of [`SubjectPublicKeyInfo`].
+///
+/// This type provides the same functionality as [`SubjectPublicKeyInfo`] but
+/// owns the backing data. As with [`SubjectPublicKeyInfo`], the
+/// `subject_public_key` is expected to be octet aligned.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SubjectPublicKeyInfoOwned {
+    /// X.509 [`AlgorithmIdentifierOwned`] for the public key type
+    pub algorithm: AlgorithmIdentifierOwned,
+
+    /// Public key data
+    pub subject_public_key: BitString,
+}
+
+#[cfg(feature = "alloc")]
+impl SubjectPublicKeyInfoOwned {
+    /// Borrow this public key as a [`SubjectPublicKeyInfo`].
+    pub fn as_ref(&self) -> SubjectPublicKeyInfo<'_> {
+        self.into()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> DecodeValue<'a> for SubjectPublicKeyInfoOwned {
+    fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> der::Result<Self> {
+        reader.read_nested(header.length, |reader| {
+            let algorithm = reader.decode()?;
+            let subject_public_key = BitString::decode(reader)?;
+
+            if subject_public_key.has_unused_bits() {
+                return Err(der::Tag::BitString.value_error());
+            }
+
+            Ok(Self {
+                algorithm,
+                subject_public_key,
+            })
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Sequence<'_> for SubjectPublicKeyInfoOwned {
+    fn fields<F, T>(&self, f: F) -> der::Result<T>
+    where
+        F: FnOnce(&[&dyn Encode]) -> der::Result<T>,
+    {
+        f(&[&self.algorithm, &self.subject_public_key])
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl ValueOrd for SubjectPublicKeyInfoOwned {
+    fn value_cmp(&self, other: &Self) -> der::Result<Ordering> {
+        self.as_ref().value_cmp(&other.as_ref())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<&SubjectPublicKeyInfo<'_>> for SubjectPublicKeyInfoOwned {
+    fn from(spki: &SubjectPublicKeyInfo<'_>) -> Self {
+        Self {
+            algorithm: (&spki.algorithm).into(),
+            subject_public_key: spki
+                .bitstring()
+                .map(BitString::from)
+                .expect("invalid BIT STRING"),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&'a SubjectPublicKeyInfoOwned> for SubjectPublicKeyInfo<'a> {
+    fn from(spki: &'a SubjectPublicKeyInfoOwned) -> Self {
+        Self {
+            algorithm: spki.algorithm.as_ref(),
+            subject_public_key: spki.subject_public_key.raw_bytes(),
+        }
+    }
+}
+
+#[cfg(feature = "pem")]
+#[cfg_attr(docsrs, doc(cfg(feature = "pem")))]
+impl PemLabel for SubjectPublicKeyInfoOwned {
+    const PEM_LABEL: &'static str = "PUBLIC KEY";
+}
@@ -131,6 +131,19 @@ fn encode_rsa_2048_der() {
     assert_eq!(RSA_2048_DER_EXAMPLE, pk_encoded.as_slice());
 }
 
+#[test]
+#[cfg(feature = "alloc")]
+fn owned_ec_p256_der() {
+    use der::Decode;
+    use spki::SubjectPublicKeyInfoOwned;
+
+    let pk = SubjectPublicKeyInfo::try_from(EC_P256_DER_EXAMPLE).unwrap();
+    let owned = SubjectPublicKeyInfoOwned::from_der(EC_P256_DER_EXAMPLE).unwrap();
+    assert_eq!(owned, SubjectPublicKeyInfoOwned::from(&pk));
+    assert_eq!(owned.as_ref(), pk);
+    assert_eq!(EC_P256_DER_EXAMPLE, owned.to_vec().unwrap().as_slice());
+}
+
 #[test]
 #[cfg(feature = "pem")]
 fn encode_ec_p256_pem() {
@@ -0,0 +1,613 @@
+//! Conversion of X.509 structures into output trees.
+
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use const_oid::db::rfc5280::ID_CE_INVALIDITY_DATE;
+use const_oid::db::rfc5912::ID_EXTENSION_REQ;
+use const_oid::db::DB;
+use der::asn1::{AnyRef, BitStringRef, GeneralizedTime, IntRef, ObjectIdentifier, UIntRef};
+use der::{Decode, Encode, Tag, Tagged};
+use spki::{AlgorithmIdentifier, SubjectPublicKeyInfo};
+use x509_cert::anchor::{CertPathControls, TrustAnchorChoice, TrustAnchorInfo};
+use x509_cert::attr::Attribute;
+use x509_cert::crl::{CertificateList, RevokedCert, TbsCertList};
+use x509_cert::ext::dept::DepartmentIdentity;
+use x509_cert::ext::pkix::certpolicy::PolicyInformation;
+use x509_cert::ext::pkix::constraints::name::GeneralSubtree;
+use x509_cert::ext::pkix::crl::dp::{DistributionPoint, ReasonFlags};
+use x509_cert::ext::pkix::name::{DistributionPointName, GeneralName};
+use x509_cert::ext::pkix::{AccessDescription, NameConstraints};
+use x509_cert::ext::registry::{ExtensionRegistry, ParsedExtension};
+use x509_cert::ext::Extension;
+use x509_cert::name::Name;
+use x509_cert::request::{CertReq, ExtensionReq};
+use x509_cert::time::{Time, Validity};
+use x509_cert::{Certificate, TbsCertificate};
+
+use crate::node::Node;
+
+/// `Certificate`
+pub fn certificate(cert: &Certificate<'_>) -> Node {
+    Node::fields([
+        (
+            "tbsCertificate",
+            Some(tbs_certificate(&cert.tbs_certificate)),
+        ),
+        (
+            "signatureAlgorithm",
+            Some(algorithm(&cert.signature_algorithm)),
+        ),
+        ("signature", Some(bit_string(&cert.signature))),
+    ])
+}
+
+/// `TBSCertificate`
+pub fn tbs_certificate(tbs: &TbsCertificate<'_>) -> Node {
+    Node::fields([
+        (
+            "version",
+            Some(Node::display(format_args!("{:?}", tbs.version))),
+        ),
+        ("serialNumber", Some(int(&tbs.serial_number))),
+        ("signature", Some(algorithm(&tbs.signature))),
+        ("issuer", Some(name(&tbs.issuer))),
+        ("validity", Some(validity(&tbs.validity))),
+        ("subject", Some(name(&tbs.subject))),
+        (
+            "subjectPublicKeyInfo",
+            Some(public_key(&tbs.subject_public_key_info)),
+        ),
+        (
+            "issuerUniqueID",
+            tbs.issuer_unique_id.as_ref().map(bit_string),
+        ),
+        (
+            "subjectUniqueID",
+            tbs.subject_unique_id.as_ref().map(bit_string),
+        ),
+        ("extensions", tbs.extensions.as_deref().map(extensions)),
+    ])
+}
+
+/// `CertificationRequest`
+pub fn cert_req(req: &CertReq<'_>) -> Node {
+    let info = &req.info;
+
+    Node::fields([
+        (
+            "certificationRequestInfo",
+            Some(Node::fields([
+                (
+                    "version",
+                    Some(Node::display(format_args!("{:?}", info.version))),
+                ),
+                ("subject", Some(name(&info.subject))),
+                ("subjectPKInfo", Some(public_key(&info.public_key))),
+                (
+                    "attributes",
+                    Some(Node::List(info.attributes.iter().map(attribute).collect())),
+                ),
+            ])),
+        ),
+        ("signatureAlgorithm", Some(algorithm(&req.algorithm))),
+        ("signature", Some(bit_string(&req.signature))),
+    ])
+}
+
+/// `CertificateList`
+pub fn certificate_list(crl: &CertificateList<'_>) -> Node {
+    Node::fields([
+        ("tbsCertList", Some(tbs_cert_list(&crl.tbs_cert_list))),
+        (
+            "signatureAlgorithm",
+            Some(algorithm(&crl.signature_algorithm)),
+        ),
+        ("signature", Some(bit_string(&crl.signature))),
+    ])
+}
+
+fn tbs_cert_list(tbs: &TbsCertList<'_>) -> Node {
+    Node::fields([
+        (
+            "version",
+            Some(Node::display(format_args!("{:?}", tbs.version))),
+        ),
+        ("signature", Some(algorithm(&tbs.signature))),
+        ("issuer", Some(name(&tbs.issuer))),
+        ("thisUpdate", Some(time(&tbs.this_update))),
+        ("nextUpdate", tbs.next_update.as_ref().map(time)),
+        (
+            "revokedCertificates",
+            tbs.revoked_certificates
+                .as_ref()
+                .map(|revoked| Node::List(revoked.iter().map(revoked_cert).collect())),
+        ),
+        (
+            "crlExtensions",
+            tbs.crl_extensions.as_deref().map(extensions),
+        ),
+    ])
+}
+
+fn revoked_cert(revoked: &RevokedCert<'_>) -> Node {
+    Node::fields([
+        ("userCertificate", Some(int(&revoked.serial_number))),
+        ("revocationDate", Some(time(&revoked.revocation_date))),
+        (
+            "crlEntryExtensions",
+            revoked.crl_entry_extensions.as_deref().map(extensions),
+        ),
+    ])
+}
+
+/// `TrustAnchorChoice`
+pub fn trust_anchor(choice: &TrustAnchorChoice<'_>) -> Node {
+    match choice {
+        TrustAnchorChoice::Certificate(cert) => {
+            Node::fields([("certificate", Some(certificate(cert)))])
+        }
+        TrustAnchorChoice::TbsCertificate(tbs) => {
+            Node::fields([("tbsCert", Some(tbs_certificate(tbs)))])
+        }
+        TrustAnchorChoice::TaInfo(info) => Node::fields([("taInfo", Some(ta_info(info)))]),
+    }
+}
+
+fn ta_info(info: &TrustAnchorInfo<'_>) -> Node {
+    Node::fields([
+        (
+            "version",
+            Some(Node::display(format_args!("{:?}", info.version))),
+        ),
+        ("pubKey", Some(public_key(&info.pub_key))),
+        ("keyId", Some(hex(info.key_id.as_bytes()))),
+        ("taTitle", info.ta_title.as_ref().map(Node::display)),
+        ("certPath", info.cert_path.as_ref().map(cert_path_controls)),
+        ("exts", info.extensions.as_deref().map(extensions)),
+        (
+            "taTitleLangTag",
+            info.ta_title_lang_tag.as_ref().map(Node::display),
+        ),
+    ])
+}
+
+fn cert_path_controls(controls: &CertPathControls<'_>) -> Node {
+    Node::fields([
+        ("taName", Some(name(&controls.ta_name))),
+        (
+            "certificate",
+            controls.certificate.as_ref().map(certificate),
+        ),
+        (
+            "policySet",
+            controls
+                .policy_set
+                .as_ref()
+                .map(|policies| Node::List(policies.0.iter().map(policy_information).collect())),
+        ),
+        (
+            "policyFlags",
+            controls
+                .policy_flags
+                .map(|flags| Node::List(flags.into_iter().map(debug).collect())),
+        ),
+        (
+            "nameConstr",
+            controls.name_constr.as_ref().map(name_constraints),
+        ),
+        (
+            "pathLenConstraint",
+            controls.path_len_constraint.map(|n| Node::Number(n.into())),
+        ),
+    ])
+}
+
+fn attribute(attr: &Attribute<'_>) -> Node {
+    let values = attr.values.iter().map(|value| match attr.oid {
+        ID_EXTENSION_REQ => match value.to_vec() {
+            Ok(der) => decode::<ExtensionReq<'_>>(&der, |req| extensions(&req.0)),
+            Err(err) => invalid(err),
+        },
+        _ => any(value),
+    });
+
+    Node::fields([
+        ("type", Some(oid(&attr.oid))),
+        ("values", Some(Node::List(values.collect()))),
+    ])
+}
+
+fn extensions(exts: &[Extension<'_>]) -> Node {
+    Node::List(exts.iter().map(extension).collect())
+}
+
+fn extension(ext: &Extension<'_>) -> Node {
+    Node::fields([
+        ("extnID", Some(oid(&ext.extn_id))),
+        ("critical", Some(Node::Bool(ext.critical))),
+        ("extnValue", Some(extension_value(ext))),
+    ])
+}
+
+/// Extension registry with the extensions known to this crate and the
+/// department identity extension.
+fn registry() -> ExtensionRegistry {
+    let mut registry = ExtensionRegistry::new();
+    registry.register::<DepartmentIdentity>();
+    registry
+}
+
+/// Decode the value of an extension with the extension registry, falling
+/// back to hex.
+fn extension_value(ext: &Extension<'_>) -> Node {
+    let parsed = match registry().decode(ext) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            return Node::Value(format!("<invalid: {}> {}", err, hex_string(ext.extn_value)))
+        }
+    };
+
+    match parsed {
+        ParsedExtension::BasicConstraints(bc) => Node::fields([
+            ("cA", Some(Node::Bool(bc.ca))),
+            (
+                "pathLenConstraint",
+                bc.path_len_constraint.map(|n| Node::Number(n.into())),
+            ),
+        ]),
+        ParsedExtension::KeyUsage(ku) => Node::List(ku.0.into_iter().map(debug).collect()),
+        ParsedExtension::ExtendedKeyUsage(eku) => Node::List(eku.0.iter().map(oid).collect()),
+        ParsedExtension::SubjectKeyIdentifier(skid) => hex(skid.0.as_bytes()),
+        ParsedExtension::AuthorityKeyIdentifier(akid) => Node::fields([
+            (
+                "keyIdentifier",
+                akid.key_identifier.map(|id| hex(id.as_bytes())),
+            ),
+            (
+                "authorityCertIssuer",
+                akid.authority_cert_issuer.as_deref().map(general_names),
+            ),
+            (
+                "authorityCertSerialNumber",
+                akid.authority_cert_serial_number.as_ref().map(int),
+            ),
+        ]),
+        ParsedExtension::SubjectAltName(names) => general_names(&names.0),
+        ParsedExtension::IssuerAltName(names) => general_names(&names.0),
+        ParsedExtension::CertificateIssuer(names) => general_names(&names.0),
+        ParsedExtension::SubjectDirectoryAttributes(attrs) => {
+            Node::List(attrs.0.iter().map(Node::display).collect())
+        }
+        ParsedExtension::CertificatePolicies(policies) => {
+            Node::List(policies.0.iter().map(policy_information).collect())
+        }
+        ParsedExtension::PolicyMappings(mappings) => Node::List(
+            mappings
+                .0
+                .iter()
+                .map(|mapping| {
+                    Node::fields([
+                        (
+                            "issuerDomainPolicy",
+                            Some(oid(&mapping.issuer_domain_policy)),
+                        ),
+                        (
+                            "subjectDomainPolicy",
+                            Some(oid(&mapping.subject_domain_policy)),
+                        ),
+                    ])
+                })
+                .collect(),
+        ),
+        ParsedExtension::PolicyConstraints(pc) => Node::fields([
+            (
+                "requireExplicitPolicy",
+                pc.require_explicit_policy.map(|n| Node::Number(n.into())),
+            ),
+            (
+                "inhibitPolicyMapping",
+                pc.inhibit_policy_mapping.map(|n| Node::Number(n.into())),
+            ),
+        ]),
+        ParsedExtension::InhibitAnyPolicy(skip) => Node::Number(skip.0.into()),
+        ParsedExtension::NameConstraints(nc) => name_constraints(&nc),
+        ParsedExtension::CrlDistributionPoints(points) => {
+            Node::List(points.0.iter().map(distribution_point).collect())
+        }
+        ParsedExtension::FreshestCrl(points) => {
+            Node::List(points.0.iter().map(distribution_point).collect())
+        }
+        ParsedExtension::AuthorityInfoAccess(aia) => {
+            Node::List(aia.0.iter().map(access_description).collect())
+        }
+        ParsedExtension::SubjectInfoAccess(sia) => {
+            Node::List(sia.0.iter().map(access_description).collect())
+        }
+        ParsedExtension::PrivateKeyUsagePeriod(period) => Node::fields([
+            (
+                "notBefore",
+                period.not_before.as_ref().map(generalized_time),
+            ),
+            ("notAfter", period.not_after.as_ref().map(generalized_time)),
+        ]),
+        ParsedExtension::CrlNumber(number) => uint(&number.0),
+        ParsedExtension::DeltaCrlIndicator(number) => uint(&number.0),
+        ParsedExtension::CrlReason(reason) => debug(reason),
+        ParsedExtension::IssuingDistributionPoint(idp) => Node::fields([
+            (
+                "distributionPoint",
+                idp.distribution_point.as_ref().map(distribution_point_name),
+            ),
+            (
+                "onlyContainsUserCerts",
+                Some(Node::Bool(idp.only_contains_user_certs)),
+            ),
+            (
+                "onlyContainsCACerts",
+                Some(Node::Bool(idp.only_contains_ca_certs)),
+            ),
+            ("onlySomeReasons", idp.only_some_reasons.map(reason_flags)),
+            ("indirectCRL", Some(Node::Bool(idp.indirect_crl))),
+            (
+                "onlyContainsAttributeCerts",
+                Some(Node::Bool(idp.only_contains_attribute_certs)),
+            ),
+        ]),
+        ParsedExtension::Custom(_) => match parsed.downcast_ref() {
+            Some(dept) => department_identity(dept),
+            None => hex(ext.extn_value),
+        },
+        ParsedExtension::Unknown(value) if ext.extn_id == ID_CE_INVALIDITY_DATE => {
+            decode::<GeneralizedTime>(value, |t| generalized_time(&t))
+        }
+        _ => hex(ext.extn_value),
+    }
+}
+
+/// `DepartmentIdentity`
+fn department_identity(dept: &DepartmentIdentity) -> Node {
+    let mut fields = vec![
+        ("DeptType", Node::Value(dept.dept_type.clone())),
+        ("DeptLevel", Node::Number(dept.dept_level.into())),
+        ("DeptName", Node::Value(dept.dept_name.clone())),
+        ("SuperDeptName", Node::Value(dept.super_dept_name.clone())),
+    ];
+    let others = dept
+        .other_attributes
+        .iter()
+        .map(|(name, value)| {
+            Node::fields([
+                ("name", Some(Node::Value(name.clone()))),
+                ("value", Some(Node::Value(value.clone()))),
+            ])
+        })
+        .collect();
+    fields.push(("otherAttributes", Node::List(others)));
+    Node::Fields(fields)
+}
+
+/// Decode `bytes` as `T` and describe it, or describe the error.
+fn decode<'a, T: Decode<'a>>(bytes: &'a [u8], describe: impl FnOnce(T) -> Node) -> Node {
+    match T::from_der(bytes) {
+        Ok(value) => describe(value),
+        Err(err) => Node::Value(format!("<invalid: {}> {}", err, hex_string(bytes))),
+    }
+}
+
+fn invalid(err: der::Error) -> Node {
+    Node::Value(format!("<invalid: {}>", err))
+}
+
+fn policy_information(policy: &PolicyInformation<'_>) -> Node {
+    Node::fields([
+        ("policyIdentifier", Some(oid(&policy.policy_identifier))),
+        (
+            "policyQualifiers",
+            policy.policy_qualifiers.as_ref().map(|qualifiers| {
+                Node::List(
+                    qualifiers
+                        .iter()
+                        .map(|qualifier| {
+                            let value = qualifier.qualifier.as_ref().map(any);
+
+                            Node::fields([
+                                (
+                                    "policyQualifierId",
+                                    Some(oid(&qualifier.policy_qualifier_id)),
+                                ),
+                                ("qualifier", value),
+                            ])
+                        })
+                        .collect(),
+                )
+            }),
+        ),
+    ])
+}
+
+fn name_constraints(nc: &NameConstraints<'_>) -> Node {
+    let subtrees = |subtrees: &Vec<GeneralSubtree<'_>>| {
+        Node::List(
+            subtrees
+                .iter()
+                .map(|subtree| general_name(&subtree.base))
+                .collect(),
+        )
+    };
+
+    Node::fields([
+        (
+            "permittedSubtrees",
+            nc.permitted_subtrees.as_ref().map(subtrees),
+        ),
+        (
+            "excludedSubtrees",
+            nc.excluded_subtrees.as_ref().map(subtrees),
+        ),
+    ])
+}
+
+fn distribution_point(dp: &DistributionPoint<'_>) -> Node {
+    Node::fields([
+        (
+            "distributionPoint",
+            dp.distribution_point.as_ref().map(distribution_point_name),
+        ),
+        ("reasons", dp.reasons.map(reason_flags)),
+        ("cRLIssuer", dp.crl_issuer.as_deref().map(general_names)),
+    ])
+}
+
+fn distribution_point_name(name: &DistributionPointName<'_>) -> Node {
+    match name {
+        DistributionPointName::FullName(names) => {
+            Node::fields([("fullName", Some(general_names(names)))])
+        }
+        DistributionPointName::NameRelativeToCRLIssuer(rdn) => {
+            Node::fields([("nameRelativeToCRLIssuer", Some(Node::display(rdn)))])
+        }
+    }
+}
+
+fn reason_flags(flags: ReasonFlags) -> Node {
+    Node::List(flags.into_iter().map(debug).collect())
+}
+
+fn access_description(access: &AccessDescription<'_>) -> Node {
+    Node::fields([
+        ("accessMethod", Some(oid(&access.access_method))),
+        (
+            "accessLocation",
+            Some(general_name(&access.access_location)),
+        ),
+    ])
+}
+
+fn general_names(names: &[GeneralName<'_>]) -> Node {
+    Node::List(names.iter().map(general_name).collect())
+}
+
+fn general_name(name: &GeneralName<'_>) -> Node {
+    Node::Value(match name {
+        GeneralName::OtherName(other) => format!(
+            "othername:{}:{}",
+            oid_string(&other.type_id),
+            hex_string(other.value.value())
+        ),
+        GeneralName::Rfc822Name(email) => format!("email:{}", email),
+        GeneralName::DnsName(dns) => format!("DNS:{}", dns),
+        GeneralName::DirectoryName(dn) => format!("DirName:{}", dn),
+        GeneralName::EdiPartyName(edi) => {
+            format!("EdiPartyName:{}", edi.party_name)
+        }
+        GeneralName::UniformResourceIdentifier(uri) => format!("URI:{}", uri),
+        GeneralName::IpAddress(ip) => format!("IP:{}", ip_address(ip.as_bytes())),
+        GeneralName::RegisteredId(id) => format!("RID:{}", oid_string(id)),
+    })
+}
+
+/// Format an IP address, or an address and mask as used in name constraints.
+fn ip_address(bytes: &[u8]) -> String {
+    let v4 = |b: &[u8]| Ipv4Addr::new(b[0], b[1], b[2], b[3]);
+    let v6 = |b: &[u8]| Ipv6Addr::from(<[u8; 16]>::try_from(b).expect("16 bytes"));
+
+    match bytes.len() {
+        4 => v4(bytes).to_string(),
+        8 => format!("{}/{}", v4(&bytes[..4]), v4(&bytes[4..])),
+        16 => v6(bytes).to_string(),
+        32 => format!("{}/{}", v6(&bytes[..16]), v6(&bytes[16..])),
+        _ => hex_string(bytes),
+    }
+}
+
+fn algorithm(alg: &AlgorithmIdentifier<'_>) -> Node {
+    Node::fields([
+        ("algorithm", Some(oid(&alg.oid))),
+        ("parameters", alg.parameters.as_ref().map(any)),
+    ])
+}
+
+fn public_key(spki: &SubjectPublicKeyInfo<'_>) -> Node {
+    Node::fields([
+        ("algorithm", Some(algorithm(&spki.algorithm))),
+        ("subjectPublicKey", Some(hex(spki.subject_public_key))),
+    ])
+}
+
+fn validity(validity: &Validity) -> Node {
+    Node::fields([
+        ("notBefore", Some(time(&validity.not_before))),
+        ("notAfter", Some(time(&validity.not_after))),
+    ])
+}
+
+fn time(time: &Time) -> Node {
+    Node::display(time)
+}
+
+fn generalized_time(time: &GeneralizedTime) -> Node {
+    Node::display(time.to_date_time())
+}
+
+fn name(name: &Name<'_>) -> Node {
+    Node::display(name)
+}
+
+/// Describe an `ANY` value: OIDs and strings are shown as such, anything
+/// else as the hex DER encoding.
+fn any(value: &AnyRef<'_>) -> Node {
+    match value.tag() {
+        Tag::Null => Node::Value("NULL".into()),
+        Tag::ObjectIdentifier => match value.oid() {
+            Ok(id) => oid(&id),
+            Err(err) => invalid(err),
+        },
+        Tag::PrintableString | Tag::Utf8String | Tag::Ia5String => {
+            Node::Value(String::from_utf8_lossy(value.value()).into_owned())
+        }
+        _ => match value.to_vec() {
+            Ok(der) => hex(&der),
+            Err(err) => invalid(err),
+        },
+    }
+}
+
+fn oid(oid: &ObjectIdentifier) -> Node {
+    Node::Value(oid_string(oid))
+}
+
+/// `name (dotted)` if the OID is in the database, else the dotted form.
+fn oid_string(oid: &ObjectIdentifier) -> String {
+    match DB.by_oid(oid) {
+        Some(name) => format!("{} ({})", name, oid),
+        None => oid.to_string(),
+    }
+}
+
+fn int(value: &IntRef<'_>) -> Node {
+    hex(value.as_bytes())
+}
+
+fn uint(value: &UIntRef<'_>) -> Node {
+    hex(value.as_bytes())
+}
+
+fn bit_string(bits: &BitStringRef<'_>) -> Node {
+    hex(bits.raw_bytes())
+}
+
+fn hex(bytes: &[u8]) -> Node {
+    Node::Value(hex_string(bytes))
+}
+
+fn hex_string(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+fn debug(value: impl std::fmt::Debug) -> Node {
+    Node::display(format_args!("{:?}", value))
+}
@@ -1,195 +1,304 @@
+//! Inspect X.509 certificates, certification requests, CRLs and trust
+//! anchors.
+//!
+//! ```text
+//! x509 [--json] [FILE]
+//! ```
+//!
+//! The input may be DER or PEM and is read from standard input if no file
+//! (or `-`) is given. The type of DER input is detected by trying, in order,
+//! `Certificate`, `CertificationRequest`, `CertificateList` and
+//! `TrustAnchorChoice`.
+//!
+//! With `--json`, the input is printed in the data model of the `serde`
+//! feature of `x509-cert`.
 
-use std::{collections::HashMap, time::Duration};
-
-use x509_cert::{Certificate, ext::Extension, time::Time};
-use der::{
-    asn1::{BitStringRef, ContextSpecific, ObjectIdentifier, UIntRef},
-    Decode, DecodeValue, Encode, FixedTag, Header, Reader, Tag, Tagged,
-};
-use x509_cert::*;
-use lite_json::{json_parser::parse_json, JsonValue};
-use serde_json::{Value};
-#[derive(Debug, Clone, Eq, PartialEq, Hash)]
-pub struct DepartmentIdentityStored {
-    /// 部门类型
-    pub dept_type: String,
-    /// 部门级别
-    pub dept_level: u8,
-    /// 部门名称
-    pub dept_name: String,
-    /// 上级部门名称
-    pub super_dept_name: String,
+mod describe;
+mod node;
+
+use std::fmt;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::process;
+
+use der::{pem, Decode, Tag};
+use x509_cert::anchor::TrustAnchorChoice;
+use x509_cert::crl::CertificateList;
+use x509_cert::request::CertReq;
+use x509_cert::Certificate;
+
+const USAGE: &str = "usage: x509 [--json] [FILE|-]
+
+Print the contents of a DER or PEM encoded certificate, certification
+request, CRL or trust anchor. Reads standard input if FILE is omitted or -.
+
+options:
+    --json      print JSON instead of text
+    -h, --help  print this help
+";
+
+fn main() {
+    let mut json = false;
+    let mut path = None;
+
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--json" => json = true,
+            "-h" | "--help" => {
+                print!("{}", USAGE);
+                return;
+            }
+            _ if arg.starts_with('-') && arg != "-" => fail(&format!("unknown option {}", arg)),
+            _ if path.is_some() => fail("only one input file may be given"),
+            _ => path = Some(arg),
+        }
+    }
+
+    let input = match path.as_deref() {
+        None | Some("-") => {
+            let mut input = Vec::new();
+            io::stdin()
+                .read_to_end(&mut input)
+                .map(|_| input)
+                .map_err(|err| format!("reading standard input: {}", err))
+        }
+        Some(path) => fs::read(path).map_err(|err| format!("reading {}: {}", path, err)),
+    };
+
+    let output = match input.and_then(|input| inspect(&input, json)) {
+        Ok(output) => output,
+        Err(err) => fail(&err),
+    };
+
+    // Ignore errors such as a closed pipe.
+    let _ = io::stdout().write_all(output.as_bytes());
 }
 
-///Structure supporting deferred decoding of fields in the Certificate SEQUENCE
-pub struct DeferDecodeCertificate<'a> {
-    /// tbsCertificate       TBSCertificate,
-    pub tbs_certificate: &'a [u8],
-    /// signatureAlgorithm   AlgorithmIdentifier,
-    pub signature_algorithm: &'a [u8],
-    /// signature            BIT STRING
-    pub signature: &'a [u8],
+/// A decoded input.
+#[allow(clippy::large_enum_variant)]
+enum Document<'a> {
+    Certificate(Certificate<'a>),
+    CertReq(CertReq<'a>),
+    CertificateList(CertificateList<'a>),
+    TrustAnchor(TrustAnchorChoice<'a>),
 }
 
-impl<'a> DecodeValue<'a> for DeferDecodeCertificate<'a> {
-    fn decode_value<R: Reader<'a>>(
-        reader: &mut R,
-        header: Header,
-    ) -> der::Result<DeferDecodeCertificate<'a>> {
-        reader.read_nested(header.length, |reader| {
-            Ok(Self {
-                tbs_certificate: reader.tlv_bytes()?,
-                signature_algorithm: reader.tlv_bytes()?,
-                signature: reader.tlv_bytes()?,
-            })
-        })
+impl Document<'_> {
+    /// Render as indented text.
+    fn to_text(&self) -> String {
+        match self {
+            Document::Certificate(cert) => describe::certificate(cert).to_text("Certificate"),
+            Document::CertReq(req) => describe::cert_req(req).to_text("CertificationRequest"),
+            Document::CertificateList(crl) => {
+                describe::certificate_list(crl).to_text("CertificateList")
+            }
+            Document::TrustAnchor(ta) => describe::trust_anchor(ta).to_text("TrustAnchorChoice"),
+        }
     }
-}
 
-impl FixedTag for DeferDecodeCertificate<'_> {
-    const TAG: Tag = Tag::Sequence;
+    /// Render as JSON, in the data model of the `serde` feature of
+    /// `x509-cert`.
+    fn to_json(&self) -> Result<String, String> {
+        let json = match self {
+            Document::Certificate(cert) => serde_json::to_string_pretty(cert),
+            Document::CertReq(req) => serde_json::to_string_pretty(req),
+            Document::CertificateList(crl) => serde_json::to_string_pretty(crl),
+            Document::TrustAnchor(ta) => serde_json::to_string_pretty(ta),
+        };
+
+        json.map(|json| json + "\n")
+            .map_err(|err| format!("serializing JSON: {}", err))
+    }
 }
 
-///Structure supporting deferred decoding of fields in the TBSCertificate SEQUENCE
-pub struct DeferDecodeTbsCertificate<'a> {
-    /// Decoded field
-    pub version: u8,
-    /// Defer decoded field
-    pub serial_number: &'a [u8],
-    /// Defer decoded field
-    pub signature: &'a [u8],
-    /// Defer decoded field
-    pub issuer: &'a [u8],
-    /// Defer decoded field
-    pub validity: &'a [u8],
-    /// Defer decoded field
-    pub subject: &'a [u8],
-    /// Defer decoded field
-    pub subject_public_key_info: &'a [u8],
-    /// Decoded field (never present)
-    pub issuer_unique_id: Option<BitStringRef<'a>>,
-    /// Decoded field (never present)
-    pub subject_unique_id: Option<BitStringRef<'a>>,
-    /// Defer decoded field
-    pub extensions: &'a [u8],
+/// Decode `input` and render it as text, or as JSON if `json` is set.
+fn inspect(input: &[u8], json: bool) -> Result<String, String> {
+    let start = input
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .unwrap_or(input.len());
+
+    let der;
+    let doc = if input[start..].starts_with(b"-----BEGIN") {
+        let (label, decoded) =
+            pem::decode_vec(&input[start..]).map_err(|err| format!("invalid PEM: {}", err))?;
+        der = decoded;
+
+        match label {
+            "CERTIFICATE" => decode(&der, "Certificate", Document::Certificate)?,
+            "CERTIFICATE REQUEST" | "NEW CERTIFICATE REQUEST" => {
+                decode(&der, "CertificationRequest", Document::CertReq)?
+            }
+            "X509 CRL" => decode(&der, "CertificateList", Document::CertificateList)?,
+            _ => detect(&der)?,
+        }
+    } else {
+        detect(input)?
+    };
+
+    match json {
+        true => doc.to_json(),
+        false => Ok(doc.to_text()),
+    }
 }
 
-impl<'a> DecodeValue<'a> for DeferDecodeTbsCertificate<'a> {
-    fn decode_value<R: Reader<'a>>(
-        reader: &mut R,
-        header: Header,
-    ) -> der::Result<DeferDecodeTbsCertificate<'a>> {
-        reader.read_nested(header.length, |reader| {
-            let version = ContextSpecific::decode_explicit(reader, ::der::TagNumber::N0)?
-                .map(|cs| cs.value)
-                .unwrap_or_else(Default::default);
-
-            Ok(Self {
-                version,
-                serial_number: reader.tlv_bytes()?,
-                signature: reader.tlv_bytes()?,
-                issuer: reader.tlv_bytes()?,
-                validity: reader.tlv_bytes()?,
-                subject: reader.tlv_bytes()?,
-                subject_public_key_info: reader.tlv_bytes()?,
-                issuer_unique_id: reader.decode()?,
-                subject_unique_id: reader.decode()?,
-                extensions: reader.tlv_bytes()?,
-            })
+/// Detect the type of a DER document.
+///
+/// If no type matches, the error of the attempt which got furthest into the
+/// input is reported, preferring the earlier types.
+fn detect(der: &[u8]) -> Result<Document<'_>, String> {
+    decode(der, "Certificate", Document::Certificate)
+        .or_else(|err| {
+            decode(der, "CertificationRequest", Document::CertReq).map_err(|e| err.furthest(e))
+        })
+        .or_else(|err| {
+            decode(der, "CertificateList", Document::CertificateList).map_err(|e| err.furthest(e))
+        })
+        .or_else(|err| match der.first().copied().map(Tag::try_from) {
+            // the `Certificate` choice was already attempted above
+            Some(Ok(Tag::Sequence)) => Err(err),
+            _ => {
+                decode(der, "TrustAnchorChoice", Document::TrustAnchor).map_err(|e| err.furthest(e))
+            }
         })
+        .map_err(|err| {
+            format!(
+                "input is not a certificate, certification request, CRL or trust anchor ({})",
+                err
+            )
+        })
+}
+
+fn decode<'a, T: Decode<'a>>(
+    der: &'a [u8],
+    title: &'static str,
+    document: fn(T) -> Document<'a>,
+) -> Result<Document<'a>, DecodeError> {
+    T::from_der(der)
+        .map(document)
+        .map_err(|error| DecodeError { title, error })
+}
+
+/// Error decoding a document as a particular type.
+struct DecodeError {
+    title: &'static str,
+    error: der::Error,
+}
+
+impl DecodeError {
+    /// The error of the attempt which got furthest into the input, or `self`
+    /// if neither did.
+    fn furthest(self, other: DecodeError) -> DecodeError {
+        match other.error.position() > self.error.position() {
+            true => other,
+            false => self,
+        }
     }
 }
 
-impl FixedTag for DeferDecodeTbsCertificate<'_> {
-    const TAG: Tag = Tag::Sequence;
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid {}: {}", self.title, self.error)
+    }
 }
 
-pub fn decimals_to_string(dec_vec: &[u8]) -> Result<String, String>{
+impl From<DecodeError> for String {
+    fn from(err: DecodeError) -> String {
+        err.to_string()
+    }
+}
 
-    let mut text = String::new();
+fn fail(message: &str) -> ! {
+    eprintln!("x509: {}", message);
+    process::exit(1);
+}
 
-    for d in dec_vec.iter(){
+#[cfg(test)]
+mod tests {
+    use super::inspect;
+    use der::pem::{self, LineEnding};
+    use serde_json::Value;
 
-        if !(d >=  &32 && d <= &126) {
-            return Err("the number is outside the ascii range".to_string());
-        } else {
-            text.push(*d as char);
-            
-        }
+    const CERT: &[u8] = include_bytes!("../x509-cert/tests/examples/path-leaf.der");
+    const CERT_PEM: &[u8] = include_bytes!("../x509-cert/tests/examples/amazon.pem");
+    const CRL: &[u8] = include_bytes!("../x509-cert/tests/examples/GoodCACRL.crl");
+    const CSR: &[u8] = include_bytes!("../x509-cert/tests/examples/rsa2048-csr.der");
+    const CSR_PEM: &[u8] = include_bytes!("../x509-cert/tests/examples/rsa2048-csr.pem");
+    const TA: &[u8] = include_bytes!("../x509-cert/tests/examples/eca_policies.ta");
 
+    fn title(input: &[u8]) -> String {
+        let text = inspect(input, false).unwrap();
+        text.lines().next().unwrap().to_string()
     }
 
-    Ok(text)
-}
-fn get_department_identity(extensions: &Vec<Extension>) -> HashMap<String, String>{
-    let mut map:HashMap<String,String> = HashMap::new();
-    let ipOid:ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.3.4.5.6.7.8.1");
-    for extension in extensions.iter() {
-        //println!("{:?} {:?} {:?}", x, extension.extn_id, extension.extn_id.cmp(&ipOid));
-        if (extension.extn_id.eq(&ipOid)) {
-            //println!("{:?}", extension.extn_value);
-            let data = decimals_to_string(extension.extn_value).unwrap();
-            let json_data = parse_json(&data).expect("Invalid JSON specified!");
-            let json_obj = json_data.to_object().unwrap();
-            for i in json_obj.iter(){
-                let attr_key:String = i.0.to_vec().iter().collect();
-                //println!("{:?}", attr_key);
-                let attr_value = &i.1;
-
-                for (item_key, item_value) in attr_value.as_object().unwrap(){
-                    let item_key_string:String = item_key.to_vec().iter().collect();
-                    let item_value_string:String = (*item_value).to_owned().to_string().unwrap().iter().collect();
-                    //println!("{:?}", item_value.is_string());
-                    //println!("{:?} {:?}", item_key_string, item_value_string);
-                    map.insert(item_key_string, item_value_string);
-                }
-            }
-        }
+    fn json(input: &[u8]) -> Value {
+        serde_json::from_str(&inspect(input, true).unwrap()).unwrap()
     }
-    map
-}
 
-fn get_department_identity_easier(extensions: &Vec<Extension>) -> DepartmentIdentityStored{
-    let mut dept_identity =DepartmentIdentityStored{
-        dept_type: "".into(),
-        dept_level: 0,
-        dept_name: "".into(),
-        super_dept_name: "".into()
-    };
+    #[test]
+    fn detect() {
+        assert_eq!(title(CERT), "Certificate:");
+        assert_eq!(title(CERT_PEM), "Certificate:");
+        assert_eq!(title(CRL), "CertificateList:");
+        assert_eq!(title(CSR), "CertificationRequest:");
+        assert_eq!(title(CSR_PEM), "CertificationRequest:");
+        assert_eq!(title(TA), "TrustAnchorChoice:");
 
-    let ipOid:ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.3.4.5.6.7.8.1");
-    for extension in extensions.iter() {
-        //println!("{:?} {:?} {:?}", x, extension.extn_id, extension.extn_id.cmp(&ipOid));
-        if (extension.extn_id.eq(&ipOid)) {
-            let data = decimals_to_string(extension.extn_value).unwrap();
-            let data:Value = serde_json::from_str(&data).unwrap();
-            //println!("{:?}", data["attrs"]["DeptLevel"]);
-            let dept_level = data["attrs"]["DeptLevel"].as_str().unwrap() ;
-            let dept_type = data["attrs"]["DeptType"].as_str().unwrap();
-            let dept_name = data["attrs"]["DeptName"].as_str().unwrap();
-            let super_dept_name = data["attrs"]["SuperDeptName"].as_str().unwrap();
-
-            
-            dept_identity.dept_level = u8::from_str_radix(dept_level, 10).unwrap();
-            dept_identity.dept_type = dept_type.into();
-            dept_identity.dept_name = dept_name.into();
-            dept_identity.super_dept_name = super_dept_name.into();
-        }
+        let crl_pem = pem::encode_string("X509 CRL", LineEnding::LF, CRL).unwrap();
+        assert_eq!(title(crl_pem.as_bytes()), "CertificateList:");
+
+        // leading whitespace before PEM, and PEM with an unexpected label
+        let cert_pem = pem::encode_string("TRUSTED CERTIFICATE", LineEnding::LF, CERT).unwrap();
+        assert_eq!(
+            title(format!("\n  {}", cert_pem).as_bytes()),
+            "Certificate:"
+        );
+
+        assert!(inspect(b"not a certificate", false).is_err());
+        assert!(inspect(b"-----BEGIN CERTIFICATE-----\n", false).is_err());
+
+        // the error of the attempt that got furthest is reported
+        let err = inspect(&CERT[..500], false).unwrap_err();
+        assert!(err.contains("invalid Certificate: ASN.1 DER message is incomplete"));
+
+        let mut crl = CRL.to_vec();
+        crl[240] = 0x31; // outer signatureAlgorithm tagged as SET
+        let err = inspect(&crl, false).unwrap_err();
+        assert!(err.contains("invalid CertificateList: unexpected ASN.1 DER tag"));
+    }
+
+    #[test]
+    fn text() {
+        let text = inspect(CERT, false).unwrap();
+        assert!(text.starts_with("Certificate:\n    tbsCertificate:\n"));
+        assert!(text.contains("\n        subject: C=US,O=RustCrypto,CN=leaf.example.com\n"));
+
+        let text = inspect(CRL, false).unwrap();
+        assert!(text.contains("revokedCertificates:"));
+    }
+
+    #[test]
+    fn json_output() {
+        let cert = json(CERT);
+        assert_eq!(
+            cert["tbs_certificate"]["subject"],
+            "C=US,O=RustCrypto,CN=leaf.example.com"
+        );
+        assert_eq!(
+            cert["signature_algorithm"]["algorithm"]["oid"],
+            "1.2.840.10045.4.3.2"
+        );
+
+        let crl = json(CRL);
+        assert_eq!(
+            crl["tbs_cert_list"]["revoked_certificates"][1]["serial_number"],
+            "0f"
+        );
+
+        let csr = json(CSR_PEM);
+        assert_eq!(csr["info"]["version"], "v1");
+
+        let ta = json(TA);
+        assert!(ta["ta_info"]["cert_path"].is_object());
     }
-    dept_identity
 }
-fn main() {
-    let der_encoded_cert =
-        include_bytes!("../certificatename.der");
-    let result = Certificate::from_der(der_encoded_cert).unwrap();
-    //println!("{:?}", result.tbs_certificate.extensions);
-    // println!("{:?}", result.tbs_certificate.validity);
-    let validity = result.tbs_certificate.validity;
-    let before_time = validity.not_before;
-    let after_time = validity.not_before;
-    let extensions =  result.tbs_certificate.extensions.unwrap();   
-    let test = after_time.to_date_time().unix_duration();
-    println!("afterTime: {:?} beforeTime: {:?}", after_time.to_date_time().unix_duration(), before_time.to_date_time().unix_duration());
-    println!("{:?}", get_department_identity_easier(&extensions));
-    
-}
\ No newline at end of file
@@ -0,0 +1,110 @@
+//! Output tree rendered as indented text.
+
+use std::fmt::{self, Write};
+
+/// A rendered value.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Node {
+    /// A boolean.
+    Bool(bool),
+
+    /// A non-negative integer.
+    Number(u64),
+
+    /// Any other scalar, e.g. a name, time or hex string.
+    Value(String),
+
+    /// Named fields, in order.
+    Fields(Vec<(&'static str, Node)>),
+
+    /// A list of values.
+    List(Vec<Node>),
+}
+
+impl Node {
+    /// Build a `Fields` node, skipping `None` values.
+    pub fn fields<I>(fields: I) -> Node
+    where
+        I: IntoIterator<Item = (&'static str, Option<Node>)>,
+    {
+        Node::Fields(
+            fields
+                .into_iter()
+                .filter_map(|(name, node)| node.map(|node| (name, node)))
+                .collect(),
+        )
+    }
+
+    /// Build a `Value` node from anything displayable.
+    pub fn display(value: impl fmt::Display) -> Node {
+        Node::Value(value.to_string())
+    }
+
+    /// Render as indented text.
+    pub fn to_text(&self, title: &str) -> String {
+        let mut out = String::new();
+        write_text(&mut out, 0, "", title, self);
+        out
+    }
+}
+
+const INDENT: usize = 4;
+
+fn write_text(out: &mut String, indent: usize, prefix: &str, name: &str, node: &Node) {
+    let pad = " ".repeat(indent);
+
+    match node {
+        Node::Bool(value) => {
+            let _ = writeln!(out, "{}{}{}: {}", pad, prefix, name, value);
+        }
+        Node::Number(value) => {
+            let _ = writeln!(out, "{}{}{}: {}", pad, prefix, name, value);
+        }
+        Node::Value(value) => {
+            let _ = writeln!(out, "{}{}{}: {}", pad, prefix, name, value);
+        }
+        Node::Fields(fields) => {
+            let _ = writeln!(out, "{}{}{}:", pad, prefix, name);
+            for (field, node) in fields {
+                write_text(out, indent + INDENT, "", field, node);
+            }
+        }
+        Node::List(items) => {
+            let _ = writeln!(out, "{}{}{}:", pad, prefix, name);
+            for item in items {
+                write_item(out, indent + INDENT, item);
+            }
+        }
+    }
+}
+
+fn write_item(out: &mut String, indent: usize, node: &Node) {
+    let pad = " ".repeat(indent);
+
+    match node {
+        Node::Bool(value) => {
+            let _ = writeln!(out, "{}- {}", pad, value);
+        }
+        Node::Number(value) => {
+            let _ = writeln!(out, "{}- {}", pad, value);
+        }
+        Node::Value(value) => {
+            let _ = writeln!(out, "{}- {}", pad, value);
+        }
+        Node::Fields(fields) => {
+            for (i, (field, node)) in fields.iter().enumerate() {
+                if i == 0 {
+                    write_text(out, indent, "- ", field, node);
+                } else {
+                    write_text(out, indent + 2, "", field, node);
+                }
+            }
+        }
+        Node::List(items) => {
+            let _ = writeln!(out, "{}-", pad);
+            for item in items {
+                write_item(out, indent + 2, item);
+            }
+        }
+    }
+}
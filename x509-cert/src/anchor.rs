@@ -1,6 +1,6 @@
 //! Trust anchor-related structures as defined in RFC 5914
 
-use crate::ext::pkix::{certpolicy::CertificatePolicies, NameConstraints};
+use crate::ext::pkix::{certpolicy::CertificatePolicies, NameConstraints, SubjectKeyIdentifier};
 use crate::{ext::Extensions, name::Name};
 use crate::{Certificate, TbsCertificate};
 
@@ -137,3 +137,39 @@ pub enum TrustAnchorChoice<'a> {
     #[asn1(context_specific = "2", tag_mode = "EXPLICIT", constructed = "true")]
     TaInfo(TrustAnchorInfo<'a>),
 }
+
+impl<'a> TrustAnchorChoice<'a> {
+    /// Returns the distinguished name of the trust anchor.
+    ///
+    /// A `TrustAnchorInfo` only has a name if it carries `CertPathControls`.
+    pub fn name(&self) -> Option<&Name<'a>> {
+        match self {
+            TrustAnchorChoice::Certificate(cert) => Some(&cert.tbs_certificate.subject),
+            TrustAnchorChoice::TbsCertificate(tbs) => Some(&tbs.subject),
+            TrustAnchorChoice::TaInfo(tai) => tai.cert_path.as_ref().map(|cp| &cp.ta_name),
+        }
+    }
+
+    /// Returns the public key of the trust anchor.
+    pub fn public_key(&self) -> &SubjectPublicKeyInfo<'a> {
+        match self {
+            TrustAnchorChoice::Certificate(cert) => &cert.tbs_certificate.subject_public_key_info,
+            TrustAnchorChoice::TbsCertificate(tbs) => &tbs.subject_public_key_info,
+            TrustAnchorChoice::TaInfo(tai) => &tai.pub_key,
+        }
+    }
+
+    /// Returns the key identifier of the trust anchor, i.e. the
+    /// `SubjectKeyIdentifier` of a certificate or the `keyId` of a
+    /// `TrustAnchorInfo`.
+    pub fn key_id(&self) -> Option<&[u8]> {
+        let tbs: &TbsCertificate<'_> = match self {
+            TrustAnchorChoice::Certificate(cert) => &cert.tbs_certificate,
+            TrustAnchorChoice::TbsCertificate(tbs) => tbs,
+            TrustAnchorChoice::TaInfo(tai) => return Some(tai.key_id.as_bytes()),
+        };
+
+        let (_, skid) = tbs.get::<SubjectKeyIdentifier<'_>>().ok().flatten()?;
+        Some(skid.0.as_bytes())
+    }
+}
@@ -1,10 +1,13 @@
 //! Attribute-related definitions as defined in X.501 (and updated by RFC 5280).
 
+use crate::ext::pkix::name::DirectoryString;
+
+use alloc::string::ToString;
 use alloc::vec::Vec;
 use core::fmt::{self, Write};
 
 use const_oid::db::DB;
-use der::asn1::{AnyRef, ObjectIdentifier, SetOfVec};
+use der::asn1::{Any, AnyRef, ObjectIdentifier, SetOfVec};
 use der::{Decode, Encode, Error, ErrorKind, Sequence, Tag, Tagged, ValueOrd};
 
 /// X.501 `AttributeType` as defined in [RFC 5280 Appendix A.1].
@@ -88,6 +91,39 @@ pub struct AttributeTypeAndValue<'a> {
     pub value: AnyRef<'a>,
 }
 
+/// Owned form of [`AttributeTypeAndValue`].
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord, Sequence, ValueOrd)]
+#[allow(missing_docs)]
+pub struct AttributeTypeAndValueOwned {
+    pub oid: AttributeType,
+    pub value: Any,
+}
+
+impl AttributeTypeAndValueOwned {
+    /// Borrows this value as an [`AttributeTypeAndValue`].
+    pub fn as_ref(&self) -> AttributeTypeAndValue<'_> {
+        self.into()
+    }
+}
+
+impl From<&AttributeTypeAndValue<'_>> for AttributeTypeAndValueOwned {
+    fn from(atv: &AttributeTypeAndValue<'_>) -> Self {
+        Self {
+            oid: atv.oid,
+            value: atv.value.into(),
+        }
+    }
+}
+
+impl<'a> From<&'a AttributeTypeAndValueOwned> for AttributeTypeAndValue<'a> {
+    fn from(atv: &'a AttributeTypeAndValueOwned) -> Self {
+        Self {
+            oid: atv.oid,
+            value: (&atv.value).into(),
+        }
+    }
+}
+
 #[derive(Copy, Clone)]
 enum Escape {
     None,
@@ -213,14 +249,18 @@ impl AttributeTypeAndValue<'_> {
 
 /// Serializes the structure according to the rules in [RFC 4514].
 ///
+/// String values of attribute types named in [`const_oid::db::DB`] are
+/// written as strings, whatever their string type. Other values are written
+/// as the hex encoding of their DER encoding, prefixed with `#`.
+///
 /// [RFC 4514]: https://datatracker.ietf.org/doc/html/rfc4514
 impl fmt::Display for AttributeTypeAndValue<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let val = match self.value.tag() {
-            Tag::PrintableString => self.value.printable_string().ok().map(|s| s.as_str()),
-            Tag::Utf8String => self.value.utf8_string().ok().map(|s| s.as_str()),
-            Tag::Ia5String => self.value.ia5_string().ok().map(|s| s.as_str()),
-            _ => None,
+            Tag::Ia5String => self.value.ia5_string().ok().map(|s| s.to_string()),
+            _ => DirectoryString::try_from(self.value)
+                .ok()
+                .map(|s| s.to_string()),
         };
 
         if let (Some(key), Some(val)) = (DB.by_oid(&self.oid), val) {
@@ -248,3 +288,9 @@ impl fmt::Display for AttributeTypeAndValue<'_> {
         Ok(())
     }
 }
+
+impl fmt::Display for AttributeTypeAndValueOwned {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
@@ -0,0 +1,630 @@
+//! Certificate, certification request and CRL builders.
+//!
+//! [`CertificateBuilder`], [`CertReqBuilder`] and [`CrlBuilder`] own the
+//! encoded fields of a `TBSCertificate`, `CertificationRequestInfo` or
+//! `TBSCertList` so that they do not need to be kept alive by the caller,
+//! and produce a DER encoded [`Certificate`], [`CertReq`] or
+//! [`CertificateList`] signed with a [`Signer`].
+
+use crate::attr::Attribute;
+use crate::certificate::{Certificate, TbsCertificate, Version};
+use crate::crl::{CertificateList, RevokedCert, TbsCertList};
+use crate::ext::pkix::name::GeneralName;
+use crate::ext::pkix::{
+    AuthorityKeyIdentifier, BaseCrlNumber, BasicConstraints, CertificateIssuer, CrlNumber,
+    CrlReason, IssuingDistributionPoint, KeyUsage, SubjectAltName, SubjectKeyIdentifier,
+};
+use crate::ext::Extension;
+use crate::name::Name;
+use crate::request::{self, CertReq, CertReqInfo, ExtensionReq, PKCS_9_AT_CHALLENGE_PASSWORD};
+use crate::time::{Time, Validity};
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use const_oid::db::rfc5912::ID_EXTENSION_REQ;
+use const_oid::{AssociatedOid, ObjectIdentifier};
+use der::asn1::{
+    AnyRef, BitStringRef, Int, IntRef, OctetStringRef, PrintableStringRef, UIntRef, Utf8StringRef,
+};
+use der::{Decode, Encode};
+use spki::{AlgorithmIdentifier, SubjectPublicKeyInfo};
+
+/// Result type with the `builder` module's [`Error`] type.
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// Certificate builder errors.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    /// ASN.1 DER-related errors.
+    Asn1(der::Error),
+
+    /// An attribute was added more than once.
+    DuplicateAttribute(ObjectIdentifier),
+
+    /// An extension was added more than once.
+    DuplicateExtension(ObjectIdentifier),
+
+    /// A serial number was empty or zero: [RFC 5280 Section 4.1.2.2]
+    /// requires it to be a positive integer.
+    ///
+    /// [RFC 5280 Section 4.1.2.2]: https://datatracker.ietf.org/doc/html/rfc5280#section-4.1.2.2
+    InvalidSerialNumber,
+
+    /// The signer failed to produce a signature.
+    Signature,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Asn1(err) => write!(f, "ASN.1 error: {}", err),
+            Error::DuplicateAttribute(oid) => write!(f, "duplicate attribute: {}", oid),
+            Error::DuplicateExtension(oid) => write!(f, "duplicate extension: {}", oid),
+            Error::InvalidSerialNumber => f.write_str("serial number is not positive"),
+            Error::Signature => f.write_str("signing failed"),
+        }
+    }
+}
+
+impl From<der::Error> for Error {
+    fn from(err: der::Error) -> Error {
+        Error::Asn1(err)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+/// Signature generation backend.
+pub trait Signer {
+    /// Identifier of the signature algorithm used by [`Signer::sign`].
+    fn signature_algorithm(&self) -> AlgorithmIdentifier<'_>;
+
+    /// Sign `msg`, returning the contents of the `signature` BIT STRING.
+    ///
+    /// Failures should be reported as [`Error::Signature`].
+    fn sign(&self, msg: &[u8]) -> Result<Vec<u8>>;
+}
+
+/// Encode a big endian, unsigned serial number as the contents of a signed
+/// `INTEGER`, rejecting serial numbers which are not positive.
+fn encode_serial_number(serial_number: &[u8]) -> Result<Vec<u8>> {
+    if serial_number.iter().all(|&b| b == 0) {
+        return Err(Error::InvalidSerialNumber);
+    }
+
+    Ok(Int::from(UIntRef::new(serial_number)?).as_bytes().to_vec())
+}
+
+/// An extension whose value has already been encoded.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct EncodedExtension {
+    oid: ObjectIdentifier,
+    critical: bool,
+    value: Vec<u8>,
+}
+
+impl EncodedExtension {
+    fn new<T>(critical: bool, value: &T) -> Result<Self>
+    where
+        T: AssociatedOid + Encode,
+    {
+        Ok(Self {
+            oid: T::OID,
+            critical,
+            value: value.to_vec()?,
+        })
+    }
+
+    fn to_extension(&self) -> Extension<'_> {
+        Extension {
+            extn_id: self.oid,
+            critical: self.critical,
+            extn_value: &self.value,
+        }
+    }
+
+    /// Append to `extensions` unless an extension with the same OID exists.
+    fn push_to(self, extensions: &mut Vec<EncodedExtension>) -> Result<()> {
+        if extensions.iter().any(|e| e.oid == self.oid) {
+            return Err(Error::DuplicateExtension(self.oid));
+        }
+
+        extensions.push(self);
+        Ok(())
+    }
+}
+
+/// X.509 v3 certificate builder.
+///
+/// The issuer defaults to the subject, i.e. the certificate is self-issued
+/// unless [`CertificateBuilder::issuer`] or [`CertificateBuilder::issued_by`]
+/// is used.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CertificateBuilder {
+    serial_number: Vec<u8>,
+    issuer: Vec<u8>,
+    validity: Validity,
+    subject: Vec<u8>,
+    public_key: Vec<u8>,
+    extensions: Vec<EncodedExtension>,
+}
+
+impl CertificateBuilder {
+    /// Create a new builder for a certificate with the given serial number
+    /// (big endian, unsigned), validity, subject and subject public key.
+    ///
+    /// Returns [`Error::InvalidSerialNumber`] if the serial number is empty
+    /// or zero.
+    pub fn new(
+        serial_number: &[u8],
+        validity: Validity,
+        subject: &Name<'_>,
+        public_key: &SubjectPublicKeyInfo<'_>,
+    ) -> Result<Self> {
+        let serial_number = encode_serial_number(serial_number)?;
+        let subject = subject.to_vec()?;
+
+        Ok(Self {
+            serial_number,
+            issuer: subject.clone(),
+            validity,
+            subject,
+            public_key: public_key.to_vec()?,
+            extensions: Vec::new(),
+        })
+    }
+
+    /// Create a new builder for a certificate with the subject and subject
+    /// public key of a certification request.
+    ///
+    /// The signature of the request is not checked; use
+    /// [`SignedCertReq`](crate::verify::SignedCertReq) to verify it first.
+    /// Requested extensions are only included if added with
+    /// [`CertificateBuilder::requested_extensions`].
+    pub fn from_cert_req(
+        serial_number: &[u8],
+        validity: Validity,
+        req: &CertReq<'_>,
+    ) -> Result<Self> {
+        Self::new(
+            serial_number,
+            validity,
+            &req.info.subject,
+            &req.info.public_key,
+        )
+    }
+
+    /// Set the issuer name.
+    pub fn issuer(mut self, issuer: &Name<'_>) -> Result<Self> {
+        self.issuer = issuer.to_vec()?;
+        Ok(self)
+    }
+
+    /// Set the issuer name to the subject of `issuer` and, if `issuer` has a
+    /// `SubjectKeyIdentifier`, add a matching `AuthorityKeyIdentifier`.
+    pub fn issued_by(self, issuer: &Certificate<'_>) -> Result<Self> {
+        let builder = self.issuer(&issuer.tbs_certificate.subject)?;
+
+        match issuer.tbs_certificate.get::<SubjectKeyIdentifier<'_>>()? {
+            Some((_, skid)) => builder.authority_key_identifier(&AuthorityKeyIdentifier {
+                key_identifier: Some(skid.0),
+                authority_cert_issuer: None,
+                authority_cert_serial_number: None,
+            }),
+            None => Ok(builder),
+        }
+    }
+
+    /// Add an extension, encoding `value` as the `extnValue`.
+    pub fn extension<T>(mut self, critical: bool, value: &T) -> Result<Self>
+    where
+        T: AssociatedOid + Encode,
+    {
+        EncodedExtension::new(critical, value)?.push_to(&mut self.extensions)?;
+        Ok(self)
+    }
+
+    /// Add an extension whose value is already encoded.
+    pub fn raw_extension(mut self, extension: &Extension<'_>) -> Result<Self> {
+        let extension = EncodedExtension {
+            oid: extension.extn_id,
+            critical: extension.critical,
+            value: extension.extn_value.to_vec(),
+        };
+
+        extension.push_to(&mut self.extensions)?;
+        Ok(self)
+    }
+
+    /// Add all the extensions requested in the `extensionRequest` attribute
+    /// of `req`, as is.
+    pub fn requested_extensions(self, req: &CertReq<'_>) -> Result<Self> {
+        let requested = req.info.extension_req()?.unwrap_or_default();
+
+        requested
+            .0
+            .iter()
+            .try_fold(self, |builder, extension| builder.raw_extension(extension))
+    }
+
+    /// Add a critical `BasicConstraints` extension.
+    pub fn basic_constraints(self, constraints: BasicConstraints) -> Result<Self> {
+        self.extension(true, &constraints)
+    }
+
+    /// Add a critical `KeyUsage` extension.
+    pub fn key_usage(self, usage: KeyUsage) -> Result<Self> {
+        self.extension(true, &usage)
+    }
+
+    /// Add a `SubjectAltName` extension.
+    ///
+    /// As required by [RFC 5280 Section 4.2.1.6], the extension is marked
+    /// critical when the subject name is empty.
+    ///
+    /// [RFC 5280 Section 4.2.1.6]: https://datatracker.ietf.org/doc/html/rfc5280#section-4.2.1.6
+    pub fn subject_alt_name(self, names: &[GeneralName<'_>]) -> Result<Self> {
+        let critical = Name::from_der(&self.subject)?.0.is_empty();
+        self.extension(critical, &SubjectAltName(names.to_vec()))
+    }
+
+    /// Add a non-critical `AuthorityKeyIdentifier` extension.
+    pub fn authority_key_identifier(self, id: &AuthorityKeyIdentifier<'_>) -> Result<Self> {
+        self.extension(false, id)
+    }
+
+    /// Add a non-critical `SubjectKeyIdentifier` extension.
+    pub fn subject_key_identifier(self, id: &[u8]) -> Result<Self> {
+        self.extension(false, &SubjectKeyIdentifier(OctetStringRef::new(id)?))
+    }
+
+    /// Sign the `TBSCertificate` with `signer`, returning the DER encoding
+    /// of the resulting certificate.
+    pub fn build<S>(&self, signer: &S) -> Result<Vec<u8>>
+    where
+        S: Signer + ?Sized,
+    {
+        let signature_algorithm = signer.signature_algorithm();
+
+        let extensions = self
+            .extensions
+            .iter()
+            .map(EncodedExtension::to_extension)
+            .collect::<Vec<_>>();
+
+        let tbs_certificate = TbsCertificate {
+            version: Version::V3,
+            serial_number: IntRef::new(&self.serial_number)?,
+            signature: signature_algorithm,
+            issuer: Name::from_der(&self.issuer)?,
+            validity: self.validity,
+            subject: Name::from_der(&self.subject)?,
+            subject_public_key_info: SubjectPublicKeyInfo::from_der(&self.public_key)?,
+            issuer_unique_id: None,
+            subject_unique_id: None,
+            extensions: if extensions.is_empty() {
+                None
+            } else {
+                Some(extensions)
+            },
+        };
+
+        let signature = signer.sign(&tbs_certificate.to_vec()?)?;
+
+        let certificate = Certificate {
+            tbs_certificate,
+            signature_algorithm,
+            signature: BitStringRef::from_bytes(&signature)?,
+        };
+
+        Ok(certificate.to_vec()?)
+    }
+}
+
+/// PKCS#10 certification request builder.
+///
+/// Extensions are collected into a single `extensionRequest` attribute.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CertReqBuilder {
+    subject: Vec<u8>,
+    public_key: Vec<u8>,
+    attributes: Vec<(ObjectIdentifier, Vec<u8>)>,
+    extensions: Vec<EncodedExtension>,
+}
+
+impl CertReqBuilder {
+    /// Create a new builder for a request with the given subject and subject
+    /// public key.
+    pub fn new(subject: &Name<'_>, public_key: &SubjectPublicKeyInfo<'_>) -> Result<Self> {
+        Ok(Self {
+            subject: subject.to_vec()?,
+            public_key: public_key.to_vec()?,
+            attributes: Vec::new(),
+            extensions: Vec::new(),
+        })
+    }
+
+    /// Add a single-valued attribute, encoding `value` as its value.
+    pub fn attribute<T: Encode>(mut self, oid: ObjectIdentifier, value: &T) -> Result<Self> {
+        if self.attributes.iter().any(|(attr, _)| *attr == oid) {
+            return Err(Error::DuplicateAttribute(oid));
+        }
+
+        self.attributes.push((oid, value.to_vec()?));
+        Ok(self)
+    }
+
+    /// Add a `challengePassword` attribute.
+    ///
+    /// The password is encoded as a `PrintableString` if possible and as a
+    /// `UTF8String` otherwise.
+    pub fn challenge_password(self, password: &str) -> Result<Self> {
+        match PrintableStringRef::new(password) {
+            Ok(printable) => self.attribute(PKCS_9_AT_CHALLENGE_PASSWORD, &printable),
+            Err(_) => self.attribute(PKCS_9_AT_CHALLENGE_PASSWORD, &Utf8StringRef::new(password)?),
+        }
+    }
+
+    /// Request an extension, encoding `value` as the `extnValue`.
+    pub fn extension<T>(mut self, critical: bool, value: &T) -> Result<Self>
+    where
+        T: AssociatedOid + Encode,
+    {
+        EncodedExtension::new(critical, value)?.push_to(&mut self.extensions)?;
+        Ok(self)
+    }
+
+    /// Request a critical `BasicConstraints` extension.
+    pub fn basic_constraints(self, constraints: BasicConstraints) -> Result<Self> {
+        self.extension(true, &constraints)
+    }
+
+    /// Request a critical `KeyUsage` extension.
+    pub fn key_usage(self, usage: KeyUsage) -> Result<Self> {
+        self.extension(true, &usage)
+    }
+
+    /// Request a `SubjectAltName` extension, critical if the subject name is
+    /// empty.
+    pub fn subject_alt_name(self, names: &[GeneralName<'_>]) -> Result<Self> {
+        let critical = Name::from_der(&self.subject)?.0.is_empty();
+        self.extension(critical, &SubjectAltName(names.to_vec()))
+    }
+
+    /// Sign the `CertificationRequestInfo` with `signer`, returning the DER
+    /// encoding of the resulting request.
+    pub fn build<S>(&self, signer: &S) -> Result<Vec<u8>>
+    where
+        S: Signer + ?Sized,
+    {
+        let extension_req = ExtensionReq(
+            self.extensions
+                .iter()
+                .map(EncodedExtension::to_extension)
+                .collect(),
+        )
+        .to_vec()?;
+
+        let mut attributes = self
+            .attributes
+            .iter()
+            .map(|(oid, value)| (*oid, value.as_slice()))
+            .collect::<Vec<_>>();
+
+        if !self.extensions.is_empty() {
+            if attributes.iter().any(|(oid, _)| *oid == ID_EXTENSION_REQ) {
+                return Err(Error::DuplicateAttribute(ID_EXTENSION_REQ));
+            }
+
+            attributes.push((ID_EXTENSION_REQ, &extension_req));
+        }
+
+        let attributes = attributes
+            .into_iter()
+            .map(|(oid, value)| {
+                Ok(Attribute {
+                    oid,
+                    values: alloc::vec![AnyRef::from_der(value)?].try_into()?,
+                })
+            })
+            .collect::<der::Result<Vec<_>>>()?;
+
+        let info = CertReqInfo {
+            version: request::Version::V1,
+            subject: Name::from_der(&self.subject)?,
+            public_key: SubjectPublicKeyInfo::from_der(&self.public_key)?,
+            attributes: attributes.try_into()?,
+        };
+
+        let signature = signer.sign(&info.to_vec()?)?;
+
+        let req = CertReq {
+            info,
+            algorithm: signer.signature_algorithm(),
+            signature: BitStringRef::from_bytes(&signature)?,
+        };
+
+        Ok(req.to_vec()?)
+    }
+}
+
+/// A revoked certificate entry whose extensions have already been encoded.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct EncodedRevokedCert {
+    serial_number: Vec<u8>,
+    revocation_date: Time,
+    extensions: Vec<EncodedExtension>,
+}
+
+/// X.509 v2 certificate revocation list builder.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CrlBuilder {
+    issuer: Vec<u8>,
+    this_update: Time,
+    next_update: Option<Time>,
+    revoked: Vec<EncodedRevokedCert>,
+    certificate_issuer: Option<EncodedExtension>,
+    extensions: Vec<EncodedExtension>,
+}
+
+impl CrlBuilder {
+    /// Create a new builder for a CRL issued by `issuer` at `this_update`.
+    pub fn new(issuer: &Name<'_>, this_update: Time) -> Result<Self> {
+        Ok(Self {
+            issuer: issuer.to_vec()?,
+            this_update,
+            next_update: None,
+            revoked: Vec::new(),
+            certificate_issuer: None,
+            extensions: Vec::new(),
+        })
+    }
+
+    /// Set the date by which the next CRL will be issued.
+    pub fn next_update(mut self, next_update: Time) -> Self {
+        self.next_update = Some(next_update);
+        self
+    }
+
+    /// Add a revoked certificate with the given serial number (big endian,
+    /// unsigned) and, optionally, a `CRLReason` entry extension.
+    ///
+    /// Returns [`Error::InvalidSerialNumber`] if the serial number is empty
+    /// or zero.
+    pub fn revoke(
+        mut self,
+        serial_number: &[u8],
+        revocation_date: Time,
+        reason: Option<CrlReason>,
+    ) -> Result<Self> {
+        let mut extensions = Vec::new();
+
+        if let Some(issuer) = self.certificate_issuer.take() {
+            issuer.push_to(&mut extensions)?;
+        }
+
+        if let Some(reason) = reason {
+            EncodedExtension::new(false, &reason)?.push_to(&mut extensions)?;
+        }
+
+        self.revoked.push(EncodedRevokedCert {
+            serial_number: encode_serial_number(serial_number)?,
+            revocation_date,
+            extensions,
+        });
+
+        Ok(self)
+    }
+
+    /// Attribute the certificates revoked from now on to `issuer`.
+    ///
+    /// A critical `CertificateIssuer` entry extension is added to the next
+    /// revoked certificate, which per [RFC 5280 Section 5.3.3] also applies
+    /// to all the subsequent entries. Such entries are only meaningful in
+    /// an indirect CRL, see [`CrlBuilder::issuing_distribution_point`].
+    ///
+    /// [RFC 5280 Section 5.3.3]: https://datatracker.ietf.org/doc/html/rfc5280#section-5.3.3
+    pub fn certificate_issuer(mut self, issuer: &[GeneralName<'_>]) -> Result<Self> {
+        let issuer = CertificateIssuer(issuer.to_vec());
+        self.certificate_issuer = Some(EncodedExtension::new(true, &issuer)?);
+        Ok(self)
+    }
+
+    /// Add a CRL extension, encoding `value` as the `extnValue`.
+    pub fn extension<T>(mut self, critical: bool, value: &T) -> Result<Self>
+    where
+        T: AssociatedOid + Encode,
+    {
+        EncodedExtension::new(critical, value)?.push_to(&mut self.extensions)?;
+        Ok(self)
+    }
+
+    /// Add a non-critical `CRLNumber` extension.
+    pub fn crl_number(self, number: &[u8]) -> Result<Self> {
+        self.extension(false, &CrlNumber(UIntRef::new(number)?))
+    }
+
+    /// Make this a delta CRL by adding a critical `DeltaCRLIndicator`
+    /// extension referencing the `CRLNumber` of the base CRL.
+    pub fn base_crl_number(self, number: &[u8]) -> Result<Self> {
+        self.extension(true, &BaseCrlNumber(UIntRef::new(number)?))
+    }
+
+    /// Add a critical `IssuingDistributionPoint` extension.
+    pub fn issuing_distribution_point(self, idp: &IssuingDistributionPoint<'_>) -> Result<Self> {
+        self.extension(true, idp)
+    }
+
+    /// Add a non-critical `AuthorityKeyIdentifier` extension.
+    pub fn authority_key_identifier(self, id: &AuthorityKeyIdentifier<'_>) -> Result<Self> {
+        self.extension(false, id)
+    }
+
+    /// Sign the `TBSCertList` with `signer`, returning the DER encoding of
+    /// the resulting CRL.
+    pub fn build<S>(&self, signer: &S) -> Result<Vec<u8>>
+    where
+        S: Signer + ?Sized,
+    {
+        let signature_algorithm = signer.signature_algorithm();
+
+        let revoked_certificates = self
+            .revoked
+            .iter()
+            .map(|entry| {
+                let extensions = entry
+                    .extensions
+                    .iter()
+                    .map(EncodedExtension::to_extension)
+                    .collect::<Vec<_>>();
+
+                Ok(RevokedCert {
+                    serial_number: IntRef::new(&entry.serial_number)?,
+                    revocation_date: entry.revocation_date,
+                    crl_entry_extensions: if extensions.is_empty() {
+                        None
+                    } else {
+                        Some(extensions)
+                    },
+                })
+            })
+            .collect::<der::Result<Vec<_>>>()?;
+
+        let extensions = self
+            .extensions
+            .iter()
+            .map(EncodedExtension::to_extension)
+            .collect::<Vec<_>>();
+
+        let tbs_cert_list = TbsCertList {
+            version: Version::V2,
+            signature: signature_algorithm,
+            issuer: Name::from_der(&self.issuer)?,
+            this_update: self.this_update,
+            next_update: self.next_update,
+            revoked_certificates: if revoked_certificates.is_empty() {
+                None
+            } else {
+                Some(revoked_certificates)
+            },
+            crl_extensions: if extensions.is_empty() {
+                None
+            } else {
+                Some(extensions)
+            },
+        };
+
+        let signature = signer.sign(&tbs_cert_list.to_vec()?)?;
+
+        let crl = CertificateList {
+            tbs_cert_list,
+            signature_algorithm,
+            signature: BitStringRef::from_bytes(&signature)?,
+        };
+
+        Ok(crl.to_vec()?)
+    }
+}
@@ -1,13 +1,21 @@
 //! Certificate types
 
-use crate::{name::Name, time::Validity};
+use crate::ext::registry::{ExtensionRegistry, ParsedExtension};
+use crate::ext::{Extension, ExtensionsOwned};
+use crate::name::{Name, NameOwned};
+use crate::time::Validity;
 
 use alloc::vec::Vec;
 
 use const_oid::AssociatedOid;
-use der::asn1::{BitStringRef, UIntRef};
-use der::{Decode, Enumerated, Error, ErrorKind, Sequence};
-use spki::{AlgorithmIdentifier, SubjectPublicKeyInfo};
+use der::asn1::{BitString, BitStringRef, Int, IntRef};
+#[cfg(feature = "pem")]
+use der::pem::PemLabel;
+use der::{Decode, Enumerated, Error, Sequence};
+
+use spki::{
+    AlgorithmIdentifier, AlgorithmIdentifierOwned, SubjectPublicKeyInfo, SubjectPublicKeyInfoOwned,
+};
 
 /// Certificate `Version` as defined in [RFC 5280 Section 4.1].
 ///
@@ -73,7 +81,7 @@ pub struct TbsCertificate<'a> {
     #[asn1(context_specific = "0", default = "Default::default")]
     pub version: Version,
 
-    pub serial_number: UIntRef<'a>,
+    pub serial_number: IntRef<'a>,
     pub signature: AlgorithmIdentifier<'a>,
     pub issuer: Name<'a>,
     pub validity: Validity,
@@ -99,14 +107,7 @@ impl<'a> TbsCertificate<'a> {
     pub fn get<'b: 'a, T: Decode<'a> + AssociatedOid>(
         &'b self,
     ) -> Result<Option<(bool, T)>, Error> {
-        let mut iter = self.filter::<T>().peekable();
-        match iter.next() {
-            None => Ok(None),
-            Some(item) => match iter.peek() {
-                Some(..) => Err(ErrorKind::Failed.into()),
-                None => Ok(Some(item?)),
-            },
-        }
+        crate::ext::get(self.extensions.as_deref())
     }
 
     /// Filters extensions by an associated OID
@@ -115,12 +116,18 @@ impl<'a> TbsCertificate<'a> {
     pub fn filter<'b: 'a, T: Decode<'a> + AssociatedOid>(
         &'b self,
     ) -> impl 'b + Iterator<Item = Result<(bool, T), Error>> {
-        self.extensions
-            .as_deref()
-            .unwrap_or(&[])
-            .iter()
-            .filter(|e| e.extn_id == T::OID)
-            .map(|e| Ok((e.critical, T::from_der(e.extn_value)?)))
+        crate::ext::filter(self.extensions.as_deref())
+    }
+
+    /// Decodes all extensions with `registry`
+    ///
+    /// Returns an iterator over each extension paired with its decoded value
+    /// or decoding error.
+    pub fn parsed_extensions<'b: 'a>(
+        &'b self,
+        registry: &'b ExtensionRegistry,
+    ) -> impl 'b + Iterator<Item = (&'b Extension<'a>, Result<ParsedExtension<'a>, Error>)> {
+        registry.decode_all(self.extensions.as_deref().unwrap_or(&[]))
     }
 }
 
@@ -143,6 +150,139 @@ pub struct Certificate<'a> {
     pub signature: BitStringRef<'a>,
 }
 
+/// Owned form of [`TbsCertificate`].
+///
+/// Unlike [`TbsCertificate`], this type does not borrow from the input data,
+/// which allows it to be stored in long-lived structures. Use
+/// the `From` conversions or [`TbsCertificateOwned::as_ref`] to
+/// convert between the two forms.
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct TbsCertificateOwned {
+    #[asn1(context_specific = "0", default = "Default::default")]
+    pub version: Version,
+
+    pub serial_number: Int,
+    pub signature: AlgorithmIdentifierOwned,
+    pub issuer: NameOwned,
+    pub validity: Validity,
+    pub subject: NameOwned,
+    pub subject_public_key_info: SubjectPublicKeyInfoOwned,
+
+    #[asn1(context_specific = "1", tag_mode = "IMPLICIT", optional = "true")]
+    pub issuer_unique_id: Option<BitString>,
+
+    #[asn1(context_specific = "2", tag_mode = "IMPLICIT", optional = "true")]
+    pub subject_unique_id: Option<BitString>,
+
+    #[asn1(context_specific = "3", tag_mode = "EXPLICIT", optional = "true")]
+    pub extensions: Option<ExtensionsOwned>,
+}
+
+impl TbsCertificateOwned {
+    /// Borrows this structure as a [`TbsCertificate`].
+    ///
+    /// The returned value refers to the data of `self`; only the containers
+    /// of the names and extensions are allocated, as described in
+    /// [`RdnSequenceOwned::as_ref`](crate::name::RdnSequenceOwned::as_ref).
+    pub fn as_ref(&self) -> TbsCertificate<'_> {
+        self.into()
+    }
+}
+
+impl From<&TbsCertificate<'_>> for TbsCertificateOwned {
+    fn from(tbs: &TbsCertificate<'_>) -> Self {
+        Self {
+            version: tbs.version,
+            serial_number: tbs.serial_number.into(),
+            signature: (&tbs.signature).into(),
+            issuer: (&tbs.issuer).into(),
+            validity: tbs.validity,
+            subject: (&tbs.subject).into(),
+            subject_public_key_info: (&tbs.subject_public_key_info).into(),
+            issuer_unique_id: tbs.issuer_unique_id.map(Into::into),
+            subject_unique_id: tbs.subject_unique_id.map(Into::into),
+            extensions: tbs
+                .extensions
+                .as_ref()
+                .map(|exts| exts.iter().map(Into::into).collect()),
+        }
+    }
+}
+
+impl<'a> From<&'a TbsCertificateOwned> for TbsCertificate<'a> {
+    fn from(tbs: &'a TbsCertificateOwned) -> Self {
+        Self {
+            version: tbs.version,
+            serial_number: (&tbs.serial_number).into(),
+            signature: tbs.signature.as_ref(),
+            issuer: tbs.issuer.as_ref(),
+            validity: tbs.validity,
+            subject: tbs.subject.as_ref(),
+            subject_public_key_info: tbs.subject_public_key_info.as_ref(),
+            issuer_unique_id: tbs.issuer_unique_id.as_ref().map(Into::into),
+            subject_unique_id: tbs.subject_unique_id.as_ref().map(Into::into),
+            extensions: tbs
+                .extensions
+                .as_ref()
+                .map(|exts| exts.iter().map(Into::into).collect()),
+        }
+    }
+}
+
+/// Owned form of [`Certificate`].
+///
+/// ```
+/// use x509_cert::{Certificate, certificate::CertificateOwned};
+/// use x509_cert::der::Decode;
+///
+/// let der = include_bytes!("../tests/examples/amazon.der").to_vec();
+/// let owned = CertificateOwned::from(&Certificate::from_der(&der).unwrap());
+/// drop(der);
+///
+/// assert_eq!(owned.as_ref().tbs_certificate.subject.to_string(), "CN=*.peg.a2z.com");
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct CertificateOwned {
+    pub tbs_certificate: TbsCertificateOwned,
+    pub signature_algorithm: AlgorithmIdentifierOwned,
+    pub signature: BitString,
+}
+
+impl CertificateOwned {
+    /// Borrows this certificate as a [`Certificate`].
+    pub fn as_ref(&self) -> Certificate<'_> {
+        self.into()
+    }
+}
+
+impl From<&Certificate<'_>> for CertificateOwned {
+    fn from(cert: &Certificate<'_>) -> Self {
+        Self {
+            tbs_certificate: (&cert.tbs_certificate).into(),
+            signature_algorithm: (&cert.signature_algorithm).into(),
+            signature: cert.signature.into(),
+        }
+    }
+}
+
+impl<'a> From<&'a CertificateOwned> for Certificate<'a> {
+    fn from(cert: &'a CertificateOwned) -> Self {
+        Self {
+            tbs_certificate: cert.tbs_certificate.as_ref(),
+            signature_algorithm: cert.signature_algorithm.as_ref(),
+            signature: (&cert.signature).into(),
+        }
+    }
+}
+
+#[cfg(feature = "pem")]
+#[cfg_attr(docsrs, doc(cfg(feature = "pem")))]
+impl PemLabel for CertificateOwned {
+    const PEM_LABEL: &'static str = "CERTIFICATE";
+}
+
 /// `PkiPath` as defined by X.509 and referenced by [RFC 6066].
 ///
 /// This contains a series of certificates in validation order from the
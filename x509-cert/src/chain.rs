@@ -0,0 +1,304 @@
+//! Certification path building.
+//!
+//! Peers frequently send their certificates out of order, with intermediates
+//! missing or with extra ones which belong to other paths. [`build_paths`]
+//! enumerates the paths from a target certificate to a set of trust anchors
+//! using the certificates of a [`CertStore`] as candidate intermediates,
+//! following the approach described in [RFC 4158]:
+//!
+//! - issuers are found by name chaining and `AuthorityKeyIdentifier`
+//!   matching, see [`CertStore::find_issuers`];
+//! - a path ends at the first trust anchor which may have issued the last
+//!   certificate, and certificates with the name and public key of a trust
+//!   anchor are never used as intermediates;
+//! - a name and public key appear at most once in a path, so that loops
+//!   formed by cross-certificates are not followed;
+//! - paths are searched breadth-first, so that shorter paths are found
+//!   first, and the number of issuer lookups and of partial paths considered
+//!   is bounded.
+//!
+//! Signatures and the other constraints of [RFC 5280 Section 6] are not
+//! checked: the resulting paths are candidates which are to be passed to
+//! [`validate_path`](crate::path::validate_path) in order, along with the
+//! original encoding of their certificates.
+//! [`validate`](crate::path::validate) does so with the same search.
+//!
+//! ```
+//! use x509_cert::anchor::TrustAnchorChoice;
+//! use x509_cert::chain::build_paths;
+//! use x509_cert::der::Decode;
+//! use x509_cert::store::CertStore;
+//! use x509_cert::Certificate;
+//!
+//! let root = Certificate::from_der(include_bytes!("../tests/examples/path-root.der")).unwrap();
+//! let leaf = Certificate::from_der(include_bytes!("../tests/examples/path-leaf.der")).unwrap();
+//!
+//! let mut pool = CertStore::new();
+//! pool.add_der(include_bytes!("../tests/examples/path-ca.der")).unwrap();
+//!
+//! let anchors = [TrustAnchorChoice::Certificate(root)];
+//! let paths = build_paths(&leaf, &pool, &anchors, 4);
+//! assert_eq!(paths.len(), 1);
+//! assert_eq!(paths[0].path.len(), 2);
+//! assert_eq!(paths[0].path[1], leaf);
+//! ```
+//!
+//! [RFC 4158]: https://datatracker.ietf.org/doc/html/rfc4158
+//! [RFC 5280 Section 6]: https://datatracker.ietf.org/doc/html/rfc5280#section-6
+
+use crate::anchor::{TrustAnchorChoice, TrustAnchorInfo};
+use crate::ext::pkix::AuthorityKeyIdentifier;
+use crate::name::Name;
+use crate::store::CertStore;
+use crate::verify::SignedCertificate;
+use crate::{Certificate, PkiPath, TbsCertificate};
+
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+
+use spki::SubjectPublicKeyInfo;
+
+/// Maximum number of certificates in a path, including the target.
+const MAX_PATH_LEN: usize = 16;
+
+/// Maximum number of partial paths considered in a single search.
+const MAX_CANDIDATE_PATHS: usize = 256;
+
+/// Maximum number of issuer lookups performed in a single search.
+const MAX_ISSUER_LOOKUPS: usize = 64;
+
+/// Certification path found by [`build_paths`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CandidatePath<'a> {
+    /// The trust anchor which may have issued the first certificate.
+    pub anchor: &'a TrustAnchorChoice<'a>,
+
+    /// Certificates in the path, starting with the one issued by the trust
+    /// anchor and ending with the target certificate.
+    pub path: PkiPath<'a>,
+}
+
+impl<'a> CandidatePath<'a> {
+    /// Returns references to the certificates of the path.
+    pub fn certificates(&self) -> Vec<&Certificate<'a>> {
+        self.path.iter().collect()
+    }
+}
+
+/// Find up to `max_paths` certification paths from `target` to one of
+/// `anchors`, using the certificates of `pool` as intermediates.
+///
+/// Shorter paths are found, and returned, first. Paths of equal length are
+/// returned in the order they were found, which prefers trust anchors in the
+/// order given and issuers whose key identifier matches the
+/// `AuthorityKeyIdentifier` of the certificate they issued.
+///
+/// At most 16 certificates, including the target, are chained. The search
+/// also gives up after 64 issuer lookups or 256 partial paths, so that a
+/// pool with many cross-certificates cannot make it run for exponential time.
+pub fn build_paths<'a>(
+    target: &Certificate<'a>,
+    pool: &'a CertStore,
+    anchors: &'a [TrustAnchorChoice<'a>],
+    max_paths: usize,
+) -> Vec<CandidatePath<'a>> {
+    let mut paths = Vec::new();
+    if max_paths == 0 {
+        return paths;
+    }
+
+    let find_issuers = |cert: &Certificate<'a>| {
+        pool.find_issuers(cert)
+            .into_iter()
+            .map(|issuer| issuer.as_ref())
+            .collect()
+    };
+
+    search(target.clone(), anchors, find_issuers, |anchor, chain| {
+        paths.push(CandidatePath {
+            anchor,
+            path: chain.iter().rev().cloned().collect(),
+        });
+        paths.len() < max_paths
+    });
+
+    paths
+}
+
+/// Trust anchors which paths may be built to.
+pub(crate) trait Anchor<'a> {
+    /// Distinguished name of the trust anchor, if any.
+    fn name(&self) -> Option<&Name<'a>>;
+
+    /// Public key of the trust anchor.
+    fn public_key(&self) -> &SubjectPublicKeyInfo<'a>;
+
+    /// Key identifier of the trust anchor, if any.
+    fn key_id(&self) -> Option<&[u8]>;
+}
+
+impl<'a> Anchor<'a> for TrustAnchorChoice<'a> {
+    fn name(&self) -> Option<&Name<'a>> {
+        TrustAnchorChoice::name(self)
+    }
+
+    fn public_key(&self) -> &SubjectPublicKeyInfo<'a> {
+        TrustAnchorChoice::public_key(self)
+    }
+
+    fn key_id(&self) -> Option<&[u8]> {
+        TrustAnchorChoice::key_id(self)
+    }
+}
+
+impl<'a> Anchor<'a> for TrustAnchorInfo<'a> {
+    fn name(&self) -> Option<&Name<'a>> {
+        self.cert_path.as_ref().map(|cp| &cp.ta_name)
+    }
+
+    fn public_key(&self) -> &SubjectPublicKeyInfo<'a> {
+        &self.pub_key
+    }
+
+    fn key_id(&self) -> Option<&[u8]> {
+        Some(self.key_id.as_bytes())
+    }
+}
+
+/// Certificates which paths are built from.
+pub(crate) trait PathCertificate<'a> {
+    /// The `tbsCertificate` of the certificate.
+    fn tbs(&self) -> &TbsCertificate<'a>;
+}
+
+impl<'a> PathCertificate<'a> for Certificate<'a> {
+    fn tbs(&self) -> &TbsCertificate<'a> {
+        &self.tbs_certificate
+    }
+}
+
+impl<'a> PathCertificate<'a> for SignedCertificate<'a> {
+    fn tbs(&self) -> &TbsCertificate<'a> {
+        &self.tbs
+    }
+}
+
+impl<'a, C: PathCertificate<'a>> PathCertificate<'a> for &C {
+    fn tbs(&self) -> &TbsCertificate<'a> {
+        (*self).tbs()
+    }
+}
+
+/// Breadth-first search for paths from `target` to one of `anchors`.
+///
+/// `find_issuers` returns the candidate issuers of a certificate, in order of
+/// preference. Each path found is passed to `found` with the target first,
+/// which returns whether the search should continue. Paths are found in
+/// order of length, within the limits described in [`build_paths`].
+pub(crate) fn search<'a, C, A>(
+    target: C,
+    anchors: &'a [A],
+    mut find_issuers: impl FnMut(&C) -> Vec<C>,
+    mut found: impl FnMut(&'a A, &[C]) -> bool,
+) where
+    C: Clone + PathCertificate<'a>,
+    A: Anchor<'a>,
+{
+    let mut queue = VecDeque::new();
+    queue.push_back(alloc::vec![target]);
+
+    let mut candidates = 1;
+    let mut lookups = 0;
+
+    while let Some(chain) = queue.pop_front() {
+        let cert = &chain[chain.len() - 1];
+
+        for anchor in anchors.iter().filter(|ta| anchor_issued(*ta, cert.tbs())) {
+            if chain.iter().any(|c| is_anchor(anchor, c.tbs())) {
+                continue;
+            }
+
+            if !found(anchor, &chain) {
+                return;
+            }
+        }
+
+        if chain.len() >= MAX_PATH_LEN || lookups >= MAX_ISSUER_LOOKUPS {
+            continue;
+        }
+        lookups += 1;
+
+        for candidate in find_issuers(cert) {
+            let tbs = candidate.tbs();
+
+            if anchors.iter().any(|ta| is_anchor(ta, tbs))
+                || chain.iter().any(|c| {
+                    let c = c.tbs();
+                    same_entity(
+                        &c.subject,
+                        &c.subject_public_key_info,
+                        &tbs.subject,
+                        &tbs.subject_public_key_info,
+                    )
+                })
+            {
+                continue;
+            }
+
+            if candidates >= MAX_CANDIDATE_PATHS {
+                break;
+            }
+            candidates += 1;
+
+            let mut next = chain.clone();
+            next.push(candidate);
+            queue.push_back(next);
+        }
+    }
+}
+
+/// Could the certificate with `tbs` have been issued by `anchor`?
+///
+/// Anchors without a name are matched by key identifier only.
+pub(crate) fn anchor_issued<'a>(anchor: &impl Anchor<'a>, tbs: &TbsCertificate<'_>) -> bool {
+    let authority_key_id = authority_key_id(tbs);
+
+    if let (Some(key_id), Some(ta_key_id)) = (authority_key_id, anchor.key_id()) {
+        if key_id != ta_key_id {
+            return false;
+        }
+    }
+
+    match anchor.name() {
+        Some(name) => name.matches(&tbs.issuer),
+        None => authority_key_id.is_some() && authority_key_id == anchor.key_id(),
+    }
+}
+
+/// Does the certificate with `tbs` have the name and public key of `anchor`?
+fn is_anchor<'a>(anchor: &impl Anchor<'a>, tbs: &TbsCertificate<'_>) -> bool {
+    match anchor.name() {
+        Some(name) => same_entity(
+            name,
+            anchor.public_key(),
+            &tbs.subject,
+            &tbs.subject_public_key_info,
+        ),
+        None => *anchor.public_key() == tbs.subject_public_key_info,
+    }
+}
+
+fn same_entity(
+    name: &Name<'_>,
+    key: &SubjectPublicKeyInfo<'_>,
+    other_name: &Name<'_>,
+    other_key: &SubjectPublicKeyInfo<'_>,
+) -> bool {
+    key == other_key && name.matches(other_name)
+}
+
+fn authority_key_id<'a>(tbs: &'a TbsCertificate<'a>) -> Option<&'a [u8]> {
+    let (_, akid) = tbs.get::<AuthorityKeyIdentifier<'_>>().ok().flatten()?;
+
+    akid.key_identifier.map(|id| id.as_bytes())
+}
@@ -0,0 +1,463 @@
+//! Cryptographic Message Syntax (CMS) `SignedData` types as defined in
+//! [RFC 5652].
+//!
+//! These types are sufficient to decode and encode signed messages as well
+//! as the "degenerate" `SignedData` bundles (`.p7b`/`.p7c`) commonly used to
+//! distribute certificates and CRLs.
+//!
+//! [RFC 5652]: https://datatracker.ietf.org/doc/html/rfc5652
+
+use crate::attr::Attributes;
+use crate::crl::CertificateList;
+use crate::ext::pkix::SubjectKeyIdentifier;
+use crate::name::Name;
+use crate::Certificate;
+
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use const_oid::db::rfc6268::ID_SIGNED_DATA;
+use der::asn1::{
+    AnyRef, ContextSpecific, ContextSpecificRef, IntRef, ObjectIdentifier, OctetStringRef, SetOfVec,
+};
+use der::{
+    Choice, Decode, Encode, EncodeValue, Enumerated, ErrorKind, Length, Reader, Sequence, Tag,
+    TagMode, TagNumber, Tagged, ValueOrd, Writer,
+};
+use spki::AlgorithmIdentifier;
+
+/// `CMSVersion` as defined in [RFC 5652 Section 10.2.5].
+///
+/// ```text
+/// CMSVersion ::= INTEGER  { v0(0), v1(1), v2(2), v3(3), v4(4), v5(5) }
+/// ```
+///
+/// [RFC 5652 Section 10.2.5]: https://datatracker.ietf.org/doc/html/rfc5652#section-10.2.5
+#[derive(Clone, Debug, Copy, PartialEq, Eq, PartialOrd, Ord, Enumerated)]
+#[asn1(type = "INTEGER")]
+#[repr(u8)]
+#[allow(missing_docs)]
+pub enum CmsVersion {
+    V0 = 0,
+    V1 = 1,
+    V2 = 2,
+    V3 = 3,
+    V4 = 4,
+    V5 = 5,
+}
+
+/// `ContentInfo` as defined in [RFC 5652 Section 3].
+///
+/// ```text
+/// ContentInfo ::= SEQUENCE {
+///     contentType ContentType,
+///     content [0] EXPLICIT ANY DEFINED BY contentType
+/// }
+///
+/// ContentType ::= OBJECT IDENTIFIER
+/// ```
+///
+/// [RFC 5652 Section 3]: https://datatracker.ietf.org/doc/html/rfc5652#section-3
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct ContentInfo<'a> {
+    pub content_type: ObjectIdentifier,
+
+    #[asn1(context_specific = "0", tag_mode = "EXPLICIT")]
+    pub content: AnyRef<'a>,
+}
+
+impl<'a> ContentInfo<'a> {
+    /// Decodes the content as `SignedData`.
+    ///
+    /// Returns an error if the content type is not `id-signedData`.
+    pub fn signed_data(&self) -> der::Result<SignedData<'a>> {
+        if self.content_type != ID_SIGNED_DATA {
+            return Err(ErrorKind::OidUnknown {
+                oid: self.content_type,
+            }
+            .into());
+        }
+
+        self.content.decode_into()
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for ContentInfo<'a> {
+    type Error = der::Error;
+
+    fn try_from(bytes: &'a [u8]) -> der::Result<Self> {
+        Self::from_der(bytes)
+    }
+}
+
+/// `SignedData` as defined in [RFC 5652 Section 5.1].
+///
+/// ```text
+/// SignedData ::= SEQUENCE {
+///     version CMSVersion,
+///     digestAlgorithms DigestAlgorithmIdentifiers,
+///     encapContentInfo EncapsulatedContentInfo,
+///     certificates [0] IMPLICIT CertificateSet OPTIONAL,
+///     crls [1] IMPLICIT RevocationInfoChoices OPTIONAL,
+///     signerInfos SignerInfos
+/// }
+///
+/// DigestAlgorithmIdentifiers ::= SET OF DigestAlgorithmIdentifier
+///
+/// SignerInfos ::= SET OF SignerInfo
+///
+/// CertificateSet ::= SET OF CertificateChoices
+///
+/// RevocationInfoChoices ::= SET OF RevocationInfoChoice
+/// ```
+///
+/// The `certificates` and `crls` sets are kept in their encoded order.
+///
+/// [RFC 5652 Section 5.1]: https://datatracker.ietf.org/doc/html/rfc5652#section-5.1
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct SignedData<'a> {
+    pub version: CmsVersion,
+    pub digest_algorithms: SetOfVec<AlgorithmIdentifier<'a>>,
+    pub encap_content_info: EncapsulatedContentInfo<'a>,
+
+    #[asn1(context_specific = "0", tag_mode = "IMPLICIT", optional = "true")]
+    pub certificates: Option<Vec<CertificateChoices<'a>>>,
+
+    #[asn1(context_specific = "1", tag_mode = "IMPLICIT", optional = "true")]
+    pub crls: Option<Vec<RevocationInfoChoice<'a>>>,
+
+    pub signer_infos: SetOfVec<SignerInfo<'a>>,
+}
+
+impl<'a> SignedData<'a> {
+    /// The X.509 certificates in the `certificates` set.
+    ///
+    /// Certificates in other formats are skipped.
+    pub fn x509_certificates(&self) -> Vec<Certificate<'a>> {
+        self.certificates
+            .iter()
+            .flatten()
+            .filter_map(|choice| match choice {
+                CertificateChoices::Certificate(cert) => Some(cert.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The X.509 CRLs in the `crls` set.
+    ///
+    /// Revocation information in other formats is skipped.
+    pub fn x509_crls(&self) -> Vec<CertificateList<'a>> {
+        self.crls
+            .iter()
+            .flatten()
+            .filter_map(|choice| match choice {
+                RevocationInfoChoice::Crl(crl) => Some(crl.clone()),
+                RevocationInfoChoice::Other(_) => None,
+            })
+            .collect()
+    }
+}
+
+/// `EncapsulatedContentInfo` as defined in [RFC 5652 Section 5.2].
+///
+/// ```text
+/// EncapsulatedContentInfo ::= SEQUENCE {
+///     eContentType ContentType,
+///     eContent [0] EXPLICIT OCTET STRING OPTIONAL
+/// }
+/// ```
+///
+/// [RFC 5652 Section 5.2]: https://datatracker.ietf.org/doc/html/rfc5652#section-5.2
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct EncapsulatedContentInfo<'a> {
+    pub econtent_type: ObjectIdentifier,
+
+    #[asn1(context_specific = "0", tag_mode = "EXPLICIT", optional = "true")]
+    pub econtent: Option<OctetStringRef<'a>>,
+}
+
+/// `CertificateChoices` as defined in [RFC 5652 Section 10.2.2].
+///
+/// ```text
+/// CertificateChoices ::= CHOICE {
+///     certificate Certificate,
+///     extendedCertificate [0] IMPLICIT ExtendedCertificate, -- Obsolete
+///     v1AttrCert [1] IMPLICIT AttributeCertificateV1,       -- Obsolete
+///     v2AttrCert [2] IMPLICIT AttributeCertificateV2,
+///     other [3] IMPLICIT OtherCertificateFormat
+/// }
+/// ```
+///
+/// The `extendedCertificate`, `v1AttrCert` and `v2AttrCert` choices are not
+/// parsed: they are kept as the raw [`AnyRef`] of the whole element, i.e.
+/// including its context-specific tag, so that they re-encode unchanged.
+///
+/// [RFC 5652 Section 10.2.2]: https://datatracker.ietf.org/doc/html/rfc5652#section-10.2.2
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[allow(clippy::large_enum_variant)]
+#[allow(missing_docs)]
+pub enum CertificateChoices<'a> {
+    Certificate(Certificate<'a>),
+    ExtendedCertificate(AnyRef<'a>),
+    V1AttrCert(AnyRef<'a>),
+    V2AttrCert(AnyRef<'a>),
+    Other(OtherCertificateFormat<'a>),
+}
+
+impl CertificateChoices<'_> {
+    const EXTENDED_CERTIFICATE: Tag = Tag::ContextSpecific {
+        constructed: true,
+        number: TagNumber::N0,
+    };
+
+    const V1_ATTR_CERT: Tag = Tag::ContextSpecific {
+        constructed: true,
+        number: TagNumber::N1,
+    };
+
+    const V2_ATTR_CERT: Tag = Tag::ContextSpecific {
+        constructed: true,
+        number: TagNumber::N2,
+    };
+
+    const OTHER: Tag = Tag::ContextSpecific {
+        constructed: true,
+        number: TagNumber::N3,
+    };
+}
+
+impl<'a> Choice<'a> for CertificateChoices<'a> {
+    fn can_decode(tag: Tag) -> bool {
+        matches!(
+            tag,
+            Tag::Sequence
+                | Self::EXTENDED_CERTIFICATE
+                | Self::V1_ATTR_CERT
+                | Self::V2_ATTR_CERT
+                | Self::OTHER
+        )
+    }
+}
+
+impl<'a> Decode<'a> for CertificateChoices<'a> {
+    fn decode<R: Reader<'a>>(reader: &mut R) -> der::Result<Self> {
+        match reader.peek_tag()? {
+            Tag::Sequence => Ok(Self::Certificate(reader.decode()?)),
+            Self::EXTENDED_CERTIFICATE => Ok(Self::ExtendedCertificate(reader.decode()?)),
+            Self::V1_ATTR_CERT => Ok(Self::V1AttrCert(reader.decode()?)),
+            Self::V2_ATTR_CERT => Ok(Self::V2AttrCert(reader.decode()?)),
+            Self::OTHER => Ok(Self::Other(
+                ContextSpecific::decode_implicit(reader, TagNumber::N3)?
+                    .ok_or_else(|| Self::OTHER.value_error())?
+                    .value,
+            )),
+            actual => Err(ErrorKind::TagUnexpected {
+                expected: None,
+                actual,
+            }
+            .into()),
+        }
+    }
+}
+
+impl EncodeValue for CertificateChoices<'_> {
+    fn encode_value(&self, writer: &mut dyn Writer) -> der::Result<()> {
+        match self {
+            Self::Certificate(cert) => cert.encode_value(writer),
+            Self::ExtendedCertificate(any) | Self::V1AttrCert(any) | Self::V2AttrCert(any) => {
+                any.encode_value(writer)
+            }
+            Self::Other(other) => ContextSpecificRef {
+                tag_number: TagNumber::N3,
+                tag_mode: TagMode::Implicit,
+                value: other,
+            }
+            .encode_value(writer),
+        }
+    }
+
+    fn value_len(&self) -> der::Result<Length> {
+        match self {
+            Self::Certificate(cert) => cert.value_len(),
+            Self::ExtendedCertificate(any) | Self::V1AttrCert(any) | Self::V2AttrCert(any) => {
+                any.value_len()
+            }
+            Self::Other(other) => ContextSpecificRef {
+                tag_number: TagNumber::N3,
+                tag_mode: TagMode::Implicit,
+                value: other,
+            }
+            .value_len(),
+        }
+    }
+}
+
+impl Tagged for CertificateChoices<'_> {
+    fn tag(&self) -> Tag {
+        match self {
+            Self::Certificate(_) => Tag::Sequence,
+            Self::ExtendedCertificate(any) | Self::V1AttrCert(any) | Self::V2AttrCert(any) => {
+                any.tag()
+            }
+            Self::Other(_) => Self::OTHER,
+        }
+    }
+}
+
+/// `OtherCertificateFormat` as defined in [RFC 5652 Section 10.2.2].
+///
+/// ```text
+/// OtherCertificateFormat ::= SEQUENCE {
+///     otherCertFormat OBJECT IDENTIFIER,
+///     otherCert ANY DEFINED BY otherCertFormat
+/// }
+/// ```
+///
+/// [RFC 5652 Section 10.2.2]: https://datatracker.ietf.org/doc/html/rfc5652#section-10.2.2
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct OtherCertificateFormat<'a> {
+    pub other_cert_format: ObjectIdentifier,
+    pub other_cert: AnyRef<'a>,
+}
+
+/// `RevocationInfoChoice` as defined in [RFC 5652 Section 10.2.1].
+///
+/// ```text
+/// RevocationInfoChoice ::= CHOICE {
+///     crl CertificateList,
+///     other [1] IMPLICIT OtherRevocationInfoFormat
+/// }
+/// ```
+///
+/// [RFC 5652 Section 10.2.1]: https://datatracker.ietf.org/doc/html/rfc5652#section-10.2.1
+#[derive(Clone, Debug, Eq, PartialEq, Choice)]
+#[allow(clippy::large_enum_variant)]
+#[allow(missing_docs)]
+pub enum RevocationInfoChoice<'a> {
+    Crl(CertificateList<'a>),
+
+    #[asn1(context_specific = "1", tag_mode = "IMPLICIT", constructed = "true")]
+    Other(OtherRevocationInfoFormat<'a>),
+}
+
+/// `OtherRevocationInfoFormat` as defined in [RFC 5652 Section 10.2.1].
+///
+/// ```text
+/// OtherRevocationInfoFormat ::= SEQUENCE {
+///     otherRevInfoFormat OBJECT IDENTIFIER,
+///     otherRevInfo ANY DEFINED BY otherRevInfoFormat
+/// }
+/// ```
+///
+/// [RFC 5652 Section 10.2.1]: https://datatracker.ietf.org/doc/html/rfc5652#section-10.2.1
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct OtherRevocationInfoFormat<'a> {
+    pub other_rev_info_format: ObjectIdentifier,
+    pub other_rev_info: AnyRef<'a>,
+}
+
+/// `SignerInfo` as defined in [RFC 5652 Section 5.3].
+///
+/// ```text
+/// SignerInfo ::= SEQUENCE {
+///     version CMSVersion,
+///     sid SignerIdentifier,
+///     digestAlgorithm DigestAlgorithmIdentifier,
+///     signedAttrs [0] IMPLICIT SignedAttributes OPTIONAL,
+///     signatureAlgorithm SignatureAlgorithmIdentifier,
+///     signature SignatureValue,
+///     unsignedAttrs [1] IMPLICIT UnsignedAttributes OPTIONAL
+/// }
+///
+/// SignedAttributes ::= SET SIZE (1..MAX) OF Attribute
+///
+/// UnsignedAttributes ::= SET SIZE (1..MAX) OF Attribute
+///
+/// SignatureValue ::= OCTET STRING
+/// ```
+///
+/// [RFC 5652 Section 5.3]: https://datatracker.ietf.org/doc/html/rfc5652#section-5.3
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct SignerInfo<'a> {
+    pub version: CmsVersion,
+    pub sid: SignerIdentifier<'a>,
+    pub digest_algorithm: AlgorithmIdentifier<'a>,
+
+    #[asn1(context_specific = "0", tag_mode = "IMPLICIT", optional = "true")]
+    pub signed_attrs: Option<Attributes<'a>>,
+
+    pub signature_algorithm: AlgorithmIdentifier<'a>,
+    pub signature: OctetStringRef<'a>,
+
+    #[asn1(context_specific = "1", tag_mode = "IMPLICIT", optional = "true")]
+    pub unsigned_attrs: Option<Attributes<'a>>,
+}
+
+/// `SignerInfos` are a `SET OF`, ordered by their DER encodings.
+impl ValueOrd for SignerInfo<'_> {
+    fn value_cmp(&self, other: &Self) -> der::Result<Ordering> {
+        Ok(self.to_vec()?.cmp(&other.to_vec()?))
+    }
+}
+
+/// `SignerIdentifier` as defined in [RFC 5652 Section 5.3].
+///
+/// ```text
+/// SignerIdentifier ::= CHOICE {
+///     issuerAndSerialNumber IssuerAndSerialNumber,
+///     subjectKeyIdentifier [0] SubjectKeyIdentifier
+/// }
+/// ```
+///
+/// [RFC 5652 Section 5.3]: https://datatracker.ietf.org/doc/html/rfc5652#section-5.3
+#[derive(Clone, Debug, Eq, PartialEq, Choice)]
+#[allow(missing_docs)]
+pub enum SignerIdentifier<'a> {
+    IssuerAndSerialNumber(IssuerAndSerialNumber<'a>),
+
+    #[asn1(context_specific = "0", tag_mode = "IMPLICIT")]
+    SubjectKeyIdentifier(SubjectKeyIdentifier<'a>),
+}
+
+impl<'a> SignerIdentifier<'a> {
+    /// Does this identifier refer to `cert`?
+    ///
+    /// A subject key identifier is compared with the certificate's
+    /// `SubjectKeyIdentifier` extension.
+    pub fn identifies(&self, cert: &'a Certificate<'a>) -> der::Result<bool> {
+        let tbs = &cert.tbs_certificate;
+
+        match self {
+            SignerIdentifier::IssuerAndSerialNumber(id) => {
+                Ok(id.issuer == tbs.issuer && id.serial_number == tbs.serial_number)
+            }
+            SignerIdentifier::SubjectKeyIdentifier(skid) => Ok(tbs
+                .get::<SubjectKeyIdentifier<'_>>()?
+                .map_or(false, |(_, cert_skid)| cert_skid == *skid)),
+        }
+    }
+}
+
+/// `IssuerAndSerialNumber` as defined in [RFC 5652 Section 10.2.4].
+///
+/// ```text
+/// IssuerAndSerialNumber ::= SEQUENCE {
+///     issuer Name,
+///     serialNumber CertificateSerialNumber
+/// }
+/// ```
+///
+/// [RFC 5652 Section 10.2.4]: https://datatracker.ietf.org/doc/html/rfc5652#section-10.2.4
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct IssuerAndSerialNumber<'a> {
+    pub issuer: Name<'a>,
+    pub serial_number: IntRef<'a>,
+}
@@ -1,15 +1,23 @@
 //! Certificate Revocation List types
 
-use crate::ext::Extensions;
-use crate::name::Name;
+use crate::ext::registry::{ExtensionRegistry, ParsedExtension};
+use crate::ext::{Extension, Extensions, ExtensionsOwned};
+use crate::name::{Name, NameOwned};
 use crate::time::Time;
 use crate::Version;
 
 use alloc::vec::Vec;
 
-use der::asn1::{BitStringRef, UIntRef};
-use der::Sequence;
-use spki::AlgorithmIdentifier;
+use const_oid::AssociatedOid;
+use der::asn1::{BitString, BitStringRef, Int, IntRef};
+use der::{Decode, Error, Sequence};
+use spki::{AlgorithmIdentifier, AlgorithmIdentifierOwned};
+
+#[cfg(feature = "std")]
+use {
+    der::{Choice, IoReader, Length, Tag},
+    std::io::Read,
+};
 
 /// `CertificateList` as defined in [RFC 5280 Section 5.1].
 ///
@@ -47,11 +55,44 @@ pub struct CertificateList<'a> {
 #[derive(Clone, Debug, Eq, PartialEq, Sequence)]
 #[allow(missing_docs)]
 pub struct RevokedCert<'a> {
-    pub serial_number: UIntRef<'a>,
+    pub serial_number: IntRef<'a>,
     pub revocation_date: Time,
     pub crl_entry_extensions: Option<Extensions<'a>>,
 }
 
+impl<'a> RevokedCert<'a> {
+    /// Decodes a single extension
+    ///
+    /// Returns an error if multiple of these extensions is present. Returns
+    /// `Ok(None)` if the extension is not present. Returns a decoding error
+    /// if decoding failed. Otherwise returns the extension.
+    pub fn get<'b: 'a, T: Decode<'a> + AssociatedOid>(
+        &'b self,
+    ) -> Result<Option<(bool, T)>, Error> {
+        crate::ext::get(self.crl_entry_extensions.as_deref())
+    }
+
+    /// Filters extensions by an associated OID
+    ///
+    /// Returns a filtered iterator over all the extensions with the OID.
+    pub fn filter<'b: 'a, T: Decode<'a> + AssociatedOid>(
+        &'b self,
+    ) -> impl 'b + Iterator<Item = Result<(bool, T), Error>> {
+        crate::ext::filter(self.crl_entry_extensions.as_deref())
+    }
+
+    /// Decodes all extensions with `registry`
+    ///
+    /// Returns an iterator over each extension paired with its decoded value
+    /// or decoding error.
+    pub fn parsed_extensions<'b: 'a>(
+        &'b self,
+        registry: &'b ExtensionRegistry,
+    ) -> impl 'b + Iterator<Item = (&'b Extension<'a>, Result<ParsedExtension<'a>, Error>)> {
+        registry.decode_all(self.crl_entry_extensions.as_deref().unwrap_or(&[]))
+    }
+}
+
 /// `TbsCertList` as defined in [RFC 5280 Section 5.1].
 ///
 /// ```text
@@ -84,3 +125,314 @@ pub struct TbsCertList<'a> {
     #[asn1(context_specific = "0", tag_mode = "EXPLICIT", optional = "true")]
     pub crl_extensions: Option<Extensions<'a>>,
 }
+
+impl<'a> TbsCertList<'a> {
+    /// Decodes a single extension
+    ///
+    /// Returns an error if multiple of these extensions is present. Returns
+    /// `Ok(None)` if the extension is not present. Returns a decoding error
+    /// if decoding failed. Otherwise returns the extension.
+    pub fn get<'b: 'a, T: Decode<'a> + AssociatedOid>(
+        &'b self,
+    ) -> Result<Option<(bool, T)>, Error> {
+        crate::ext::get(self.crl_extensions.as_deref())
+    }
+
+    /// Filters extensions by an associated OID
+    ///
+    /// Returns a filtered iterator over all the extensions with the OID.
+    pub fn filter<'b: 'a, T: Decode<'a> + AssociatedOid>(
+        &'b self,
+    ) -> impl 'b + Iterator<Item = Result<(bool, T), Error>> {
+        crate::ext::filter(self.crl_extensions.as_deref())
+    }
+
+    /// Decodes all extensions with `registry`
+    ///
+    /// Returns an iterator over each extension paired with its decoded value
+    /// or decoding error.
+    pub fn parsed_extensions<'b: 'a>(
+        &'b self,
+        registry: &'b ExtensionRegistry,
+    ) -> impl 'b + Iterator<Item = (&'b Extension<'a>, Result<ParsedExtension<'a>, Error>)> {
+        registry.decode_all(self.crl_extensions.as_deref().unwrap_or(&[]))
+    }
+}
+
+/// Owned form of [`CertificateList`].
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct CertificateListOwned {
+    pub tbs_cert_list: TbsCertListOwned,
+    pub signature_algorithm: AlgorithmIdentifierOwned,
+    pub signature: BitString,
+}
+
+impl CertificateListOwned {
+    /// Borrows this CRL as a [`CertificateList`].
+    pub fn as_ref(&self) -> CertificateList<'_> {
+        self.into()
+    }
+}
+
+impl From<&CertificateList<'_>> for CertificateListOwned {
+    fn from(crl: &CertificateList<'_>) -> Self {
+        Self {
+            tbs_cert_list: (&crl.tbs_cert_list).into(),
+            signature_algorithm: (&crl.signature_algorithm).into(),
+            signature: crl.signature.into(),
+        }
+    }
+}
+
+impl<'a> From<&'a CertificateListOwned> for CertificateList<'a> {
+    fn from(crl: &'a CertificateListOwned) -> Self {
+        Self {
+            tbs_cert_list: crl.tbs_cert_list.as_ref(),
+            signature_algorithm: crl.signature_algorithm.as_ref(),
+            signature: (&crl.signature).into(),
+        }
+    }
+}
+
+/// Owned form of [`RevokedCert`].
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct RevokedCertOwned {
+    pub serial_number: Int,
+    pub revocation_date: Time,
+    pub crl_entry_extensions: Option<ExtensionsOwned>,
+}
+
+impl RevokedCertOwned {
+    /// Borrows this entry as a [`RevokedCert`].
+    pub fn as_ref(&self) -> RevokedCert<'_> {
+        self.into()
+    }
+}
+
+impl From<&RevokedCert<'_>> for RevokedCertOwned {
+    fn from(revoked: &RevokedCert<'_>) -> Self {
+        Self {
+            serial_number: revoked.serial_number.into(),
+            revocation_date: revoked.revocation_date,
+            crl_entry_extensions: revoked
+                .crl_entry_extensions
+                .as_ref()
+                .map(|exts| exts.iter().map(Into::into).collect()),
+        }
+    }
+}
+
+impl<'a> From<&'a RevokedCertOwned> for RevokedCert<'a> {
+    fn from(revoked: &'a RevokedCertOwned) -> Self {
+        Self {
+            serial_number: (&revoked.serial_number).into(),
+            revocation_date: revoked.revocation_date,
+            crl_entry_extensions: revoked
+                .crl_entry_extensions
+                .as_ref()
+                .map(|exts| exts.iter().map(Into::into).collect()),
+        }
+    }
+}
+
+/// Owned form of [`TbsCertList`].
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct TbsCertListOwned {
+    pub version: Version,
+    pub signature: AlgorithmIdentifierOwned,
+    pub issuer: NameOwned,
+    pub this_update: Time,
+    pub next_update: Option<Time>,
+    pub revoked_certificates: Option<Vec<RevokedCertOwned>>,
+
+    #[asn1(context_specific = "0", tag_mode = "EXPLICIT", optional = "true")]
+    pub crl_extensions: Option<ExtensionsOwned>,
+}
+
+impl TbsCertListOwned {
+    /// Borrows this structure as a [`TbsCertList`].
+    ///
+    /// The returned value refers to the data of `self`; only the containers
+    /// of the names, entries and extensions are allocated, as described in
+    /// [`RdnSequenceOwned::as_ref`](crate::name::RdnSequenceOwned::as_ref).
+    pub fn as_ref(&self) -> TbsCertList<'_> {
+        self.into()
+    }
+}
+
+impl From<&TbsCertList<'_>> for TbsCertListOwned {
+    fn from(tbs: &TbsCertList<'_>) -> Self {
+        Self {
+            version: tbs.version,
+            signature: (&tbs.signature).into(),
+            issuer: (&tbs.issuer).into(),
+            this_update: tbs.this_update,
+            next_update: tbs.next_update,
+            revoked_certificates: tbs
+                .revoked_certificates
+                .as_ref()
+                .map(|revoked| revoked.iter().map(Into::into).collect()),
+            crl_extensions: tbs
+                .crl_extensions
+                .as_ref()
+                .map(|exts| exts.iter().map(Into::into).collect()),
+        }
+    }
+}
+
+impl<'a> From<&'a TbsCertListOwned> for TbsCertList<'a> {
+    fn from(tbs: &'a TbsCertListOwned) -> Self {
+        Self {
+            version: tbs.version,
+            signature: tbs.signature.as_ref(),
+            issuer: tbs.issuer.as_ref(),
+            this_update: tbs.this_update,
+            next_update: tbs.next_update,
+            revoked_certificates: tbs
+                .revoked_certificates
+                .as_ref()
+                .map(|revoked| revoked.iter().map(Into::into).collect()),
+            crl_extensions: tbs
+                .crl_extensions
+                .as_ref()
+                .map(|exts| exts.iter().map(Into::into).collect()),
+        }
+    }
+}
+
+/// Streaming reader of the entries of a DER-encoded [`CertificateList`].
+///
+/// CRLs can hold millions of entries. Rather than decoding the whole list,
+/// this reader decodes the fields of the `TBSCertList` which precede
+/// `revokedCertificates`, then yields its entries one at a time, so that only
+/// a single entry is held in memory:
+///
+/// ```
+/// # fn main() -> der::Result<()> {
+/// use der::asn1::IntRef;
+/// use x509_cert::crl::RevokedCerts;
+///
+/// let crl = include_bytes!("../tests/examples/GoodCACRL.crl");
+/// let serial = IntRef::new(&[0x0E])?;
+///
+/// let mut entries = RevokedCerts::new(&crl[..])?;
+/// assert!(entries.any(|entry| entry.map_or(false, |e| e.as_ref().serial_number == serial)));
+/// # Ok(())
+/// # }
+/// ```
+///
+/// The fields which follow the entries, including `crlExtensions` and the
+/// signature, are not read: the signature of a CRL read this way is not
+/// verified.
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[derive(Debug)]
+pub struct RevokedCerts<R> {
+    reader: IoReader<R>,
+
+    /// Length of the entries which are left to read.
+    remaining: Length,
+
+    version: Version,
+    signature: AlgorithmIdentifierOwned,
+    issuer: NameOwned,
+    this_update: Time,
+    next_update: Option<Time>,
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl<R: Read> RevokedCerts<R> {
+    /// Reads a `CertificateList` from `inner` up to its first entry.
+    pub fn new(inner: R) -> Result<Self, Error> {
+        let mut reader = IoReader::new(inner);
+        reader.read_header()?.tag.assert_eq(Tag::Sequence)?;
+
+        let tbs_cert_list = reader.read_header()?;
+        tbs_cert_list.tag.assert_eq(Tag::Sequence)?;
+        let end = (reader.position() + tbs_cert_list.length)?;
+
+        let version = match reader.peek_tag()? {
+            Tag::Integer => reader.decode()?,
+            _ => Version::V1,
+        };
+
+        let signature = reader.decode()?;
+        let issuer = reader.decode()?;
+        let this_update = reader.decode()?;
+
+        let mut next_update = None;
+        if reader.position() < end && Time::can_decode(reader.peek_tag()?) {
+            next_update = Some(reader.decode()?);
+        }
+
+        let mut remaining = Length::ZERO;
+        if reader.position() < end && reader.peek_tag()? == Tag::Sequence {
+            remaining = reader.read_header()?.length;
+        }
+
+        Ok(Self {
+            reader,
+            remaining,
+            version,
+            signature,
+            issuer,
+            this_update,
+            next_update,
+        })
+    }
+
+    /// The version of the CRL.
+    pub fn version(&self) -> Version {
+        self.version
+    }
+
+    /// The algorithm the CRL is signed with.
+    pub fn signature(&self) -> &AlgorithmIdentifierOwned {
+        &self.signature
+    }
+
+    /// The issuer of the CRL.
+    pub fn issuer(&self) -> &NameOwned {
+        &self.issuer
+    }
+
+    /// The issue date of the CRL.
+    pub fn this_update(&self) -> Time {
+        self.this_update
+    }
+
+    /// The date by which the next CRL will be issued.
+    pub fn next_update(&self) -> Option<Time> {
+        self.next_update
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl<R: Read> Iterator for RevokedCerts<R> {
+    type Item = Result<RevokedCertOwned, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == Length::ZERO {
+            return None;
+        }
+
+        let position = self.reader.position();
+        let entry = self.reader.decode().and_then(|entry| {
+            let len = (self.reader.position() - position)?;
+            self.remaining = (self.remaining - len)?;
+            Ok(entry)
+        });
+
+        // Nothing can be read after an error.
+        if entry.is_err() {
+            self.remaining = Length::ZERO;
+        }
+
+        Some(entry)
+    }
+}
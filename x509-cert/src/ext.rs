@@ -1,9 +1,12 @@
 //! Standardized X.509 Certificate Extensions
 
-use der::Sequence;
+use const_oid::AssociatedOid;
+use der::{Decode, DecodeValue, Encode, Error, ErrorKind, Header, Reader, Sequence};
 use spki::ObjectIdentifier;
 
+pub mod dept;
 pub mod pkix;
+pub mod registry;
 
 /// Extension as defined in [RFC 5280 Section 4.1.2.9].
 ///
@@ -42,3 +45,86 @@ pub struct Extension<'a> {
 ///
 /// [RFC 5280 Section 4.1.2.9]: https://datatracker.ietf.org/doc/html/rfc5280#section-4.1.2.9
 pub type Extensions<'a> = alloc::vec::Vec<Extension<'a>>;
+
+/// Owned form of [`Extension`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[allow(missing_docs)]
+pub struct ExtensionOwned {
+    pub extn_id: ObjectIdentifier,
+    pub critical: bool,
+    pub extn_value: alloc::vec::Vec<u8>,
+}
+
+impl ExtensionOwned {
+    /// Borrows this extension as an [`Extension`].
+    pub fn as_ref(&self) -> Extension<'_> {
+        self.into()
+    }
+}
+
+impl<'a> DecodeValue<'a> for ExtensionOwned {
+    fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> Result<Self, Error> {
+        Extension::decode_value(reader, header).map(|ext| (&ext).into())
+    }
+}
+
+impl Sequence<'_> for ExtensionOwned {
+    fn fields<F, T>(&self, f: F) -> Result<T, Error>
+    where
+        F: FnOnce(&[&dyn Encode]) -> Result<T, Error>,
+    {
+        self.as_ref().fields(f)
+    }
+}
+
+impl From<&Extension<'_>> for ExtensionOwned {
+    fn from(ext: &Extension<'_>) -> Self {
+        Self {
+            extn_id: ext.extn_id,
+            critical: ext.critical,
+            extn_value: ext.extn_value.to_vec(),
+        }
+    }
+}
+
+impl<'a> From<&'a ExtensionOwned> for Extension<'a> {
+    fn from(ext: &'a ExtensionOwned) -> Self {
+        Self {
+            extn_id: ext.extn_id,
+            critical: ext.critical,
+            extn_value: &ext.extn_value,
+        }
+    }
+}
+
+/// Owned form of [`Extensions`].
+pub type ExtensionsOwned = alloc::vec::Vec<ExtensionOwned>;
+
+/// Decodes the single extension of type `T` in `extensions`.
+///
+/// Shared by the `get` methods of the structures which hold extensions.
+pub(crate) fn get<'a, 'b: 'a, T: Decode<'a> + AssociatedOid>(
+    extensions: Option<&'b [Extension<'a>]>,
+) -> Result<Option<(bool, T)>, Error> {
+    let mut iter = filter::<T>(extensions).peekable();
+    match iter.next() {
+        None => Ok(None),
+        Some(item) => match iter.peek() {
+            Some(..) => Err(ErrorKind::Failed.into()),
+            None => Ok(Some(item?)),
+        },
+    }
+}
+
+/// Decodes all the extensions of type `T` in `extensions`.
+///
+/// Shared by the `filter` methods of the structures which hold extensions.
+pub(crate) fn filter<'a, 'b: 'a, T: Decode<'a> + AssociatedOid>(
+    extensions: Option<&'b [Extension<'a>]>,
+) -> impl 'b + Iterator<Item = Result<(bool, T), Error>> {
+    extensions
+        .unwrap_or(&[])
+        .iter()
+        .filter(|e| e.extn_id == T::OID)
+        .map(|e| Ok((e.critical, T::from_der(e.extn_value)?)))
+}
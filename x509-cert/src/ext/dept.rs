@@ -0,0 +1,402 @@
+//! Department identity private extension.
+//!
+//! The `1.2.3.4.5.6.7.8.1` extension carries the enrollment attributes of the
+//! subject, such as those of Hyperledger Fabric CA enrollments. Unlike other
+//! extensions, the `extnValue` of this extension is not DER, but a UTF-8 JSON
+//! document of the form:
+//!
+//! ```text
+//! {"attrs":{"DeptLevel":"2","DeptName":"812","DeptType":"computer","SuperDeptName":"804",...}}
+//! ```
+//!
+//! [`DepartmentIdentity`] implements [`Decode`] and [`Encode`] over this JSON
+//! so that it can be used with [`TbsCertificate::get`] and the certificate
+//! builder like any other extension. The OID is in a private arc, so the
+//! extension is only decoded by an
+//! [`ExtensionRegistry`](crate::ext::registry::ExtensionRegistry) after
+//! registering it.
+//!
+//! [`TbsCertificate::get`]: crate::TbsCertificate::get
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{self, Write as _};
+use core::str::Utf8Error;
+
+use const_oid::{AssociatedOid, ObjectIdentifier};
+use der::{Decode, Encode, ErrorKind, Length, Reader, Tag, Writer};
+
+/// OID of the department identity extension.
+pub const DEPARTMENT_IDENTITY: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.3.4.5.6.7.8.1");
+
+/// Result type with the `dept` module's [`Error`] type.
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// Department identity errors.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The extension value is not valid UTF-8.
+    Utf8(Utf8Error),
+
+    /// The extension value is not a JSON object of the expected form, or
+    /// contains a duplicate attribute, at the given byte offset.
+    Syntax {
+        /// Byte offset of the error.
+        position: usize,
+    },
+
+    /// A required attribute is missing.
+    MissingAttribute(&'static str),
+
+    /// `DeptLevel` is not a decimal number in the range 0-255.
+    InvalidDeptLevel,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Utf8(err) => write!(f, "department identity: {}", err),
+            Error::Syntax { position } => {
+                write!(f, "department identity: syntax error at byte {}", position)
+            }
+            Error::MissingAttribute(name) => {
+                write!(f, "department identity: missing attribute {}", name)
+            }
+            Error::InvalidDeptLevel => f.write_str("department identity: invalid DeptLevel"),
+        }
+    }
+}
+
+impl From<Utf8Error> for Error {
+    fn from(err: Utf8Error) -> Error {
+        Error::Utf8(err)
+    }
+}
+
+impl From<Error> for der::Error {
+    fn from(err: Error) -> der::Error {
+        match err {
+            Error::Utf8(err) => ErrorKind::Utf8(err).into(),
+            _ => ErrorKind::Value {
+                tag: Tag::OctetString,
+            }
+            .into(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+/// Department identity extension.
+///
+/// The `DeptLevel`, `DeptName`, `DeptType` and `SuperDeptName` attributes are
+/// required. All other attributes, e.g. `hf.EnrollmentID`, are preserved in
+/// `other_attributes`.
+///
+/// The [`Decode`] and [`Encode`] impls only make sense for a whole
+/// `extnValue`: decoding consumes the rest of the reader, and encoding writes
+/// the raw JSON without a tag or length. This type must therefore not be
+/// nested inside other DER types.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct DepartmentIdentity {
+    /// Department type (`DeptType`).
+    pub dept_type: String,
+
+    /// Department level (`DeptLevel`).
+    pub dept_level: u8,
+
+    /// Department name (`DeptName`).
+    pub dept_name: String,
+
+    /// Name of the parent department (`SuperDeptName`).
+    pub super_dept_name: String,
+
+    /// Other attributes as name/value pairs, in their original order.
+    pub other_attributes: Vec<(String, String)>,
+}
+
+impl DepartmentIdentity {
+    /// Parses the JSON extension value.
+    pub fn from_json(bytes: &[u8]) -> Result<Self> {
+        let input = core::str::from_utf8(bytes)?;
+        let mut parser = Parser { input, pos: 0 };
+
+        let mut dept_type = None;
+        let mut dept_level = None;
+        let mut dept_name = None;
+        let mut super_dept_name = None;
+        let mut other_attributes = Vec::<(String, String)>::new();
+        let mut attrs = false;
+
+        parser.object(|parser, key| {
+            if key != "attrs" || attrs {
+                return Err(parser.error());
+            }
+            attrs = true;
+
+            parser.object(|parser, key| {
+                let position = parser.pos;
+                let value = parser.string()?;
+
+                let known = match key.as_str() {
+                    "DeptType" => &mut dept_type,
+                    "DeptLevel" => &mut dept_level,
+                    "DeptName" => &mut dept_name,
+                    "SuperDeptName" => &mut super_dept_name,
+                    _ if other_attributes.iter().any(|(name, _)| *name == key) => {
+                        return Err(Error::Syntax { position })
+                    }
+                    _ => {
+                        other_attributes.push((key, value));
+                        return Ok(());
+                    }
+                };
+
+                match known {
+                    Some(_) => Err(Error::Syntax { position }),
+                    None => {
+                        *known = Some(value);
+                        Ok(())
+                    }
+                }
+            })
+        })?;
+
+        parser.whitespace();
+        if parser.pos != input.len() {
+            return Err(parser.error());
+        }
+
+        let dept_level = dept_level.ok_or(Error::MissingAttribute("DeptLevel"))?;
+
+        Ok(Self {
+            dept_type: dept_type.ok_or(Error::MissingAttribute("DeptType"))?,
+            dept_level: parse_level(&dept_level)?,
+            dept_name: dept_name.ok_or(Error::MissingAttribute("DeptName"))?,
+            super_dept_name: super_dept_name.ok_or(Error::MissingAttribute("SuperDeptName"))?,
+            other_attributes,
+        })
+    }
+
+    /// Serializes the extension value as JSON.
+    ///
+    /// Attributes are sorted by name, as in the values issued by the
+    /// Hyperledger Fabric CA.
+    pub fn to_json(&self) -> String {
+        let dept_level = self.dept_level.to_string();
+        let mut attrs: Vec<(&str, &str)> = alloc::vec![
+            ("DeptLevel", dept_level.as_str()),
+            ("DeptName", self.dept_name.as_str()),
+            ("DeptType", self.dept_type.as_str()),
+            ("SuperDeptName", self.super_dept_name.as_str()),
+        ];
+        attrs.extend(
+            self.other_attributes
+                .iter()
+                .map(|(name, value)| (name.as_str(), value.as_str())),
+        );
+        attrs.sort_by(|a, b| a.0.cmp(b.0));
+
+        let mut json = String::from("{\"attrs\":{");
+        for (i, (name, value)) in attrs.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            push_string(&mut json, name);
+            json.push(':');
+            push_string(&mut json, value);
+        }
+        json.push_str("}}");
+        json
+    }
+}
+
+impl AssociatedOid for DepartmentIdentity {
+    const OID: ObjectIdentifier = DEPARTMENT_IDENTITY;
+}
+
+impl<'a> Decode<'a> for DepartmentIdentity {
+    fn decode<R: Reader<'a>>(reader: &mut R) -> der::Result<Self> {
+        let bytes = reader.read_slice(reader.remaining_len())?;
+        Ok(Self::from_json(bytes)?)
+    }
+}
+
+impl Encode for DepartmentIdentity {
+    fn encoded_len(&self) -> der::Result<Length> {
+        self.to_json().len().try_into()
+    }
+
+    fn encode(&self, writer: &mut dyn Writer) -> der::Result<()> {
+        writer.write(self.to_json().as_bytes())
+    }
+}
+
+/// Parse a `DeptLevel` value.
+fn parse_level(level: &str) -> Result<u8> {
+    if level.is_empty() || !level.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(Error::InvalidDeptLevel);
+    }
+
+    level.parse().map_err(|_| Error::InvalidDeptLevel)
+}
+
+/// Append `value` to `json` as a JSON string.
+fn push_string(json: &mut String, value: &str) {
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
+/// Minimal parser for JSON objects with string values.
+struct Parser<'i> {
+    input: &'i str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self) -> Error {
+        Error::Syntax { position: self.pos }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.as_bytes().get(self.pos).copied()
+    }
+
+    fn whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<()> {
+        self.whitespace();
+        if self.peek() != Some(byte) {
+            return Err(self.error());
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    /// Parse an object, calling `member` for each key with the parser
+    /// positioned at the value.
+    fn object<F>(&mut self, mut member: F) -> Result<()>
+    where
+        F: FnMut(&mut Self, String) -> Result<()>,
+    {
+        self.expect(b'{')?;
+        self.whitespace();
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(());
+        }
+
+        loop {
+            let key = self.string()?;
+            self.expect(b':')?;
+            self.whitespace();
+            member(self, key)?;
+
+            self.whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                _ => return Err(self.error()),
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String> {
+        self.expect(b'"')?;
+        let mut value = String::new();
+        let mut start = self.pos;
+
+        loop {
+            match self.peek() {
+                Some(b'"') => {
+                    value.push_str(&self.input[start..self.pos]);
+                    self.pos += 1;
+                    return Ok(value);
+                }
+                Some(b'\\') => {
+                    value.push_str(&self.input[start..self.pos]);
+                    self.pos += 1;
+                    value.push(self.escape()?);
+                    start = self.pos;
+                }
+                Some(byte) if byte >= 0x20 => self.pos += 1,
+                _ => return Err(self.error()),
+            }
+        }
+    }
+
+    /// Parse the escape sequence following a backslash.
+    fn escape(&mut self) -> Result<char> {
+        let c = match self.peek() {
+            Some(b'"') => '"',
+            Some(b'\\') => '\\',
+            Some(b'/') => '/',
+            Some(b'b') => '\u{8}',
+            Some(b'f') => '\u{c}',
+            Some(b'n') => '\n',
+            Some(b'r') => '\r',
+            Some(b't') => '\t',
+            Some(b'u') => {
+                let position = self.pos;
+                self.pos += 1;
+                let high = self.hex4()?;
+
+                let code = if (0xD800..0xDC00).contains(&high) {
+                    if !self.input[self.pos..].starts_with("\\u") {
+                        return Err(Error::Syntax { position });
+                    }
+                    self.pos += 2;
+                    let low = self.hex4()?;
+                    if !(0xDC00..0xE000).contains(&low) {
+                        return Err(Error::Syntax { position });
+                    }
+                    0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+                } else {
+                    high
+                };
+
+                return char::from_u32(code).ok_or(Error::Syntax { position });
+            }
+            _ => return Err(self.error()),
+        };
+
+        self.pos += 1;
+        Ok(c)
+    }
+
+    fn hex4(&mut self) -> Result<u32> {
+        let digits = self
+            .input
+            .get(self.pos..self.pos + 4)
+            .ok_or_else(|| self.error())?;
+
+        if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(self.error());
+        }
+
+        self.pos += 4;
+        u32::from_str_radix(digits, 16).map_err(|_| self.error())
+    }
+}
@@ -18,7 +18,7 @@ pub use certpolicy::CertificatePolicies;
 use const_oid::{AssociatedOid, ObjectIdentifier};
 pub use constraints::{BasicConstraints, NameConstraints, PolicyConstraints};
 pub use crl::{
-    BaseCrlNumber, CrlDistributionPoints, CrlNumber, CrlReason, FreshestCrl,
+    BaseCrlNumber, CertificateIssuer, CrlDistributionPoints, CrlNumber, CrlReason, FreshestCrl,
     IssuingDistributionPoint,
 };
 pub use keyusage::{ExtendedKeyUsage, KeyUsage, KeyUsages, PrivateKeyUsagePeriod};
@@ -3,12 +3,14 @@
 pub mod dp;
 
 use const_oid::db::rfc5280::{
-    ID_CE_CRL_DISTRIBUTION_POINTS, ID_CE_CRL_NUMBER, ID_CE_CRL_REASONS, ID_CE_DELTA_CRL_INDICATOR,
-    ID_CE_FRESHEST_CRL,
+    ID_CE_CERTIFICATE_ISSUER, ID_CE_CRL_DISTRIBUTION_POINTS, ID_CE_CRL_NUMBER, ID_CE_CRL_REASONS,
+    ID_CE_DELTA_CRL_INDICATOR, ID_CE_FRESHEST_CRL,
 };
 use const_oid::{AssociatedOid, ObjectIdentifier};
 pub use dp::IssuingDistributionPoint;
 
+use crate::ext::pkix::name::GeneralNames;
+
 use alloc::vec::Vec;
 
 use der::{asn1::UIntRef, Enumerated};
@@ -114,3 +116,19 @@ pub enum CrlReason {
 impl AssociatedOid for CrlReason {
     const OID: ObjectIdentifier = ID_CE_CRL_REASONS;
 }
+
+/// CertificateIssuer as defined in [RFC 5280 Section 5.3.3].
+///
+/// ```text
+/// CertificateIssuer ::= GeneralNames
+/// ```
+///
+/// [RFC 5280 Section 5.3.3]: https://datatracker.ietf.org/doc/html/rfc5280#section-5.3.3
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CertificateIssuer<'a>(pub GeneralNames<'a>);
+
+impl<'a> AssociatedOid for CertificateIssuer<'a> {
+    const OID: ObjectIdentifier = ID_CE_CERTIFICATE_ISSUER;
+}
+
+impl_newtype!(CertificateIssuer<'a>, GeneralNames<'a>);
@@ -1,6 +1,6 @@
 //! PKIX distribution point types
 
-use const_oid::{db::rfc5280::ID_PE_SUBJECT_INFO_ACCESS, AssociatedOid, ObjectIdentifier};
+use const_oid::{db::rfc5280::ID_CE_ISSUING_DISTRIBUTION_POINT, AssociatedOid, ObjectIdentifier};
 use der::Sequence;
 use flagset::{flags, FlagSet};
 
@@ -61,7 +61,7 @@ pub struct IssuingDistributionPoint<'a> {
 }
 
 impl<'a> AssociatedOid for IssuingDistributionPoint<'a> {
-    const OID: ObjectIdentifier = ID_PE_SUBJECT_INFO_ACCESS;
+    const OID: ObjectIdentifier = ID_CE_ISSUING_DISTRIBUTION_POINT;
 }
 
 /// DistributionPoint as defined in [RFC 5280 Section 4.2.1.13].
@@ -1,5 +1,9 @@
-use der::asn1::{PrintableStringRef, Utf8StringRef};
-use der::Choice;
+use core::fmt;
+
+use der::asn1::{
+    AnyRef, BmpStringRef, PrintableStringRef, TeletexStringRef, UniversalStringRef, Utf8StringRef,
+};
+use der::{Choice, Tag, Tagged};
 
 /// DirectoryString as defined in [RFC 5280 Section 4.2.1.4].
 ///
@@ -34,16 +38,54 @@ use der::Choice;
 /// ```
 ///
 /// The implication of the above paragraph is that `PrintableString` and
-/// `UTF8String` are the new types and the other types are legacy. Until
-/// the need arises, we only support `PrintableString` and `UTF8String`.
+/// `UTF8String` are the new types and the other types are legacy. The
+/// legacy types are nonetheless supported, since they are still found in
+/// certificates issued by long-lived CAs.
 ///
 /// [RFC 5280 Section 4.2.1.4]: https://datatracker.ietf.org/doc/html/rfc5280#section-4.2.1.4
 #[derive(Clone, Debug, Eq, PartialEq, Choice)]
 #[allow(missing_docs)]
 pub enum DirectoryString<'a> {
+    #[asn1(type = "TeletexString")]
+    TeletexString(TeletexStringRef<'a>),
+
     #[asn1(type = "PrintableString")]
     PrintableString(PrintableStringRef<'a>),
 
+    #[asn1(type = "UniversalString")]
+    UniversalString(UniversalStringRef<'a>),
+
     #[asn1(type = "UTF8String")]
     Utf8String(Utf8StringRef<'a>),
+
+    #[asn1(type = "BMPString")]
+    BmpString(BmpStringRef<'a>),
+}
+
+impl<'a> TryFrom<AnyRef<'a>> for DirectoryString<'a> {
+    type Error = der::Error;
+
+    fn try_from(any: AnyRef<'a>) -> der::Result<Self> {
+        match any.tag() {
+            Tag::TeletexString => any.teletex_string().map(Self::TeletexString),
+            Tag::PrintableString => any.printable_string().map(Self::PrintableString),
+            Tag::UniversalString => any.universal_string().map(Self::UniversalString),
+            Tag::Utf8String => any.utf8_string().map(Self::Utf8String),
+            Tag::BmpString => any.bmp_string().map(Self::BmpString),
+            tag => Err(tag.unexpected_error(None)),
+        }
+    }
+}
+
+/// Displays the characters of the string, whatever its encoding.
+impl fmt::Display for DirectoryString<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DirectoryString::TeletexString(s) => s.fmt(f),
+            DirectoryString::PrintableString(s) => s.fmt(f),
+            DirectoryString::UniversalString(s) => s.fmt(f),
+            DirectoryString::Utf8String(s) => s.fmt(f),
+            DirectoryString::BmpString(s) => s.fmt(f),
+        }
+    }
 }
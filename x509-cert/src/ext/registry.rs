@@ -0,0 +1,219 @@
+//! Decoding extensions by OID
+//!
+//! [`TbsCertificate::get`] and [`TbsCertificate::filter`] require the type
+//! of the extension to be known up front. An [`ExtensionRegistry`] instead
+//! maps the `extnID` of each extension to a decoder, so that all of the
+//! extensions of a certificate or CRL can be decoded into
+//! [`ParsedExtension`] values in a single pass:
+//!
+//! ```
+//! use x509_cert::Certificate;
+//! use x509_cert::der::Decode;
+//! use x509_cert::ext::registry::{ExtensionRegistry, ParsedExtension};
+//!
+//! let cert = Certificate::from_der(include_bytes!("../../tests/examples/amazon.der")).unwrap();
+//! let registry = ExtensionRegistry::new();
+//!
+//! for (ext, parsed) in cert.tbs_certificate.parsed_extensions(&registry) {
+//!     match parsed {
+//!         Ok(ParsedExtension::SubjectAltName(san)) => assert!(!san.0.is_empty()),
+//!         Ok(_) => {}
+//!         Err(err) => panic!("invalid {} extension: {}", ext.extn_id, err),
+//!     }
+//! }
+//! ```
+//!
+//! The extensions defined in RFC 5280 are decoded by default. Applications
+//! can register decoders for their own extensions with
+//! [`ExtensionRegistry::register`], which yields [`ParsedExtension::Custom`]
+//! values, or with [`ExtensionRegistry::register_with`]. This includes the
+//! [`DepartmentIdentity`] extension, whose OID is in a private arc:
+//!
+//! ```
+//! use x509_cert::ext::dept::DepartmentIdentity;
+//! use x509_cert::ext::registry::ExtensionRegistry;
+//!
+//! let mut registry = ExtensionRegistry::new();
+//! registry.register::<DepartmentIdentity>();
+//! ```
+//!
+//! [`DepartmentIdentity`]: crate::ext::dept::DepartmentIdentity
+//! [`TbsCertificate::get`]: crate::TbsCertificate::get
+//! [`TbsCertificate::filter`]: crate::TbsCertificate::filter
+
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use core::any::Any;
+use core::fmt;
+
+use const_oid::{AssociatedOid, ObjectIdentifier};
+use der::{Decode, DecodeOwned};
+
+use crate::ext::pkix::{
+    AuthorityInfoAccessSyntax, AuthorityKeyIdentifier, BaseCrlNumber, BasicConstraints,
+    CertificateIssuer, CertificatePolicies, CrlDistributionPoints, CrlNumber, CrlReason,
+    ExtendedKeyUsage, FreshestCrl, InhibitAnyPolicy, IssuerAltName, IssuingDistributionPoint,
+    KeyUsage, NameConstraints, PolicyConstraints, PolicyMappings, PrivateKeyUsagePeriod,
+    SubjectAltName, SubjectDirectoryAttributes, SubjectInfoAccessSyntax, SubjectKeyIdentifier,
+};
+use crate::ext::Extension;
+
+/// Decoder for the `extnValue` of an extension.
+pub type DecodeFn = for<'a> fn(&'a [u8]) -> der::Result<ParsedExtension<'a>>;
+
+/// Value of an extension registered with [`ExtensionRegistry::register`].
+///
+/// This trait is implemented for all `'static` types implementing `Debug`.
+pub trait CustomExtension: Any + fmt::Debug {
+    /// Returns the value as [`Any`], for downcasting.
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<T: Any + fmt::Debug> CustomExtension for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+macro_rules! parsed_extension {
+    ($($variant:ident($ty:ty) => $doc:literal,)*) => {
+        /// A decoded extension value.
+        #[derive(Debug)]
+        #[non_exhaustive]
+        pub enum ParsedExtension<'a> {
+            $(
+                #[doc = $doc]
+                $variant($ty),
+            )*
+
+            /// Value decoded by a decoder registered with
+            /// [`ExtensionRegistry::register`].
+            Custom(Box<dyn CustomExtension>),
+
+            /// Raw `extnValue` of an extension without a decoder.
+            Unknown(&'a [u8]),
+        }
+
+        impl<'a> ParsedExtension<'a> {
+            /// Decodes an extension known to this crate.
+            fn decode_builtin(oid: ObjectIdentifier, bytes: &'a [u8]) -> Option<der::Result<Self>> {
+                $(
+                    if oid == <$ty as AssociatedOid>::OID {
+                        return Some(<$ty>::from_der(bytes).map(Self::$variant));
+                    }
+                )*
+
+                None
+            }
+
+            /// Returns whether `oid` identifies an extension known to this crate.
+            fn is_builtin(oid: &ObjectIdentifier) -> bool {
+                $(*oid == <$ty as AssociatedOid>::OID)||*
+            }
+        }
+    };
+}
+
+parsed_extension! {
+    AuthorityKeyIdentifier(AuthorityKeyIdentifier<'a>) => "Authority key identifier.",
+    SubjectKeyIdentifier(SubjectKeyIdentifier<'a>) => "Subject key identifier.",
+    KeyUsage(KeyUsage) => "Key usage.",
+    PrivateKeyUsagePeriod(PrivateKeyUsagePeriod) => "Private key usage period.",
+    CertificatePolicies(CertificatePolicies<'a>) => "Certificate policies.",
+    PolicyMappings(PolicyMappings) => "Policy mappings.",
+    SubjectAltName(SubjectAltName<'a>) => "Subject alternative name.",
+    IssuerAltName(IssuerAltName<'a>) => "Issuer alternative name.",
+    SubjectDirectoryAttributes(SubjectDirectoryAttributes<'a>) => "Subject directory attributes.",
+    BasicConstraints(BasicConstraints) => "Basic constraints.",
+    NameConstraints(NameConstraints<'a>) => "Name constraints.",
+    PolicyConstraints(PolicyConstraints) => "Policy constraints.",
+    ExtendedKeyUsage(ExtendedKeyUsage) => "Extended key usage.",
+    CrlDistributionPoints(CrlDistributionPoints<'a>) => "CRL distribution points.",
+    InhibitAnyPolicy(InhibitAnyPolicy) => "Inhibit anyPolicy.",
+    FreshestCrl(FreshestCrl<'a>) => "Freshest CRL.",
+    AuthorityInfoAccess(AuthorityInfoAccessSyntax<'a>) => "Authority information access.",
+    SubjectInfoAccess(SubjectInfoAccessSyntax<'a>) => "Subject information access.",
+    CrlNumber(CrlNumber<'a>) => "CRL number.",
+    DeltaCrlIndicator(BaseCrlNumber<'a>) => "Delta CRL indicator.",
+    IssuingDistributionPoint(IssuingDistributionPoint<'a>) => "Issuing distribution point.",
+    CrlReason(CrlReason) => "CRL entry reason code.",
+    CertificateIssuer(CertificateIssuer<'a>) => "CRL entry certificate issuer.",
+}
+
+impl ParsedExtension<'_> {
+    /// Returns a reference to the value of a [`ParsedExtension::Custom`]
+    /// extension, if it is of type `T`.
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        match self {
+            ParsedExtension::Custom(value) => (**value).as_any().downcast_ref(),
+            _ => None,
+        }
+    }
+}
+
+/// Registry of extension decoders.
+///
+/// Decoders registered by the application take precedence over the ones
+/// built into this crate.
+#[derive(Clone, Default)]
+pub struct ExtensionRegistry {
+    decoders: BTreeMap<ObjectIdentifier, DecodeFn>,
+}
+
+impl fmt::Debug for ExtensionRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExtensionRegistry")
+            .field("decoders", &self.decoders.keys())
+            .finish()
+    }
+}
+
+impl ExtensionRegistry {
+    /// Creates a registry decoding the extensions known to this crate.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `T` as the decoder for extensions with `T::OID`, yielding
+    /// [`ParsedExtension::Custom`] values.
+    pub fn register<T>(&mut self) -> &mut Self
+    where
+        T: AssociatedOid + CustomExtension + DecodeOwned,
+    {
+        self.register_with(T::OID, |bytes| {
+            Ok(ParsedExtension::Custom(Box::new(T::from_der(bytes)?)))
+        })
+    }
+
+    /// Registers `decoder` for extensions with `oid`.
+    pub fn register_with(&mut self, oid: ObjectIdentifier, decoder: DecodeFn) -> &mut Self {
+        self.decoders.insert(oid, decoder);
+        self
+    }
+
+    /// Returns whether extensions with `oid` are decoded.
+    pub fn contains(&self, oid: &ObjectIdentifier) -> bool {
+        self.decoders.contains_key(oid) || ParsedExtension::is_builtin(oid)
+    }
+
+    /// Decodes the value of `ext`.
+    ///
+    /// Returns [`ParsedExtension::Unknown`] if no decoder is registered for
+    /// the extension.
+    pub fn decode<'a>(&self, ext: &Extension<'a>) -> der::Result<ParsedExtension<'a>> {
+        match self.decoders.get(&ext.extn_id) {
+            Some(decoder) => decoder(ext.extn_value),
+            None => ParsedExtension::decode_builtin(ext.extn_id, ext.extn_value)
+                .unwrap_or(Ok(ParsedExtension::Unknown(ext.extn_value))),
+        }
+    }
+
+    /// Decodes each of `exts`, pairing every extension with its value or
+    /// decoding error.
+    pub fn decode_all<'a, 'b>(
+        &'b self,
+        exts: &'b [Extension<'a>],
+    ) -> impl 'b + Iterator<Item = (&'b Extension<'a>, der::Result<ParsedExtension<'a>>)> {
+        exts.iter().map(move |ext| (ext, self.decode(ext)))
+    }
+}
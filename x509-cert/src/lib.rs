@@ -21,14 +21,28 @@ extern crate std;
 #[macro_use]
 mod macros;
 
+#[cfg(feature = "serde")]
+mod serde_impls;
+
 pub mod anchor;
 pub mod attr;
+pub mod builder;
 pub mod certificate;
+pub mod chain;
+pub mod cms;
 pub mod crl;
 pub mod ext;
 pub mod name;
+pub mod ocsp;
+pub mod path;
+pub mod policy;
 pub mod request;
+pub mod revocation;
+pub mod store;
 pub mod time;
+pub mod verify;
 
-pub use certificate::{Certificate, PkiPath, TbsCertificate, Version};
+pub use certificate::{
+    Certificate, CertificateOwned, PkiPath, TbsCertificate, TbsCertificateOwned, Version,
+};
 pub use der;
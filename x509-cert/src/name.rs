@@ -1,9 +1,16 @@
 //! Name-related definitions as defined in X.501 (and updated by RFC 5280).
 
-use crate::attr::AttributeTypeAndValue;
+pub mod constraints;
+
+mod parse;
+mod prep;
+
+use crate::attr::{AttributeTypeAndValue, AttributeTypeAndValueOwned};
 use alloc::vec::Vec;
+use const_oid::ObjectIdentifier;
 use core::fmt;
 use der::{asn1::SetOfVec, Decode, Encode};
+use prep::CanonicalValue;
 
 /// X.501 Name as defined in [RFC 5280 Section 4.1.2.4]. X.501 Name is used to represent distinguished names.
 ///
@@ -14,6 +21,9 @@ use der::{asn1::SetOfVec, Decode, Encode};
 /// [RFC 5280 Section 4.1.2.4]: https://datatracker.ietf.org/doc/html/rfc5280#section-4.1.2.4
 pub type Name<'a> = RdnSequence<'a>;
 
+/// Owned form of [`Name`].
+pub type NameOwned = RdnSequenceOwned;
+
 /// X.501 RDNSequence as defined in [RFC 5280 Section 4.1.2.4].
 ///
 /// ```text
@@ -27,7 +37,9 @@ pub struct RdnSequence<'a>(pub Vec<RelativeDistinguishedName<'a>>);
 impl RdnSequence<'_> {
     /// Converts an RDNSequence string into an encoded RDNSequence
     ///
-    /// This function follows the rules in [RFC 4514].
+    /// This function follows the rules in [RFC 4514], but encodes all string
+    /// values as `UTF8String`. Parse an [`RdnSequenceOwned`] instead for
+    /// string types appropriate to each attribute.
     ///
     /// [RFC 4514]: https://datatracker.ietf.org/doc/html/rfc4514
     pub fn encode_from_string(s: &str) -> Result<Vec<u8>, der::Error> {
@@ -42,6 +54,25 @@ impl RdnSequence<'_> {
 
         RdnSequence(out).to_vec()
     }
+
+    /// Compares two names as described in [RFC 5280 Section 7.1].
+    ///
+    /// Names match if they have the same number of RDNs and each RDN
+    /// [matches](RelativeDistinguishedName::matches) the one at the same
+    /// position. Unlike `==`, which compares encodings, string values are
+    /// compared after the string preparation of [RFC 4518], so that e.g. a
+    /// `PrintableString` "Acme" matches a `UTF8String` "ACME ".
+    ///
+    /// [RFC 5280 Section 7.1]: https://datatracker.ietf.org/doc/html/rfc5280#section-7.1
+    /// [RFC 4518]: https://datatracker.ietf.org/doc/html/rfc4518
+    pub fn matches(&self, other: &RdnSequence<'_>) -> bool {
+        self.0.len() == other.0.len() && self.0.iter().zip(&other.0).all(|(a, b)| a.matches(b))
+    }
+
+    /// Returns the canonical form of this name.
+    pub fn canonical(&self) -> CanonicalName {
+        CanonicalName(self.0.iter().map(canonical_rdn).collect())
+    }
 }
 
 /// Serializes the structure according to the rules in [RFC 4514].
@@ -62,6 +93,73 @@ impl fmt::Display for RdnSequence<'_> {
 
 impl_newtype!(RdnSequence<'a>, Vec<RelativeDistinguishedName<'a>>);
 
+/// Owned form of [`RdnSequence`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RdnSequenceOwned(pub Vec<RelativeDistinguishedNameOwned>);
+
+impl RdnSequenceOwned {
+    /// Borrows this name as an [`RdnSequence`].
+    ///
+    /// This is not free: the attribute values are borrowed, but the vector
+    /// of RDNs and the `SET OF` of every RDN are allocated, as described in
+    /// [`RelativeDistinguishedNameOwned::as_ref`].
+    pub fn as_ref(&self) -> RdnSequence<'_> {
+        self.into()
+    }
+}
+
+impl From<&RdnSequence<'_>> for RdnSequenceOwned {
+    fn from(name: &RdnSequence<'_>) -> Self {
+        Self(name.0.iter().map(Into::into).collect())
+    }
+}
+
+impl<'a> From<&'a RdnSequenceOwned> for RdnSequence<'a> {
+    fn from(name: &'a RdnSequenceOwned) -> Self {
+        Self(name.0.iter().map(Into::into).collect())
+    }
+}
+
+impl RdnSequenceOwned {
+    /// Compares two names as described in [`RdnSequence::matches`].
+    pub fn matches(&self, other: &RdnSequence<'_>) -> bool {
+        self.as_ref().matches(other)
+    }
+
+    /// Returns the canonical form of this name.
+    pub fn canonical(&self) -> CanonicalName {
+        self.as_ref().canonical()
+    }
+}
+
+impl fmt::Display for RdnSequenceOwned {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl_newtype!(RdnSequenceOwned, Vec<RelativeDistinguishedNameOwned>);
+
+/// Canonical form of a [`Name`].
+///
+/// Names have equal canonical forms if and only if they
+/// [match](RdnSequence::matches), which makes the canonical form suitable
+/// as the key of a map of names. The order of attributes within
+/// multi-valued RDNs is insignificant.
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct CanonicalName(Vec<Vec<(ObjectIdentifier, CanonicalValue)>>);
+
+fn canonical_rdn(rdn: &RelativeDistinguishedName<'_>) -> Vec<(ObjectIdentifier, CanonicalValue)> {
+    let mut atvs: Vec<_> = rdn
+        .0
+        .iter()
+        .map(|atv| (atv.oid, CanonicalValue::from(&atv.value)))
+        .collect();
+
+    atvs.sort();
+    atvs
+}
+
 /// Find the indices of all non-escaped separators.
 fn find(s: &str, b: u8) -> impl '_ + Iterator<Item = usize> {
     (0..s.len())
@@ -91,6 +189,20 @@ fn split(s: &str, b: u8) -> impl '_ + Iterator<Item = &'_ str> {
     })
 }
 
+/// Map the elements of a `SET OF`, which preserves their DER ordering.
+fn map_set<'a, T, U>(set: &'a SetOfVec<T>, f: impl FnMut(&'a T) -> U) -> SetOfVec<U>
+where
+    T: der::DerOrd,
+    U: der::DerOrd,
+{
+    // Ensured to be valid and ordered by the source `SET OF`
+    set.iter()
+        .map(f)
+        .collect::<Vec<_>>()
+        .try_into()
+        .expect("invalid SET OF")
+}
+
 /// X.501 DistinguishedName as defined in [RFC 5280 Section 4.1.2.4].
 ///
 /// ```text
@@ -145,6 +257,17 @@ impl RelativeDistinguishedName<'_> {
 
         RelativeDistinguishedName(atvs.try_into()?).to_vec()
     }
+
+    /// Compares two RDNs as described in [RFC 5280 Section 7.1].
+    ///
+    /// RDNs match if they have the same number of attributes and each
+    /// attribute of one has the same type as, and a value matching, an
+    /// attribute of the other.
+    ///
+    /// [RFC 5280 Section 7.1]: https://datatracker.ietf.org/doc/html/rfc5280#section-7.1
+    pub fn matches(&self, other: &RelativeDistinguishedName<'_>) -> bool {
+        self.0.len() == other.0.len() && canonical_rdn(self) == canonical_rdn(other)
+    }
 }
 
 /// Serializes the structure according to the rules in [RFC 4514].
@@ -167,3 +290,41 @@ impl_newtype!(
     RelativeDistinguishedName<'a>,
     SetOfVec<AttributeTypeAndValue<'a>>
 );
+
+/// Owned form of [`RelativeDistinguishedName`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RelativeDistinguishedNameOwned(pub SetOfVec<AttributeTypeAndValueOwned>);
+
+impl RelativeDistinguishedNameOwned {
+    /// Borrows this RDN as a [`RelativeDistinguishedName`].
+    ///
+    /// The attribute values are borrowed, but the `SET OF` is allocated, and
+    /// its ordering is checked again by comparing adjacent attributes. The
+    /// attributes are never reordered.
+    pub fn as_ref(&self) -> RelativeDistinguishedName<'_> {
+        self.into()
+    }
+}
+
+impl From<&RelativeDistinguishedName<'_>> for RelativeDistinguishedNameOwned {
+    fn from(rdn: &RelativeDistinguishedName<'_>) -> Self {
+        Self(map_set(&rdn.0, Into::into))
+    }
+}
+
+impl<'a> From<&'a RelativeDistinguishedNameOwned> for RelativeDistinguishedName<'a> {
+    fn from(rdn: &'a RelativeDistinguishedNameOwned) -> Self {
+        Self(map_set(&rdn.0, Into::into))
+    }
+}
+
+impl fmt::Display for RelativeDistinguishedNameOwned {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl_newtype!(
+    RelativeDistinguishedNameOwned,
+    SetOfVec<AttributeTypeAndValueOwned>
+);
@@ -0,0 +1,340 @@
+//! Name constraints processing as described in [RFC 5280 Section 4.2.1.10].
+//!
+//! A [`NameConstraintsMatcher`] accumulates the `NameConstraints` of the
+//! certificates in a path, or of a trust anchor, and checks the subject name
+//! and subject alternative names of the following certificates against them.
+//! The forms supported are:
+//!
+//! - `directoryName`: the name must begin with the RDNs of the subtree,
+//!   which are compared as described in [`RdnSequence::matches`](super::RdnSequence::matches);
+//! - `dNSName`: the name must be the subtree or end with `.` followed by
+//!   the subtree, ignoring case;
+//! - `rfc822Name`: the subtree is a mailbox, a host, or a domain when it
+//!   begins with `.`. `emailAddress` attributes of the subject name are
+//!   checked as `rfc822Name`s when there are no subject alternative names,
+//!   and those whose value is not an ASCII string are never within a subtree;
+//! - `uniformResourceIdentifier`: the subtree is the host of the URI, or a
+//!   domain when it begins with `.`;
+//! - `iPAddress`: the subtree is an IPv4 or IPv6 address followed by a mask.
+//!   Addresses of another length are treated like names of unsupported forms.
+//!
+//! Names of other forms are never within a subtree of the same form: they
+//! are rejected as soon as such a subtree is permitted or excluded.
+//!
+//! ```
+//! use x509_cert::der::Decode;
+//! use x509_cert::ext::pkix::constraints::name::GeneralSubtree;
+//! use x509_cert::ext::pkix::name::GeneralName;
+//! use x509_cert::ext::pkix::NameConstraints;
+//! use x509_cert::name::constraints::{NameConstraintsMatcher, Violation};
+//! use x509_cert::name::Name;
+//!
+//! let base = Name::encode_from_string("C=US,O=Example").unwrap();
+//! let mut matcher = NameConstraintsMatcher::new();
+//! matcher.add(NameConstraints {
+//!     permitted_subtrees: Some(vec![GeneralSubtree {
+//!         base: GeneralName::DirectoryName(Name::from_der(&base).unwrap()),
+//!         minimum: 0,
+//!         maximum: None,
+//!     }]),
+//!     excluded_subtrees: None,
+//! });
+//!
+//! let subject = Name::encode_from_string("C=US,O=Example,CN=Jane").unwrap();
+//! assert!(matcher.check(&Name::from_der(&subject).unwrap(), None).is_ok());
+//!
+//! let subject = Name::encode_from_string("C=US,O=Other,CN=Jane").unwrap();
+//! assert!(matches!(
+//!     matcher.check(&Name::from_der(&subject).unwrap(), None),
+//!     Err(Violation::NotPermitted { .. })
+//! ));
+//! ```
+//!
+//! [RFC 5280 Section 4.2.1.10]: https://datatracker.ietf.org/doc/html/rfc5280#section-4.2.1.10
+
+use super::Name;
+use crate::ext::pkix::constraints::name::{GeneralSubtree, GeneralSubtrees};
+use crate::ext::pkix::name::{GeneralName, OtherName};
+use crate::ext::pkix::NameConstraints;
+
+use alloc::vec::Vec;
+use core::fmt;
+use core::mem::discriminant;
+
+use const_oid::db::rfc3280::EMAIL_ADDRESS;
+use der::asn1::Ia5StringRef;
+
+/// A name which is not permitted by the accumulated name constraints.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Violation<'a> {
+    /// The name is not within any of the subtrees of its form in the
+    /// `permittedSubtrees` of a `NameConstraints` extension.
+    NotPermitted {
+        /// The name which was checked.
+        name: GeneralName<'a>,
+
+        /// The permitted subtrees of the same form as the name.
+        permitted: GeneralSubtrees<'a>,
+    },
+
+    /// The name is within one of the `excludedSubtrees`.
+    Excluded {
+        /// The name which was checked.
+        name: GeneralName<'a>,
+
+        /// The excluded subtree containing the name.
+        excluded: GeneralSubtree<'a>,
+    },
+}
+
+impl fmt::Display for Violation<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::NotPermitted { .. } => f.write_str("name is not within permitted subtrees"),
+            Violation::Excluded { .. } => f.write_str("name is within an excluded subtree"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Violation<'_> {}
+
+/// The `permitted_subtrees` and `excluded_subtrees` state variables of
+/// [RFC 5280 Section 6.1.2].
+///
+/// Rather than computing the intersection of permitted subtrees eagerly,
+/// every `permittedSubtrees` added is kept and a name must be within each of
+/// them.
+///
+/// [RFC 5280 Section 6.1.2]: https://datatracker.ietf.org/doc/html/rfc5280#section-6.1.2
+#[derive(Clone, Debug, Default)]
+pub struct NameConstraintsMatcher<'a> {
+    permitted: Vec<GeneralSubtrees<'a>>,
+    excluded: Vec<GeneralSubtree<'a>>,
+}
+
+#[allow(clippy::result_large_err)]
+impl<'a> NameConstraintsMatcher<'a> {
+    /// Create a matcher which permits every name.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accumulate the subtrees of a `NameConstraints` extension.
+    pub fn add(&mut self, constraints: NameConstraints<'a>) {
+        if let Some(permitted) = constraints.permitted_subtrees {
+            self.permitted.push(permitted);
+        }
+
+        if let Some(excluded) = constraints.excluded_subtrees {
+            self.excluded.extend(excluded);
+        }
+    }
+
+    /// Check the subject name and the subject alternative names of a
+    /// certificate.
+    ///
+    /// An empty subject name is not checked. When there are no subject
+    /// alternative names, the `emailAddress` attributes of the subject name
+    /// are checked as `rfc822Name`s. An attribute whose value is not an
+    /// ASCII string is reported as an `otherName` of type `emailAddress`
+    /// which is not within any `rfc822Name` subtree.
+    pub fn check(
+        &self,
+        subject: &Name<'a>,
+        alt_names: Option<&[GeneralName<'a>]>,
+    ) -> Result<(), Violation<'a>> {
+        if !subject.0.is_empty() {
+            self.check_name(&GeneralName::DirectoryName(subject.clone()))?;
+        }
+
+        match alt_names {
+            Some(alt_names) => alt_names.iter().try_for_each(|name| self.check_name(name)),
+            None => subject
+                .0
+                .iter()
+                .flat_map(|rdn| rdn.0.iter())
+                .filter(|atv| atv.oid == EMAIL_ADDRESS)
+                .try_for_each(|atv| match Ia5StringRef::new(atv.value.value()) {
+                    Ok(email) => self.check_name(&GeneralName::Rfc822Name(email)),
+                    Err(_) => self.check_form(
+                        &GeneralName::OtherName(OtherName {
+                            type_id: EMAIL_ADDRESS,
+                            value: atv.value,
+                        }),
+                        |base| matches!(base, GeneralName::Rfc822Name(_)),
+                    ),
+                }),
+        }
+    }
+
+    /// Check a single name.
+    pub fn check_name(&self, name: &GeneralName<'a>) -> Result<(), Violation<'a>> {
+        self.check_form(name, |base| discriminant(base) == discriminant(name))
+    }
+
+    /// Check `name` against the subtrees whose base is of the same form
+    /// according to `is_same_form`.
+    fn check_form(
+        &self,
+        name: &GeneralName<'a>,
+        is_same_form: impl Fn(&GeneralName<'a>) -> bool,
+    ) -> Result<(), Violation<'a>> {
+        for subtrees in &self.permitted {
+            let same_form: GeneralSubtrees<'a> = subtrees
+                .iter()
+                .filter(|subtree| is_same_form(&subtree.base))
+                .cloned()
+                .collect();
+
+            if !same_form.is_empty()
+                && !same_form
+                    .iter()
+                    .any(|subtree| within(name, &subtree.base) == Some(true))
+            {
+                return Err(Violation::NotPermitted {
+                    name: name.clone(),
+                    permitted: same_form,
+                });
+            }
+        }
+
+        match self.excluded.iter().find(|subtree| {
+            is_same_form(&subtree.base) && within(name, &subtree.base) != Some(false)
+        }) {
+            Some(excluded) => Err(Violation::Excluded {
+                name: name.clone(),
+                excluded: excluded.clone(),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<'a> From<NameConstraints<'a>> for NameConstraintsMatcher<'a> {
+    fn from(constraints: NameConstraints<'a>) -> Self {
+        let mut matcher = Self::new();
+        matcher.add(constraints);
+        matcher
+    }
+}
+
+/// Is `name` within the subtree rooted at `base`?
+///
+/// Returns `None` for name forms which are not supported, which are
+/// therefore never within permitted subtrees and always within excluded
+/// subtrees.
+fn within(name: &GeneralName<'_>, base: &GeneralName<'_>) -> Option<bool> {
+    match (name, base) {
+        (GeneralName::DirectoryName(name), GeneralName::DirectoryName(base)) => Some(
+            name.0.len() >= base.0.len() && name.0.iter().zip(&base.0).all(|(a, b)| a.matches(b)),
+        ),
+        (GeneralName::DnsName(name), GeneralName::DnsName(base)) => {
+            Some(dns_within(name.as_str(), base.as_str()))
+        }
+        (GeneralName::Rfc822Name(name), GeneralName::Rfc822Name(base)) => {
+            Some(rfc822_within(name.as_str(), base.as_str()))
+        }
+        (
+            GeneralName::UniformResourceIdentifier(name),
+            GeneralName::UniformResourceIdentifier(base),
+        ) => uri_within(name.as_str(), base.as_str()),
+        (GeneralName::IpAddress(name), GeneralName::IpAddress(base)) => {
+            ip_within(name.as_bytes(), base.as_bytes())
+        }
+        _ => None,
+    }
+}
+
+/// Does `name` equal `base` or end with `.` followed by `base`?
+fn dns_within(name: &str, base: &str) -> bool {
+    if base.is_empty() {
+        return true;
+    }
+
+    let (name, base) = (name.as_bytes(), base.as_bytes());
+    if name.len() < base.len() {
+        return false;
+    }
+
+    let (prefix, suffix) = name.split_at(name.len() - base.len());
+    suffix.eq_ignore_ascii_case(base)
+        && (prefix.is_empty() || base[0] == b'.' || prefix.ends_with(b"."))
+}
+
+/// A mailbox, a particular host, or any host within a domain (leading `.`).
+///
+/// The local part of a mailbox is case-sensitive, unlike its host.
+fn rfc822_within(name: &str, base: &str) -> bool {
+    let (local, host) = match name.rsplit_once('@') {
+        Some(mailbox) => mailbox,
+        None => return false,
+    };
+
+    match base.rsplit_once('@') {
+        Some((base_local, base_host)) => {
+            local == base_local && host.eq_ignore_ascii_case(base_host)
+        }
+        None if base.starts_with('.') => dns_within(host, base),
+        None => host.eq_ignore_ascii_case(base),
+    }
+}
+
+/// A particular host, or any host within a domain (leading `.`).
+///
+/// Returns `None` for URIs without a host.
+fn uri_within(uri: &str, base: &str) -> Option<bool> {
+    let host = uri_host(uri)?;
+
+    Some(match base.starts_with('.') {
+        true => dns_within(host, base),
+        false => host.eq_ignore_ascii_case(base),
+    })
+}
+
+/// The host in the authority component of a URI, as described in
+/// [RFC 3986 Section 3.2].
+///
+/// [RFC 3986 Section 3.2]: https://datatracker.ietf.org/doc/html/rfc3986#section-3.2
+fn uri_host(uri: &str) -> Option<&str> {
+    let (_, rest) = uri.split_once(':')?;
+    let rest = rest.strip_prefix("//")?;
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let host_port = match authority.rsplit_once('@') {
+        Some((_, host_port)) => host_port,
+        None => authority,
+    };
+
+    let host = match host_port.strip_prefix('[') {
+        Some(literal) => &host_port[..literal.find(']')? + 2],
+        None => host_port.split(':').next().unwrap_or_default(),
+    };
+
+    match host.is_empty() {
+        true => None,
+        false => Some(host),
+    }
+}
+
+/// Is the address within the network given as an address followed by a mask?
+///
+/// Returns `None` if the address is neither an IPv4 nor an IPv6 address, so
+/// that it is rejected like names of unsupported forms.
+fn ip_within(addr: &[u8], network: &[u8]) -> Option<bool> {
+    if addr.len() != 4 && addr.len() != 16 {
+        return None;
+    }
+
+    if network.len() != addr.len() * 2 {
+        return Some(false);
+    }
+
+    let (base, mask) = network.split_at(addr.len());
+    Some(
+        addr.iter()
+            .zip(base)
+            .zip(mask)
+            .all(|((a, b), m)| a & m == b & m),
+    )
+}
@@ -0,0 +1,313 @@
+//! Parsing of the string representation of names described in [RFC 4514].
+//!
+//! [RFC 4514]: https://datatracker.ietf.org/doc/html/rfc4514
+
+use super::{RdnSequenceOwned, RelativeDistinguishedNameOwned};
+use crate::attr::AttributeTypeAndValueOwned;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::str::FromStr;
+
+use const_oid::db::{rfc3280, rfc4519, DB};
+use der::asn1::{Any, AnyRef, Ia5StringRef, ObjectIdentifier, PrintableStringRef, Utf8StringRef};
+use der::{Decode, Error, ErrorKind, Result, Tag};
+
+/// Parses a distinguished name according to [RFC 4514 Section 3].
+///
+/// Attribute types are either names from [`const_oid::db::DB`], compared
+/// case-insensitively, or OIDs in dotted-decimal form with an optional `OID.`
+/// prefix. Attribute values are either strings, possibly quoted or containing
+/// `\` escapes of special characters and of bytes as two hex digits, or the
+/// DER encoding of the value in hex prefixed with `#`. Spaces around the
+/// separators are ignored.
+///
+/// String values are encoded as `PrintableString` for `countryName` and
+/// `serialNumber`, as `IA5String` for `emailAddress` and `domainComponent`,
+/// and as `UTF8String` otherwise. The string representation of a name, as
+/// produced by its `Display` impl, therefore parses back to a name that
+/// [matches](super::RdnSequence::matches) the original, but which is not
+/// necessarily encoded the same way.
+///
+/// ```
+/// use x509_cert::name::NameOwned;
+///
+/// let name: NameOwned = "C=US,O=Example\\, Inc.,CN=J. Smith+UID=jsmith".parse().unwrap();
+/// assert_eq!(name.0.len(), 3);
+/// assert!(name.to_string().parse::<NameOwned>().unwrap().matches(&name.as_ref()));
+/// ```
+///
+/// [RFC 4514 Section 3]: https://datatracker.ietf.org/doc/html/rfc4514#section-3
+impl FromStr for RdnSequenceOwned {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut parser = Parser::new(s);
+        let mut rdns = Vec::new();
+
+        parser.skip_spaces();
+        if !parser.is_done() {
+            rdns.push(parser.rdn()?);
+            while parser.accept(b',') {
+                rdns.push(parser.rdn()?);
+            }
+        }
+
+        parser.finish()?;
+        Ok(Self(rdns))
+    }
+}
+
+/// Parses a relative distinguished name, i.e. attributes separated by `+`,
+/// as described for [`RdnSequenceOwned`].
+impl FromStr for RelativeDistinguishedNameOwned {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut parser = Parser::new(s);
+        let rdn = parser.rdn()?;
+        parser.finish()?;
+        Ok(rdn)
+    }
+}
+
+/// Parses an attribute in the `TYPE=VALUE` format, as described
+/// for [`RdnSequenceOwned`].
+impl FromStr for AttributeTypeAndValueOwned {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut parser = Parser::new(s);
+        let atv = parser.atv()?;
+        parser.finish()?;
+        Ok(atv)
+    }
+}
+
+/// Returns the string type used to encode the string values of `oid`.
+fn string_tag(oid: &ObjectIdentifier) -> Tag {
+    match *oid {
+        rfc4519::C | rfc4519::SERIAL_NUMBER => Tag::PrintableString,
+        rfc3280::EMAIL_ADDRESS | rfc4519::DC => Tag::Ia5String,
+        _ => Tag::Utf8String,
+    }
+}
+
+/// Recursive descent parser over the bytes of a string representation.
+///
+/// All separators and special characters are ASCII, so they never occur
+/// within the encoding of multi-byte characters.
+struct Parser<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(s: &'a str) -> Self {
+        Self {
+            bytes: s.as_bytes(),
+            pos: 0,
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<u8> {
+        let byte = self.peek()?;
+        self.pos += 1;
+        Some(byte)
+    }
+
+    fn is_done(&self) -> bool {
+        self.pos == self.bytes.len()
+    }
+
+    /// Consumes `byte` if it is next.
+    fn accept(&mut self, byte: u8) -> bool {
+        let accepted = self.peek() == Some(byte);
+        if accepted {
+            self.pos += 1;
+        }
+        accepted
+    }
+
+    fn skip_spaces(&mut self) {
+        while self.accept(b' ') {}
+    }
+
+    /// Ensures the input has been consumed entirely.
+    fn finish(&mut self) -> Result<()> {
+        self.skip_spaces();
+        match self.is_done() {
+            true => Ok(()),
+            false => Err(ErrorKind::Failed.into()),
+        }
+    }
+
+    fn rdn(&mut self) -> Result<RelativeDistinguishedNameOwned> {
+        let mut atvs = alloc::vec![self.atv()?];
+        while self.accept(b'+') {
+            atvs.push(self.atv()?);
+        }
+
+        Ok(RelativeDistinguishedNameOwned(atvs.try_into()?))
+    }
+
+    fn atv(&mut self) -> Result<AttributeTypeAndValueOwned> {
+        self.skip_spaces();
+        let oid = self.attribute_type()?;
+
+        self.skip_spaces();
+        if !self.accept(b'=') {
+            return Err(ErrorKind::Failed.into());
+        }
+
+        self.skip_spaces();
+        let value = match self.peek() {
+            Some(b'#') => {
+                self.pos += 1;
+                self.hex_value()?
+            }
+            Some(b'"') => {
+                self.pos += 1;
+                let value = self.quoted_value()?;
+                encode_string(&oid, value)?
+            }
+            _ => {
+                let value = self.string_value()?;
+                encode_string(&oid, value)?
+            }
+        };
+
+        self.skip_spaces();
+        Ok(AttributeTypeAndValueOwned { oid, value })
+    }
+
+    /// Parses a `descr` or `numericoid`.
+    fn attribute_type(&mut self) -> Result<ObjectIdentifier> {
+        let start = self.pos;
+        while let Some(b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'.') = self.peek() {
+            self.pos += 1;
+        }
+
+        // Only ASCII has been consumed
+        let key = core::str::from_utf8(&self.bytes[start..self.pos])
+            .map_err(|_| Error::from(ErrorKind::Failed))?;
+
+        let numeric = match key.get(..4) {
+            Some(prefix) if prefix.eq_ignore_ascii_case("oid.") => &key[4..],
+            _ => key,
+        };
+
+        match numeric.as_bytes().first() {
+            Some(b'0'..=b'9') => Ok(ObjectIdentifier::new(numeric)?),
+            Some(_) => DB
+                .by_name(key)
+                .copied()
+                .ok_or_else(|| ErrorKind::Failed.into()),
+            None => Err(ErrorKind::Failed.into()),
+        }
+    }
+
+    /// Parses the DER encoding of a value as pairs of hex digits.
+    fn hex_value(&mut self) -> Result<Any> {
+        let mut bytes = Vec::new();
+        while let Some(high) = self.peek().and_then(hex_digit) {
+            self.pos += 1;
+            let low = self
+                .next()
+                .and_then(hex_digit)
+                .ok_or_else(|| Error::from(ErrorKind::Failed))?;
+
+            bytes.push(high << 4 | low);
+        }
+
+        Ok(AnyRef::from_der(&bytes)?.into())
+    }
+
+    /// Parses a string up to the next unescaped separator, without its
+    /// unescaped leading and trailing spaces.
+    fn string_value(&mut self) -> Result<String> {
+        let mut bytes = Vec::new();
+        let mut significant = 0;
+
+        while let Some(byte) = self.peek() {
+            match byte {
+                b',' | b'+' => break,
+                b'\\' => {
+                    self.pos += 1;
+                    bytes.push(self.escaped()?);
+                    significant = bytes.len();
+                }
+                b' ' => {
+                    self.pos += 1;
+                    bytes.push(byte);
+                }
+                b'\0' | b'"' | b';' | b'<' | b'>' => return Err(ErrorKind::Failed.into()),
+                _ => {
+                    self.pos += 1;
+                    bytes.push(byte);
+                    significant = bytes.len();
+                }
+            }
+        }
+
+        bytes.truncate(significant);
+        String::from_utf8(bytes).map_err(|_| ErrorKind::Failed.into())
+    }
+
+    /// Parses the remainder of a string enclosed in double quotes, as
+    /// allowed by [RFC 2253].
+    ///
+    /// [RFC 2253]: https://datatracker.ietf.org/doc/html/rfc2253#section-3
+    fn quoted_value(&mut self) -> Result<String> {
+        let mut bytes = Vec::new();
+
+        loop {
+            match self.next() {
+                Some(b'"') => break,
+                Some(b'\\') => bytes.push(self.escaped()?),
+                Some(byte) => bytes.push(byte),
+                None => return Err(ErrorKind::Failed.into()),
+            }
+        }
+
+        String::from_utf8(bytes).map_err(|_| ErrorKind::Failed.into())
+    }
+
+    /// Parses the character or byte following a `\`.
+    fn escaped(&mut self) -> Result<u8> {
+        match self.next() {
+            Some(byte @ (b' ' | b'"' | b'#' | b'+' | b',' | b';' | b'<' | b'=' | b'>' | b'\\')) => {
+                Ok(byte)
+            }
+            Some(high) => match (hex_digit(high), self.next().and_then(hex_digit)) {
+                (Some(high), Some(low)) => Ok(high << 4 | low),
+                _ => Err(ErrorKind::Failed.into()),
+            },
+            None => Err(ErrorKind::Failed.into()),
+        }
+    }
+}
+
+fn hex_digit(byte: u8) -> Option<u8> {
+    match byte {
+        b'0'..=b'9' => Some(byte - b'0'),
+        b'a'..=b'f' => Some(byte - b'a' + 10),
+        b'A'..=b'F' => Some(byte - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// Encodes a string value with the string type of its attribute.
+fn encode_string(oid: &ObjectIdentifier, value: String) -> Result<Any> {
+    let value: AnyRef<'_> = match string_tag(oid) {
+        Tag::PrintableString => PrintableStringRef::new(&value)?.into(),
+        Tag::Ia5String => Ia5StringRef::new(&value)?.into(),
+        _ => Utf8StringRef::new(&value)?.into(),
+    };
+
+    Ok(value.into())
+}
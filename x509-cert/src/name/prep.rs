@@ -0,0 +1,101 @@
+//! String preparation for name comparison as described in [RFC 4518].
+//!
+//! [RFC 4518]: https://datatracker.ietf.org/doc/html/rfc4518
+
+use crate::ext::pkix::name::DirectoryString;
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use der::asn1::AnyRef;
+use der::{Encode, Tag, Tagged};
+use unicode_normalization::UnicodeNormalization;
+
+/// Attribute value in a form suitable for comparison.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub(crate) enum CanonicalValue {
+    /// A string prepared with [`prepare`].
+    String(String),
+
+    /// The DER encoding of a value which is not a string, or of a string
+    /// which cannot be prepared.
+    Der(Vec<u8>),
+}
+
+impl From<&AnyRef<'_>> for CanonicalValue {
+    fn from(value: &AnyRef<'_>) -> Self {
+        let bytes = value.value();
+        let prepared = match value.tag() {
+            Tag::Ia5String | Tag::VisibleString => {
+                core::str::from_utf8(bytes).ok().and_then(prepare)
+            }
+            Tag::NumericString => core::str::from_utf8(bytes)
+                .ok()
+                .map(|s| s.chars().filter(|c| *c != ' ').collect()),
+            _ => DirectoryString::try_from(*value)
+                .ok()
+                .and_then(|s| prepare(&s.to_string())),
+        };
+
+        match prepared {
+            Some(prepared) => CanonicalValue::String(prepared),
+            None => CanonicalValue::Der(value.to_vec().unwrap_or_default()),
+        }
+    }
+}
+
+/// Prepares `value` for the `caseIgnoreMatch` rule.
+///
+/// This performs the mapping with case folding, NFKC normalization,
+/// prohibition and insignificant space handling steps of [RFC 4518 Section 2].
+/// Returns `None` if `value` contains prohibited characters, in which case the
+/// comparison is undefined.
+///
+/// [RFC 4518 Section 2]: https://datatracker.ietf.org/doc/html/rfc4518#section-2
+pub(crate) fn prepare(value: &str) -> Option<String> {
+    let mut mapped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            // Mapped to nothing
+            '\u{00AD}'
+            | '\u{034F}'
+            | '\u{1806}'
+            | '\u{180B}'..='\u{180D}'
+            | '\u{200B}'
+            | '\u{FE00}'..='\u{FE0F}'
+            | '\u{FFFC}' => {}
+
+            // Mapped to SPACE
+            '\u{0009}'..='\u{000D}' | '\u{0085}' => mapped.push(' '),
+            c if c.is_whitespace() => mapped.push(' '),
+
+            // Other control and format characters are mapped to nothing
+            '\u{0000}'..='\u{001F}'
+            | '\u{007F}'..='\u{009F}'
+            | '\u{200C}'..='\u{200F}'
+            | '\u{202A}'..='\u{202E}'
+            | '\u{2060}'..='\u{2063}'
+            | '\u{FEFF}' => {}
+
+            // Prohibited: private use, non-characters and the replacement character
+            '\u{E000}'..='\u{F8FF}' | '\u{FDD0}'..='\u{FDEF}' | '\u{FFFD}'..='\u{FFFF}' => {
+                return None
+            }
+
+            c => mapped.extend(c.to_lowercase()),
+        }
+    }
+
+    let mut prepared = String::with_capacity(mapped.len());
+    for word in mapped.nfkc().collect::<String>().split(' ') {
+        if word.is_empty() {
+            continue;
+        }
+        if !prepared.is_empty() {
+            prepared.push(' ');
+        }
+        prepared.push_str(word);
+    }
+
+    Some(prepared)
+}
@@ -0,0 +1,480 @@
+//! Online Certificate Status Protocol (OCSP) types as defined in [RFC 6960].
+//!
+//! [RFC 6960]: https://datatracker.ietf.org/doc/html/rfc6960
+
+use crate::ext::pkix::name::GeneralName;
+use crate::ext::pkix::CrlReason;
+use crate::ext::Extensions;
+use crate::name::Name;
+use crate::Certificate;
+
+use alloc::vec::Vec;
+
+use const_oid::db::rfc6960::ID_PKIX_OCSP_BASIC;
+use der::asn1::{
+    BitStringRef, GeneralizedTime, Int, IntRef, Null, ObjectIdentifier, OctetString, OctetStringRef,
+};
+use der::{Choice, Decode, Encode, Enumerated, ErrorKind, Sequence};
+use spki::{AlgorithmIdentifier, AlgorithmIdentifierOwned};
+
+/// OCSP `Version` as defined in [RFC 6960 Section 4.1.1].
+///
+/// ```text
+/// Version ::= INTEGER { v1(0) }
+/// ```
+///
+/// [RFC 6960 Section 4.1.1]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.1.1
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Enumerated)]
+#[asn1(type = "INTEGER")]
+#[repr(u8)]
+pub enum Version {
+    /// Version 1 (default)
+    V1 = 0,
+}
+
+impl Default for Version {
+    fn default() -> Self {
+        Self::V1
+    }
+}
+
+/// Message digest used to compute the hashes of a [`CertId`].
+///
+/// This crate does not implement any digest algorithms itself.
+pub trait Digest {
+    /// The algorithm identifier of the digest, e.g. `id-sha256`.
+    fn algorithm(&self) -> AlgorithmIdentifier<'_>;
+
+    /// Compute the digest of `msg`.
+    fn digest(&self, msg: &[u8]) -> Vec<u8>;
+}
+
+/// `OCSPRequest` as defined in [RFC 6960 Section 4.1.1].
+///
+/// ```text
+/// OCSPRequest ::= SEQUENCE {
+///     tbsRequest                  TBSRequest,
+///     optionalSignature   [0]     EXPLICIT Signature OPTIONAL
+/// }
+/// ```
+///
+/// [RFC 6960 Section 4.1.1]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.1.1
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct OcspRequest<'a> {
+    pub tbs_request: TbsRequest<'a>,
+
+    #[asn1(context_specific = "0", tag_mode = "EXPLICIT", optional = "true")]
+    pub optional_signature: Option<Signature<'a>>,
+}
+
+/// `TBSRequest` as defined in [RFC 6960 Section 4.1.1].
+///
+/// ```text
+/// TBSRequest ::= SEQUENCE {
+///     version             [0]     EXPLICIT Version DEFAULT v1,
+///     requestorName       [1]     EXPLICIT GeneralName OPTIONAL,
+///     requestList                 SEQUENCE OF Request,
+///     requestExtensions   [2]     EXPLICIT Extensions OPTIONAL
+/// }
+/// ```
+///
+/// [RFC 6960 Section 4.1.1]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.1.1
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct TbsRequest<'a> {
+    #[asn1(context_specific = "0", default = "Default::default")]
+    pub version: Version,
+
+    #[asn1(context_specific = "1", tag_mode = "EXPLICIT", optional = "true")]
+    pub requestor_name: Option<GeneralName<'a>>,
+
+    pub request_list: Vec<Request<'a>>,
+
+    #[asn1(context_specific = "2", tag_mode = "EXPLICIT", optional = "true")]
+    pub request_extensions: Option<Extensions<'a>>,
+}
+
+/// `Signature` as defined in [RFC 6960 Section 4.1.1].
+///
+/// ```text
+/// Signature ::= SEQUENCE {
+///     signatureAlgorithm      AlgorithmIdentifier,
+///     signature               BIT STRING,
+///     certs               [0] EXPLICIT SEQUENCE OF Certificate OPTIONAL
+/// }
+/// ```
+///
+/// [RFC 6960 Section 4.1.1]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.1.1
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct Signature<'a> {
+    pub signature_algorithm: AlgorithmIdentifier<'a>,
+    pub signature: BitStringRef<'a>,
+
+    #[asn1(context_specific = "0", tag_mode = "EXPLICIT", optional = "true")]
+    pub certs: Option<Vec<Certificate<'a>>>,
+}
+
+/// `Request` as defined in [RFC 6960 Section 4.1.1].
+///
+/// ```text
+/// Request ::= SEQUENCE {
+///     reqCert                     CertID,
+///     singleRequestExtensions     [0] EXPLICIT Extensions OPTIONAL
+/// }
+/// ```
+///
+/// [RFC 6960 Section 4.1.1]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.1.1
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct Request<'a> {
+    pub req_cert: CertId<'a>,
+
+    #[asn1(context_specific = "0", tag_mode = "EXPLICIT", optional = "true")]
+    pub single_request_extensions: Option<Extensions<'a>>,
+}
+
+/// `CertID` as defined in [RFC 6960 Section 4.1.1].
+///
+/// ```text
+/// CertID ::= SEQUENCE {
+///     hashAlgorithm           AlgorithmIdentifier,
+///     issuerNameHash          OCTET STRING, -- Hash of issuer's DN
+///     issuerKeyHash           OCTET STRING, -- Hash of issuer's public key
+///     serialNumber            CertificateSerialNumber
+/// }
+/// ```
+///
+/// [RFC 6960 Section 4.1.1]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.1.1
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct CertId<'a> {
+    pub hash_algorithm: AlgorithmIdentifier<'a>,
+    pub issuer_name_hash: OctetStringRef<'a>,
+    pub issuer_key_hash: OctetStringRef<'a>,
+    pub serial_number: IntRef<'a>,
+}
+
+impl<'a> CertId<'a> {
+    /// Does this `CertID` identify `cert`, which was issued by `issuer`?
+    ///
+    /// Returns `Ok(false)` if the hash algorithm of this `CertID` is not the
+    /// algorithm of `digest`.
+    pub fn matches<D: Digest>(
+        &self,
+        cert: &Certificate<'_>,
+        issuer: &Certificate<'_>,
+        digest: &D,
+    ) -> der::Result<bool> {
+        if self.hash_algorithm.oid != digest.algorithm().oid
+            || self.serial_number != cert.tbs_certificate.serial_number
+        {
+            return Ok(false);
+        }
+
+        Ok(
+            self.issuer_name_hash.as_bytes() == issuer_name_hash(cert, digest)?
+                && self.issuer_key_hash.as_bytes() == issuer_key_hash(issuer, digest),
+        )
+    }
+}
+
+/// Owned form of [`CertId`].
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct CertIdOwned {
+    pub hash_algorithm: AlgorithmIdentifierOwned,
+    pub issuer_name_hash: OctetString,
+    pub issuer_key_hash: OctetString,
+    pub serial_number: Int,
+}
+
+impl CertIdOwned {
+    /// Computes the `CertID` of `cert`, which was issued by `issuer`.
+    ///
+    /// The issuer name hash is computed over the DER encoding of the issuer
+    /// field of `cert`, and the issuer key hash over the value of the
+    /// `subjectPublicKey` BIT STRING of `issuer`, excluding the unused bits.
+    pub fn from_certificate<D: Digest>(
+        cert: &Certificate<'_>,
+        issuer: &Certificate<'_>,
+        digest: &D,
+    ) -> der::Result<Self> {
+        Ok(Self {
+            hash_algorithm: (&digest.algorithm()).into(),
+            issuer_name_hash: OctetString::new(issuer_name_hash(cert, digest)?)?,
+            issuer_key_hash: OctetString::new(issuer_key_hash(issuer, digest))?,
+            serial_number: cert.tbs_certificate.serial_number.into(),
+        })
+    }
+
+    /// Borrows this `CertID` as a [`CertId`].
+    pub fn as_ref(&self) -> CertId<'_> {
+        self.into()
+    }
+}
+
+impl From<&CertId<'_>> for CertIdOwned {
+    fn from(cert_id: &CertId<'_>) -> Self {
+        Self {
+            hash_algorithm: (&cert_id.hash_algorithm).into(),
+            issuer_name_hash: cert_id.issuer_name_hash.into(),
+            issuer_key_hash: cert_id.issuer_key_hash.into(),
+            serial_number: cert_id.serial_number.into(),
+        }
+    }
+}
+
+impl<'a> From<&'a CertIdOwned> for CertId<'a> {
+    fn from(cert_id: &'a CertIdOwned) -> Self {
+        Self {
+            hash_algorithm: (&cert_id.hash_algorithm).into(),
+            issuer_name_hash: (&cert_id.issuer_name_hash).into(),
+            issuer_key_hash: (&cert_id.issuer_key_hash).into(),
+            serial_number: (&cert_id.serial_number).into(),
+        }
+    }
+}
+
+/// Hash of the issuer name of `cert`.
+fn issuer_name_hash<D: Digest>(cert: &Certificate<'_>, digest: &D) -> der::Result<Vec<u8>> {
+    Ok(digest.digest(&cert.tbs_certificate.issuer.to_vec()?))
+}
+
+/// Hash of the public key of `issuer`.
+fn issuer_key_hash<D: Digest>(issuer: &Certificate<'_>, digest: &D) -> Vec<u8> {
+    digest.digest(
+        issuer
+            .tbs_certificate
+            .subject_public_key_info
+            .subject_public_key,
+    )
+}
+
+/// `OCSPResponse` as defined in [RFC 6960 Section 4.2.1].
+///
+/// ```text
+/// OCSPResponse ::= SEQUENCE {
+///     responseStatus         OCSPResponseStatus,
+///     responseBytes          [0] EXPLICIT ResponseBytes OPTIONAL
+/// }
+/// ```
+///
+/// [RFC 6960 Section 4.2.1]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.2.1
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct OcspResponse<'a> {
+    pub response_status: OcspResponseStatus,
+
+    #[asn1(context_specific = "0", tag_mode = "EXPLICIT", optional = "true")]
+    pub response_bytes: Option<ResponseBytes<'a>>,
+}
+
+impl<'a> OcspResponse<'a> {
+    /// Decodes the `BasicOCSPResponse` carried in the response bytes.
+    ///
+    /// Returns `Ok(None)` if there are no response bytes, and an error if
+    /// the response type is not `id-pkix-ocsp-basic`.
+    pub fn basic(&self) -> der::Result<Option<BasicOcspResponse<'a>>> {
+        match &self.response_bytes {
+            None => Ok(None),
+            Some(bytes) if bytes.response_type == ID_PKIX_OCSP_BASIC => {
+                BasicOcspResponse::from_der(bytes.response.as_bytes()).map(Some)
+            }
+            Some(bytes) => Err(ErrorKind::OidUnknown {
+                oid: bytes.response_type,
+            }
+            .into()),
+        }
+    }
+}
+
+/// `OCSPResponseStatus` as defined in [RFC 6960 Section 4.2.1].
+///
+/// ```text
+/// OCSPResponseStatus ::= ENUMERATED {
+///     successful            (0),  -- Response has valid confirmations
+///     malformedRequest      (1),  -- Illegal confirmation request
+///     internalError         (2),  -- Internal error in issuer
+///     tryLater              (3),  -- Try again later
+///                                 -- (4) is not used
+///     sigRequired           (5),  -- Must sign the request
+///     unauthorized          (6)   -- Request unauthorized
+/// }
+/// ```
+///
+/// [RFC 6960 Section 4.2.1]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.2.1
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Enumerated)]
+#[allow(missing_docs)]
+#[repr(u32)]
+pub enum OcspResponseStatus {
+    Successful = 0,
+    MalformedRequest = 1,
+    InternalError = 2,
+    TryLater = 3,
+    SigRequired = 5,
+    Unauthorized = 6,
+}
+
+/// `ResponseBytes` as defined in [RFC 6960 Section 4.2.1].
+///
+/// ```text
+/// ResponseBytes ::= SEQUENCE {
+///     responseType   OBJECT IDENTIFIER,
+///     response       OCTET STRING
+/// }
+/// ```
+///
+/// [RFC 6960 Section 4.2.1]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.2.1
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct ResponseBytes<'a> {
+    pub response_type: ObjectIdentifier,
+    pub response: OctetStringRef<'a>,
+}
+
+/// `BasicOCSPResponse` as defined in [RFC 6960 Section 4.2.1].
+///
+/// ```text
+/// BasicOCSPResponse ::= SEQUENCE {
+///     tbsResponseData      ResponseData,
+///     signatureAlgorithm   AlgorithmIdentifier,
+///     signature            BIT STRING,
+///     certs            [0] EXPLICIT SEQUENCE OF Certificate OPTIONAL
+/// }
+/// ```
+///
+/// [RFC 6960 Section 4.2.1]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.2.1
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct BasicOcspResponse<'a> {
+    pub tbs_response_data: ResponseData<'a>,
+    pub signature_algorithm: AlgorithmIdentifier<'a>,
+    pub signature: BitStringRef<'a>,
+
+    #[asn1(context_specific = "0", tag_mode = "EXPLICIT", optional = "true")]
+    pub certs: Option<Vec<Certificate<'a>>>,
+}
+
+/// `ResponseData` as defined in [RFC 6960 Section 4.2.1].
+///
+/// ```text
+/// ResponseData ::= SEQUENCE {
+///     version              [0] EXPLICIT Version DEFAULT v1,
+///     responderID              ResponderID,
+///     producedAt               GeneralizedTime,
+///     responses                SEQUENCE OF SingleResponse,
+///     responseExtensions   [1] EXPLICIT Extensions OPTIONAL
+/// }
+/// ```
+///
+/// [RFC 6960 Section 4.2.1]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.2.1
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct ResponseData<'a> {
+    #[asn1(context_specific = "0", default = "Default::default")]
+    pub version: Version,
+
+    pub responder_id: ResponderId<'a>,
+    pub produced_at: GeneralizedTime,
+    pub responses: Vec<SingleResponse<'a>>,
+
+    #[asn1(context_specific = "1", tag_mode = "EXPLICIT", optional = "true")]
+    pub response_extensions: Option<Extensions<'a>>,
+}
+
+/// `ResponderID` as defined in [RFC 6960 Section 4.2.1].
+///
+/// ```text
+/// ResponderID ::= CHOICE {
+///     byName   [1] Name,
+///     byKey    [2] KeyHash
+/// }
+///
+/// KeyHash ::= OCTET STRING -- SHA-1 hash of responder's public key
+///                          -- (excluding the tag and length fields)
+/// ```
+///
+/// [RFC 6960 Section 4.2.1]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.2.1
+#[derive(Clone, Debug, Eq, PartialEq, Choice)]
+#[allow(missing_docs)]
+pub enum ResponderId<'a> {
+    #[asn1(context_specific = "1", tag_mode = "EXPLICIT", constructed = "true")]
+    ByName(Name<'a>),
+
+    #[asn1(context_specific = "2", tag_mode = "EXPLICIT", constructed = "true")]
+    ByKey(OctetStringRef<'a>),
+}
+
+/// `SingleResponse` as defined in [RFC 6960 Section 4.2.1].
+///
+/// ```text
+/// SingleResponse ::= SEQUENCE {
+///     certID                       CertID,
+///     certStatus                   CertStatus,
+///     thisUpdate                   GeneralizedTime,
+///     nextUpdate         [0]       EXPLICIT GeneralizedTime OPTIONAL,
+///     singleExtensions   [1]       EXPLICIT Extensions OPTIONAL
+/// }
+/// ```
+///
+/// [RFC 6960 Section 4.2.1]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.2.1
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct SingleResponse<'a> {
+    pub cert_id: CertId<'a>,
+    pub cert_status: CertStatus,
+    pub this_update: GeneralizedTime,
+
+    #[asn1(context_specific = "0", tag_mode = "EXPLICIT", optional = "true")]
+    pub next_update: Option<GeneralizedTime>,
+
+    #[asn1(context_specific = "1", tag_mode = "EXPLICIT", optional = "true")]
+    pub single_extensions: Option<Extensions<'a>>,
+}
+
+/// `CertStatus` as defined in [RFC 6960 Section 4.2.1].
+///
+/// ```text
+/// CertStatus ::= CHOICE {
+///     good        [0]     IMPLICIT NULL,
+///     revoked     [1]     IMPLICIT RevokedInfo,
+///     unknown     [2]     IMPLICIT UnknownInfo
+/// }
+///
+/// UnknownInfo ::= NULL
+/// ```
+///
+/// [RFC 6960 Section 4.2.1]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.2.1
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Choice)]
+#[allow(missing_docs)]
+pub enum CertStatus {
+    #[asn1(context_specific = "0", tag_mode = "IMPLICIT")]
+    Good(Null),
+
+    #[asn1(context_specific = "1", tag_mode = "IMPLICIT", constructed = "true")]
+    Revoked(RevokedInfo),
+
+    #[asn1(context_specific = "2", tag_mode = "IMPLICIT")]
+    Unknown(Null),
+}
+
+/// `RevokedInfo` as defined in [RFC 6960 Section 4.2.1].
+///
+/// ```text
+/// RevokedInfo ::= SEQUENCE {
+///     revocationTime              GeneralizedTime,
+///     revocationReason    [0]     EXPLICIT CRLReason OPTIONAL
+/// }
+/// ```
+///
+/// [RFC 6960 Section 4.2.1]: https://datatracker.ietf.org/doc/html/rfc6960#section-4.2.1
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct RevokedInfo {
+    pub revocation_time: GeneralizedTime,
+
+    #[asn1(context_specific = "0", tag_mode = "EXPLICIT", optional = "true")]
+    pub revocation_reason: Option<CrlReason>,
+}
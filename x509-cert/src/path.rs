@@ -0,0 +1,525 @@
+//! Certification path validation as defined in [RFC 5280 Section 6].
+//!
+//! The entry points are [`validate`], which discovers a path from a target
+//! certificate to one of the supplied trust anchors using name chaining, and
+//! [`validate_path`], which runs the basic path validation algorithm over a
+//! path which has already been constructed.
+//!
+//! Certificates are passed as [`SignedCertificate`]s, so that their
+//! signatures are verified over the original encoding of their
+//! `tbsCertificate` with the supplied [`SignatureVerifier`]. Revocation
+//! checking (step (a)(3) of [RFC 5280 Section 6.1.3]) is not performed by
+//! this module.
+//!
+//! [RFC 5280 Section 6]: https://datatracker.ietf.org/doc/html/rfc5280#section-6
+//! [RFC 5280 Section 6.1.3]: https://datatracker.ietf.org/doc/html/rfc5280#section-6.1.3
+
+use crate::anchor::{CertPolicies, TrustAnchorInfo};
+use crate::ext::pkix::{
+    AuthorityKeyIdentifier, BasicConstraints, KeyUsage, KeyUsages, NameConstraints, SubjectAltName,
+    SubjectKeyIdentifier,
+};
+use crate::name::constraints::{NameConstraintsMatcher, Violation};
+use crate::name::Name;
+use crate::policy::{self, PolicyParams, PolicyProcessor};
+use crate::verify::{self, SignatureVerifier, SignedCertificate};
+use crate::{chain, TbsCertificate};
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt;
+
+use const_oid::db::rfc5280::{
+    ANY_POLICY, ID_CE_AUTHORITY_KEY_IDENTIFIER, ID_CE_BASIC_CONSTRAINTS,
+    ID_CE_CERTIFICATE_POLICIES, ID_CE_INHIBIT_ANY_POLICY, ID_CE_KEY_USAGE, ID_CE_NAME_CONSTRAINTS,
+    ID_CE_POLICY_CONSTRAINTS, ID_CE_POLICY_MAPPINGS, ID_CE_SUBJECT_ALT_NAME,
+    ID_CE_SUBJECT_KEY_IDENTIFIER,
+};
+use const_oid::ObjectIdentifier;
+use der::DateTime;
+use spki::SubjectPublicKeyInfo;
+
+/// Result type with the `path` module's [`Error`] type.
+pub type Result<'a, T> = core::result::Result<T, Error<'a>>;
+
+/// Extensions processed by the path validation algorithm.
+///
+/// Critical extensions which are neither in this list nor in
+/// [`ValidationParams::known_extensions`] cause validation to fail.
+const PROCESSED_EXTENSIONS: &[ObjectIdentifier] = &[
+    ID_CE_AUTHORITY_KEY_IDENTIFIER,
+    ID_CE_BASIC_CONSTRAINTS,
+    ID_CE_CERTIFICATE_POLICIES,
+    ID_CE_INHIBIT_ANY_POLICY,
+    ID_CE_KEY_USAGE,
+    ID_CE_NAME_CONSTRAINTS,
+    ID_CE_POLICY_CONSTRAINTS,
+    ID_CE_POLICY_MAPPINGS,
+    ID_CE_SUBJECT_ALT_NAME,
+    ID_CE_SUBJECT_KEY_IDENTIFIER,
+];
+
+/// Path validation errors.
+///
+/// Errors which relate to a particular certificate carry its `index` in the
+/// path, where `0` is the certificate issued by the trust anchor.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Error<'a> {
+    /// ASN.1 DER-related errors, e.g. a malformed extension.
+    Asn1(der::Error),
+
+    /// A CA certificate is missing `BasicConstraints` or does not assert `cA`.
+    NotCa {
+        /// Position of the certificate in the path.
+        index: usize,
+    },
+
+    /// A CA certificate's `KeyUsage` does not include `keyCertSign`.
+    KeyCertSignNotAsserted {
+        /// Position of the certificate in the path.
+        index: usize,
+    },
+
+    /// The certificate is not valid after its `notAfter` time.
+    Expired {
+        /// Position of the certificate in the path.
+        index: usize,
+    },
+
+    /// The certificate is not valid before its `notBefore` time.
+    NotYetValid {
+        /// Position of the certificate in the path.
+        index: usize,
+    },
+
+    /// The signature of a certificate could not be verified with the public
+    /// key of its issuer.
+    Signature {
+        /// Position of the certificate in the path.
+        index: usize,
+
+        /// The reason verification failed.
+        error: verify::Error,
+    },
+
+    /// The issuer name does not match the subject name of the previous
+    /// certificate (or the trust anchor name).
+    NameChaining {
+        /// Position of the certificate in the path.
+        index: usize,
+    },
+
+    /// A subject name is not within the permitted subtrees, or is within
+    /// the excluded subtrees, of the accumulated `NameConstraints`.
+    NameConstraints {
+        /// Position of the certificate in the path.
+        index: usize,
+
+        /// The offending name and the subtrees it violates.
+        violation: Box<Violation<'a>>,
+    },
+
+    /// The path is longer than permitted by a `pathLenConstraint`.
+    PathLength {
+        /// Position of the certificate in the path.
+        index: usize,
+    },
+
+    /// No path to a trust anchor could be constructed.
+    PathNotFound,
+
+    /// An explicit policy is required but the `valid_policy_tree` is empty.
+    PolicyRequired {
+        /// Position of the certificate in the path.
+        index: usize,
+    },
+
+    /// A `PolicyMappings` extension maps to or from `anyPolicy`.
+    PolicyMapping {
+        /// Position of the certificate in the path.
+        index: usize,
+    },
+
+    /// A critical extension was not recognized.
+    UnknownCriticalExtension {
+        /// Position of the certificate in the path.
+        index: usize,
+
+        /// Extension identifier.
+        oid: ObjectIdentifier,
+    },
+}
+
+impl fmt::Display for Error<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Asn1(err) => write!(f, "ASN.1 error: {}", err),
+            Error::NotCa { index } => write!(f, "certificate {} is not a CA", index),
+            Error::KeyCertSignNotAsserted { index } => {
+                write!(f, "certificate {} does not assert keyCertSign", index)
+            }
+            Error::Expired { index } => write!(f, "certificate {} has expired", index),
+            Error::NotYetValid { index } => write!(f, "certificate {} is not yet valid", index),
+            Error::Signature { index, error } => {
+                write!(
+                    f,
+                    "certificate {} has an invalid signature: {}",
+                    index, error
+                )
+            }
+            Error::NameChaining { index } => {
+                write!(f, "certificate {} issuer does not chain", index)
+            }
+            Error::NameConstraints { index, violation } => {
+                write!(
+                    f,
+                    "certificate {} violates name constraints: {}",
+                    index, violation
+                )
+            }
+            Error::PathLength { index } => {
+                write!(f, "certificate {} exceeds the maximum path length", index)
+            }
+            Error::PathNotFound => f.write_str("no path to a trust anchor"),
+            Error::PolicyRequired { index } => {
+                write!(f, "certificate {} has no acceptable policy", index)
+            }
+            Error::PolicyMapping { index } => {
+                write!(f, "certificate {} maps anyPolicy", index)
+            }
+            Error::UnknownCriticalExtension { index, oid } => {
+                write!(
+                    f,
+                    "certificate {} has unknown critical extension {}",
+                    index, oid
+                )
+            }
+        }
+    }
+}
+
+impl From<der::Error> for Error<'_> {
+    fn from(err: der::Error) -> Self {
+        Error::Asn1(err)
+    }
+}
+
+impl From<policy::Error> for Error<'_> {
+    fn from(err: policy::Error) -> Self {
+        match err {
+            policy::Error::Asn1(err) => Error::Asn1(err),
+            policy::Error::PolicyRequired { index } => Error::PolicyRequired { index },
+            policy::Error::PolicyMapping { index } => Error::PolicyMapping { index },
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error<'_> {}
+
+/// Inputs to the path validation algorithm as described in
+/// [RFC 5280 Section 6.1.1].
+///
+/// [RFC 5280 Section 6.1.1]: https://datatracker.ietf.org/doc/html/rfc5280#section-6.1.1
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ValidationParams {
+    /// The time at which the validity of the path is determined.
+    pub time: DateTime,
+
+    /// Policies acceptable to the certificate user.
+    pub user_initial_policy_set: Vec<ObjectIdentifier>,
+
+    /// Whether policy mapping is forbidden in the path.
+    pub initial_policy_mapping_inhibit: bool,
+
+    /// Whether the path must be valid for at least one policy in the
+    /// `user_initial_policy_set`.
+    pub initial_explicit_policy: bool,
+
+    /// Whether the `anyPolicy` OID is ignored when included in a certificate.
+    pub initial_any_policy_inhibit: bool,
+
+    /// Extensions processed by the application which may be marked critical.
+    pub known_extensions: Vec<ObjectIdentifier>,
+}
+
+impl ValidationParams {
+    /// Create new parameters validating at `time` with the defaults of
+    /// [RFC 5280 Section 6.1.1]: any policy is acceptable and no policy
+    /// related input is inhibited.
+    ///
+    /// [RFC 5280 Section 6.1.1]: https://datatracker.ietf.org/doc/html/rfc5280#section-6.1.1
+    pub fn new(time: DateTime) -> Self {
+        Self {
+            time,
+            user_initial_policy_set: alloc::vec![ANY_POLICY],
+            initial_policy_mapping_inhibit: false,
+            initial_explicit_policy: false,
+            initial_any_policy_inhibit: false,
+            known_extensions: Vec::new(),
+        }
+    }
+}
+
+/// Outputs of a successful run of the path validation algorithm.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ValidPath<'a> {
+    /// The trust anchor the path starts from.
+    pub anchor: &'a TrustAnchorInfo<'a>,
+
+    /// Certificates in the path, starting with the one issued by the trust
+    /// anchor and ending with the target certificate.
+    pub certificates: Vec<&'a SignedCertificate<'a>>,
+
+    /// Policies for which the path is valid, i.e. the `valid_policy` of every
+    /// leaf of the `valid_policy_tree`. Empty if the tree is NULL.
+    pub policies: Vec<ObjectIdentifier>,
+
+    /// The subject public key of the target certificate.
+    pub public_key: SubjectPublicKeyInfo<'a>,
+}
+
+/// Find and validate a path from `target` to one of `anchors`.
+///
+/// Candidate paths are built as described in the [`chain`] module, by
+/// chaining the issuer name of each certificate to the subject name of an
+/// intermediate (or the name of a trust anchor), skipping candidates whose
+/// `SubjectKeyIdentifier` contradicts the `AuthorityKeyIdentifier`. The first
+/// candidate which validates, including the signatures of its certificates,
+/// is returned; if none does, the error for the last candidate is returned.
+pub fn validate<'a, V>(
+    target: &'a SignedCertificate<'a>,
+    intermediates: &'a [SignedCertificate<'a>],
+    anchors: &'a [TrustAnchorInfo<'a>],
+    params: &ValidationParams,
+    verifier: &V,
+) -> Result<'a, ValidPath<'a>>
+where
+    V: SignatureVerifier + ?Sized,
+{
+    let find_issuers = |cert: &&'a SignedCertificate<'a>| {
+        intermediates
+            .iter()
+            .filter(|candidate| {
+                candidate.tbs.subject.matches(&cert.tbs.issuer)
+                    && key_ids_match(&candidate.tbs, &cert.tbs)
+            })
+            .collect()
+    };
+
+    let mut result = Err(Error::PathNotFound);
+    chain::search(target, anchors, find_issuers, |anchor, candidate| {
+        let path: Vec<_> = candidate.iter().rev().copied().collect();
+        result = validate_path(&path, anchor, params, verifier);
+        result.is_err()
+    });
+
+    result
+}
+
+/// Could `issuer` have issued `tbs` according to their key identifiers?
+fn key_ids_match(issuer: &TbsCertificate<'_>, tbs: &TbsCertificate<'_>) -> bool {
+    let skid = issuer.get::<SubjectKeyIdentifier<'_>>().ok().flatten();
+
+    let akid = tbs
+        .get::<AuthorityKeyIdentifier<'_>>()
+        .ok()
+        .flatten()
+        .and_then(|(_, akid)| akid.key_identifier);
+
+    match (skid, akid) {
+        (Some((_, skid)), Some(akid)) => skid.0 == akid,
+        _ => true,
+    }
+}
+
+fn anchor_name<'a>(anchor: &'a TrustAnchorInfo<'a>) -> Option<&'a Name<'a>> {
+    anchor.cert_path.as_ref().map(|cp| &cp.ta_name)
+}
+
+/// Run the basic path validation algorithm of [RFC 5280 Section 6.1].
+///
+/// `path` is ordered like a [`PkiPath`][`crate::PkiPath`]: the first
+/// certificate is issued by `anchor` and the last one is the target.
+///
+/// The signature of each certificate is verified with `verifier`, using the
+/// public key of the trust anchor for the first certificate and the subject
+/// public key of the previous certificate for the others. Certification path
+/// controls carried by the trust anchor are applied as described in
+/// [RFC 5937].
+///
+/// [RFC 5280 Section 6.1]: https://datatracker.ietf.org/doc/html/rfc5280#section-6.1
+/// [RFC 5937]: https://datatracker.ietf.org/doc/html/rfc5937
+pub fn validate_path<'a, V>(
+    path: &[&'a SignedCertificate<'a>],
+    anchor: &'a TrustAnchorInfo<'a>,
+    params: &ValidationParams,
+    verifier: &V,
+) -> Result<'a, ValidPath<'a>>
+where
+    V: SignatureVerifier + ?Sized,
+{
+    let n = path.len();
+    if n == 0 {
+        return Err(Error::PathNotFound);
+    }
+
+    let controls = anchor.cert_path.as_ref();
+    let flags = controls.and_then(|cp| cp.policy_flags).unwrap_or_default();
+
+    let mut policy_params = PolicyParams {
+        user_initial_policy_set: params.user_initial_policy_set.clone(),
+        initial_policy_mapping_inhibit: params.initial_policy_mapping_inhibit
+            || flags.contains(CertPolicies::InhibitPolicyMapping),
+        initial_explicit_policy: params.initial_explicit_policy
+            || flags.contains(CertPolicies::RequireExplicitPolicy),
+        initial_any_policy_inhibit: params.initial_any_policy_inhibit
+            || flags.contains(CertPolicies::InhibitAnyPolicy),
+    };
+    if let Some(policy_set) = controls.and_then(|cp| cp.policy_set.as_ref()) {
+        let ta_policies: Vec<_> = policy_set.0.iter().map(|p| p.policy_identifier).collect();
+        policy_params.user_initial_policy_set =
+            intersect_policies(&policy_params.user_initial_policy_set, &ta_policies);
+    }
+
+    // RFC 5280 Section 6.1.2: initialization
+    let mut policies = PolicyProcessor::new(&policy_params, n);
+    let mut permitted_names = NameConstraintsMatcher::new();
+    let mut working_public_key = &anchor.pub_key;
+    let mut working_issuer_name = anchor_name(anchor);
+    let mut max_path_length = controls
+        .and_then(|cp| cp.path_len_constraint)
+        .map(|len| len as usize)
+        .unwrap_or(n);
+
+    if let Some(name_constraints) = controls.and_then(|cp| cp.name_constr.as_ref()) {
+        permitted_names.add(name_constraints.clone());
+    }
+
+    for (index, cert) in path.iter().enumerate() {
+        let depth = index + 1;
+        let tbs = &cert.tbs;
+        let self_issued = tbs.subject.matches(&tbs.issuer);
+
+        // RFC 5280 Section 6.1.3: basic certificate processing
+        let now = params.time;
+        if now < tbs.validity.not_before.to_date_time() {
+            return Err(Error::NotYetValid { index });
+        }
+        if now > tbs.validity.not_after.to_date_time() {
+            return Err(Error::Expired { index });
+        }
+
+        match working_issuer_name {
+            Some(name) if !name.matches(&tbs.issuer) => return Err(Error::NameChaining { index }),
+            None if index == 0 && !chain::anchor_issued(anchor, tbs) => {
+                return Err(Error::NameChaining { index })
+            }
+            _ => {}
+        }
+
+        if tbs.signature != cert.signature_algorithm {
+            return Err(Error::Signature {
+                index,
+                error: verify::Error::AlgorithmMismatch,
+            });
+        }
+        cert.verify(working_public_key, verifier)
+            .map_err(|error| Error::Signature { index, error })?;
+
+        if !self_issued || depth == n {
+            let san = tbs.get::<SubjectAltName<'a>>()?.map(|(_, san)| san);
+            permitted_names
+                .check(&tbs.subject, san.as_ref().map(|san| &san.0[..]))
+                .map_err(|violation| Error::NameConstraints {
+                    index,
+                    violation: Box::new(violation),
+                })?;
+        }
+
+        // Policy processing, including the policy related steps of the
+        // preparation for certificate i+1 and of the wrap-up procedure
+        policies.process(tbs)?;
+
+        if depth == n {
+            // RFC 5280 Section 6.1.5: wrap-up procedure
+            check_critical_extensions(tbs, index, params)?;
+            break;
+        }
+
+        // RFC 5280 Section 6.1.4: preparation for certificate i+1
+        working_issuer_name = Some(&tbs.subject);
+        working_public_key = &tbs.subject_public_key_info;
+
+        if let Some((_, name_constraints)) = tbs.get::<NameConstraints<'_>>()? {
+            permitted_names.add(name_constraints);
+        }
+
+        match tbs.get::<BasicConstraints>()? {
+            Some((_, bc)) if bc.ca => {
+                if !self_issued {
+                    if max_path_length == 0 {
+                        return Err(Error::PathLength { index });
+                    }
+                    max_path_length -= 1;
+                }
+
+                if let Some(len) = bc.path_len_constraint {
+                    max_path_length = max_path_length.min(len as usize);
+                }
+            }
+            _ => return Err(Error::NotCa { index }),
+        }
+
+        if let Some((_, KeyUsage(ku))) = tbs.get::<KeyUsage>()? {
+            if !ku.contains(KeyUsages::KeyCertSign) {
+                return Err(Error::KeyCertSignNotAsserted { index });
+            }
+        }
+
+        check_critical_extensions(tbs, index, params)?;
+    }
+
+    let target = path[n - 1];
+    let policies = policies.finish()?;
+
+    Ok(ValidPath {
+        anchor,
+        certificates: path.to_vec(),
+        policies: policies.user_constrained.iter().map(|p| p.policy).collect(),
+        public_key: target.tbs.subject_public_key_info,
+    })
+}
+
+/// Reject critical extensions which are not processed.
+fn check_critical_extensions(
+    tbs: &TbsCertificate<'_>,
+    index: usize,
+    params: &ValidationParams,
+) -> Result<'static, ()> {
+    let extensions = tbs.extensions.as_deref().unwrap_or(&[]);
+
+    match extensions.iter().find(|ext| {
+        ext.critical
+            && !PROCESSED_EXTENSIONS.contains(&ext.extn_id)
+            && !params.known_extensions.contains(&ext.extn_id)
+    }) {
+        Some(ext) => Err(Error::UnknownCriticalExtension {
+            index,
+            oid: ext.extn_id,
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Intersect two policy sets where `anyPolicy` matches every policy.
+fn intersect_policies(a: &[ObjectIdentifier], b: &[ObjectIdentifier]) -> Vec<ObjectIdentifier> {
+    if a.contains(&ANY_POLICY) {
+        b.to_vec()
+    } else if b.contains(&ANY_POLICY) {
+        a.to_vec()
+    } else {
+        a.iter().filter(|p| b.contains(p)).copied().collect()
+    }
+}
@@ -0,0 +1,315 @@
+//! Certificate policy processing as defined in [RFC 5280 Section 6.1].
+//!
+//! [`process_policies`] runs the policy related steps of the path validation
+//! algorithm over a certification path: it builds the `valid_policy_tree`
+//! from the `CertificatePolicies` extensions, applies `PolicyMappings`, and
+//! enforces the `PolicyConstraints` and `InhibitAnyPolicy` extensions along
+//! with the initial inputs of [`PolicyParams`]. Nothing else is checked: the
+//! path is expected to be validated by other means, e.g. with
+//! [`validate_path`](crate::path::validate_path), which applies the same
+//! processing.
+//!
+//! ```
+//! use x509_cert::der::Decode;
+//! use x509_cert::policy::{process_policies, PolicyParams};
+//! use x509_cert::Certificate;
+//!
+//! let ca = Certificate::from_der(include_bytes!("../tests/examples/path-ca.der")).unwrap();
+//! let leaf = Certificate::from_der(include_bytes!("../tests/examples/path-leaf.der")).unwrap();
+//!
+//! // Require one of the policies acceptable to the application
+//! let mut params = PolicyParams::default();
+//! params.user_initial_policy_set = vec!["2.16.840.1.101.3.2.1.48.1".parse().unwrap()];
+//! params.initial_explicit_policy = true;
+//!
+//! let policies = process_policies(&[&ca, &leaf], &params).unwrap();
+//! assert_eq!(policies.user_constrained.len(), 1);
+//! ```
+//!
+//! [RFC 5280 Section 6.1]: https://datatracker.ietf.org/doc/html/rfc5280#section-6.1
+
+mod tree;
+
+use crate::ext::pkix::certpolicy::{CertificatePolicies, PolicyQualifierInfo};
+use crate::ext::pkix::{InhibitAnyPolicy, PolicyConstraints, PolicyMappings};
+use crate::{Certificate, TbsCertificate};
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use const_oid::db::rfc5280::ANY_POLICY;
+use const_oid::ObjectIdentifier;
+
+use tree::PolicyTree;
+
+/// Result type with the `policy` module's [`Error`] type.
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// Policy processing errors.
+///
+/// Errors carry the `index` of the certificate in the path, where `0` is the
+/// certificate issued by the trust anchor.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    /// ASN.1 DER-related errors, e.g. a malformed extension.
+    Asn1(der::Error),
+
+    /// An explicit policy is required but the `valid_policy_tree` is empty.
+    PolicyRequired {
+        /// Position of the certificate in the path.
+        index: usize,
+    },
+
+    /// A `PolicyMappings` extension maps to or from `anyPolicy`.
+    PolicyMapping {
+        /// Position of the certificate in the path.
+        index: usize,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Asn1(err) => write!(f, "ASN.1 error: {}", err),
+            Error::PolicyRequired { index } => {
+                write!(f, "certificate {} has no acceptable policy", index)
+            }
+            Error::PolicyMapping { index } => {
+                write!(f, "certificate {} maps anyPolicy", index)
+            }
+        }
+    }
+}
+
+impl From<der::Error> for Error {
+    fn from(err: der::Error) -> Error {
+        Error::Asn1(err)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+/// Policy related inputs to the path validation algorithm as described in
+/// [RFC 5280 Section 6.1.1].
+///
+/// The default parameters accept any policy and inhibit nothing.
+///
+/// [RFC 5280 Section 6.1.1]: https://datatracker.ietf.org/doc/html/rfc5280#section-6.1.1
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PolicyParams {
+    /// Policies acceptable to the certificate user.
+    pub user_initial_policy_set: Vec<ObjectIdentifier>,
+
+    /// Whether policy mapping is forbidden in the path.
+    pub initial_policy_mapping_inhibit: bool,
+
+    /// Whether the path must be valid for at least one policy in the
+    /// `user_initial_policy_set`.
+    pub initial_explicit_policy: bool,
+
+    /// Whether the `anyPolicy` OID is ignored when included in a certificate.
+    pub initial_any_policy_inhibit: bool,
+}
+
+impl Default for PolicyParams {
+    fn default() -> Self {
+        Self {
+            user_initial_policy_set: alloc::vec![ANY_POLICY],
+            initial_policy_mapping_inhibit: false,
+            initial_explicit_policy: false,
+            initial_any_policy_inhibit: false,
+        }
+    }
+}
+
+/// A policy for which a path is valid.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ValidPolicy<'a> {
+    /// The policy in the domain of the trust anchor, or `anyPolicy`.
+    pub policy: ObjectIdentifier,
+
+    /// The qualifiers of the policy in the target certificate.
+    pub qualifiers: Vec<PolicyQualifierInfo<'a>>,
+}
+
+/// Outputs of policy processing.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ValidPolicies<'a> {
+    /// Policies for which the path is valid according to the certificates
+    /// alone, i.e. the leaves of the final `valid_policy_tree` before its
+    /// intersection with the `user_initial_policy_set`.
+    pub authority_constrained: Vec<ValidPolicy<'a>>,
+
+    /// Policies for which the path is valid and which are acceptable to the
+    /// certificate user, i.e. the leaves of the `valid_policy_tree` output by
+    /// the wrap-up procedure of [RFC 5280 Section 6.1.5].
+    ///
+    /// [RFC 5280 Section 6.1.5]: https://datatracker.ietf.org/doc/html/rfc5280#section-6.1.5
+    pub user_constrained: Vec<ValidPolicy<'a>>,
+}
+
+/// Process the policies of `path` as described in [RFC 5280 Section 6.1].
+///
+/// `path` is ordered like a [`PkiPath`][`crate::PkiPath`]: the first
+/// certificate is issued by the trust anchor and the last one is the target.
+/// Both policy sets are empty if the `valid_policy_tree` becomes NULL, which
+/// is an error if an explicit policy is required.
+///
+/// [RFC 5280 Section 6.1]: https://datatracker.ietf.org/doc/html/rfc5280#section-6.1
+pub fn process_policies<'a>(
+    path: &[&'a Certificate<'a>],
+    params: &PolicyParams,
+) -> Result<ValidPolicies<'a>> {
+    let mut processor = PolicyProcessor::new(params, path.len());
+    for cert in path {
+        processor.process(&cert.tbs_certificate)?;
+    }
+
+    processor.finish()
+}
+
+/// State of policy processing, fed one certificate at a time.
+pub(crate) struct PolicyProcessor<'a> {
+    valid_policy_tree: Option<PolicyTree<'a>>,
+    user_initial_policy_set: Vec<ObjectIdentifier>,
+    explicit_policy: usize,
+    inhibit_any_policy: usize,
+    policy_mapping: usize,
+
+    /// Number of certificates in the path.
+    len: usize,
+
+    /// Position of the next certificate in the path.
+    index: usize,
+}
+
+impl<'a> PolicyProcessor<'a> {
+    /// Initialization as described in RFC 5280 Section 6.1.2 for a path of
+    /// `len` certificates.
+    pub fn new(params: &PolicyParams, len: usize) -> Self {
+        let initial = |inhibit: bool| match inhibit {
+            true => 0,
+            false => len + 1,
+        };
+
+        Self {
+            valid_policy_tree: Some(PolicyTree::new()),
+            user_initial_policy_set: params.user_initial_policy_set.clone(),
+            explicit_policy: initial(params.initial_explicit_policy),
+            inhibit_any_policy: initial(params.initial_any_policy_inhibit),
+            policy_mapping: initial(params.initial_policy_mapping_inhibit),
+            len,
+            index: 0,
+        }
+    }
+
+    /// Process the next certificate of the path: steps (d) to (f) of RFC 5280
+    /// Section 6.1.3, followed by the policy related steps of Section 6.1.4,
+    /// or of Section 6.1.5 for the last certificate.
+    pub fn process(&mut self, tbs: &'a TbsCertificate<'a>) -> Result<()> {
+        let index = self.index;
+        let depth = index + 1;
+        let self_issued = tbs.subject.matches(&tbs.issuer);
+        self.index += 1;
+
+        match tbs.get::<CertificatePolicies<'a>>()? {
+            Some((_, policies)) => {
+                if let Some(tree) = self.valid_policy_tree.as_mut() {
+                    let any_policy =
+                        self.inhibit_any_policy > 0 || (depth < self.len && self_issued);
+                    tree.add_level(&policies.0, any_policy);
+                    if !tree.prune() {
+                        self.valid_policy_tree = None;
+                    }
+                }
+            }
+            None => self.valid_policy_tree = None,
+        }
+
+        if self.explicit_policy == 0 && self.valid_policy_tree.is_none() {
+            return Err(Error::PolicyRequired { index });
+        }
+
+        let policy_constraints = tbs.get::<PolicyConstraints>()?.map(|(_, pc)| pc);
+
+        if depth >= self.len {
+            self.explicit_policy = self.explicit_policy.saturating_sub(1);
+            if let Some(PolicyConstraints {
+                require_explicit_policy: Some(0),
+                ..
+            }) = policy_constraints
+            {
+                self.explicit_policy = 0;
+            }
+
+            return Ok(());
+        }
+
+        if let Some((_, mappings)) = tbs.get::<PolicyMappings>()? {
+            if mappings.0.iter().any(|m| {
+                m.issuer_domain_policy == ANY_POLICY || m.subject_domain_policy == ANY_POLICY
+            }) {
+                return Err(Error::PolicyMapping { index });
+            }
+
+            if let Some(tree) = self.valid_policy_tree.as_mut() {
+                if !tree.map(&mappings.0, self.policy_mapping > 0) {
+                    self.valid_policy_tree = None;
+                }
+            }
+        }
+
+        if !self_issued {
+            self.explicit_policy = self.explicit_policy.saturating_sub(1);
+            self.policy_mapping = self.policy_mapping.saturating_sub(1);
+            self.inhibit_any_policy = self.inhibit_any_policy.saturating_sub(1);
+        }
+
+        if let Some(pc) = policy_constraints {
+            if let Some(skip) = pc.require_explicit_policy {
+                self.explicit_policy = self.explicit_policy.min(skip as usize);
+            }
+            if let Some(skip) = pc.inhibit_policy_mapping {
+                self.policy_mapping = self.policy_mapping.min(skip as usize);
+            }
+        }
+
+        if let Some((_, InhibitAnyPolicy(skip))) = tbs.get::<InhibitAnyPolicy>()? {
+            self.inhibit_any_policy = self.inhibit_any_policy.min(skip as usize);
+        }
+
+        Ok(())
+    }
+
+    /// Complete the wrap-up procedure of RFC 5280 Section 6.1.5 (g) once
+    /// every certificate has been processed.
+    pub fn finish(mut self) -> Result<ValidPolicies<'a>> {
+        let authority_constrained = self
+            .valid_policy_tree
+            .as_ref()
+            .map(PolicyTree::policies)
+            .unwrap_or_default();
+
+        if let Some(tree) = self.valid_policy_tree.as_mut() {
+            if !tree.intersect(&self.user_initial_policy_set) {
+                self.valid_policy_tree = None;
+            }
+        }
+
+        if self.explicit_policy == 0 && self.valid_policy_tree.is_none() {
+            return Err(Error::PolicyRequired {
+                index: self.index.saturating_sub(1),
+            });
+        }
+
+        Ok(ValidPolicies {
+            authority_constrained,
+            user_constrained: self
+                .valid_policy_tree
+                .map(|tree| tree.policies())
+                .unwrap_or_default(),
+        })
+    }
+}
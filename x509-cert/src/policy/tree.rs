@@ -0,0 +1,268 @@
+//! The `valid_policy_tree` as described in [RFC 5280 Section 6.1.2].
+//!
+//! [RFC 5280 Section 6.1.2]: https://datatracker.ietf.org/doc/html/rfc5280#section-6.1.2
+
+use super::ValidPolicy;
+use crate::ext::pkix::certpolicy::{PolicyInformation, PolicyQualifierInfo};
+use crate::ext::pkix::PolicyMapping;
+
+use alloc::vec::Vec;
+
+use const_oid::db::rfc5280::ANY_POLICY;
+use const_oid::ObjectIdentifier;
+
+/// A node of the `valid_policy_tree`.
+#[derive(Clone, Debug)]
+struct Node<'a> {
+    valid_policy: ObjectIdentifier,
+    qualifier_set: Vec<PolicyQualifierInfo<'a>>,
+    expected_policy_set: Vec<ObjectIdentifier>,
+
+    /// Index of the parent node in the previous level.
+    parent: usize,
+}
+
+impl<'a> Node<'a> {
+    fn new(
+        valid_policy: ObjectIdentifier,
+        qualifier_set: Vec<PolicyQualifierInfo<'a>>,
+        parent: usize,
+    ) -> Self {
+        Self {
+            valid_policy,
+            qualifier_set,
+            expected_policy_set: alloc::vec![valid_policy],
+            parent,
+        }
+    }
+}
+
+/// A non-NULL `valid_policy_tree`, stored one level per depth.
+///
+/// Operations which may leave the tree empty return `false` when they do,
+/// in which case the caller must treat the tree as NULL.
+#[derive(Clone, Debug)]
+pub(crate) struct PolicyTree<'a> {
+    levels: Vec<Vec<Node<'a>>>,
+}
+
+impl<'a> PolicyTree<'a> {
+    /// The initial tree: a single `anyPolicy` node at depth zero.
+    pub fn new() -> Self {
+        Self {
+            levels: alloc::vec![alloc::vec![Node::new(ANY_POLICY, Vec::new(), 0)]],
+        }
+    }
+
+    fn depth(&self) -> usize {
+        self.levels.len() - 1
+    }
+
+    /// Process the certificate policies of the certificate at the next depth
+    /// as described in RFC 5280 Section 6.1.3 (d)(1)-(2).
+    pub fn add_level(&mut self, policies: &[PolicyInformation<'a>], any_policy: bool) {
+        let parents = &self.levels[self.depth()];
+        let mut level = Vec::new();
+
+        for info in policies {
+            let policy = info.policy_identifier;
+            if policy == ANY_POLICY {
+                continue;
+            }
+
+            let qualifiers = info.policy_qualifiers.as_deref().unwrap_or_default();
+            let matches = parents
+                .iter()
+                .enumerate()
+                .filter(|(_, node)| node.expected_policy_set.contains(&policy))
+                .map(|(parent, _)| Node::new(policy, qualifiers.to_vec(), parent))
+                .collect::<Vec<_>>();
+
+            if matches.is_empty() {
+                level.extend(
+                    parents
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, node)| node.valid_policy == ANY_POLICY)
+                        .map(|(parent, _)| Node::new(policy, qualifiers.to_vec(), parent)),
+                );
+            } else {
+                level.extend(matches);
+            }
+        }
+
+        let any = policies.iter().find(|p| p.policy_identifier == ANY_POLICY);
+        if let (true, Some(any)) = (any_policy, any) {
+            let qualifiers = any.policy_qualifiers.as_deref().unwrap_or_default();
+            for (parent, node) in parents.iter().enumerate() {
+                for &policy in &node.expected_policy_set {
+                    if !level
+                        .iter()
+                        .any(|child| child.parent == parent && child.valid_policy == policy)
+                    {
+                        level.push(Node::new(policy, qualifiers.to_vec(), parent));
+                    }
+                }
+            }
+        }
+
+        self.levels.push(level);
+    }
+
+    /// Apply policy mappings to the deepest level as described in
+    /// RFC 5280 Section 6.1.4 (b).
+    pub fn map(&mut self, mappings: &[PolicyMapping], allowed: bool) -> bool {
+        let depth = self.depth();
+        let mut issuer_policies: Vec<ObjectIdentifier> = Vec::new();
+        for mapping in mappings {
+            if !issuer_policies.contains(&mapping.issuer_domain_policy) {
+                issuer_policies.push(mapping.issuer_domain_policy);
+            }
+        }
+
+        if !allowed {
+            self.retain(depth, |node| !issuer_policies.contains(&node.valid_policy));
+            return self.prune();
+        }
+
+        for issuer_policy in issuer_policies {
+            let subject_policies: Vec<_> = mappings
+                .iter()
+                .filter(|m| m.issuer_domain_policy == issuer_policy)
+                .map(|m| m.subject_domain_policy)
+                .collect();
+
+            let level = &mut self.levels[depth];
+            let mut mapped = false;
+            for node in level.iter_mut() {
+                if node.valid_policy == issuer_policy {
+                    node.expected_policy_set = subject_policies.clone();
+                    mapped = true;
+                }
+            }
+
+            if !mapped {
+                if let Some(any) = level.iter().find(|n| n.valid_policy == ANY_POLICY) {
+                    let node = Node {
+                        valid_policy: issuer_policy,
+                        qualifier_set: any.qualifier_set.clone(),
+                        expected_policy_set: subject_policies,
+                        parent: any.parent,
+                    };
+                    level.push(node);
+                }
+            }
+        }
+
+        true
+    }
+
+    /// Compute the intersection with the user initial policy set as
+    /// described in RFC 5280 Section 6.1.5 (g)(iii).
+    pub fn intersect(&mut self, user_policies: &[ObjectIdentifier]) -> bool {
+        if user_policies.contains(&ANY_POLICY) {
+            return true;
+        }
+
+        // Nodes whose parent is `anyPolicy` make up the valid_policy_node_set
+        let mut node_set = Vec::new();
+        for depth in 1..self.levels.len() {
+            let parents = &self.levels[depth - 1];
+            let mut keep = Vec::new();
+
+            for node in &self.levels[depth] {
+                let in_set = parents[node.parent].valid_policy == ANY_POLICY;
+                if in_set {
+                    node_set.push(node.valid_policy);
+                }
+
+                keep.push(
+                    !in_set
+                        || node.valid_policy == ANY_POLICY
+                        || user_policies.contains(&node.valid_policy),
+                );
+            }
+
+            let mut keep = keep.into_iter();
+            self.retain(depth, |_| keep.next().unwrap_or(true));
+        }
+
+        let depth = self.depth();
+        if let Some(any) = self.levels[depth]
+            .iter()
+            .position(|n| n.valid_policy == ANY_POLICY)
+        {
+            let parent = self.levels[depth][any].parent;
+            let qualifiers = self.levels[depth][any].qualifier_set.clone();
+            for &policy in user_policies {
+                if !node_set.contains(&policy) {
+                    let node = Node::new(policy, qualifiers.clone(), parent);
+                    self.levels[depth].push(node);
+                }
+            }
+
+            self.retain(depth, |node| node.valid_policy != ANY_POLICY);
+        }
+
+        self.prune()
+    }
+
+    /// Delete nodes without children at depths less than the deepest level.
+    pub fn prune(&mut self) -> bool {
+        for depth in (0..self.depth()).rev() {
+            let children = &self.levels[depth + 1];
+            let has_child: Vec<bool> = (0..self.levels[depth].len())
+                .map(|i| children.iter().any(|child| child.parent == i))
+                .collect();
+
+            let mut has_child = has_child.into_iter();
+            self.retain(depth, |_| has_child.next().unwrap_or(false));
+        }
+
+        !self.levels[0].is_empty()
+    }
+
+    /// Keep the nodes at `depth` for which `f` returns true, deleting the
+    /// others along with all of their descendants.
+    fn retain(&mut self, depth: usize, mut f: impl FnMut(&Node<'a>) -> bool) {
+        let mut keep: Vec<bool> = self.levels[depth].iter().map(&mut f).collect();
+
+        for d in depth..self.levels.len() {
+            let mut index = Vec::with_capacity(keep.len());
+            let mut next = 0;
+            for &k in &keep {
+                index.push(next);
+                next += usize::from(k);
+            }
+
+            let mut k = keep.iter();
+            self.levels[d].retain(|_| *k.next().unwrap_or(&false));
+
+            match self.levels.get_mut(d + 1) {
+                Some(children) => {
+                    keep = children.iter().map(|c| keep[c.parent]).collect();
+                    for child in children.iter_mut() {
+                        child.parent = index[child.parent];
+                    }
+                }
+                None => break,
+            }
+        }
+    }
+
+    /// The `valid_policy` and `qualifier_set` of every node at the deepest
+    /// level.
+    pub fn policies(&self) -> Vec<ValidPolicy<'a>> {
+        let mut policies: Vec<ValidPolicy<'a>> = Vec::new();
+        for node in &self.levels[self.depth()] {
+            if !policies.iter().any(|p| p.policy == node.valid_policy) {
+                policies.push(ValidPolicy {
+                    policy: node.valid_policy,
+                    qualifiers: node.qualifier_set.clone(),
+                });
+            }
+        }
+
+        policies
+    }
+}
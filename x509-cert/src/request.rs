@@ -1,5 +1,6 @@
 //! PKCS#10 Certification Request types
 
+use crate::ext::pkix::name::DirectoryString;
 use crate::ext::Extension;
 use crate::{attr::Attributes, name::Name};
 
@@ -7,10 +8,25 @@ use alloc::vec::Vec;
 
 use const_oid::db::rfc5912::ID_EXTENSION_REQ;
 use const_oid::{AssociatedOid, ObjectIdentifier};
-use der::asn1::BitStringRef;
-use der::{Decode, Enumerated, Sequence};
+use der::asn1::{AnyRef, BitStringRef};
+use der::{Decode, Enumerated, ErrorKind, Sequence};
 use spki::{AlgorithmIdentifier, SubjectPublicKeyInfo};
 
+/// `pkcs-9-at-challengePassword` as defined in [RFC 2985 Section 5.4.1].
+///
+/// ```text
+/// challengePassword ATTRIBUTE ::= {
+///     WITH SYNTAX DirectoryString {pkcs-9-ub-challengePassword}
+///     EQUALITY MATCHING RULE caseExactMatch
+///     SINGLE VALUE TRUE
+///     ID pkcs-9-at-challengePassword
+/// }
+/// ```
+///
+/// [RFC 2985 Section 5.4.1]: https://datatracker.ietf.org/doc/html/rfc2985#section-5.4.1
+pub const PKCS_9_AT_CHALLENGE_PASSWORD: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.2.840.113549.1.9.7");
+
 /// Version identifier for certification request information.
 ///
 /// (RFC 2986 designates `0` as the only valid version)
@@ -50,6 +66,38 @@ pub struct CertReqInfo<'a> {
     pub attributes: Attributes<'a>,
 }
 
+impl<'a> CertReqInfo<'a> {
+    /// Returns the requested extensions, if an `extensionRequest` attribute
+    /// is present.
+    pub fn extension_req(&self) -> Result<Option<ExtensionReq<'a>>, der::Error> {
+        self.attribute(ID_EXTENSION_REQ)?
+            .map(AnyRef::decode_into)
+            .transpose()
+    }
+
+    /// Returns the challenge password, if a `challengePassword` attribute is
+    /// present.
+    pub fn challenge_password(&self) -> Result<Option<DirectoryString<'a>>, der::Error> {
+        self.attribute(PKCS_9_AT_CHALLENGE_PASSWORD)?
+            .map(DirectoryString::try_from)
+            .transpose()
+    }
+
+    /// Returns the value of a single-valued attribute.
+    ///
+    /// Returns an error if the attribute is present more than once or does
+    /// not have exactly one value.
+    fn attribute(&self, oid: ObjectIdentifier) -> Result<Option<AnyRef<'a>>, der::Error> {
+        let mut iter = self.attributes.iter().filter(|attr| attr.oid == oid);
+
+        match (iter.next(), iter.next()) {
+            (None, _) => Ok(None),
+            (Some(attr), None) if attr.values.len() == 1 => Ok(attr.values.get(0).copied()),
+            _ => Err(ErrorKind::Failed.into()),
+        }
+    }
+}
+
 impl<'a> TryFrom<&'a [u8]> for CertReqInfo<'a> {
     type Error = der::Error;
 
@@ -96,7 +144,7 @@ impl<'a> TryFrom<&'a [u8]> for CertReq<'a> {
 /// ```
 ///
 /// [RFC 5272 Section 3.1]: https://datatracker.ietf.org/doc/html/rfc5272#section-3.1
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct ExtensionReq<'a>(pub Vec<Extension<'a>>);
 
 impl<'a> AssociatedOid for ExtensionReq<'a> {
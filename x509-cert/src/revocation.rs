@@ -0,0 +1,437 @@
+//! Certificate revocation checking using CRLs as described in
+//! [RFC 5280 Section 6.3].
+//!
+//! [`RevocationChecker`] determines the status of a certificate from a
+//! complete CRL and, optionally, a delta CRL. The CRLs must already have been
+//! obtained and their signatures verified, e.g. with
+//! [`SignedCertificateList`](crate::verify::SignedCertificateList).
+//!
+//! [RFC 5280 Section 6.3]: https://datatracker.ietf.org/doc/html/rfc5280#section-6.3
+
+use crate::crl::CertificateList;
+use crate::ext::pkix::crl::dp::{DistributionPoint, Reasons};
+use crate::ext::pkix::name::{DistributionPointName, GeneralName};
+use crate::ext::pkix::{
+    BaseCrlNumber, BasicConstraints, CertificateIssuer, CrlDistributionPoints, CrlNumber,
+    CrlReason, IssuingDistributionPoint,
+};
+use crate::name::Name;
+use crate::time::Time;
+use crate::Certificate;
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use const_oid::db::rfc5280::{
+    ID_CE_AUTHORITY_KEY_IDENTIFIER, ID_CE_CERTIFICATE_ISSUER, ID_CE_CRL_NUMBER, ID_CE_CRL_REASONS,
+    ID_CE_DELTA_CRL_INDICATOR, ID_CE_FRESHEST_CRL, ID_CE_INVALIDITY_DATE, ID_CE_ISSUER_ALT_NAME,
+    ID_CE_ISSUING_DISTRIBUTION_POINT,
+};
+use const_oid::ObjectIdentifier;
+use der::DateTime;
+use flagset::FlagSet;
+
+/// Result type with the `revocation` module's [`Error`] type.
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// CRL extensions understood by [`RevocationChecker`].
+const CRL_EXTENSIONS: &[ObjectIdentifier] = &[
+    ID_CE_AUTHORITY_KEY_IDENTIFIER,
+    ID_CE_CRL_NUMBER,
+    ID_CE_DELTA_CRL_INDICATOR,
+    ID_CE_FRESHEST_CRL,
+    ID_CE_ISSUER_ALT_NAME,
+    ID_CE_ISSUING_DISTRIBUTION_POINT,
+];
+
+/// CRL entry extensions understood by [`RevocationChecker`].
+const ENTRY_EXTENSIONS: &[ObjectIdentifier] = &[
+    ID_CE_CERTIFICATE_ISSUER,
+    ID_CE_CRL_REASONS,
+    ID_CE_INVALIDITY_DATE,
+];
+
+/// Revocation checking errors.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    /// ASN.1 DER-related errors, e.g. a malformed extension.
+    Asn1(der::Error),
+
+    /// A delta CRL was supplied where a complete CRL was expected.
+    DeltaCrl,
+
+    /// The delta CRL does not have a `DeltaCRLIndicator`, or does not match
+    /// the issuer, scope or `CRLNumber` of the complete CRL.
+    DeltaMismatch,
+
+    /// The CRL's `nextUpdate` time has passed.
+    Expired,
+
+    /// The CRL is not issued by the certificate issuer, nor is it an
+    /// indirect CRL naming the certificate issuer.
+    IssuerMismatch,
+
+    /// The CRL's `thisUpdate` time is in the future.
+    NotYetValid,
+
+    /// The certificate is outside the scope of the CRL's
+    /// `IssuingDistributionPoint`.
+    OutOfScope,
+
+    /// The certificate is not listed, but the CRL only covers some
+    /// revocation reasons.
+    PartialReasons,
+
+    /// A critical CRL or CRL entry extension was not recognized.
+    UnknownCriticalExtension(ObjectIdentifier),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Asn1(err) => write!(f, "ASN.1 error: {}", err),
+            Error::DeltaCrl => f.write_str("unexpected delta CRL"),
+            Error::DeltaMismatch => f.write_str("delta CRL does not match complete CRL"),
+            Error::Expired => f.write_str("CRL has expired"),
+            Error::IssuerMismatch => f.write_str("CRL issuer does not match"),
+            Error::NotYetValid => f.write_str("CRL is not yet valid"),
+            Error::OutOfScope => f.write_str("certificate is out of the CRL scope"),
+            Error::PartialReasons => f.write_str("CRL only covers some reasons"),
+            Error::UnknownCriticalExtension(oid) => {
+                write!(f, "unknown critical extension {}", oid)
+            }
+        }
+    }
+}
+
+impl From<der::Error> for Error {
+    fn from(err: der::Error) -> Error {
+        Error::Asn1(err)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+/// Revocation status of a certificate.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Status {
+    /// The certificate is not revoked.
+    Good,
+
+    /// The certificate is revoked.
+    Revoked {
+        /// Date on which the revocation occurred.
+        date: Time,
+
+        /// Reason for the revocation, if given.
+        reason: Option<CrlReason>,
+    },
+}
+
+/// Determines the revocation status of certificates from CRLs.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct RevocationChecker {
+    /// The time at which the CRLs must be fresh.
+    pub time: DateTime,
+}
+
+impl RevocationChecker {
+    /// Create a new checker requiring CRLs to be fresh at `time`.
+    pub fn new(time: DateTime) -> Self {
+        Self { time }
+    }
+
+    /// Determine the status of `cert` from a complete CRL.
+    pub fn check<'a>(
+        &self,
+        cert: &'a Certificate<'a>,
+        crl: &'a CertificateList<'a>,
+    ) -> Result<Status> {
+        if crl.tbs_cert_list.get::<BaseCrlNumber<'_>>()?.is_some() {
+            return Err(Error::DeltaCrl);
+        }
+
+        self.check_fresh(crl, true)?;
+        let scope = self.check_scope(cert, crl)?;
+
+        scope.status(find_entry(cert, crl, scope.indirect)?)
+    }
+
+    /// Determine the status of `cert` from a complete CRL and a delta CRL.
+    ///
+    /// Only the delta CRL needs to be fresh. Entries in the delta CRL take
+    /// precedence over the complete CRL, with `removeFromCRL` indicating
+    /// that a certificate is no longer revoked.
+    pub fn check_with_delta<'a>(
+        &self,
+        cert: &'a Certificate<'a>,
+        crl: &'a CertificateList<'a>,
+        delta: &'a CertificateList<'a>,
+    ) -> Result<Status> {
+        if crl.tbs_cert_list.get::<BaseCrlNumber<'_>>()?.is_some() {
+            return Err(Error::DeltaCrl);
+        }
+
+        let base_number = match delta.tbs_cert_list.get::<BaseCrlNumber<'_>>()? {
+            Some((_, number)) => number.0,
+            None => return Err(Error::DeltaMismatch),
+        };
+
+        let crl_number = match crl.tbs_cert_list.get::<CrlNumber<'_>>()? {
+            Some((_, number)) => number.0,
+            None => return Err(Error::DeltaMismatch),
+        };
+
+        let crl_idp = crl.tbs_cert_list.get::<IssuingDistributionPoint<'_>>()?;
+        let delta_idp = delta.tbs_cert_list.get::<IssuingDistributionPoint<'_>>()?;
+
+        if !crl
+            .tbs_cert_list
+            .issuer
+            .matches(&delta.tbs_cert_list.issuer)
+            || crl_idp.map(|(_, idp)| idp) != delta_idp.map(|(_, idp)| idp)
+            || uint_cmp(crl_number.as_bytes(), base_number.as_bytes()).is_lt()
+        {
+            return Err(Error::DeltaMismatch);
+        }
+
+        self.check_fresh(crl, false)?;
+        self.check_fresh(delta, true)?;
+        let scope = self.check_scope(cert, crl)?;
+
+        let entry = match find_entry(cert, delta, scope.indirect)? {
+            Some(status) => Some(status),
+            None => find_entry(cert, crl, scope.indirect)?,
+        };
+
+        scope.status(entry)
+    }
+
+    /// Check `thisUpdate`, `nextUpdate` (if `next_update` is set) and the
+    /// criticality of the CRL extensions.
+    fn check_fresh(&self, crl: &CertificateList<'_>, next_update: bool) -> Result<()> {
+        let time = self.time;
+        let tbs = &crl.tbs_cert_list;
+
+        if tbs.this_update.to_date_time() > time {
+            return Err(Error::NotYetValid);
+        }
+
+        if next_update && matches!(tbs.next_update, Some(t) if t.to_date_time() < time) {
+            return Err(Error::Expired);
+        }
+
+        for ext in tbs.crl_extensions.as_deref().unwrap_or(&[]) {
+            if ext.critical && !CRL_EXTENSIONS.contains(&ext.extn_id) {
+                return Err(Error::UnknownCriticalExtension(ext.extn_id));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Verify the CRL issuer and scope as described in RFC 5280
+    /// Section 6.3.3 (b).
+    fn check_scope<'a>(
+        &self,
+        cert: &'a Certificate<'a>,
+        crl: &'a CertificateList<'a>,
+    ) -> Result<Scope> {
+        let tbs = &cert.tbs_certificate;
+        let crl_issuer = &crl.tbs_cert_list.issuer;
+
+        let idp = crl
+            .tbs_cert_list
+            .get::<IssuingDistributionPoint<'_>>()?
+            .map(|(_, idp)| idp);
+
+        let indirect = idp.as_ref().map_or(false, |idp| idp.indirect_crl);
+
+        let points = tbs
+            .get::<CrlDistributionPoints<'_>>()?
+            .map(|(_, points)| points.0)
+            .unwrap_or_default();
+
+        // The CRL must be issued by the certificate issuer or, for an
+        // indirect CRL, by a cRLIssuer of one of the distribution points
+        let issued = tbs.issuer.matches(crl_issuer)
+            || (indirect && points.iter().any(|dp| names_crl_issuer(dp, crl_issuer)));
+
+        if !issued {
+            return Err(Error::IssuerMismatch);
+        }
+
+        let idp = match idp {
+            Some(idp) => idp,
+            None => {
+                return Ok(Scope {
+                    indirect,
+                    partial: false,
+                })
+            }
+        };
+
+        if let Some(name) = &idp.distribution_point {
+            let idp_names = full_names(name, crl_issuer);
+
+            let matched = points.iter().any(|dp| match &dp.distribution_point {
+                Some(name) => {
+                    let issuer = dp
+                        .crl_issuer
+                        .as_ref()
+                        .and_then(|names| directory_name(names))
+                        .unwrap_or(&tbs.issuer);
+
+                    full_names(name, issuer)
+                        .iter()
+                        .any(|name| idp_names.contains(name))
+                }
+                None => dp
+                    .crl_issuer
+                    .as_ref()
+                    .map_or(false, |names| names.iter().any(|n| idp_names.contains(n))),
+            });
+
+            if !matched {
+                return Err(Error::OutOfScope);
+            }
+        }
+
+        let is_ca = tbs
+            .get::<BasicConstraints>()?
+            .map_or(false, |(_, bc)| bc.ca);
+
+        if (idp.only_contains_user_certs && is_ca)
+            || (idp.only_contains_ca_certs && !is_ca)
+            || idp.only_contains_attribute_certs
+        {
+            return Err(Error::OutOfScope);
+        }
+
+        let partial = idp
+            .only_some_reasons
+            .map_or(false, |reasons| !reasons.contains(all_reasons()));
+
+        Ok(Scope { indirect, partial })
+    }
+}
+
+/// Properties of a CRL which applies to a certificate.
+struct Scope {
+    /// The CRL is an indirect CRL.
+    indirect: bool,
+
+    /// The CRL does not cover all revocation reasons.
+    partial: bool,
+}
+
+impl Scope {
+    /// The status of a certificate given its CRL entry, if any.
+    fn status(&self, entry: Option<Status>) -> Result<Status> {
+        match entry {
+            Some(Status::Revoked {
+                reason: Some(CrlReason::RemoveFromCRL),
+                ..
+            }) => Ok(Status::Good),
+            Some(status) => Ok(status),
+            None if self.partial => Err(Error::PartialReasons),
+            None => Ok(Status::Good),
+        }
+    }
+}
+
+/// Look up `cert` in the revoked certificates of `crl`.
+///
+/// In an indirect CRL the `CertificateIssuer` entry extension determines the
+/// issuer of an entry and of all the entries following it.
+fn find_entry<'a>(
+    cert: &'a Certificate<'a>,
+    crl: &'a CertificateList<'a>,
+    indirect: bool,
+) -> Result<Option<Status>> {
+    let tbs = &cert.tbs_certificate;
+    let mut issuer = Some(crl.tbs_cert_list.issuer.clone());
+
+    for entry in crl
+        .tbs_cert_list
+        .revoked_certificates
+        .as_deref()
+        .unwrap_or(&[])
+    {
+        for ext in entry.crl_entry_extensions.as_deref().unwrap_or(&[]) {
+            if ext.critical && !ENTRY_EXTENSIONS.contains(&ext.extn_id) {
+                return Err(Error::UnknownCriticalExtension(ext.extn_id));
+            }
+        }
+
+        if indirect {
+            if let Some((_, names)) = entry.get::<CertificateIssuer<'_>>()? {
+                issuer = directory_name(&names.0).cloned();
+            }
+        }
+
+        let issued = issuer
+            .as_ref()
+            .map_or(false, |issuer| issuer.matches(&tbs.issuer));
+
+        if issued && entry.serial_number == tbs.serial_number {
+            let reason = entry.get::<CrlReason>()?.map(|(_, reason)| reason);
+
+            return Ok(Some(Status::Revoked {
+                date: entry.revocation_date,
+                reason,
+            }));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Is the CRL issuer one of the `cRLIssuer` names of a distribution point?
+fn names_crl_issuer(dp: &DistributionPoint<'_>, crl_issuer: &Name<'_>) -> bool {
+    dp.crl_issuer.as_ref().map_or(false, |names| {
+        names
+            .iter()
+            .any(|name| matches!(name, GeneralName::DirectoryName(dn) if dn.matches(crl_issuer)))
+    })
+}
+
+/// The first directory name in `names`.
+fn directory_name<'n, 'a>(names: &'n [GeneralName<'a>]) -> Option<&'n Name<'a>> {
+    names.iter().find_map(|name| match name {
+        GeneralName::DirectoryName(dn) => Some(dn),
+        _ => None,
+    })
+}
+
+/// Convert a distribution point name into a list of names, appending a
+/// name relative to the CRL issuer to `issuer`.
+fn full_names<'a>(name: &DistributionPointName<'a>, issuer: &Name<'a>) -> Vec<GeneralName<'a>> {
+    match name {
+        DistributionPointName::FullName(names) => names.clone(),
+        DistributionPointName::NameRelativeToCRLIssuer(rdn) => {
+            let mut name = issuer.clone();
+            name.0.push(rdn.clone());
+            alloc::vec![GeneralName::DirectoryName(name)]
+        }
+    }
+}
+
+/// All the revocation reasons of `ReasonFlags`.
+fn all_reasons() -> FlagSet<Reasons> {
+    Reasons::KeyCompromise
+        | Reasons::CaCompromise
+        | Reasons::AffiliationChanged
+        | Reasons::Superseded
+        | Reasons::CessationOfOperation
+        | Reasons::CertificateHold
+        | Reasons::PrivilegeWithdrawn
+        | Reasons::AaCompromise
+}
+
+/// Compare two unsigned big endian integers without leading zeros.
+fn uint_cmp(a: &[u8], b: &[u8]) -> core::cmp::Ordering {
+    a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+}
@@ -0,0 +1,1190 @@
+//! [`serde`] support, enabled by the `serde` feature.
+//!
+//! The data model is meant to be human-readable and stable, e.g. for logging
+//! certificate metadata as JSON:
+//!
+//! - object identifiers are maps with the dotted-decimal `oid` and, when it
+//!   is known to [`const_oid::db::DB`], its `name`;
+//! - names are strings in the format of [RFC 4514];
+//! - times are [RFC 3339] strings in UTC, e.g. `2022-01-01T00:00:00Z`;
+//! - bytes, serial numbers and the DER encoding of opaque values are
+//!   lowercase hex strings;
+//! - bit strings are maps with their `bytes` as hex and the number of
+//!   `unused_bits` in the last byte;
+//! - structures are maps with the snake case names of their fields, and
+//!   absent `OPTIONAL` fields are omitted.
+//!
+//! Types borrowing from their encoding only implement `Serialize`, while
+//! their owned forms implement both `Serialize` and `Deserialize`.
+//! Deserializing is not guaranteed to reproduce the original encoding:
+//! string values of names are encoded as described for
+//! [`RdnSequenceOwned`](crate::name::RdnSequenceOwned), so deserialized names
+//! only [match](crate::name::RdnSequence::matches) the original ones, and
+//! times use `UTCTime` from 1950 through 2049 and `GeneralizedTime`
+//! otherwise.
+//!
+//! [RFC 4514]: https://datatracker.ietf.org/doc/html/rfc4514
+//! [RFC 3339]: https://datatracker.ietf.org/doc/html/rfc3339
+
+use crate::anchor::{
+    self, CertPathControls, CertPolicies, CertPolicyFlags, TrustAnchorChoice, TrustAnchorInfo,
+};
+use crate::attr::Attribute;
+use crate::certificate::{Certificate, CertificateOwned, TbsCertificate, TbsCertificateOwned};
+use crate::crl::{
+    CertificateList, CertificateListOwned, RevokedCert, RevokedCertOwned, TbsCertList,
+    TbsCertListOwned,
+};
+use crate::ext::pkix::certpolicy::{CertificatePolicies, PolicyInformation, PolicyQualifierInfo};
+use crate::ext::pkix::constraints::name::GeneralSubtree;
+use crate::ext::pkix::name::GeneralName;
+use crate::ext::pkix::{
+    AccessDescription, AuthorityInfoAccessSyntax, AuthorityKeyIdentifier, BasicConstraints,
+    ExtendedKeyUsage, InhibitAnyPolicy, IssuerAltName, KeyUsage, KeyUsages, NameConstraints,
+    PolicyConstraints, PolicyMapping, PolicyMappings, SubjectAltName, SubjectInfoAccessSyntax,
+    SubjectKeyIdentifier,
+};
+use crate::ext::{Extension, ExtensionOwned};
+use crate::name::{Name, NameOwned};
+use crate::request::{self, CertReq, CertReqInfo};
+use crate::time::{Time, Validity};
+use crate::Version;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+use const_oid::db::DB;
+use der::asn1::{Any, BitString, BitStringRef, GeneralizedTime, Int, UtcTime};
+use der::{DateTime, Decode, Encode};
+use flagset::FlagSet;
+use serde::de::{self, Deserializer};
+use serde::ser::{self, SerializeSeq, SerializeStruct, Serializer};
+use serde::{Deserialize, Serialize};
+use spki::{
+    AlgorithmIdentifier, AlgorithmIdentifierOwned, ObjectIdentifier, SubjectPublicKeyInfo,
+    SubjectPublicKeyInfoOwned,
+};
+
+/// Serializes a value as the string produced by its `Display` impl.
+struct Str<T>(T);
+
+impl<T: fmt::Display> Serialize for Str<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&self.0)
+    }
+}
+
+/// Deserializes a value from a string with its `FromStr` impl.
+struct Parsed<T>(T);
+
+impl<'de, T> Deserialize<'de> for Parsed<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map(Parsed)
+            .map_err(de::Error::custom)
+    }
+}
+
+/// Bytes displayed as lowercase hex.
+struct Hex<'a>(&'a [u8]);
+
+impl fmt::Display for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|byte| write!(f, "{:02x}", byte))
+    }
+}
+
+impl Serialize for Hex<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Bytes parsed from hex.
+struct HexBuf(Vec<u8>);
+
+impl FromStr for HexBuf {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() % 2 != 0 {
+            return Err("odd number of hex digits");
+        }
+
+        s.as_bytes()
+            .chunks(2)
+            .map(|pair| {
+                core::str::from_utf8(pair)
+                    .ok()
+                    .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                    .ok_or("invalid hex digit")
+            })
+            .collect::<Result<_, _>>()
+            .map(HexBuf)
+    }
+}
+
+impl<'de> Deserialize<'de> for HexBuf {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Parsed::deserialize(deserializer).map(|Parsed(hex)| hex)
+    }
+}
+
+/// The DER encoding of a value, serialized as hex.
+struct DerHex(Vec<u8>);
+
+impl Serialize for DerHex {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Hex(&self.0).serialize(serializer)
+    }
+}
+
+fn der_hex<T: Encode, E: ser::Error>(value: &T) -> Result<DerHex, E> {
+    value.to_vec().map(DerHex).map_err(E::custom)
+}
+
+/// Decodes the DER encoding of a value from hex.
+fn from_der_hex<T: for<'a> Decode<'a>, E: de::Error>(hex: HexBuf) -> Result<T, E> {
+    T::from_der(&hex.0).map_err(E::custom)
+}
+
+/// A bit string, along with the number of unused bits in its last byte.
+struct Bits<'a>(BitStringRef<'a>);
+
+impl Serialize for Bits<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("BitString", 2)?;
+        s.serialize_field("bytes", &Hex(self.0.raw_bytes()))?;
+        s.serialize_field("unused_bits", &self.0.unused_bits())?;
+        s.end()
+    }
+}
+
+#[derive(Deserialize)]
+struct BitsModel {
+    bytes: HexBuf,
+    unused_bits: u8,
+}
+
+impl BitsModel {
+    fn into_owned<E: de::Error>(self) -> Result<BitString, E> {
+        BitString::new(self.unused_bits, self.bytes.0).map_err(E::custom)
+    }
+}
+
+/// An object identifier along with its name.
+struct Oid(ObjectIdentifier);
+
+impl Serialize for Oid {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let name = DB.by_oid(&self.0);
+        let mut s = serializer.serialize_struct("ObjectIdentifier", 2)?;
+        s.serialize_field("oid", &Str(self.0))?;
+        match name {
+            Some(name) => s.serialize_field("name", name)?,
+            None => s.skip_field("name")?,
+        }
+        s.end()
+    }
+}
+
+/// An object identifier, whose name is ignored.
+#[derive(Deserialize)]
+struct OidModel {
+    oid: Parsed<ObjectIdentifier>,
+}
+
+impl From<OidModel> for ObjectIdentifier {
+    fn from(model: OidModel) -> Self {
+        model.oid.0
+    }
+}
+
+/// Serializes the elements of `iter`.
+fn collect<S, I>(serializer: S, iter: I) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    I: ExactSizeIterator,
+    I::Item: Serialize,
+{
+    let mut seq = serializer.serialize_seq(Some(iter.len()))?;
+    for item in iter {
+        seq.serialize_element(&item)?;
+    }
+    seq.end()
+}
+
+impl Serialize for Version {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(match self {
+            Version::V1 => "v1",
+            Version::V2 => "v2",
+            Version::V3 => "v3",
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for Version {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match String::deserialize(deserializer)?.as_str() {
+            "v1" => Ok(Version::V1),
+            "v2" => Ok(Version::V2),
+            "v3" => Ok(Version::V3),
+            other => Err(de::Error::unknown_variant(other, &["v1", "v2", "v3"])),
+        }
+    }
+}
+
+impl Serialize for Time {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&self.to_date_time())
+    }
+}
+
+impl<'de> Deserialize<'de> for Time {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        let datetime =
+            parse_rfc3339(&s).ok_or_else(|| de::Error::custom("invalid RFC 3339 time"))?;
+
+        // RFC 5280 Section 4.1.2.5
+        match datetime.year() {
+            1950..=2049 => UtcTime::from_date_time(datetime)
+                .map(Time::UtcTime)
+                .map_err(de::Error::custom),
+            _ => Ok(Time::GeneralTime(GeneralizedTime::from_date_time(datetime))),
+        }
+    }
+}
+
+/// Parses a time in the `YYYY-MM-DDTHH:MM:SSZ` format.
+fn parse_rfc3339(s: &str) -> Option<DateTime> {
+    let bytes = s.as_bytes();
+    if bytes.len() != 20
+        || [
+            (4, b'-'),
+            (7, b'-'),
+            (10, b'T'),
+            (13, b':'),
+            (16, b':'),
+            (19, b'Z'),
+        ]
+        .iter()
+        .any(|&(i, sep)| bytes[i] != sep)
+    {
+        return None;
+    }
+
+    let field = |range: core::ops::Range<usize>| -> Option<u16> {
+        let digits = &s[range];
+        match digits.bytes().all(|b| b.is_ascii_digit()) {
+            true => digits.parse().ok(),
+            false => None,
+        }
+    };
+
+    DateTime::new(
+        field(0..4)?,
+        field(5..7)? as u8,
+        field(8..10)? as u8,
+        field(11..13)? as u8,
+        field(14..16)? as u8,
+        field(17..19)? as u8,
+    )
+    .ok()
+}
+
+impl Serialize for Validity {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("Validity", 2)?;
+        s.serialize_field("not_before", &self.not_before)?;
+        s.serialize_field("not_after", &self.not_after)?;
+        s.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Validity {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct Model {
+            not_before: Time,
+            not_after: Time,
+        }
+
+        let model = Model::deserialize(deserializer)?;
+        Ok(Validity {
+            not_before: model.not_before,
+            not_after: model.not_after,
+        })
+    }
+}
+
+impl Serialize for Name<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl Serialize for NameOwned {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for NameOwned {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Parsed::deserialize(deserializer).map(|Parsed(name)| name)
+    }
+}
+
+/// `AlgorithmIdentifier`, which is defined in the `spki` crate.
+struct Algorithm<'a, 'b>(&'b AlgorithmIdentifier<'a>);
+
+impl Serialize for Algorithm<'_, '_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("AlgorithmIdentifier", 2)?;
+        s.serialize_field("algorithm", &Oid(self.0.oid))?;
+        match &self.0.parameters {
+            Some(params) => s.serialize_field("parameters", &der_hex::<_, S::Error>(params)?)?,
+            None => s.skip_field("parameters")?,
+        }
+        s.end()
+    }
+}
+
+#[derive(Deserialize)]
+struct AlgorithmModel {
+    algorithm: OidModel,
+    #[serde(default)]
+    parameters: Option<HexBuf>,
+}
+
+impl AlgorithmModel {
+    fn into_owned<E: de::Error>(self) -> Result<AlgorithmIdentifierOwned, E> {
+        Ok(AlgorithmIdentifierOwned {
+            oid: self.algorithm.into(),
+            parameters: self.parameters.map(from_der_hex::<Any, E>).transpose()?,
+        })
+    }
+}
+
+/// `SubjectPublicKeyInfo`, which is defined in the `spki` crate.
+struct PublicKeyInfo<'a, 'b>(&'b SubjectPublicKeyInfo<'a>);
+
+impl Serialize for PublicKeyInfo<'_, '_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("SubjectPublicKeyInfo", 2)?;
+        s.serialize_field("algorithm", &Algorithm(&self.0.algorithm))?;
+        s.serialize_field("subject_public_key", &Hex(self.0.subject_public_key))?;
+        s.end()
+    }
+}
+
+#[derive(Deserialize)]
+struct PublicKeyInfoModel {
+    algorithm: AlgorithmModel,
+    subject_public_key: HexBuf,
+}
+
+impl PublicKeyInfoModel {
+    fn into_owned<E: de::Error>(self) -> Result<SubjectPublicKeyInfoOwned, E> {
+        Ok(SubjectPublicKeyInfoOwned {
+            algorithm: self.algorithm.into_owned()?,
+            subject_public_key: BitString::from_bytes(&self.subject_public_key.0)
+                .map_err(E::custom)?,
+        })
+    }
+}
+
+impl Serialize for Extension<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("Extension", 3)?;
+        s.serialize_field("extn_id", &Oid(self.extn_id))?;
+        s.serialize_field("critical", &self.critical)?;
+        s.serialize_field("extn_value", &Hex(self.extn_value))?;
+        s.end()
+    }
+}
+
+impl Serialize for ExtensionOwned {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_ref().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ExtensionOwned {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct Model {
+            extn_id: OidModel,
+            #[serde(default)]
+            critical: bool,
+            extn_value: HexBuf,
+        }
+
+        let model = Model::deserialize(deserializer)?;
+        Ok(ExtensionOwned {
+            extn_id: model.extn_id.into(),
+            critical: model.critical,
+            extn_value: model.extn_value.0,
+        })
+    }
+}
+
+impl Serialize for TbsCertificate<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("TbsCertificate", 10)?;
+        s.serialize_field("version", &self.version)?;
+        s.serialize_field("serial_number", &Hex(self.serial_number.as_bytes()))?;
+        s.serialize_field("signature", &Algorithm(&self.signature))?;
+        s.serialize_field("issuer", &self.issuer)?;
+        s.serialize_field("validity", &self.validity)?;
+        s.serialize_field("subject", &self.subject)?;
+        s.serialize_field(
+            "subject_public_key_info",
+            &PublicKeyInfo(&self.subject_public_key_info),
+        )?;
+
+        for (key, id) in [
+            ("issuer_unique_id", self.issuer_unique_id),
+            ("subject_unique_id", self.subject_unique_id),
+        ] {
+            match id {
+                Some(id) => s.serialize_field(key, &Bits(id))?,
+                None => s.skip_field(key)?,
+            }
+        }
+
+        match &self.extensions {
+            Some(extensions) => s.serialize_field("extensions", extensions)?,
+            None => s.skip_field("extensions")?,
+        }
+        s.end()
+    }
+}
+
+impl Serialize for TbsCertificateOwned {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_ref().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for TbsCertificateOwned {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct Model {
+            #[serde(default)]
+            version: Version,
+            serial_number: HexBuf,
+            signature: AlgorithmModel,
+            issuer: NameOwned,
+            validity: Validity,
+            subject: NameOwned,
+            subject_public_key_info: PublicKeyInfoModel,
+            issuer_unique_id: Option<BitsModel>,
+            subject_unique_id: Option<BitsModel>,
+            extensions: Option<Vec<ExtensionOwned>>,
+        }
+
+        let model = Model::deserialize(deserializer)?;
+        let unique_id = |id: Option<BitsModel>| id.map(BitsModel::into_owned).transpose();
+
+        Ok(TbsCertificateOwned {
+            version: model.version,
+            serial_number: Int::new(&model.serial_number.0).map_err(de::Error::custom)?,
+            signature: model.signature.into_owned()?,
+            issuer: model.issuer,
+            validity: model.validity,
+            subject: model.subject,
+            subject_public_key_info: model.subject_public_key_info.into_owned()?,
+            issuer_unique_id: unique_id(model.issuer_unique_id)?,
+            subject_unique_id: unique_id(model.subject_unique_id)?,
+            extensions: model.extensions,
+        })
+    }
+}
+
+impl Serialize for Certificate<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("Certificate", 3)?;
+        s.serialize_field("tbs_certificate", &self.tbs_certificate)?;
+        s.serialize_field("signature_algorithm", &Algorithm(&self.signature_algorithm))?;
+        s.serialize_field("signature", &Bits(self.signature))?;
+        s.end()
+    }
+}
+
+impl Serialize for CertificateOwned {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_ref().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for CertificateOwned {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct Model {
+            tbs_certificate: TbsCertificateOwned,
+            signature_algorithm: AlgorithmModel,
+            signature: BitsModel,
+        }
+
+        let model = Model::deserialize(deserializer)?;
+        Ok(CertificateOwned {
+            tbs_certificate: model.tbs_certificate,
+            signature_algorithm: model.signature_algorithm.into_owned()?,
+            signature: model.signature.into_owned()?,
+        })
+    }
+}
+
+impl Serialize for BasicConstraints {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("BasicConstraints", 2)?;
+        s.serialize_field("ca", &self.ca)?;
+        match self.path_len_constraint {
+            Some(len) => s.serialize_field("path_len_constraint", &len)?,
+            None => s.skip_field("path_len_constraint")?,
+        }
+        s.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for BasicConstraints {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct Model {
+            #[serde(default)]
+            ca: bool,
+            path_len_constraint: Option<u8>,
+        }
+
+        let model = Model::deserialize(deserializer)?;
+        Ok(BasicConstraints {
+            ca: model.ca,
+            path_len_constraint: model.path_len_constraint,
+        })
+    }
+}
+
+/// Names of the [`KeyUsages`] bits, as in [RFC 5280 Section 4.2.1.3].
+///
+/// [RFC 5280 Section 4.2.1.3]: https://datatracker.ietf.org/doc/html/rfc5280#section-4.2.1.3
+const KEY_USAGES: [(KeyUsages, &str); 9] = [
+    (KeyUsages::DigitalSignature, "digitalSignature"),
+    (KeyUsages::NonRepudiation, "nonRepudiation"),
+    (KeyUsages::KeyEncipherment, "keyEncipherment"),
+    (KeyUsages::DataEncipherment, "dataEncipherment"),
+    (KeyUsages::KeyAgreement, "keyAgreement"),
+    (KeyUsages::KeyCertSign, "keyCertSign"),
+    (KeyUsages::CRLSign, "cRLSign"),
+    (KeyUsages::EncipherOnly, "encipherOnly"),
+    (KeyUsages::DecipherOnly, "decipherOnly"),
+];
+
+impl Serialize for KeyUsage {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let names: Vec<_> = KEY_USAGES
+            .iter()
+            .filter(|(usage, _)| self.0.contains(*usage))
+            .map(|(_, name)| *name)
+            .collect();
+
+        names.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for KeyUsage {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut usages = FlagSet::default();
+        for name in Vec::<String>::deserialize(deserializer)? {
+            match KEY_USAGES.iter().find(|(_, n)| *n == name) {
+                Some((usage, _)) => usages |= *usage,
+                None => {
+                    return Err(de::Error::custom(format_args!(
+                        "unknown key usage `{}`",
+                        name
+                    )))
+                }
+            }
+        }
+
+        Ok(KeyUsage(usages))
+    }
+}
+
+impl Serialize for ExtendedKeyUsage {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        collect(serializer, self.0.iter().map(|oid| Oid(*oid)))
+    }
+}
+
+impl<'de> Deserialize<'de> for ExtendedKeyUsage {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let oids = Vec::<OidModel>::deserialize(deserializer)?;
+        Ok(ExtendedKeyUsage(oids.into_iter().map(Into::into).collect()))
+    }
+}
+
+impl Serialize for SubjectKeyIdentifier<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Hex(self.0.as_bytes()).serialize(serializer)
+    }
+}
+
+impl Serialize for AuthorityKeyIdentifier<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("AuthorityKeyIdentifier", 3)?;
+        match self.key_identifier {
+            Some(id) => s.serialize_field("key_identifier", &Hex(id.as_bytes()))?,
+            None => s.skip_field("key_identifier")?,
+        }
+        match &self.authority_cert_issuer {
+            Some(names) => s.serialize_field("authority_cert_issuer", names)?,
+            None => s.skip_field("authority_cert_issuer")?,
+        }
+        match self.authority_cert_serial_number {
+            Some(serial) => {
+                s.serialize_field("authority_cert_serial_number", &Hex(serial.as_bytes()))?
+            }
+            None => s.skip_field("authority_cert_serial_number")?,
+        }
+        s.end()
+    }
+}
+
+/// An IP address, or an address and mask as found in name constraints.
+struct IpAddress<'a>(&'a [u8]);
+
+impl fmt::Display for IpAddress<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0.len() {
+            4 | 8 => {
+                for (i, byte) in self.0.iter().enumerate() {
+                    match i {
+                        0 => {}
+                        4 => f.write_str("/")?,
+                        _ => f.write_str(".")?,
+                    }
+                    write!(f, "{}", byte)?;
+                }
+                Ok(())
+            }
+            16 | 32 => {
+                for (i, pair) in self.0.chunks(2).enumerate() {
+                    match i {
+                        0 => {}
+                        8 => f.write_str("/")?,
+                        _ => f.write_str(":")?,
+                    }
+                    write!(f, "{:x}", u16::from_be_bytes([pair[0], pair[1]]))?;
+                }
+                Ok(())
+            }
+            _ => Hex(self.0).fmt(f),
+        }
+    }
+}
+
+/// A `GeneralName` is a map with a single entry, whose key is the snake case
+/// name of its form. IP addresses are displayed in the usual text forms,
+/// followed by `/` and the mask within name constraints.
+impl Serialize for GeneralName<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        const NAME: &str = "GeneralName";
+
+        match self {
+            GeneralName::OtherName(other) => {
+                #[derive(Serialize)]
+                struct Model {
+                    type_id: Oid,
+                    value: DerHex,
+                }
+
+                let model = Model {
+                    type_id: Oid(other.type_id),
+                    value: der_hex::<_, S::Error>(&other.value)?,
+                };
+                serializer.serialize_newtype_variant(NAME, 0, "other_name", &model)
+            }
+            GeneralName::Rfc822Name(name) => {
+                serializer.serialize_newtype_variant(NAME, 1, "rfc822_name", name.as_str())
+            }
+            GeneralName::DnsName(name) => {
+                serializer.serialize_newtype_variant(NAME, 2, "dns_name", name.as_str())
+            }
+            GeneralName::DirectoryName(name) => {
+                serializer.serialize_newtype_variant(NAME, 4, "directory_name", name)
+            }
+            GeneralName::EdiPartyName(name) => serializer.serialize_newtype_variant(
+                NAME,
+                5,
+                "edi_party_name",
+                &der_hex::<_, S::Error>(name)?,
+            ),
+            GeneralName::UniformResourceIdentifier(uri) => serializer.serialize_newtype_variant(
+                NAME,
+                6,
+                "uniform_resource_identifier",
+                uri.as_str(),
+            ),
+            GeneralName::IpAddress(addr) => serializer.serialize_newtype_variant(
+                NAME,
+                7,
+                "ip_address",
+                &Str(IpAddress(addr.as_bytes())),
+            ),
+            GeneralName::RegisteredId(oid) => {
+                serializer.serialize_newtype_variant(NAME, 8, "registered_id", &Oid(*oid))
+            }
+        }
+    }
+}
+
+impl Serialize for SubjectAltName<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl Serialize for IssuerAltName<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl Serialize for GeneralSubtree<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("GeneralSubtree", 3)?;
+        s.serialize_field("base", &self.base)?;
+        s.serialize_field("minimum", &self.minimum)?;
+        match self.maximum {
+            Some(maximum) => s.serialize_field("maximum", &maximum)?,
+            None => s.skip_field("maximum")?,
+        }
+        s.end()
+    }
+}
+
+impl Serialize for NameConstraints<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("NameConstraints", 2)?;
+        match &self.permitted_subtrees {
+            Some(subtrees) => s.serialize_field("permitted_subtrees", subtrees)?,
+            None => s.skip_field("permitted_subtrees")?,
+        }
+        match &self.excluded_subtrees {
+            Some(subtrees) => s.serialize_field("excluded_subtrees", subtrees)?,
+            None => s.skip_field("excluded_subtrees")?,
+        }
+        s.end()
+    }
+}
+
+impl Serialize for PolicyConstraints {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("PolicyConstraints", 2)?;
+        match self.require_explicit_policy {
+            Some(skip) => s.serialize_field("require_explicit_policy", &skip)?,
+            None => s.skip_field("require_explicit_policy")?,
+        }
+        match self.inhibit_policy_mapping {
+            Some(skip) => s.serialize_field("inhibit_policy_mapping", &skip)?,
+            None => s.skip_field("inhibit_policy_mapping")?,
+        }
+        s.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for PolicyConstraints {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct Model {
+            require_explicit_policy: Option<u32>,
+            inhibit_policy_mapping: Option<u32>,
+        }
+
+        let model = Model::deserialize(deserializer)?;
+        Ok(PolicyConstraints {
+            require_explicit_policy: model.require_explicit_policy,
+            inhibit_policy_mapping: model.inhibit_policy_mapping,
+        })
+    }
+}
+
+impl Serialize for InhibitAnyPolicy {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for InhibitAnyPolicy {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        u32::deserialize(deserializer).map(InhibitAnyPolicy)
+    }
+}
+
+/// Qualifiers are serialized as the DER encoding of their value.
+impl Serialize for PolicyQualifierInfo<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("PolicyQualifierInfo", 2)?;
+        s.serialize_field("policy_qualifier_id", &Oid(self.policy_qualifier_id))?;
+        match &self.qualifier {
+            Some(qualifier) => {
+                s.serialize_field("qualifier", &der_hex::<_, S::Error>(qualifier)?)?
+            }
+            None => s.skip_field("qualifier")?,
+        }
+        s.end()
+    }
+}
+
+impl Serialize for PolicyInformation<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("PolicyInformation", 2)?;
+        s.serialize_field("policy_identifier", &Oid(self.policy_identifier))?;
+        match &self.policy_qualifiers {
+            Some(qualifiers) => s.serialize_field("policy_qualifiers", qualifiers)?,
+            None => s.skip_field("policy_qualifiers")?,
+        }
+        s.end()
+    }
+}
+
+impl Serialize for CertificatePolicies<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl Serialize for PolicyMapping {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("PolicyMapping", 2)?;
+        s.serialize_field("issuer_domain_policy", &Oid(self.issuer_domain_policy))?;
+        s.serialize_field("subject_domain_policy", &Oid(self.subject_domain_policy))?;
+        s.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for PolicyMapping {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct Model {
+            issuer_domain_policy: OidModel,
+            subject_domain_policy: OidModel,
+        }
+
+        let model = Model::deserialize(deserializer)?;
+        Ok(PolicyMapping {
+            issuer_domain_policy: model.issuer_domain_policy.into(),
+            subject_domain_policy: model.subject_domain_policy.into(),
+        })
+    }
+}
+
+impl Serialize for PolicyMappings {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for PolicyMappings {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::deserialize(deserializer).map(PolicyMappings)
+    }
+}
+
+impl Serialize for AccessDescription<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("AccessDescription", 2)?;
+        s.serialize_field("access_method", &Oid(self.access_method))?;
+        s.serialize_field("access_location", &self.access_location)?;
+        s.end()
+    }
+}
+
+impl Serialize for AuthorityInfoAccessSyntax<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl Serialize for SubjectInfoAccessSyntax<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl Serialize for RevokedCert<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("RevokedCert", 3)?;
+        s.serialize_field("serial_number", &Hex(self.serial_number.as_bytes()))?;
+        s.serialize_field("revocation_date", &self.revocation_date)?;
+        match &self.crl_entry_extensions {
+            Some(extensions) => s.serialize_field("crl_entry_extensions", extensions)?,
+            None => s.skip_field("crl_entry_extensions")?,
+        }
+        s.end()
+    }
+}
+
+impl Serialize for RevokedCertOwned {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_ref().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for RevokedCertOwned {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct Model {
+            serial_number: HexBuf,
+            revocation_date: Time,
+            crl_entry_extensions: Option<Vec<ExtensionOwned>>,
+        }
+
+        let model = Model::deserialize(deserializer)?;
+        Ok(RevokedCertOwned {
+            serial_number: Int::new(&model.serial_number.0).map_err(de::Error::custom)?,
+            revocation_date: model.revocation_date,
+            crl_entry_extensions: model.crl_entry_extensions,
+        })
+    }
+}
+
+impl Serialize for TbsCertList<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("TbsCertList", 7)?;
+        s.serialize_field("version", &self.version)?;
+        s.serialize_field("signature", &Algorithm(&self.signature))?;
+        s.serialize_field("issuer", &self.issuer)?;
+        s.serialize_field("this_update", &self.this_update)?;
+        match &self.next_update {
+            Some(time) => s.serialize_field("next_update", time)?,
+            None => s.skip_field("next_update")?,
+        }
+        match &self.revoked_certificates {
+            Some(revoked) => s.serialize_field("revoked_certificates", revoked)?,
+            None => s.skip_field("revoked_certificates")?,
+        }
+        match &self.crl_extensions {
+            Some(extensions) => s.serialize_field("crl_extensions", extensions)?,
+            None => s.skip_field("crl_extensions")?,
+        }
+        s.end()
+    }
+}
+
+impl Serialize for TbsCertListOwned {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_ref().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for TbsCertListOwned {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct Model {
+            #[serde(default)]
+            version: Version,
+            signature: AlgorithmModel,
+            issuer: NameOwned,
+            this_update: Time,
+            next_update: Option<Time>,
+            revoked_certificates: Option<Vec<RevokedCertOwned>>,
+            crl_extensions: Option<Vec<ExtensionOwned>>,
+        }
+
+        let model = Model::deserialize(deserializer)?;
+        Ok(TbsCertListOwned {
+            version: model.version,
+            signature: model.signature.into_owned()?,
+            issuer: model.issuer,
+            this_update: model.this_update,
+            next_update: model.next_update,
+            revoked_certificates: model.revoked_certificates,
+            crl_extensions: model.crl_extensions,
+        })
+    }
+}
+
+impl Serialize for CertificateList<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("CertificateList", 3)?;
+        s.serialize_field("tbs_cert_list", &self.tbs_cert_list)?;
+        s.serialize_field("signature_algorithm", &Algorithm(&self.signature_algorithm))?;
+        s.serialize_field("signature", &Bits(self.signature))?;
+        s.end()
+    }
+}
+
+impl Serialize for CertificateListOwned {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_ref().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for CertificateListOwned {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct Model {
+            tbs_cert_list: TbsCertListOwned,
+            signature_algorithm: AlgorithmModel,
+            signature: BitsModel,
+        }
+
+        let model = Model::deserialize(deserializer)?;
+        Ok(CertificateListOwned {
+            tbs_cert_list: model.tbs_cert_list,
+            signature_algorithm: model.signature_algorithm.into_owned()?,
+            signature: model.signature.into_owned()?,
+        })
+    }
+}
+
+/// Attribute values are serialized as their DER encoding.
+impl Serialize for Attribute<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let values = self
+            .values
+            .iter()
+            .map(der_hex::<_, S::Error>)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut s = serializer.serialize_struct("Attribute", 2)?;
+        s.serialize_field("oid", &Oid(self.oid))?;
+        s.serialize_field("values", &values)?;
+        s.end()
+    }
+}
+
+impl Serialize for CertReqInfo<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let version = match self.version {
+            request::Version::V1 => "v1",
+        };
+
+        let mut s = serializer.serialize_struct("CertReqInfo", 4)?;
+        s.serialize_field("version", version)?;
+        s.serialize_field("subject", &self.subject)?;
+        s.serialize_field("public_key", &PublicKeyInfo(&self.public_key))?;
+        s.serialize_field("attributes", self.attributes.as_slice())?;
+        s.end()
+    }
+}
+
+impl Serialize for CertReq<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("CertReq", 3)?;
+        s.serialize_field("info", &self.info)?;
+        s.serialize_field("algorithm", &Algorithm(&self.algorithm))?;
+        s.serialize_field("signature", &Bits(self.signature))?;
+        s.end()
+    }
+}
+
+/// Names of the [`CertPolicies`] bits, as in [RFC 5914 Section 2].
+///
+/// [RFC 5914 Section 2]: https://datatracker.ietf.org/doc/html/rfc5914#section-2
+const CERT_POLICIES: [(CertPolicies, &str); 3] = [
+    (CertPolicies::InhibitPolicyMapping, "inhibitPolicyMapping"),
+    (CertPolicies::RequireExplicitPolicy, "requireExplicitPolicy"),
+    (CertPolicies::InhibitAnyPolicy, "inhibitAnyPolicy"),
+];
+
+/// `CertPolicyFlags`, which is a `FlagSet`.
+struct PolicyFlags(CertPolicyFlags);
+
+impl Serialize for PolicyFlags {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let names: Vec<_> = CERT_POLICIES
+            .iter()
+            .filter(|(flag, _)| self.0.contains(*flag))
+            .map(|(_, name)| *name)
+            .collect();
+
+        names.serialize(serializer)
+    }
+}
+
+impl Serialize for CertPathControls<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("CertPathControls", 6)?;
+        s.serialize_field("ta_name", &self.ta_name)?;
+        match &self.certificate {
+            Some(cert) => s.serialize_field("certificate", cert)?,
+            None => s.skip_field("certificate")?,
+        }
+        match &self.policy_set {
+            Some(policies) => s.serialize_field("policy_set", policies)?,
+            None => s.skip_field("policy_set")?,
+        }
+        match self.policy_flags {
+            Some(flags) => s.serialize_field("policy_flags", &PolicyFlags(flags))?,
+            None => s.skip_field("policy_flags")?,
+        }
+        match &self.name_constr {
+            Some(constraints) => s.serialize_field("name_constr", constraints)?,
+            None => s.skip_field("name_constr")?,
+        }
+        match self.path_len_constraint {
+            Some(len) => s.serialize_field("path_len_constraint", &len)?,
+            None => s.skip_field("path_len_constraint")?,
+        }
+        s.end()
+    }
+}
+
+impl Serialize for TrustAnchorInfo<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let version = match self.version {
+            anchor::Version::V1 => "v1",
+        };
+
+        let mut s = serializer.serialize_struct("TrustAnchorInfo", 7)?;
+        s.serialize_field("version", version)?;
+        s.serialize_field("pub_key", &PublicKeyInfo(&self.pub_key))?;
+        s.serialize_field("key_id", &Hex(self.key_id.as_bytes()))?;
+        match &self.ta_title {
+            Some(title) => s.serialize_field("ta_title", title.as_str())?,
+            None => s.skip_field("ta_title")?,
+        }
+        match &self.cert_path {
+            Some(controls) => s.serialize_field("cert_path", controls)?,
+            None => s.skip_field("cert_path")?,
+        }
+        match &self.extensions {
+            Some(extensions) => s.serialize_field("extensions", extensions)?,
+            None => s.skip_field("extensions")?,
+        }
+        match &self.ta_title_lang_tag {
+            Some(tag) => s.serialize_field("ta_title_lang_tag", tag.as_str())?,
+            None => s.skip_field("ta_title_lang_tag")?,
+        }
+        s.end()
+    }
+}
+
+/// Serialized as a map with a single key naming the alternative.
+impl Serialize for TrustAnchorChoice<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            TrustAnchorChoice::Certificate(cert) => {
+                serializer.serialize_newtype_variant("TrustAnchorChoice", 0, "certificate", cert)
+            }
+            TrustAnchorChoice::TbsCertificate(tbs) => {
+                serializer.serialize_newtype_variant("TrustAnchorChoice", 1, "tbs_cert", tbs)
+            }
+            TrustAnchorChoice::TaInfo(info) => {
+                serializer.serialize_newtype_variant("TrustAnchorChoice", 2, "ta_info", info)
+            }
+        }
+    }
+}
@@ -0,0 +1,423 @@
+//! Certificate store with indexed lookup.
+//!
+//! A [`CertStore`] holds [`CertificateOwned`] values and indexes them by
+//! normalized subject name, `SubjectKeyIdentifier`, issuer and serial number
+//! and (with the `fingerprint` feature) SHA-256 fingerprint of the subject
+//! public key. [`CertStore::find_issuers`] uses these indexes to find the
+//! certificates which may have issued a given certificate, which is the
+//! building block for constructing certification paths.
+//!
+//! ```
+//! use x509_cert::Certificate;
+//! use x509_cert::der::Decode;
+//! use x509_cert::name::NameOwned;
+//! use x509_cert::store::CertStore;
+//!
+//! let mut store = CertStore::new();
+//! store.add_der(include_bytes!("../tests/examples/path-root.der")).unwrap();
+//! store.add_der(include_bytes!("../tests/examples/path-ca.der")).unwrap();
+//!
+//! let leaf = Certificate::from_der(include_bytes!("../tests/examples/path-leaf.der")).unwrap();
+//! let issuers = store.find_issuers(&leaf);
+//! assert_eq!(issuers.len(), 1);
+//! assert_eq!(issuers[0].tbs_certificate.subject, NameOwned::from(&leaf.tbs_certificate.issuer));
+//! ```
+//!
+//! Names are compared as described in [`RdnSequence::matches`], i.e. after
+//! the string preparation of RFC 4518.
+//!
+//! [`RdnSequence::matches`]: crate::name::RdnSequence::matches
+
+use crate::ext::pkix::{AuthorityKeyIdentifier, SubjectKeyIdentifier};
+use crate::name::{CanonicalName, Name};
+use crate::{Certificate, CertificateOwned};
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use der::asn1::IntRef;
+use der::Decode;
+
+#[cfg(feature = "pem")]
+use der::{pem::PemLabel, PemReader, Reader};
+
+#[cfg(feature = "fingerprint")]
+use spki::FingerprintBytes;
+
+#[cfg(feature = "std")]
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Store of certificates indexed for lookup.
+#[derive(Clone, Debug, Default)]
+pub struct CertStore {
+    certs: Vec<CertificateOwned>,
+    by_subject: BTreeMap<CanonicalName, Vec<usize>>,
+    by_key_id: BTreeMap<Vec<u8>, Vec<usize>>,
+    by_issuer_serial: BTreeMap<(CanonicalName, Vec<u8>), Vec<usize>>,
+    #[cfg(feature = "fingerprint")]
+    by_fingerprint: BTreeMap<FingerprintBytes, Vec<usize>>,
+}
+
+/// Outcome of adding several certificates to a [`CertStore`] at once.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct LoadReport {
+    /// Number of certificates which were not already present.
+    pub added: usize,
+
+    /// Inputs which could not be decoded, with the corresponding error.
+    pub errors: Vec<(Source, der::Error)>,
+}
+
+/// Location of an input which could not be added to a [`CertStore`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Source {
+    /// Zero-based index of a block within a PEM document.
+    Block(usize),
+
+    /// File within a directory, and the index of the block if the file is
+    /// PEM encoded.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    File {
+        /// Path of the file.
+        path: PathBuf,
+
+        /// Zero-based index of the block within the file.
+        block: Option<usize>,
+    },
+}
+
+impl CertStore {
+    /// Creates an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of certificates in the store.
+    pub fn len(&self) -> usize {
+        self.certs.len()
+    }
+
+    /// Returns whether the store is empty.
+    pub fn is_empty(&self) -> bool {
+        self.certs.is_empty()
+    }
+
+    /// Iterates over the certificates in the order they were added.
+    pub fn iter(&self) -> impl '_ + ExactSizeIterator<Item = &CertificateOwned> {
+        self.certs.iter()
+    }
+
+    /// Adds `cert` to the store.
+    ///
+    /// Returns `false` if the certificate was already present.
+    pub fn add(&mut self, cert: CertificateOwned) -> bool {
+        let tbs = &cert.tbs_certificate;
+        let issuer_serial = (
+            tbs.issuer.canonical(),
+            tbs.serial_number.as_bytes().to_vec(),
+        );
+
+        if let Some(indices) = self.by_issuer_serial.get(&issuer_serial) {
+            if indices.iter().any(|&i| self.certs[i] == cert) {
+                return false;
+            }
+        }
+
+        let index = self.certs.len();
+        let tbs = tbs.as_ref();
+
+        insert(&mut self.by_issuer_serial, issuer_serial, index);
+        insert(&mut self.by_subject, tbs.subject.canonical(), index);
+
+        if let Some(key_id) = subject_key_id(&tbs) {
+            insert(&mut self.by_key_id, key_id.to_vec(), index);
+        }
+
+        #[cfg(feature = "fingerprint")]
+        if let Ok(fingerprint) = tbs.subject_public_key_info.fingerprint_bytes() {
+            insert(&mut self.by_fingerprint, fingerprint, index);
+        }
+
+        self.certs.push(cert);
+        true
+    }
+
+    /// Decodes a DER encoded certificate and adds it to the store.
+    ///
+    /// Returns `false` if the certificate was already present.
+    pub fn add_der(&mut self, der: &[u8]) -> der::Result<bool> {
+        Ok(self.add(CertificateOwned::from_der(der)?))
+    }
+
+    /// Decodes the certificates of a PEM document and adds them to the
+    /// store.
+    ///
+    /// The document may contain several concatenated certificates, as in a
+    /// CA bundle. Text outside of the encapsulation boundaries and blocks
+    /// with a label other than `CERTIFICATE` are ignored. A block which
+    /// cannot be decoded does not prevent the remaining blocks from being
+    /// added, but is recorded in the returned [`LoadReport`].
+    #[cfg(feature = "pem")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "pem")))]
+    pub fn add_pem(&mut self, pem: &[u8]) -> LoadReport {
+        let mut report = LoadReport::default();
+
+        for (index, block) in pem_blocks(pem).enumerate() {
+            match self.add_pem_block(block) {
+                Ok(added) => report.added += usize::from(added),
+                Err(error) => report.errors.push((Source::Block(index), error)),
+            }
+        }
+
+        report
+    }
+
+    /// Decodes a single PEM encapsulated certificate and adds it to the
+    /// store, returning `false` if it was already present or is not a
+    /// certificate.
+    #[cfg(feature = "pem")]
+    fn add_pem_block(&mut self, block: &[u8]) -> der::Result<bool> {
+        let mut reader = PemReader::new(block)?;
+        if reader.type_label() != CertificateOwned::PEM_LABEL {
+            return Ok(false);
+        }
+
+        let der = reader.read_vec(reader.input_len())?;
+        self.add_der(&der)
+    }
+
+    /// Reads a DER or PEM encoded file and adds its certificates to the
+    /// store.
+    ///
+    /// An error is returned if the file cannot be read, or if it is DER
+    /// encoded and does not contain a certificate. The blocks of a PEM file
+    /// which cannot be decoded are recorded in the returned [`LoadReport`].
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn add_file(&mut self, path: impl AsRef<Path>) -> der::Result<LoadReport> {
+        let bytes = fs::read(path)?;
+
+        #[cfg(feature = "pem")]
+        if find(&bytes, b"-----BEGIN ").is_some() {
+            return Ok(self.add_pem(&bytes));
+        }
+
+        Ok(LoadReport {
+            added: self.add_der(&bytes)?.into(),
+            errors: Vec::new(),
+        })
+    }
+
+    /// Adds the certificates of each file in a directory to the store.
+    ///
+    /// Subdirectories are skipped. Files (or PEM blocks) which cannot be
+    /// decoded do not prevent the remaining ones from being added, so that
+    /// directories such as `/etc/ssl/certs` can be loaded, but are recorded
+    /// in the returned [`LoadReport`]. An error is only returned if the
+    /// directory itself cannot be read.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn add_dir(&mut self, path: impl AsRef<Path>) -> der::Result<LoadReport> {
+        let mut report = LoadReport::default();
+
+        for entry in fs::read_dir(path)? {
+            let path = entry?.path();
+            if !path.is_file() {
+                continue;
+            }
+
+            match self.add_file(&path) {
+                Ok(file) => {
+                    report.added += file.added;
+                    report.errors.extend(file.errors.into_iter().map(
+                        |(source, error)| match source {
+                            Source::Block(index) => {
+                                let path = path.clone();
+                                (
+                                    Source::File {
+                                        path,
+                                        block: Some(index),
+                                    },
+                                    error,
+                                )
+                            }
+                            source => (source, error),
+                        },
+                    ));
+                }
+                Err(error) => report
+                    .errors
+                    .push((Source::File { path, block: None }, error)),
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Finds the certificates with a subject matching `name`.
+    pub fn find_by_subject<'a>(
+        &'a self,
+        name: &Name<'_>,
+    ) -> impl 'a + Iterator<Item = &'a CertificateOwned> {
+        self.lookup(self.by_subject.get(&name.canonical()))
+    }
+
+    /// Finds the certificates with a `SubjectKeyIdentifier` of `key_id`.
+    pub fn find_by_key_id<'a>(
+        &'a self,
+        key_id: &[u8],
+    ) -> impl 'a + Iterator<Item = &'a CertificateOwned> {
+        self.lookup(self.by_key_id.get(key_id))
+    }
+
+    /// Finds the certificates issued by `issuer` with `serial_number`.
+    ///
+    /// Conforming CAs never reuse a serial number, so at most one
+    /// certificate is expected.
+    pub fn find_by_issuer_serial<'a>(
+        &'a self,
+        issuer: &Name<'_>,
+        serial_number: &IntRef<'_>,
+    ) -> impl 'a + Iterator<Item = &'a CertificateOwned> {
+        let key = (issuer.canonical(), serial_number.as_bytes().to_vec());
+        self.lookup(self.by_issuer_serial.get(&key))
+    }
+
+    /// Finds the certificates whose subject public key has the SHA-256
+    /// `fingerprint`, as calculated by
+    /// [`SubjectPublicKeyInfo::fingerprint_bytes`](spki::SubjectPublicKeyInfo::fingerprint_bytes).
+    #[cfg(feature = "fingerprint")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "fingerprint")))]
+    pub fn find_by_spki_fingerprint<'a>(
+        &'a self,
+        fingerprint: &FingerprintBytes,
+    ) -> impl 'a + Iterator<Item = &'a CertificateOwned> {
+        self.lookup(self.by_fingerprint.get(fingerprint))
+    }
+
+    /// Finds the certificates which may have issued `cert`.
+    ///
+    /// Candidates have a subject matching the issuer of `cert`. If `cert`
+    /// has an `AuthorityKeyIdentifier`, candidates contradicting it are
+    /// excluded and those matching its key identifier or issuer and serial
+    /// number are returned first. The signature of `cert` is not verified.
+    pub fn find_issuers(&self, cert: &Certificate<'_>) -> Vec<&CertificateOwned> {
+        let akid = cert
+            .tbs_certificate
+            .get::<AuthorityKeyIdentifier<'_>>()
+            .ok()
+            .flatten()
+            .map(|(_, akid)| akid);
+
+        let mut ranked: Vec<_> = self
+            .find_by_subject(&cert.tbs_certificate.issuer)
+            .filter_map(|candidate| {
+                let rank = match &akid {
+                    Some(akid) => key_id_rank(akid, &candidate.as_ref())?,
+                    None => 0,
+                };
+                Some((rank, candidate))
+            })
+            .collect();
+
+        // Stable, so that candidates of equal rank remain in insertion order
+        ranked.sort_by_key(|(rank, _)| core::cmp::Reverse(*rank));
+        ranked.into_iter().map(|(_, candidate)| candidate).collect()
+    }
+
+    fn lookup<'a>(
+        &'a self,
+        indices: Option<&'a Vec<usize>>,
+    ) -> impl 'a + Iterator<Item = &'a CertificateOwned> {
+        indices
+            .into_iter()
+            .flatten()
+            .map(move |&index| &self.certs[index])
+    }
+}
+
+impl Extend<CertificateOwned> for CertStore {
+    fn extend<T: IntoIterator<Item = CertificateOwned>>(&mut self, iter: T) {
+        for cert in iter {
+            self.add(cert);
+        }
+    }
+}
+
+impl FromIterator<CertificateOwned> for CertStore {
+    fn from_iter<T: IntoIterator<Item = CertificateOwned>>(iter: T) -> Self {
+        let mut store = Self::new();
+        store.extend(iter);
+        store
+    }
+}
+
+fn insert<K: Ord>(index: &mut BTreeMap<K, Vec<usize>>, key: K, value: usize) {
+    index.entry(key).or_default().push(value);
+}
+
+fn subject_key_id<'a>(tbs: &'a crate::TbsCertificate<'a>) -> Option<&'a [u8]> {
+    let (_, skid) = tbs.get::<SubjectKeyIdentifier<'_>>().ok().flatten()?;
+    Some(skid.0.as_bytes())
+}
+
+/// Ranks `candidate` as the issuer identified by `akid`.
+///
+/// Returns `None` if the candidate contradicts `akid`, `Some(1)` if it
+/// matches the key identifier or issuer and serial number, and `Some(0)` if
+/// neither can be compared.
+fn key_id_rank(akid: &AuthorityKeyIdentifier<'_>, candidate: &Certificate<'_>) -> Option<u8> {
+    let tbs = &candidate.tbs_certificate;
+    let mut rank = 0;
+
+    if let (Some(key_id), Some(skid)) = (akid.key_identifier, subject_key_id(tbs)) {
+        if key_id.as_bytes() != skid {
+            return None;
+        }
+        rank = 1;
+    }
+
+    if let Some(serial) = akid.authority_cert_serial_number {
+        if serial != tbs.serial_number {
+            return None;
+        }
+        rank = 1;
+    }
+
+    Some(rank)
+}
+
+/// Splits a PEM document into its encapsulated blocks, which are then
+/// decoded with a [`PemReader`].
+///
+/// A block which lacks a post-encapsulation boundary extends to the end of
+/// the document, so that it is reported as invalid rather than ignored.
+#[cfg(feature = "pem")]
+fn pem_blocks(mut pem: &[u8]) -> impl '_ + Iterator<Item = &[u8]> {
+    const BEGIN: &[u8] = b"-----BEGIN ";
+    const END: &[u8] = b"-----END ";
+    const DASHES: &[u8] = b"-----";
+
+    core::iter::from_fn(move || {
+        let start = find(pem, BEGIN)?;
+        let end = find(&pem[start..], END)
+            .map(|end| end + start + END.len())
+            .and_then(|end| Some(find(&pem[end..], DASHES)? + end + DASHES.len()))
+            .unwrap_or(pem.len());
+
+        let block = &pem[start..end];
+        pem = &pem[end..];
+        Some(block)
+    })
+}
+
+#[cfg(feature = "pem")]
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
@@ -8,6 +8,12 @@ use der::{Choice, DateTime, Decode, Error, Result, Sequence};
 #[cfg(feature = "std")]
 use std::time::SystemTime;
 
+#[cfg(feature = "time")]
+use der::time::OffsetDateTime;
+
+#[cfg(feature = "chrono")]
+use der::chrono::{self, Utc};
+
 /// X.501 `Time` as defined in [RFC 5280 Section 4.1.2.5].
 ///
 /// Schema definition from [RFC 5280 Appendix A]:
@@ -33,7 +39,18 @@ pub enum Time {
 }
 
 impl Time {
-    /// Get duration since `UNIX_EPOCH`.
+    /// The `99991231235959Z` value which [RFC 5280 Section 4.1.2.5] assigns
+    /// to `notAfter` when a certificate has no well-defined expiration date.
+    ///
+    /// [RFC 5280 Section 4.1.2.5]: https://datatracker.ietf.org/doc/html/rfc5280#section-4.1.2.5
+    pub const INFINITY: Time = Time::GeneralTime(GeneralizedTime::INFINITY);
+
+    /// Is this the [`Time::INFINITY`] value?
+    pub fn is_infinity(&self) -> bool {
+        *self == Self::INFINITY
+    }
+
+    /// Get duration since `UNIX_EPOCH`, which is zero for times before it.
     pub fn to_unix_duration(self) -> Duration {
         match self {
             Time::UtcTime(t) => t.to_unix_duration(),
@@ -104,6 +121,46 @@ impl TryFrom<SystemTime> for Time {
     }
 }
 
+#[cfg(feature = "time")]
+#[cfg_attr(docsrs, doc(cfg(feature = "time")))]
+impl TryFrom<Time> for OffsetDateTime {
+    type Error = Error;
+
+    fn try_from(time: Time) -> Result<OffsetDateTime> {
+        time.to_date_time().try_into()
+    }
+}
+
+#[cfg(feature = "time")]
+#[cfg_attr(docsrs, doc(cfg(feature = "time")))]
+impl TryFrom<OffsetDateTime> for Time {
+    type Error = Error;
+
+    fn try_from(time: OffsetDateTime) -> Result<Time> {
+        Ok(GeneralizedTime::try_from(time)?.into())
+    }
+}
+
+#[cfg(feature = "chrono")]
+#[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+impl TryFrom<Time> for chrono::DateTime<Utc> {
+    type Error = Error;
+
+    fn try_from(time: Time) -> Result<chrono::DateTime<Utc>> {
+        time.to_date_time().try_into()
+    }
+}
+
+#[cfg(feature = "chrono")]
+#[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+impl TryFrom<chrono::DateTime<Utc>> for Time {
+    type Error = Error;
+
+    fn try_from(time: chrono::DateTime<Utc>) -> Result<Time> {
+        Ok(GeneralizedTime::try_from(time)?.into())
+    }
+}
+
 /// X.501 `Validity` as defined in [RFC 5280 Section 4.1.2.5]
 ///
 /// ```text
@@ -123,6 +180,21 @@ pub struct Validity {
 }
 
 impl Validity {
+    /// Creates a `Validity` which starts at `not_before` and has no
+    /// well-defined expiration date, i.e. whose `notAfter` is
+    /// [`Time::INFINITY`].
+    pub fn without_expiration(not_before: Time) -> Self {
+        Self {
+            not_before,
+            not_after: Time::INFINITY,
+        }
+    }
+
+    /// Does this `Validity` have a well-defined expiration date?
+    pub fn has_expiration(&self) -> bool {
+        !self.not_after.is_infinity()
+    }
+
     /// Creates a `Validity` which starts now and lasts for `duration`.
     #[cfg(feature = "std")]
     #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
@@ -0,0 +1,261 @@
+//! Signature verification for signed X.509 structures.
+//!
+//! [`Certificate`], [`CertificateList`] and [`CertReq`] are all instances of
+//! the `SIGNED{}` parameterized type from [RFC 5912 Section 14]:
+//!
+//! ```text
+//! SIGNED{ToBeSigned} ::= SEQUENCE {
+//!     toBeSigned           ToBeSigned,
+//!     algorithmIdentifier  SEQUENCE {
+//!         algorithm        SIGNATURE-ALGORITHM.&id({SignatureAlgorithms}),
+//!         parameters       SIGNATURE-ALGORITHM.&Params({SignatureAlgorithms}{@algorithmIdentifier.algorithm}) OPTIONAL
+//!     },
+//!     signature BIT STRING (CONTAINING SIGNATURE-ALGORITHM.&Value({SignatureAlgorithms}{@algorithmIdentifier.algorithm}))
+//! }
+//! ```
+//!
+//! Signatures are computed over the DER encoding of `toBeSigned` exactly as
+//! it appears in the signed structure, which is not guaranteed to match a
+//! re-encoding of the decoded value. [`Signed`] retains those bytes
+//! alongside the decoded value so that they can be passed as-is to a
+//! [`SignatureVerifier`].
+//!
+//! This crate does not implement any signature algorithms itself.
+//!
+//! [RFC 5912 Section 14]: https://datatracker.ietf.org/doc/html/rfc5912#section-14
+
+use crate::certificate::{Certificate, TbsCertificate};
+use crate::crl::{CertificateList, TbsCertList};
+use crate::request::{CertReq, CertReqInfo};
+
+use core::fmt;
+
+use const_oid::ObjectIdentifier;
+use der::asn1::BitStringRef;
+use der::{
+    Decode, DecodeValue, Encode, EncodeValue, FixedTag, Header, Length, Reader, Tag, Writer,
+};
+use spki::{AlgorithmIdentifier, SubjectPublicKeyInfo};
+
+/// Result type with the `verify` module's [`Error`] type.
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// Signature verification errors.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    /// ASN.1 DER-related errors.
+    Asn1(der::Error),
+
+    /// The signature algorithm inside the signed data does not match the
+    /// signature algorithm of the enclosing structure.
+    AlgorithmMismatch,
+
+    /// The signature is not a whole number of bytes.
+    SignatureLength,
+
+    /// The signature or public key algorithm is not supported by the
+    /// verifier.
+    UnsupportedAlgorithm(ObjectIdentifier),
+
+    /// The signature is invalid.
+    Verification,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Asn1(err) => write!(f, "ASN.1 error: {}", err),
+            Error::AlgorithmMismatch => f.write_str("signature algorithm mismatch"),
+            Error::SignatureLength => f.write_str("signature has unused bits"),
+            Error::UnsupportedAlgorithm(oid) => write!(f, "unsupported algorithm: {}", oid),
+            Error::Verification => f.write_str("signature verification failed"),
+        }
+    }
+}
+
+impl From<der::Error> for Error {
+    fn from(err: der::Error) -> Error {
+        Error::Asn1(err)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+/// Signature verification backend.
+///
+/// Implementations select the signature scheme from the `algorithm`
+/// identifier and the key from `public_key`, returning
+/// [`Error::UnsupportedAlgorithm`] for combinations they do not handle.
+pub trait SignatureVerifier {
+    /// Verify that `signature` is a valid signature over `msg` made with the
+    /// private key corresponding to `public_key`.
+    fn verify(
+        &self,
+        algorithm: &AlgorithmIdentifier<'_>,
+        public_key: &SubjectPublicKeyInfo<'_>,
+        msg: &[u8],
+        signature: &[u8],
+    ) -> Result<()>;
+}
+
+/// A signed structure which retains the original encoding of the data
+/// covered by its signature.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Signed<'a, T> {
+    /// Decoded `toBeSigned` value.
+    pub tbs: T,
+
+    /// Signature algorithm identifier.
+    pub signature_algorithm: AlgorithmIdentifier<'a>,
+
+    /// Signature.
+    pub signature: BitStringRef<'a>,
+
+    tbs_bytes: &'a [u8],
+}
+
+/// [`Certificate`] retaining the encoding of its `tbsCertificate`.
+pub type SignedCertificate<'a> = Signed<'a, TbsCertificate<'a>>;
+
+/// [`CertificateList`] retaining the encoding of its `tbsCertList`.
+pub type SignedCertificateList<'a> = Signed<'a, TbsCertList<'a>>;
+
+/// [`CertReq`] retaining the encoding of its `certificationRequestInfo`.
+pub type SignedCertReq<'a> = Signed<'a, CertReqInfo<'a>>;
+
+impl<'a, T> Signed<'a, T> {
+    /// The DER encoding of `toBeSigned` as it appeared in the input.
+    pub fn tbs_bytes(&self) -> &'a [u8] {
+        self.tbs_bytes
+    }
+
+    /// Verify the signature with `public_key` using `verifier`.
+    pub fn verify<V>(&self, public_key: &SubjectPublicKeyInfo<'_>, verifier: &V) -> Result<()>
+    where
+        V: SignatureVerifier + ?Sized,
+    {
+        let signature = self.signature.as_bytes().ok_or(Error::SignatureLength)?;
+
+        verifier.verify(
+            &self.signature_algorithm,
+            public_key,
+            self.tbs_bytes,
+            signature,
+        )
+    }
+}
+
+impl<'a> SignedCertificate<'a> {
+    /// Verify that this certificate was signed by the subject of `issuer`.
+    ///
+    /// The `signature` field of the `tbsCertificate` must match the
+    /// `signatureAlgorithm` of the certificate.
+    pub fn verify_signed_by<V>(&self, issuer: &Certificate<'_>, verifier: &V) -> Result<()>
+    where
+        V: SignatureVerifier + ?Sized,
+    {
+        if self.tbs.signature != self.signature_algorithm {
+            return Err(Error::AlgorithmMismatch);
+        }
+
+        self.verify(&issuer.tbs_certificate.subject_public_key_info, verifier)
+    }
+
+    /// Convert into a [`Certificate`], discarding the original encoding.
+    pub fn certificate(&self) -> Certificate<'a> {
+        Certificate {
+            tbs_certificate: self.tbs.clone(),
+            signature_algorithm: self.signature_algorithm,
+            signature: self.signature,
+        }
+    }
+}
+
+impl<'a> SignedCertificateList<'a> {
+    /// Verify that this CRL was signed by the subject of `issuer`.
+    ///
+    /// The `signature` field of the `tbsCertList` must match the
+    /// `signatureAlgorithm` of the CRL.
+    pub fn verify_signed_by<V>(&self, issuer: &Certificate<'_>, verifier: &V) -> Result<()>
+    where
+        V: SignatureVerifier + ?Sized,
+    {
+        if self.tbs.signature != self.signature_algorithm {
+            return Err(Error::AlgorithmMismatch);
+        }
+
+        self.verify(&issuer.tbs_certificate.subject_public_key_info, verifier)
+    }
+
+    /// Convert into a [`CertificateList`], discarding the original encoding.
+    pub fn certificate_list(&self) -> CertificateList<'a> {
+        CertificateList {
+            tbs_cert_list: self.tbs.clone(),
+            signature_algorithm: self.signature_algorithm,
+            signature: self.signature,
+        }
+    }
+}
+
+impl<'a> SignedCertReq<'a> {
+    /// Verify the signature with the public key of the request itself, as
+    /// proof of possession of the corresponding private key.
+    pub fn verify_self_signed<V>(&self, verifier: &V) -> Result<()>
+    where
+        V: SignatureVerifier + ?Sized,
+    {
+        self.verify(&self.tbs.public_key, verifier)
+    }
+
+    /// Convert into a [`CertReq`], discarding the original encoding.
+    pub fn cert_req(&self) -> CertReq<'a> {
+        CertReq {
+            info: self.tbs.clone(),
+            algorithm: self.signature_algorithm,
+            signature: self.signature,
+        }
+    }
+}
+
+impl<'a, T: Decode<'a>> DecodeValue<'a> for Signed<'a, T> {
+    fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> der::Result<Self> {
+        reader.read_nested(header.length, |reader| {
+            let tbs_bytes = reader.tlv_bytes()?;
+
+            Ok(Self {
+                tbs: T::from_der(tbs_bytes)?,
+                signature_algorithm: reader.decode()?,
+                signature: reader.decode()?,
+                tbs_bytes,
+            })
+        })
+    }
+}
+
+impl<'a, T> EncodeValue for Signed<'a, T> {
+    fn value_len(&self) -> der::Result<Length> {
+        Length::try_from(self.tbs_bytes.len())?
+            + self.signature_algorithm.encoded_len()?
+            + self.signature.encoded_len()?
+    }
+
+    fn encode_value(&self, writer: &mut dyn Writer) -> der::Result<()> {
+        writer.write(self.tbs_bytes)?;
+        self.signature_algorithm.encode(writer)?;
+        self.signature.encode(writer)
+    }
+}
+
+impl<'a, T> FixedTag for Signed<'a, T> {
+    const TAG: Tag = Tag::Sequence;
+}
+
+impl<'a, T: Decode<'a>> TryFrom<&'a [u8]> for Signed<'a, T> {
+    type Error = der::Error;
+
+    fn try_from(bytes: &'a [u8]) -> der::Result<Self> {
+        Self::from_der(bytes)
+    }
+}
@@ -0,0 +1,299 @@
+//! Certificate builder tests
+
+mod common;
+
+use common::EcdsaP256Verifier;
+use const_oid::db::rfc5912::{ECDSA_WITH_SHA_256, ID_EC_PUBLIC_KEY, SECP_256_R_1};
+use der::asn1::{Ia5StringRef, OctetStringRef};
+use der::{DateTime, Decode};
+use p256::ecdsa::signature::{Signer as _, Verifier as _};
+use p256::ecdsa::{Signature, SigningKey};
+use spki::{AlgorithmIdentifier, SubjectPublicKeyInfo};
+use x509_cert::anchor::{TrustAnchorInfo, Version};
+use x509_cert::builder::{CertReqBuilder, CertificateBuilder, CrlBuilder, Error, Result, Signer};
+use x509_cert::ext::pkix::name::DirectoryString;
+use x509_cert::ext::pkix::name::GeneralName;
+use x509_cert::ext::pkix::{
+    AuthorityKeyIdentifier, BasicConstraints, KeyUsage, KeyUsages, SubjectAltName,
+};
+use x509_cert::name::Name;
+use x509_cert::path::{validate_path, ValidationParams};
+use x509_cert::request::CertReq;
+use x509_cert::time::{Time, Validity};
+use x509_cert::verify::{SignedCertReq, SignedCertificate};
+use x509_cert::Certificate;
+
+/// ECDSA P-256 with SHA-256 signer.
+struct EcdsaP256(SigningKey);
+
+impl EcdsaP256 {
+    fn new(seed: u8) -> Self {
+        Self(SigningKey::from_bytes(&[seed; 32]).unwrap())
+    }
+
+    fn public_key(&self) -> Vec<u8> {
+        self.0
+            .verifying_key()
+            .to_encoded_point(false)
+            .as_bytes()
+            .to_vec()
+    }
+}
+
+impl Signer for EcdsaP256 {
+    fn signature_algorithm(&self) -> AlgorithmIdentifier<'_> {
+        AlgorithmIdentifier {
+            oid: ECDSA_WITH_SHA_256,
+            parameters: None,
+        }
+    }
+
+    fn sign(&self, msg: &[u8]) -> Result<Vec<u8>> {
+        let signature: Signature = self.0.try_sign(msg).map_err(|_| Error::Signature)?;
+        Ok(signature.to_der().as_bytes().to_vec())
+    }
+}
+
+fn spki(key: &[u8]) -> SubjectPublicKeyInfo<'_> {
+    SubjectPublicKeyInfo {
+        algorithm: AlgorithmIdentifier {
+            oid: ID_EC_PUBLIC_KEY,
+            parameters: Some((&SECP_256_R_1).into()),
+        },
+        subject_public_key: key,
+    }
+}
+
+fn validity() -> Validity {
+    Validity {
+        not_before: Time::GeneralTime(DateTime::new(2022, 1, 1, 0, 0, 0).unwrap().into()),
+        not_after: Time::GeneralTime(DateTime::new(2032, 1, 1, 0, 0, 0).unwrap().into()),
+    }
+}
+
+#[test]
+fn build_chain() {
+    let root_key = EcdsaP256::new(1);
+    let root_pub = root_key.public_key();
+    let root_name = Name::encode_from_string("CN=Builder Root").unwrap();
+    let root_name = Name::from_der(&root_name).unwrap();
+
+    let root_der = CertificateBuilder::new(&[1], validity(), &root_name, &spki(&root_pub))
+        .unwrap()
+        .basic_constraints(BasicConstraints {
+            ca: true,
+            path_len_constraint: None,
+        })
+        .unwrap()
+        .key_usage(KeyUsage(KeyUsages::KeyCertSign | KeyUsages::CRLSign))
+        .unwrap()
+        .subject_key_identifier(&[0x11; 20])
+        .unwrap()
+        .build(&root_key)
+        .unwrap();
+    let root = Certificate::from_der(&root_der).unwrap();
+
+    assert_eq!(root.tbs_certificate.issuer, root_name);
+    assert_eq!(root.tbs_certificate.subject, root_name);
+    assert_eq!(root.tbs_certificate.serial_number.as_bytes(), &[1]);
+    assert_eq!(root.tbs_certificate.validity, validity());
+
+    let leaf_key = EcdsaP256::new(2);
+    let leaf_pub = leaf_key.public_key();
+    let dns = GeneralName::DnsName(Ia5StringRef::new("leaf.example.com").unwrap());
+
+    let leaf_der = CertificateBuilder::new(
+        &[0x00, 0x80],
+        validity(),
+        &Name::default(),
+        &spki(&leaf_pub),
+    )
+    .unwrap()
+    .issued_by(&root)
+    .unwrap()
+    .subject_alt_name(core::slice::from_ref(&dns))
+    .unwrap()
+    .build(&root_key)
+    .unwrap();
+    let leaf = Certificate::from_der(&leaf_der).unwrap();
+
+    assert_eq!(leaf.tbs_certificate.issuer, root_name);
+    assert_eq!(leaf.tbs_certificate.serial_number.as_bytes(), &[0x00, 0x80]);
+
+    let (critical, san) = leaf
+        .tbs_certificate
+        .get::<SubjectAltName<'_>>()
+        .unwrap()
+        .unwrap();
+    assert!(critical);
+    assert_eq!(san.0, [dns]);
+
+    let (critical, akid) = leaf
+        .tbs_certificate
+        .get::<AuthorityKeyIdentifier<'_>>()
+        .unwrap()
+        .unwrap();
+    assert!(!critical);
+    assert_eq!(
+        akid.key_identifier,
+        Some(OctetStringRef::new(&[0x11; 20]).unwrap())
+    );
+
+    // signatures are over the encoded tbsCertificate
+    let signed = SignedCertificate::from_der(&leaf_der).unwrap();
+    let signature = Signature::from_der(signed.signature.raw_bytes()).unwrap();
+    assert!(root_key
+        .0
+        .verifying_key()
+        .verify(signed.tbs_bytes(), &signature)
+        .is_ok());
+
+    let anchor = TrustAnchorInfo {
+        version: Version::V1,
+        pub_key: root.tbs_certificate.subject_public_key_info,
+        key_id: OctetStringRef::new(&[0x11; 20]).unwrap(),
+        ta_title: None,
+        cert_path: None,
+        extensions: None,
+        ta_title_lang_tag: None,
+    };
+    let params = ValidationParams::new(DateTime::new(2025, 1, 1, 0, 0, 0).unwrap());
+    assert!(validate_path(&[&signed], &anchor, &params, &EcdsaP256Verifier).is_ok());
+}
+
+#[test]
+fn build_duplicate_extension() {
+    let key = EcdsaP256::new(3);
+    let public_key = key.public_key();
+    let constraints = BasicConstraints {
+        ca: false,
+        path_len_constraint: None,
+    };
+
+    let builder = CertificateBuilder::new(&[1], validity(), &Name::default(), &spki(&public_key))
+        .unwrap()
+        .basic_constraints(constraints.clone())
+        .unwrap();
+
+    assert_eq!(
+        builder.basic_constraints(constraints),
+        Err(Error::DuplicateExtension(
+            const_oid::db::rfc5280::ID_CE_BASIC_CONSTRAINTS
+        ))
+    );
+}
+
+#[test]
+fn reject_non_positive_serial_number() {
+    let key = EcdsaP256::new(3);
+    let public_key = key.public_key();
+
+    for serial_number in [&[][..], &[0], &[0, 0]] {
+        assert_eq!(
+            CertificateBuilder::new(
+                serial_number,
+                validity(),
+                &Name::default(),
+                &spki(&public_key)
+            ),
+            Err(Error::InvalidSerialNumber)
+        );
+
+        let crl = CrlBuilder::new(&Name::default(), validity().not_before).unwrap();
+        assert_eq!(
+            crl.revoke(serial_number, validity().not_before, None),
+            Err(Error::InvalidSerialNumber)
+        );
+    }
+
+    assert!(
+        CertificateBuilder::new(&[0, 1], validity(), &Name::default(), &spki(&public_key)).is_ok()
+    );
+}
+
+#[test]
+fn issue_from_cert_req() {
+    let ca_key = EcdsaP256::new(4);
+    let ca_pub = ca_key.public_key();
+    let ca_name = Name::encode_from_string("CN=Builder CA").unwrap();
+    let ca_name = Name::from_der(&ca_name).unwrap();
+    let ca_der = CertificateBuilder::new(&[1], validity(), &ca_name, &spki(&ca_pub))
+        .unwrap()
+        .build(&ca_key)
+        .unwrap();
+    let ca = Certificate::from_der(&ca_der).unwrap();
+
+    let key = EcdsaP256::new(5);
+    let public_key = key.public_key();
+    let subject = Name::encode_from_string("CN=Requester").unwrap();
+    let subject = Name::from_der(&subject).unwrap();
+    let dns = GeneralName::DnsName(Ia5StringRef::new("requester.example.com").unwrap());
+
+    let req_der = CertReqBuilder::new(&subject, &spki(&public_key))
+        .unwrap()
+        .challenge_password("s3cret")
+        .unwrap()
+        .key_usage(KeyUsage(KeyUsages::DigitalSignature.into()))
+        .unwrap()
+        .subject_alt_name(core::slice::from_ref(&dns))
+        .unwrap()
+        .build(&key)
+        .unwrap();
+
+    let signed = SignedCertReq::from_der(&req_der).unwrap();
+    let signature = Signature::from_der(signed.signature.raw_bytes()).unwrap();
+    assert!(key
+        .0
+        .verifying_key()
+        .verify(signed.tbs_bytes(), &signature)
+        .is_ok());
+
+    let req = CertReq::from_der(&req_der).unwrap();
+    assert_eq!(req.info.subject, subject);
+    match req.info.challenge_password().unwrap().unwrap() {
+        DirectoryString::PrintableString(password) => assert_eq!(password.as_str(), "s3cret"),
+        other => panic!("unexpected challenge password: {:?}", other),
+    }
+    assert_eq!(req.info.extension_req().unwrap().unwrap().0.len(), 2);
+
+    let cert_der = CertificateBuilder::from_cert_req(&[2], validity(), &req)
+        .unwrap()
+        .issued_by(&ca)
+        .unwrap()
+        .requested_extensions(&req)
+        .unwrap()
+        .build(&ca_key)
+        .unwrap();
+    let cert = Certificate::from_der(&cert_der).unwrap();
+
+    assert_eq!(cert.tbs_certificate.subject, subject);
+    assert_eq!(cert.tbs_certificate.issuer, ca_name);
+    assert_eq!(
+        cert.tbs_certificate.subject_public_key_info,
+        req.info.public_key
+    );
+
+    let (critical, usage) = cert.tbs_certificate.get::<KeyUsage>().unwrap().unwrap();
+    assert!(critical);
+    assert_eq!(usage, KeyUsage(KeyUsages::DigitalSignature.into()));
+
+    let (critical, san) = cert
+        .tbs_certificate
+        .get::<SubjectAltName<'_>>()
+        .unwrap()
+        .unwrap();
+    assert!(!critical);
+    assert_eq!(san.0, [dns]);
+
+    // requested extensions cannot replace ones added by the CA
+    assert_eq!(
+        CertificateBuilder::from_cert_req(&[3], validity(), &req)
+            .unwrap()
+            .key_usage(KeyUsage(KeyUsages::KeyCertSign.into()))
+            .unwrap()
+            .requested_extensions(&req),
+        Err(Error::DuplicateExtension(
+            const_oid::db::rfc5280::ID_CE_KEY_USAGE
+        ))
+    );
+}
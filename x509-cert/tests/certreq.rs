@@ -68,6 +68,8 @@ fn decode_rsa_2048_der() {
         assert_eq!(ext.extn_value, *val);
         assert!(!ext.critical);
     }
+    assert_eq!(cr.info.extension_req().unwrap().unwrap().0, extensions);
+    assert_eq!(cr.info.challenge_password(), Ok(None));
 
     // Check the signature value.
     assert_eq!(cr.algorithm.oid, "1.2.840.113549.1.1.11".parse().unwrap());
@@ -0,0 +1,185 @@
+//! Certification path building tests
+
+mod common;
+
+use common::EcdsaP256Verifier;
+use der::{asn1::OctetStringRef, DateTime, Decode};
+use x509_cert::anchor::{TrustAnchorChoice, TrustAnchorInfo, Version};
+use x509_cert::chain::build_paths;
+use x509_cert::path::{validate, validate_path, Error, ValidationParams};
+use x509_cert::store::CertStore;
+use x509_cert::verify::SignedCertificate;
+use x509_cert::{Certificate, CertificateOwned};
+
+const ROOT: &[u8] = include_bytes!("examples/path-root.der");
+const ROOT_CROSS: &[u8] = include_bytes!("examples/path-root-cross.der");
+const ALT_ROOT: &[u8] = include_bytes!("examples/path-alt-root.der");
+const ALT_ROOT_CROSS: &[u8] = include_bytes!("examples/path-alt-root-cross.der");
+const CA: &[u8] = include_bytes!("examples/path-ca.der");
+const CA_NC: &[u8] = include_bytes!("examples/path-ca-nc.der");
+const CA_REKEY: &[u8] = include_bytes!("examples/path-ca-rekey.der");
+const SUB_CA: &[u8] = include_bytes!("examples/path-sub-ca.der");
+const SUB_LEAF: &[u8] = include_bytes!("examples/path-sub-leaf.der");
+const LEAF: &[u8] = include_bytes!("examples/path-leaf.der");
+
+fn store(ders: &[&[u8]]) -> CertStore {
+    ders.iter()
+        .map(|der| CertificateOwned::from(&Certificate::from_der(der).unwrap()))
+        .collect()
+}
+
+fn anchor(der: &[u8]) -> TrustAnchorChoice<'_> {
+    TrustAnchorChoice::Certificate(Certificate::from_der(der).unwrap())
+}
+
+fn ders(path: &[Certificate<'_>]) -> Vec<Vec<u8>> {
+    path.iter()
+        .map(|cert| der::Encode::to_vec(cert).unwrap())
+        .collect()
+}
+
+#[test]
+fn unordered_pool() {
+    let leaf = Certificate::from_der(LEAF).unwrap();
+    let pool = store(&[SUB_LEAF, ROOT, SUB_CA, CA_NC, CA_REKEY, CA]);
+    let anchors = [anchor(ROOT)];
+
+    let paths = build_paths(&leaf, &pool, &anchors, 4);
+    assert_eq!(paths.len(), 1);
+    assert_eq!(paths[0].anchor, &anchors[0]);
+    assert_eq!(ders(&paths[0].path), [CA, LEAF]);
+
+    // the path is suitable for validation against the anchor, along with the
+    // original encoding of its certificates
+    let tai = TrustAnchorInfo {
+        version: Version::V1,
+        pub_key: *anchors[0].public_key(),
+        key_id: OctetStringRef::new(anchors[0].key_id().unwrap()).unwrap(),
+        ta_title: None,
+        cert_path: None,
+        extensions: None,
+        ta_title_lang_tag: None,
+    };
+    let params = ValidationParams::new(DateTime::new(2025, 1, 1, 0, 0, 0).unwrap());
+    let path = [CA, LEAF].map(|der| SignedCertificate::from_der(der).unwrap());
+    let valid = validate_path(&[&path[0], &path[1]], &tai, &params, &EcdsaP256Verifier).unwrap();
+    assert_eq!(valid.certificates.len(), 2);
+
+    // missing intermediates
+    let pool = store(&[ROOT, CA_REKEY, SUB_CA]);
+    assert!(build_paths(&leaf, &pool, &anchors, 4).is_empty());
+}
+
+#[test]
+fn cross_certificates() {
+    let leaf = Certificate::from_der(LEAF).unwrap();
+    let pool = store(&[ROOT, ROOT_CROSS, ALT_ROOT, ALT_ROOT_CROSS, CA]);
+
+    // the alternate root reaches the leaf through its cross-certificate
+    let anchors = [anchor(ALT_ROOT)];
+    let paths = build_paths(&leaf, &pool, &anchors, 4);
+    assert_eq!(paths.len(), 1);
+    assert_eq!(ders(&paths[0].path), [ROOT_CROSS, CA, LEAF]);
+
+    // certificates for a trust anchor are not used as intermediates
+    let anchors = [anchor(ALT_ROOT), anchor(ROOT)];
+    let paths = build_paths(&leaf, &pool, &anchors, 4);
+    assert_eq!(paths.len(), 1);
+    assert_eq!(paths[0].anchor, &anchors[1]);
+    assert_eq!(ders(&paths[0].path), [CA, LEAF]);
+
+    // anchors without a name are matched by key identifier
+    let alt_root = anchor(ALT_ROOT);
+    let tai = TrustAnchorInfo {
+        version: Version::V1,
+        pub_key: *alt_root.public_key(),
+        key_id: OctetStringRef::new(alt_root.key_id().unwrap()).unwrap(),
+        ta_title: None,
+        cert_path: None,
+        extensions: None,
+        ta_title_lang_tag: None,
+    };
+    let anchors = [TrustAnchorChoice::TaInfo(tai)];
+    let paths = build_paths(&leaf, &pool, &anchors, 4);
+    assert_eq!(paths.len(), 1);
+    assert_eq!(ders(&paths[0].path), [ROOT_CROSS, CA, LEAF]);
+}
+
+#[test]
+fn loops() {
+    let leaf = Certificate::from_der(LEAF).unwrap();
+    let pool = store(&[ROOT_CROSS, ALT_ROOT_CROSS, CA, ROOT, ALT_ROOT]);
+
+    // the cross-certificates form a loop which never reaches an anchor
+    assert!(build_paths(&leaf, &pool, &[anchor(SUB_CA)], 4).is_empty());
+    assert!(build_paths(&leaf, &pool, &[], 4).is_empty());
+}
+
+#[test]
+fn ranking() {
+    let mut leaf = Certificate::from_der(LEAF).unwrap();
+    let pool = store(&[ROOT_CROSS, CA_REKEY, CA]);
+    let anchors = [anchor(ALT_ROOT), anchor(ROOT)];
+
+    let paths = build_paths(&leaf, &pool, &anchors, 4);
+    let intermediates: Vec<_> = paths.iter().map(|p| ders(&p.path)[0].clone()).collect();
+    assert_eq!(intermediates, [CA]);
+
+    // without an AuthorityKeyIdentifier both intermediates are candidates
+    leaf.tbs_certificate
+        .extensions
+        .as_mut()
+        .unwrap()
+        .retain(|ext| ext.extn_id != const_oid::db::rfc5280::ID_CE_AUTHORITY_KEY_IDENTIFIER);
+
+    let paths = build_paths(&leaf, &pool, &anchors, 4);
+    let intermediates: Vec<_> = paths.iter().map(|p| ders(&p.path)[0].clone()).collect();
+    assert_eq!(intermediates, [CA_REKEY, CA]);
+}
+
+#[test]
+fn bounded_search() {
+    let leaf = Certificate::from_der(LEAF).unwrap();
+    let ca = Certificate::from_der(CA).unwrap();
+
+    // self-issued certificates with distinct keys chain to each other in any
+    // order, so that the number of paths is exponential in their number
+    let keys: Vec<[u8; 1]> = (0..16).map(|i| [i]).collect();
+    let intermediates: Vec<_> = keys
+        .iter()
+        .map(|key| {
+            let mut cert = ca.clone();
+            let tbs = &mut cert.tbs_certificate;
+            tbs.issuer = tbs.subject.clone();
+            tbs.subject_public_key_info.subject_public_key = key;
+            tbs.extensions = None;
+            cert
+        })
+        .collect();
+
+    let mut pool = store(&[CA]);
+    pool.extend(intermediates.iter().map(CertificateOwned::from));
+    assert_eq!(pool.len(), 17);
+
+    // the best paths are returned
+    let anchors = [anchor(ROOT)];
+    let paths = build_paths(&leaf, &pool, &anchors, 3);
+    let lens: Vec<_> = paths.iter().map(|p| p.path.len()).collect();
+    assert_eq!(lens, [2, 3, 3]);
+    assert_eq!(ders(&paths[0].path), [CA, LEAF]);
+
+    // the search gives up instead of trying every ordering
+    let params = ValidationParams::new(DateTime::new(2025, 1, 1, 0, 0, 0).unwrap());
+    assert!(build_paths(&leaf, &pool, &[anchor(SUB_CA)], usize::MAX).is_empty());
+
+    let leaf = SignedCertificate::from_der(LEAF).unwrap();
+    let ders = ders(&intermediates);
+    let intermediates: Vec<_> = ders
+        .iter()
+        .map(|der| SignedCertificate::from_der(der).unwrap())
+        .collect();
+    assert_eq!(
+        validate(&leaf, &intermediates, &[], &params, &EcdsaP256Verifier),
+        Err(Error::PathNotFound)
+    );
+}
@@ -0,0 +1,154 @@
+//! CMS SignedData tests
+
+use const_oid::db::rfc5912::{ECDSA_WITH_SHA_256, ID_SHA_256};
+use const_oid::db::rfc6268::{ID_CONTENT_TYPE, ID_DATA, ID_MESSAGE_DIGEST};
+use der::asn1::{AnyRef, ObjectIdentifier, OctetStringRef};
+use der::{Decode, Document, Encode, Tag, TagNumber};
+use sha2::{Digest, Sha256};
+use x509_cert::cms::{CertificateChoices, CmsVersion, ContentInfo, SignedData, SignerIdentifier};
+use x509_cert::crl::CertificateList;
+use x509_cert::Certificate;
+
+#[test]
+fn decode_bundle() {
+    let der = include_bytes!("examples/path-bundle.p7b");
+    let content_info = ContentInfo::from_der(der).unwrap();
+    assert_eq!(content_info.to_vec().unwrap(), der);
+
+    let signed_data = content_info.signed_data().unwrap();
+    assert_eq!(signed_data.version, CmsVersion::V1);
+    assert!(signed_data.digest_algorithms.is_empty());
+    assert!(signed_data.signer_infos.is_empty());
+    assert_eq!(signed_data.encap_content_info.econtent_type, ID_DATA);
+    assert!(signed_data.encap_content_info.econtent.is_none());
+
+    let certs = signed_data.x509_certificates();
+    assert_eq!(
+        certs,
+        [
+            Certificate::from_der(include_bytes!("examples/path-root.der")).unwrap(),
+            Certificate::from_der(include_bytes!("examples/path-ca.der")).unwrap(),
+            Certificate::from_der(include_bytes!("examples/path-leaf.der")).unwrap(),
+        ]
+    );
+
+    let crls = signed_data.x509_crls();
+    assert_eq!(
+        crls,
+        [CertificateList::from_der(include_bytes!("examples/path-ca.crl")).unwrap()]
+    );
+}
+
+#[test]
+fn skip_attribute_certificates() {
+    let der = include_bytes!("examples/path-bundle.p7b");
+    let mut signed_data = ContentInfo::from_der(der).unwrap().signed_data().unwrap();
+    let certs = signed_data.x509_certificates();
+
+    // `[2] IMPLICIT` v2 attribute certificate, with a placeholder body
+    let attr_cert = AnyRef::new(
+        Tag::ContextSpecific {
+            constructed: true,
+            number: TagNumber::N2,
+        },
+        &[0x02, 0x01, 0x01],
+    )
+    .unwrap();
+    let choice = CertificateChoices::V2AttrCert(attr_cert);
+    assert_eq!(choice.to_vec().unwrap(), [0xa2, 0x03, 0x02, 0x01, 0x01]);
+    assert_eq!(
+        CertificateChoices::from_der(&[0xa2, 0x03, 0x02, 0x01, 0x01]).unwrap(),
+        choice
+    );
+
+    signed_data.certificates.as_mut().unwrap().insert(1, choice);
+    let der = signed_data.to_vec().unwrap();
+    let signed_data = SignedData::from_der(&der).unwrap();
+    assert_eq!(signed_data.x509_certificates(), certs);
+    assert!(matches!(
+        signed_data.certificates.as_ref().unwrap()[1],
+        CertificateChoices::V2AttrCert(any) if any == attr_cert
+    ));
+    assert_eq!(signed_data.to_vec().unwrap(), der);
+}
+
+#[test]
+fn decode_signed_message() {
+    let der = include_bytes!("examples/path-signed.p7m");
+    let content_info = ContentInfo::from_der(der).unwrap();
+    assert_eq!(content_info.to_vec().unwrap(), der);
+
+    let signed_data = content_info.signed_data().unwrap();
+    let content = signed_data.encap_content_info.econtent.unwrap();
+    assert_eq!(content.as_bytes(), b"hello, world\n");
+    assert_eq!(signed_data.digest_algorithms.len(), 1);
+    assert_eq!(
+        signed_data.digest_algorithms.get(0).unwrap().oid,
+        ID_SHA_256
+    );
+    assert!(signed_data.crls.is_none());
+
+    let certs = signed_data.x509_certificates();
+    assert_eq!(certs.len(), 2);
+    let leaf = Certificate::from_der(include_bytes!("examples/path-leaf.der")).unwrap();
+    let ca = Certificate::from_der(include_bytes!("examples/path-ca.der")).unwrap();
+    assert!(certs.contains(&leaf));
+    assert!(certs.contains(&ca));
+
+    assert_eq!(signed_data.signer_infos.len(), 1);
+    let signer_info = signed_data.signer_infos.get(0).unwrap();
+    assert_eq!(signer_info.version, CmsVersion::V1);
+    assert_eq!(signer_info.signature_algorithm.oid, ECDSA_WITH_SHA_256);
+    assert!(matches!(
+        signer_info.sid,
+        SignerIdentifier::IssuerAndSerialNumber(_)
+    ));
+    assert!(signer_info.sid.identifies(&leaf).unwrap());
+    assert!(!signer_info.sid.identifies(&ca).unwrap());
+
+    // the message digest attribute covers the encapsulated content
+    let attrs = signer_info.signed_attrs.as_ref().unwrap();
+    let attr = |oid: ObjectIdentifier| attrs.iter().find(|attr| attr.oid == oid).unwrap();
+    let content_type: ObjectIdentifier = attr(ID_CONTENT_TYPE)
+        .values
+        .get(0)
+        .unwrap()
+        .decode_into()
+        .unwrap();
+    assert_eq!(content_type, ID_DATA);
+    let digest: OctetStringRef<'_> = attr(ID_MESSAGE_DIGEST)
+        .values
+        .get(0)
+        .unwrap()
+        .decode_into()
+        .unwrap();
+    assert_eq!(
+        digest.as_bytes(),
+        Sha256::digest(content.as_bytes()).as_slice()
+    );
+}
+
+#[test]
+fn decode_ber_signed_message() {
+    let ber = include_bytes!("examples/path-signed-ber.p7m");
+    assert!(ContentInfo::from_der(ber).is_err());
+
+    let doc = Document::from_ber(ber).unwrap();
+    let content_info: ContentInfo<'_> = doc.decode_msg().unwrap();
+    let signed_data = content_info.signed_data().unwrap();
+    let content = signed_data.encap_content_info.econtent.unwrap();
+    assert_eq!(content.as_bytes(), b"hello, world\n");
+
+    let leaf = Certificate::from_der(include_bytes!("examples/path-leaf.der")).unwrap();
+    assert!(signed_data.x509_certificates().contains(&leaf));
+    let signer_info = signed_data.signer_infos.get(0).unwrap();
+    assert!(signer_info.sid.identifies(&leaf).unwrap());
+}
+
+#[test]
+fn reject_other_content() {
+    let der = include_bytes!("examples/path-bundle.p7b");
+    let mut content_info = ContentInfo::from_der(der).unwrap();
+    content_info.content_type = ID_DATA;
+    assert!(content_info.signed_data().is_err());
+}
@@ -0,0 +1,34 @@
+//! Helpers shared by the integration tests
+
+use const_oid::db::rfc5912::{ECDSA_WITH_SHA_256, ID_EC_PUBLIC_KEY, SECP_256_R_1};
+use p256::ecdsa::{signature::Verifier, Signature, VerifyingKey};
+use spki::{AlgorithmIdentifier, SubjectPublicKeyInfo};
+use x509_cert::verify::{Error, Result, SignatureVerifier};
+
+/// ECDSA P-256 with SHA-256 verifier.
+pub struct EcdsaP256Verifier;
+
+impl SignatureVerifier for EcdsaP256Verifier {
+    fn verify(
+        &self,
+        algorithm: &AlgorithmIdentifier<'_>,
+        public_key: &SubjectPublicKeyInfo<'_>,
+        msg: &[u8],
+        signature: &[u8],
+    ) -> Result<()> {
+        if algorithm.oid != ECDSA_WITH_SHA_256 {
+            return Err(Error::UnsupportedAlgorithm(algorithm.oid));
+        }
+
+        if public_key.algorithm.oid != ID_EC_PUBLIC_KEY
+            || public_key.algorithm.parameters_oid() != Ok(SECP_256_R_1)
+        {
+            return Err(Error::UnsupportedAlgorithm(public_key.algorithm.oid));
+        }
+
+        let key = VerifyingKey::from_sec1_bytes(public_key.subject_public_key)
+            .map_err(|_| Error::Verification)?;
+        let signature = Signature::from_der(signature).map_err(|_| Error::Verification)?;
+        key.verify(msg, &signature).map_err(|_| Error::Verification)
+    }
+}
@@ -1,4 +1,4 @@
-use der::Decode;
+use der::{Decode, Encode};
 use x509_cert::crl::CertificateList;
 
 #[test]
@@ -15,3 +15,54 @@ fn decode_crl() {
     assert_eq!(2, crl.tbs_cert_list.crl_extensions.unwrap().len());
     assert_eq!(4, crl.tbs_cert_list.revoked_certificates.unwrap().len());
 }
+
+#[cfg(feature = "std")]
+#[test]
+fn stream_revoked_certs() {
+    use x509_cert::crl::RevokedCerts;
+
+    for der_encoded_crl in [
+        &include_bytes!("examples/GoodCACRL.crl")[..],
+        &include_bytes!("examples/tscpbcasha256.crl")[..],
+    ] {
+        let crl = CertificateList::from_der(der_encoded_crl).unwrap();
+        let tbs = crl.tbs_cert_list;
+
+        let stream = RevokedCerts::new(der_encoded_crl).unwrap();
+        assert_eq!(tbs.version, stream.version());
+        assert_eq!(tbs.signature, stream.signature().as_ref());
+        assert_eq!(tbs.issuer, stream.issuer().as_ref());
+        assert_eq!(tbs.this_update, stream.this_update());
+        assert_eq!(tbs.next_update, stream.next_update());
+
+        let entries = stream.collect::<der::Result<Vec<_>>>().unwrap();
+        let expected = tbs.revoked_certificates.unwrap();
+        assert_eq!(expected.len(), entries.len());
+
+        for (expected, entry) in expected.iter().zip(&entries) {
+            assert_eq!(*expected, entry.as_ref());
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn stream_revoked_certs_truncated() {
+    use x509_cert::crl::{RevokedCertOwned, RevokedCerts};
+
+    let der_encoded_crl = include_bytes!("examples/GoodCACRL.crl");
+    let crl = CertificateList::from_der(der_encoded_crl).unwrap();
+    let first = RevokedCertOwned::from(&crl.tbs_cert_list.revoked_certificates.unwrap()[0]);
+
+    // Cut the input in the middle of the second entry
+    let entry_len = usize::try_from(first.encoded_len().unwrap()).unwrap();
+    let offset = der_encoded_crl
+        .windows(entry_len)
+        .position(|w| w == first.to_vec().unwrap())
+        .unwrap();
+
+    let mut stream = RevokedCerts::new(&der_encoded_crl[..offset + entry_len + 4]).unwrap();
+    assert_eq!(stream.next().unwrap().unwrap(), first);
+    assert!(stream.next().unwrap().is_err());
+    assert!(stream.next().is_none());
+}
@@ -0,0 +1,100 @@
+//! Department identity extension tests
+
+use der::{Decode, Encode};
+use x509_cert::ext::dept::{DepartmentIdentity, Error};
+use x509_cert::Certificate;
+
+const JSON: &str = r#"{"attrs":{"DeptLevel":"2","DeptName":"812","DeptType":"computer","SuperDeptName":"804","admin":"true","hf.Affiliation":"","hf.EnrollmentID":"peer0","hf.Registrar.Roles":"peer","hf.Type":"peer"}}"#;
+
+#[test]
+fn decode_from_certificate() {
+    let cert = Certificate::from_der(include_bytes!("examples/dept-identity.der")).unwrap();
+    let (critical, dept) = cert
+        .tbs_certificate
+        .get::<DepartmentIdentity>()
+        .unwrap()
+        .unwrap();
+
+    assert!(!critical);
+    assert_eq!(dept.dept_type, "computer");
+    assert_eq!(dept.dept_level, 2);
+    assert_eq!(dept.dept_name, "812");
+    assert_eq!(dept.super_dept_name, "804");
+    assert_eq!(
+        dept.other_attributes,
+        [
+            ("admin".into(), "true".into()),
+            ("hf.Affiliation".into(), "".into()),
+            ("hf.EnrollmentID".into(), "peer0".into()),
+            ("hf.Registrar.Roles".into(), "peer".into()),
+            ("hf.Type".into(), "peer".into()),
+        ]
+    );
+
+    // the extension value is reproduced exactly
+    assert_eq!(dept.to_vec().unwrap(), JSON.as_bytes());
+}
+
+#[test]
+fn utf8_names() {
+    let dept = DepartmentIdentity::from_json(
+        r#" { "attrs" : { "DeptType":"研发", "DeptLevel":"3",
+            "DeptName":"计算机系", "SuperDeptName":"信息\"学院\"" } } "#
+            .as_bytes(),
+    )
+    .unwrap();
+
+    assert_eq!(dept.dept_type, "研发");
+    assert_eq!(dept.dept_level, 3);
+    assert_eq!(dept.dept_name, "计算机系");
+    assert_eq!(dept.super_dept_name, "信息\"学院\"");
+    assert!(dept.other_attributes.is_empty());
+
+    let der = dept.to_vec().unwrap();
+    assert_eq!(
+        core::str::from_utf8(&der).unwrap(),
+        r#"{"attrs":{"DeptLevel":"3","DeptName":"计算机系","DeptType":"研发","SuperDeptName":"信息\"学院\""}}"#
+    );
+    assert_eq!(DepartmentIdentity::from_der(&der).unwrap(), dept);
+}
+
+#[test]
+fn invalid_values() {
+    let parse = |json: &str| DepartmentIdentity::from_json(json.as_bytes());
+
+    assert_eq!(
+        parse(r#"{"attrs":{"DeptLevel":"two","DeptName":"a","DeptType":"b","SuperDeptName":"c"}}"#),
+        Err(Error::InvalidDeptLevel)
+    );
+    assert_eq!(
+        parse(r#"{"attrs":{"DeptLevel":"256","DeptName":"a","DeptType":"b","SuperDeptName":"c"}}"#),
+        Err(Error::InvalidDeptLevel)
+    );
+    assert_eq!(
+        parse(r#"{"attrs":{"DeptLevel":"+1","DeptName":"a","DeptType":"b","SuperDeptName":"c"}}"#),
+        Err(Error::InvalidDeptLevel)
+    );
+    assert_eq!(
+        parse(r#"{"attrs":{"DeptLevel":"1","DeptType":"b","SuperDeptName":"c"}}"#),
+        Err(Error::MissingAttribute("DeptName"))
+    );
+    assert_eq!(
+        parse(r#"{"attrs":{"DeptLevel":"1","DeptLevel":"2"}}"#),
+        Err(Error::Syntax { position: 38 })
+    );
+    assert_eq!(
+        parse(r#"{"attrs":{"DeptLevel":1}}"#),
+        Err(Error::Syntax { position: 22 })
+    );
+    assert_eq!(
+        parse(r#"{"attrs":{}} x"#),
+        Err(Error::Syntax { position: 13 })
+    );
+    assert!(matches!(
+        DepartmentIdentity::from_json(b"{\"attrs\":{\"DeptName\":\"\xff\"}}"),
+        Err(Error::Utf8(_))
+    ));
+
+    // decoding errors are reported as `der` errors
+    assert!(DepartmentIdentity::from_der(b"not json").is_err());
+}
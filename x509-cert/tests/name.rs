@@ -4,8 +4,10 @@ use const_oid::ObjectIdentifier;
 use der::asn1::{OctetStringRef, SetOfVec, Utf8StringRef};
 use der::{AnyRef, Decode, Encode, Tag, Tagged};
 use hex_literal::hex;
-use x509_cert::attr::AttributeTypeAndValue;
-use x509_cert::name::{Name, RdnSequence, RelativeDistinguishedName};
+use x509_cert::attr::{AttributeTypeAndValue, AttributeTypeAndValueOwned};
+use x509_cert::name::{
+    Name, NameOwned, RdnSequence, RelativeDistinguishedName, RelativeDistinguishedNameOwned,
+};
 
 #[test]
 fn decode_name() {
@@ -334,3 +336,312 @@ fn rdns_serde() {
         }
     }
 }
+
+fn name(rdns: &[&[(ObjectIdentifier, Tag, &[u8])]]) -> RdnSequence<'static> {
+    // Leaked so that the values of the name may borrow them
+    let rdns = rdns
+        .iter()
+        .map(|atvs| {
+            let atvs: Vec<_> = atvs
+                .iter()
+                .map(|(oid, tag, value)| AttributeTypeAndValue {
+                    oid: *oid,
+                    value: AnyRef::new(*tag, Box::leak(value.to_vec().into_boxed_slice())).unwrap(),
+                })
+                .collect();
+            RelativeDistinguishedName(atvs.try_into().unwrap())
+        })
+        .collect();
+    RdnSequence(rdns)
+}
+
+#[test]
+fn name_matches() {
+    use const_oid::db::rfc4519::{C, CN, O, OU, SERIAL_NUMBER};
+
+    let printable = name(&[
+        &[(C, Tag::PrintableString, b"US")],
+        &[(O, Tag::PrintableString, b"Acme")],
+    ]);
+    let utf8 = name(&[
+        &[(C, Tag::PrintableString, b"us")],
+        &[(O, Tag::Utf8String, b"  ACME ")],
+    ]);
+    assert_ne!(printable, utf8);
+    assert!(printable.matches(&utf8));
+    assert!(utf8.matches(&printable));
+
+    // BMPString, internal whitespace and compatibility characters
+    let bmp = name(&[
+        &[(C, Tag::PrintableString, b"US")],
+        &[(O, Tag::BmpString, &hex!("FF210063006D0065"))],
+    ]);
+    assert!(bmp.matches(&printable));
+    let spaced = name(&[&[(CN, Tag::Utf8String, "J.\t Smith\u{AD}".as_bytes())]]);
+    assert!(spaced.matches(&name(&[&[(CN, Tag::PrintableString, b"j. smith")]])));
+
+    // NumericString ignores all spaces
+    let numeric = name(&[&[(SERIAL_NUMBER, Tag::NumericString, b"12 34")]]);
+    assert!(numeric.matches(&name(&[&[(SERIAL_NUMBER, Tag::NumericString, b"1234")]])));
+
+    // the type, value, number and order of RDNs is significant
+    let reversed = name(&[
+        &[(O, Tag::PrintableString, b"Acme")],
+        &[(C, Tag::PrintableString, b"US")],
+    ]);
+    assert!(!printable.matches(&reversed));
+    assert!(!printable.matches(&name(&[&[(C, Tag::PrintableString, b"US")]])));
+    assert!(!printable.matches(&name(&[
+        &[(C, Tag::PrintableString, b"US")],
+        &[(OU, Tag::PrintableString, b"Acme")],
+    ])));
+    assert!(!printable.matches(&name(&[
+        &[(C, Tag::PrintableString, b"US")],
+        &[(O, Tag::PrintableString, b"Acme Corp")],
+    ])));
+
+    // values which are not strings are compared by their encoding
+    let octets = name(&[&[(CN, Tag::OctetString, b"Acme")]]);
+    assert!(octets.matches(&name(&[&[(CN, Tag::OctetString, b"Acme")]])));
+    assert!(!octets.matches(&name(&[&[(CN, Tag::OctetString, b"acme")]])));
+    assert!(!octets.matches(&name(&[&[(CN, Tag::Utf8String, b"Acme")]])));
+}
+
+#[test]
+fn multi_valued_rdn_matches() {
+    use const_oid::db::rfc4519::{CN, OU};
+
+    let der = Name::encode_from_string("OU=Sales+CN=J.  Smith").unwrap();
+    let sales = Name::from_der(&der).unwrap();
+    let other = name(&[&[
+        (CN, Tag::PrintableString, b"j. smith"),
+        (OU, Tag::Utf8String, b"SALES"),
+    ]]);
+    assert!(sales.matches(&other));
+    assert!(sales.0[0].matches(&other.0[0]));
+
+    let fewer = name(&[&[(OU, Tag::Utf8String, b"Sales")]]);
+    assert!(!sales.matches(&fewer));
+    assert!(!fewer.0[0].matches(&sales.0[0]));
+}
+
+#[test]
+fn canonical_name() {
+    use const_oid::db::rfc4519::{C, O};
+    use std::collections::HashMap;
+
+    let printable = name(&[
+        &[(C, Tag::PrintableString, b"US")],
+        &[(O, Tag::PrintableString, b"Acme")],
+    ]);
+    let utf8 = name(&[
+        &[(C, Tag::Utf8String, b"US")],
+        &[(O, Tag::Utf8String, b"ACME")],
+    ]);
+    assert_eq!(printable.canonical(), utf8.canonical());
+    assert_eq!(NameOwned::from(&printable).canonical(), utf8.canonical());
+
+    let mut names = HashMap::new();
+    names.insert(printable.canonical(), "acme");
+    assert_eq!(names.get(&utf8.canonical()), Some(&"acme"));
+    assert_eq!(names.get(&RdnSequence::default().canonical()), None);
+}
+
+#[test]
+fn directory_string() {
+    use x509_cert::ext::pkix::name::DirectoryString;
+
+    for (der, expected) in [
+        (&hex!("14 04 41636d65")[..], "Acme"),
+        (&hex!("14 03 c56be9")[..], "\u{c5}k\u{e9}"),
+        (&hex!("13 04 41636d65")[..], "Acme"),
+        (&hex!("1c 08 000000c5 0000006b")[..], "\u{c5}k"),
+        (&hex!("0c 03 c3856b")[..], "\u{c5}k"),
+        (&hex!("1e 06 00c5 006b 20ac")[..], "\u{c5}k\u{20ac}"),
+    ] {
+        let value = DirectoryString::from_der(der).unwrap();
+        assert_eq!(value.to_string(), expected);
+        assert_eq!(value.to_vec().unwrap(), der);
+        assert_eq!(
+            DirectoryString::try_from(AnyRef::from_der(der).unwrap()).unwrap(),
+            value
+        );
+    }
+
+    assert!(DirectoryString::from_der(&hex!("16 04 41636d65")).is_err());
+}
+
+#[test]
+fn display_legacy_strings() {
+    // O=Ministère as BMPString, CN=€ as UniversalString + OU=Test, User as TeletexString
+    let name = Name::from_der(&hex!(
+        "303f"
+        "311b 3019 060355040a 1e12 004d0069006e00690073007400e800720065"
+        "3120"
+        "  300b 0603550403 1c04 000020ac"
+        "  3011 060355040b 140a 546573742c2055736572"
+    ))
+    .unwrap();
+    let s = name.to_string();
+    assert_eq!(s, "O=Minist\u{e8}re,CN=\u{20ac}+OU=Test\\, User");
+
+    // the string parses back to UTF8String values, which match
+    assert!(s.parse::<NameOwned>().unwrap().matches(&name));
+
+    // the same name with UTF8String values matches
+    let der = Name::encode_from_string("O=MINIST\u{c8}RE,CN=\u{20ac}+OU=test\\,  user").unwrap();
+    assert!(name.matches(&Name::from_der(&der).unwrap()));
+}
+
+#[test]
+fn round_trip_examples() {
+    use x509_cert::crl::CertificateList;
+    use x509_cert::Certificate;
+
+    let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/examples");
+    let mut count = 0;
+
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let der = std::fs::read(entry.unwrap().path()).unwrap();
+        let names = match (Certificate::from_der(&der), CertificateList::from_der(&der)) {
+            (Ok(cert), _) => vec![cert.tbs_certificate.issuer, cert.tbs_certificate.subject],
+            (_, Ok(crl)) => vec![crl.tbs_cert_list.issuer],
+            _ => continue,
+        };
+
+        for name in names {
+            let s = name.to_string();
+            let parsed: NameOwned = s.parse().unwrap_or_else(|e| panic!("{}: {}", s, e));
+            assert!(parsed.matches(&name), "{}", s);
+            count += 1;
+        }
+    }
+
+    assert!(count > 50, "{}", count);
+}
+
+#[test]
+fn parse_string_types() {
+    let name: NameOwned =
+        "C=US,serialNumber=1234,DC=example,emailAddress=jsmith@example.com,CN=J. Smith"
+            .parse()
+            .unwrap();
+
+    let tags: Vec<_> = name
+        .0
+        .iter()
+        .map(|rdn| rdn.0.get(0).unwrap().value.tag())
+        .collect();
+    assert_eq!(
+        tags,
+        [
+            Tag::PrintableString,
+            Tag::PrintableString,
+            Tag::Ia5String,
+            Tag::Ia5String,
+            Tag::Utf8String
+        ]
+    );
+
+    // values which cannot be encoded with the type of their attribute
+    assert!("C=\u{c9}U".parse::<NameOwned>().is_err());
+    assert!("DC=\u{e9}xample".parse::<NameOwned>().is_err());
+}
+
+#[test]
+fn parse_values() {
+    for (input, expected) in [
+        // escapes of special characters and of UTF-8 bytes
+        (
+            "CN=James \\\"Jim\\\" Smith\\, III",
+            "James \"Jim\" Smith, III",
+        ),
+        ("CN=Lu\\C4\\8Di\\C4\\87", "Lu\u{10d}i\u{107}"),
+        ("CN=Before\\0dAfter", "Before\rAfter"),
+        ("CN=\\#1\\=\\+2", "#1=+2"),
+        // unescaped spaces around values are insignificant
+        ("CN =  a  b  ", "a  b"),
+        ("CN=\\ a\\ ", " a "),
+        // quoted values
+        ("CN=\"Smith, James\"", "Smith, James"),
+        ("CN=\" a+b \\\" \"", " a+b \" "),
+        // OIDs
+        ("2.5.4.3=x", "x"),
+        ("OID.2.5.4.3=x", "x"),
+        ("commonName=x", "x"),
+        ("cn=x", "x"),
+    ] {
+        let atv: AttributeTypeAndValueOwned = input.parse().unwrap();
+        assert_eq!(atv.oid, const_oid::db::rfc4519::CN);
+        assert_eq!(atv.as_ref().value.utf8_string().unwrap().as_str(), expected);
+    }
+
+    // values in hex
+    let atv: AttributeTypeAndValueOwned = "1.3.6.1.4.1.1466.0=#04024869".parse().unwrap();
+    assert_eq!(atv.value.to_vec().unwrap(), hex!("04024869"));
+
+    for input in [
+        "CN",
+        "=x",
+        "CN=a,",
+        "CN=a\"b",
+        "CN=a;b",
+        "CN=a\\",
+        "CN=a\\4",
+        "CN=a\\x1",
+        "CN=\\C4",
+        "CN=\"a",
+        "CN=\"a\"b",
+        "CN=#0402486",
+        "CN=#040248",
+        "CN=#04024869ff",
+        "unknownAttribute=x",
+        "OID.cn=x",
+    ] {
+        assert!(input.parse::<NameOwned>().is_err(), "{}", input);
+    }
+
+    let name: NameOwned = "".parse().unwrap();
+    assert!(name.0.is_empty());
+}
+
+#[test]
+fn parse_multi_valued_rdn() {
+    let rdn: RelativeDistinguishedNameOwned = "OU=Sales + CN=J. Smith".parse().unwrap();
+    let reversed: RelativeDistinguishedNameOwned = "CN=J. Smith+OU=Sales".parse().unwrap();
+    assert_eq!(rdn, reversed);
+    assert_eq!(rdn.to_string(), "OU=Sales+CN=J. Smith");
+
+    let name: NameOwned = "OU=Sales+CN=J. Smith,DC=example,DC=net".parse().unwrap();
+    assert_eq!(name.0.len(), 3);
+    assert_eq!(name.0[0], rdn);
+}
+
+#[test]
+fn parse_display_round_trip() {
+    for input in [
+        "C=US,O=RustCrypto,CN=leaf.example.com",
+        r#"CN=\#\"\+\,\;\<\>\\=\ "#,
+        "CN=\\ Smith\\, J.+UID=jsmith,DC=example,DC=net",
+        "CN=Before\\0dAfter",
+        "O=Minist\u{e8}re,CN=\u{20ac}",
+        "SERIALNUMBER=1234,EMAIL=jsmith@example.com",
+        "1.3.6.1.4.1.1466.0=#04024869,CN=x",
+    ] {
+        let name: NameOwned = input.parse().unwrap();
+        assert_eq!(name.to_string(), input);
+        assert_eq!(name.to_string().parse::<NameOwned>().unwrap(), name);
+    }
+
+    // names of certificates encoded with the same string types
+    for der in [
+        &include_bytes!("examples/path-root.der")[..],
+        &include_bytes!("examples/path-leaf.der")[..],
+    ] {
+        let cert = x509_cert::Certificate::from_der(der).unwrap();
+        for name in [&cert.tbs_certificate.subject, &cert.tbs_certificate.issuer] {
+            let parsed: NameOwned = name.to_string().parse().unwrap();
+            assert_eq!(parsed.as_ref(), *name);
+        }
+    }
+}
@@ -0,0 +1,334 @@
+//! Name constraints tests
+
+use const_oid::db::rfc3280::EMAIL_ADDRESS;
+use der::asn1::{Ia5StringRef, ObjectIdentifier, OctetStringRef};
+use der::Decode;
+use hex_literal::hex;
+use x509_cert::anchor::TrustAnchorChoice;
+use x509_cert::ext::pkix::constraints::name::GeneralSubtree;
+use x509_cert::ext::pkix::name::{GeneralName, OtherName};
+use x509_cert::ext::pkix::NameConstraints;
+use x509_cert::name::constraints::{NameConstraintsMatcher, Violation};
+use x509_cert::name::{Name, NameOwned};
+
+fn dn(s: &str) -> Name<'static> {
+    // Leaked so that the name may be borrowed for the rest of the test
+    Name::from(&*Box::leak(Box::new(s.parse::<NameOwned>().unwrap())))
+}
+
+fn dns(s: &'static str) -> GeneralName<'static> {
+    GeneralName::DnsName(Ia5StringRef::new(s).unwrap())
+}
+
+fn email(s: &'static str) -> GeneralName<'static> {
+    GeneralName::Rfc822Name(Ia5StringRef::new(s).unwrap())
+}
+
+fn uri(s: &'static str) -> GeneralName<'static> {
+    GeneralName::UniformResourceIdentifier(Ia5StringRef::new(s).unwrap())
+}
+
+fn ip(bytes: &'static [u8]) -> GeneralName<'static> {
+    GeneralName::IpAddress(OctetStringRef::new(bytes).unwrap())
+}
+
+fn subtree(base: GeneralName<'static>) -> GeneralSubtree<'static> {
+    GeneralSubtree {
+        base,
+        minimum: 0,
+        maximum: None,
+    }
+}
+
+fn matcher(
+    permitted: &[GeneralName<'static>],
+    excluded: &[GeneralName<'static>],
+) -> NameConstraintsMatcher<'static> {
+    let subtrees = |names: &[GeneralName<'static>]| match names.is_empty() {
+        true => None,
+        false => Some(names.iter().cloned().map(subtree).collect()),
+    };
+
+    NameConstraintsMatcher::from(NameConstraints {
+        permitted_subtrees: subtrees(permitted),
+        excluded_subtrees: subtrees(excluded),
+    })
+}
+
+#[test]
+fn directory_name() {
+    // the trust anchor excludes C=US,O=U.S. Government,OU=DoD
+    let tac =
+        TrustAnchorChoice::from_der(include_bytes!("examples/entrust_dnConstraint.ta")).unwrap();
+    let nc = match tac {
+        TrustAnchorChoice::TaInfo(tai) => tai.cert_path.unwrap().name_constr.unwrap(),
+        _ => panic!("unexpected TrustAnchorChoice"),
+    };
+    let excluded = nc.excluded_subtrees.as_ref().unwrap()[0].clone();
+    let matcher = NameConstraintsMatcher::from(nc);
+
+    let subject = dn("C=US,O=U.S. Government,OU=DoD,OU=PKI,CN=Jane Doe");
+    assert_eq!(
+        matcher.check(&subject, None),
+        Err(Violation::Excluded {
+            name: GeneralName::DirectoryName(subject.clone()),
+            excluded,
+        })
+    );
+
+    // compared with the rules of RFC 4518
+    assert!(matcher
+        .check(&dn("C=us,O=u.s.  government,OU=DOD"), None)
+        .is_err());
+
+    assert!(matcher
+        .check(&dn("C=US,O=U.S. Government,OU=DoS"), None)
+        .is_ok());
+    assert!(matcher.check(&dn("C=US,O=U.S. Government"), None).is_ok());
+    assert!(matcher.check(&dn("C=US,O=Entrust,OU=DoD"), None).is_ok());
+
+    // empty subjects are not checked
+    let matcher = matcher_for_dn("C=US,O=Example");
+    assert!(matcher
+        .check(&Name::default(), Some(&[dns("example.com")]))
+        .is_ok());
+    assert!(matcher.check(&dn("C=US,O=Example,CN=x"), None).is_ok());
+    assert_eq!(
+        matcher.check(&dn("C=US,CN=x"), None),
+        Err(Violation::NotPermitted {
+            name: GeneralName::DirectoryName(dn("C=US,CN=x")),
+            permitted: vec![subtree(GeneralName::DirectoryName(dn("C=US,O=Example")))],
+        })
+    );
+}
+
+fn matcher_for_dn(s: &str) -> NameConstraintsMatcher<'static> {
+    matcher(&[GeneralName::DirectoryName(dn(s))], &[])
+}
+
+#[test]
+fn dns_name() {
+    let matcher = matcher(&[dns("example.com")], &[dns("bad.example.com")]);
+    let subject = Name::default();
+
+    for name in ["example.com", "www.EXAMPLE.com", "a.b.example.com"] {
+        assert!(
+            matcher.check(&subject, Some(&[dns(name)])).is_ok(),
+            "{}",
+            name
+        );
+    }
+
+    for name in ["badexample.com", "example.org", "com"] {
+        assert!(matches!(
+            matcher.check(&subject, Some(&[dns(name)])),
+            Err(Violation::NotPermitted { .. })
+        ));
+    }
+
+    for name in ["bad.example.com", "www.bad.example.com"] {
+        assert!(matches!(
+            matcher.check(&subject, Some(&[dns(name)])),
+            Err(Violation::Excluded { .. })
+        ));
+    }
+
+    // constraints on other forms do not apply
+    assert!(matcher.check(&subject, Some(&[email("a@b.org")])).is_ok());
+}
+
+#[test]
+fn rfc822_name() {
+    let matcher = matcher(
+        &[
+            email("jane@example.com"),
+            email("mail.example.org"),
+            email(".example.net"),
+        ],
+        &[],
+    );
+
+    for name in [
+        "jane@EXAMPLE.com",
+        "anyone@mail.example.org",
+        "anyone@a.example.net",
+    ] {
+        assert!(matcher.check_name(&email(name)).is_ok(), "{}", name);
+    }
+
+    for name in [
+        "Jane@example.com",
+        "john@example.com",
+        "anyone@www.mail.example.org",
+        "anyone@example.net",
+        "not-a-mailbox",
+    ] {
+        assert!(matcher.check_name(&email(name)).is_err(), "{}", name);
+    }
+
+    // emailAddress attributes are checked when there are no alternative names
+    let subject = dn("CN=Jane,1.2.840.113549.1.9.1=#16106a6f686e406578616d706c652e636f6d");
+    assert_eq!(
+        matcher.check(&subject, None),
+        Err(Violation::NotPermitted {
+            name: email("john@example.com"),
+            permitted: vec![
+                subtree(email("jane@example.com")),
+                subtree(email("mail.example.org")),
+                subtree(email(".example.net")),
+            ],
+        })
+    );
+    assert!(matcher
+        .check(&subject, Some(&[email("jane@example.com")]))
+        .is_ok());
+
+    // emailAddress attributes which are not ASCII are never permitted
+    let subject = dn("CN=Jane,1.2.840.113549.1.9.1=#0c13c3a96c6f646965406578616d706c652e6f7267");
+    assert!(matches!(
+        matcher.check(&subject, None),
+        Err(Violation::NotPermitted {
+            name: GeneralName::OtherName(OtherName { type_id, .. }),
+            ..
+        }) if type_id == EMAIL_ADDRESS
+    ));
+    assert!(matches!(
+        crate::matcher(&[], &[email(".example.org")]).check(&subject, None),
+        Err(Violation::Excluded { .. })
+    ));
+    assert!(NameConstraintsMatcher::new().check(&subject, None).is_ok());
+}
+
+#[test]
+fn uniform_resource_identifier() {
+    let matcher = matcher(
+        &[uri("pki.example.com"), uri(".example.org")],
+        &[uri("bad.example.org")],
+    );
+
+    for name in [
+        "https://pki.example.com/ca.crt",
+        "ldap://user@PKI.example.com:389/cn=CA?cACertificate",
+        "http://www.example.org",
+        "http://a.b.example.org?x#y",
+    ] {
+        assert!(matcher.check_name(&uri(name)).is_ok(), "{}", name);
+    }
+
+    for name in [
+        "https://www.pki.example.com/",
+        "https://example.org/",
+        "https://[2001:db8::1]/",
+        "urn:example:pki",
+        "file:///etc/ssl",
+    ] {
+        assert!(
+            matches!(
+                matcher.check_name(&uri(name)),
+                Err(Violation::NotPermitted { .. })
+            ),
+            "{}",
+            name
+        );
+    }
+
+    assert!(matches!(
+        matcher.check_name(&uri("https://bad.example.org/")),
+        Err(Violation::Excluded { .. })
+    ));
+
+    // URIs without a host are always within excluded subtrees
+    let matcher = self::matcher(&[], &[uri(".example.org")]);
+    assert!(matcher.check_name(&uri("urn:example:pki")).is_err());
+    assert!(matcher.check_name(&uri("https://example.com")).is_ok());
+}
+
+#[test]
+fn ip_address() {
+    let matcher = matcher(
+        &[
+            ip(&hex!("c0a80000 ffff0000")),
+            ip(&hex!(
+                "20010db8000000000000000000000000 ffffffff000000000000000000000000"
+            )),
+        ],
+        &[ip(&hex!("c0a80100 ffffff00"))],
+    );
+
+    for addr in [
+        &hex!("c0a80001")[..],
+        &hex!("c0a8ff01"),
+        &hex!("20010db8000000000000000000000001"),
+    ] {
+        assert!(matcher.check_name(&ip(addr)).is_ok(), "{:02x?}", addr);
+    }
+
+    for addr in [
+        &hex!("c0a90001")[..],
+        &hex!("20010db9000000000000000000000001"),
+        &hex!("00000000000000000000ffffc0a80001"),
+    ] {
+        assert!(matches!(
+            matcher.check_name(&ip(addr)),
+            Err(Violation::NotPermitted { .. })
+        ));
+    }
+
+    assert!(matches!(
+        matcher.check_name(&ip(&hex!("c0a80101"))),
+        Err(Violation::Excluded { .. })
+    ));
+}
+
+#[test]
+fn malformed_ip_address() {
+    // addresses of a malformed length are within no permitted subtree, and
+    // within every excluded subtree
+    let permitting = matcher(&[ip(&hex!("c0a80000 ffff0000"))], &[]);
+    let excluding = matcher(&[], &[ip(&hex!("0a000000 ff000000"))]);
+    assert!(excluding.check_name(&ip(&hex!("c0a80001"))).is_ok());
+
+    for addr in [&hex!("c0a801")[..], &hex!("c0a8000100"), &[]] {
+        assert!(matches!(
+            permitting.check_name(&ip(addr)),
+            Err(Violation::NotPermitted { .. })
+        ));
+        assert!(matches!(
+            excluding.check_name(&ip(addr)),
+            Err(Violation::Excluded { .. })
+        ));
+    }
+}
+
+#[test]
+fn accumulated_constraints() {
+    let mut matcher = matcher(&[dns("example.com")], &[]);
+    matcher.add(NameConstraints {
+        permitted_subtrees: Some(vec![subtree(dns("www.example.com"))]),
+        excluded_subtrees: None,
+    });
+
+    // names must be within the permitted subtrees of every extension
+    assert!(matcher.check_name(&dns("a.www.example.com")).is_ok());
+    assert_eq!(
+        matcher.check_name(&dns("mail.example.com")),
+        Err(Violation::NotPermitted {
+            name: dns("mail.example.com"),
+            permitted: vec![subtree(dns("www.example.com"))],
+        })
+    );
+
+    // unsupported forms are never permitted and always excluded
+    let oid = GeneralName::RegisteredId(ObjectIdentifier::new_unwrap("1.2.3.4"));
+    matcher.add(NameConstraints {
+        permitted_subtrees: None,
+        excluded_subtrees: Some(vec![subtree(oid.clone())]),
+    });
+    assert!(matches!(
+        matcher.check_name(&oid),
+        Err(Violation::Excluded { .. })
+    ));
+
+    let violation = matcher.check_name(&oid).unwrap_err();
+    assert_eq!(violation.to_string(), "name is within an excluded subtree");
+}
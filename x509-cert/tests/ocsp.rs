@@ -0,0 +1,187 @@
+//! OCSP tests
+
+use const_oid::db::rfc5912::ID_SHA_256;
+use const_oid::db::rfc6960::ID_PKIX_OCSP_BASIC;
+use der::asn1::{BitStringRef, GeneralizedTime, Null, OctetStringRef};
+use der::{Decode, Encode};
+use hex_literal::hex;
+use sha2::{Digest as _, Sha256};
+use spki::AlgorithmIdentifier;
+use x509_cert::ext::pkix::CrlReason;
+use x509_cert::ocsp::{
+    BasicOcspResponse, CertIdOwned, CertStatus, Digest, OcspRequest, OcspResponse,
+    OcspResponseStatus, Request, ResponderId, ResponseBytes, ResponseData, SingleResponse,
+    TbsRequest, Version,
+};
+use x509_cert::Certificate;
+
+struct Sha256Digest;
+
+impl Digest for Sha256Digest {
+    fn algorithm(&self) -> AlgorithmIdentifier<'_> {
+        AlgorithmIdentifier {
+            oid: ID_SHA_256,
+            parameters: Some(Null.into()),
+        }
+    }
+
+    fn digest(&self, msg: &[u8]) -> Vec<u8> {
+        Sha256::digest(msg).to_vec()
+    }
+}
+
+#[test]
+fn decode_request() {
+    let der = include_bytes!("examples/path-ocsp-req.der");
+    let req = OcspRequest::from_der(der).unwrap();
+
+    assert_eq!(req.tbs_request.version, Version::V1);
+    assert!(req.tbs_request.requestor_name.is_none());
+    assert!(req.tbs_request.request_extensions.is_none());
+    assert!(req.optional_signature.is_none());
+    assert_eq!(req.tbs_request.request_list.len(), 2);
+
+    let cert_id = &req.tbs_request.request_list[0].req_cert;
+    assert_eq!(cert_id.hash_algorithm.oid, ID_SHA_256);
+    assert_eq!(
+        cert_id.issuer_name_hash.as_bytes(),
+        hex!("CC54081A5B377ED56DAA8791BD54CDE076CDF19D7C6900FF2EC9F4948A6E6BD8")
+    );
+    assert_eq!(
+        cert_id.issuer_key_hash.as_bytes(),
+        hex!("4B69BE8FEAC764F4ED26D41803EEE367446E25633FFF442F5F66C8F163BEEF12")
+    );
+    assert_eq!(cert_id.serial_number.as_bytes(), [0x0A]);
+
+    assert_eq!(req.to_vec().unwrap(), der);
+}
+
+#[test]
+fn decode_response() {
+    let der = include_bytes!("examples/path-ocsp-resp.der");
+    let resp = OcspResponse::from_der(der).unwrap();
+    assert_eq!(resp.response_status, OcspResponseStatus::Successful);
+    assert_eq!(resp.to_vec().unwrap(), der);
+
+    let basic = resp.basic().unwrap().unwrap();
+    let data = &basic.tbs_response_data;
+    assert_eq!(data.version, Version::V1);
+    assert!(basic.certs.is_none());
+
+    let ca = Certificate::from_der(include_bytes!("examples/path-ca.der")).unwrap();
+    assert_eq!(
+        data.responder_id,
+        ResponderId::ByName(ca.tbs_certificate.subject)
+    );
+
+    assert_eq!(data.responses.len(), 2);
+    match data.responses[0].cert_status {
+        CertStatus::Revoked(info) => {
+            assert_eq!(info.revocation_reason, Some(CrlReason::KeyCompromise))
+        }
+        other => panic!("unexpected status: {:?}", other),
+    }
+    assert_eq!(data.responses[1].cert_status, CertStatus::Unknown(Null));
+    assert!(data.responses[1].next_update.is_some());
+
+    // an unsuccessful response has no response bytes
+    let resp = OcspResponse::from_der(&hex!("30030A0101")).unwrap();
+    assert_eq!(resp.response_status, OcspResponseStatus::MalformedRequest);
+    assert_eq!(resp.basic(), Ok(None));
+}
+
+#[test]
+fn cert_id_from_certificate() {
+    let ca = Certificate::from_der(include_bytes!("examples/path-ca.der")).unwrap();
+    let leaf = Certificate::from_der(include_bytes!("examples/path-leaf.der")).unwrap();
+    let expired = Certificate::from_der(include_bytes!("examples/path-leaf-expired.der")).unwrap();
+    let req = OcspRequest::from_der(include_bytes!("examples/path-ocsp-req.der")).unwrap();
+
+    let cert_id = CertIdOwned::from_certificate(&leaf, &ca, &Sha256Digest).unwrap();
+    assert_eq!(cert_id.as_ref(), req.tbs_request.request_list[0].req_cert);
+    assert!(cert_id.as_ref().matches(&leaf, &ca, &Sha256Digest).unwrap());
+    assert_eq!(
+        CertIdOwned::from(&req.tbs_request.request_list[0].req_cert),
+        cert_id
+    );
+
+    let requested = &req.tbs_request.request_list[1].req_cert;
+    assert!(requested.matches(&expired, &ca, &Sha256Digest).unwrap());
+    assert!(!requested.matches(&leaf, &ca, &Sha256Digest).unwrap());
+    assert!(!requested.matches(&expired, &leaf, &Sha256Digest).unwrap());
+}
+
+#[test]
+fn respond_to_request() {
+    let ca = Certificate::from_der(include_bytes!("examples/path-ca.der")).unwrap();
+    let leaf = Certificate::from_der(include_bytes!("examples/path-leaf.der")).unwrap();
+    let produced_at =
+        GeneralizedTime::from_unix_duration(core::time::Duration::from_secs(1_800_000_000))
+            .unwrap();
+
+    let cert_id = CertIdOwned::from_certificate(&leaf, &ca, &Sha256Digest).unwrap();
+    let req_der = OcspRequest {
+        tbs_request: TbsRequest {
+            version: Version::V1,
+            requestor_name: None,
+            request_list: vec![Request {
+                req_cert: cert_id.as_ref(),
+                single_request_extensions: None,
+            }],
+            request_extensions: None,
+        },
+        optional_signature: None,
+    }
+    .to_vec()
+    .unwrap();
+
+    // a local responder stand-in answering every request with `good`
+    let req = OcspRequest::from_der(&req_der).unwrap();
+    let responses = req
+        .tbs_request
+        .request_list
+        .into_iter()
+        .map(|request| SingleResponse {
+            cert_id: request.req_cert,
+            cert_status: CertStatus::Good(Null),
+            this_update: produced_at,
+            next_update: None,
+            single_extensions: None,
+        })
+        .collect();
+
+    let basic_der = BasicOcspResponse {
+        tbs_response_data: ResponseData {
+            version: Version::V1,
+            responder_id: ResponderId::ByKey(OctetStringRef::new(&[0x11; 20]).unwrap()),
+            produced_at,
+            responses,
+            response_extensions: None,
+        },
+        signature_algorithm: Sha256Digest.algorithm(),
+        signature: BitStringRef::from_bytes(&[]).unwrap(),
+        certs: None,
+    }
+    .to_vec()
+    .unwrap();
+
+    let resp_der = OcspResponse {
+        response_status: OcspResponseStatus::Successful,
+        response_bytes: Some(ResponseBytes {
+            response_type: ID_PKIX_OCSP_BASIC,
+            response: OctetStringRef::new(&basic_der).unwrap(),
+        }),
+    }
+    .to_vec()
+    .unwrap();
+
+    let resp = OcspResponse::from_der(&resp_der).unwrap();
+    let basic = resp.basic().unwrap().unwrap();
+    let single = basic
+        .tbs_response_data
+        .responses
+        .iter()
+        .find(|single| single.cert_id.matches(&leaf, &ca, &Sha256Digest).unwrap())
+        .unwrap();
+    assert_eq!(single.cert_status, CertStatus::Good(Null));
+}
@@ -0,0 +1,122 @@
+//! Owned type tests
+
+use std::collections::HashMap;
+
+use const_oid::db::rfc5280::ID_CE_BASIC_CONSTRAINTS;
+use der::{Decode, Encode};
+use x509_cert::crl::{CertificateList, CertificateListOwned, RevokedCertOwned};
+use x509_cert::ext::pkix::BasicConstraints;
+use x509_cert::ext::{Extension, ExtensionOwned};
+use x509_cert::name::NameOwned;
+use x509_cert::{Certificate, CertificateOwned};
+
+#[test]
+fn certificate_round_trip() {
+    let der = include_bytes!("examples/amazon.der");
+    let cert = Certificate::from_der(der).unwrap();
+
+    let owned = CertificateOwned::from(&cert);
+    assert_eq!(owned.as_ref(), cert);
+    assert_eq!(owned, CertificateOwned::from_der(der).unwrap());
+    assert_eq!(owned.to_vec().unwrap(), der);
+
+    let tbs = owned.tbs_certificate.as_ref();
+    let (_, bc) = tbs.get::<BasicConstraints>().unwrap().unwrap();
+    assert!(!bc.ca);
+    assert_eq!(
+        owned.tbs_certificate.serial_number.as_bytes(),
+        cert.tbs_certificate.serial_number.as_bytes()
+    );
+    assert_eq!(
+        owned.tbs_certificate.issuer.to_string(),
+        cert.tbs_certificate.issuer.to_string()
+    );
+}
+
+#[test]
+fn certificates_outlive_input() {
+    let paths = [
+        &include_bytes!("examples/path-root.der")[..],
+        &include_bytes!("examples/path-ca.der")[..],
+        &include_bytes!("examples/path-leaf.der")[..],
+    ];
+
+    let mut cache: HashMap<String, CertificateOwned> = HashMap::new();
+    for der in paths {
+        let der = der.to_vec();
+        let cert = Certificate::from_der(&der).unwrap();
+        cache.insert(
+            cert.tbs_certificate.subject.to_string(),
+            CertificateOwned::from(&cert),
+        );
+    }
+
+    assert_eq!(cache.len(), 3);
+    for (subject, cert) in &cache {
+        let cert = cert.as_ref();
+        assert_eq!(&cert.tbs_certificate.subject.to_string(), subject);
+        assert_eq!(
+            Certificate::from_der(&cert.to_vec().unwrap()).unwrap(),
+            cert
+        );
+    }
+
+    // owned values can be moved across threads
+    let cache = std::thread::spawn(move || cache).join().unwrap();
+    assert_eq!(cache.len(), 3);
+}
+
+#[test]
+fn name_round_trip() {
+    let der = include_bytes!("examples/amazon.der");
+    let cert = Certificate::from_der(der).unwrap();
+    let subject = &cert.tbs_certificate.subject;
+
+    let owned = NameOwned::from(subject);
+    assert_eq!(owned.to_string(), subject.to_string());
+    assert_eq!(owned.as_ref(), *subject);
+    assert_eq!(
+        NameOwned::from_der(&subject.to_vec().unwrap()).unwrap(),
+        owned
+    );
+}
+
+#[test]
+fn crl_round_trip() {
+    for der in [
+        &include_bytes!("examples/GoodCACRL.crl")[..],
+        &include_bytes!("examples/path-ca.crl")[..],
+    ] {
+        let crl = CertificateList::from_der(der).unwrap();
+
+        let owned = CertificateListOwned::from(&crl);
+        assert_eq!(owned.as_ref(), crl);
+        assert_eq!(owned, CertificateListOwned::from_der(der).unwrap());
+        assert_eq!(owned.to_vec().unwrap(), der);
+
+        let revoked = owned.tbs_cert_list.revoked_certificates.as_ref().unwrap();
+        assert_eq!(
+            revoked.len(),
+            crl.tbs_cert_list.revoked_certificates.unwrap().len()
+        );
+        assert_eq!(RevokedCertOwned::from(&revoked[0].as_ref()), revoked[0]);
+    }
+}
+
+#[test]
+fn extension_round_trip() {
+    let ext = Extension {
+        extn_id: ID_CE_BASIC_CONSTRAINTS,
+        critical: true,
+        extn_value: &[0x30, 0x03, 0x01, 0x01, 0xff],
+    };
+
+    let owned = ExtensionOwned::from(&ext);
+    assert_eq!(owned.extn_value, ext.extn_value);
+    assert_eq!(owned.as_ref(), ext);
+    assert_eq!(owned.to_vec().unwrap(), ext.to_vec().unwrap());
+    assert_eq!(
+        ExtensionOwned::from_der(&ext.to_vec().unwrap()).unwrap(),
+        owned
+    );
+}
@@ -0,0 +1,278 @@
+//! Certification path validation tests
+
+mod common;
+
+use common::EcdsaP256Verifier;
+use der::asn1::{Ia5StringRef, OctetStringRef};
+use der::{DateTime, Decode};
+use x509_cert::anchor::{CertPathControls, TrustAnchorInfo, Version};
+use x509_cert::ext::pkix::constraints::name::GeneralSubtree;
+use x509_cert::ext::pkix::name::GeneralName;
+use x509_cert::ext::pkix::SubjectKeyIdentifier;
+use x509_cert::name::constraints::Violation;
+use x509_cert::path::{validate, validate_path, Error, ValidationParams};
+use x509_cert::verify::{self, SignedCertificate};
+use x509_cert::Certificate;
+
+const POLICY_1: &str = "2.16.840.1.101.3.2.1.48.1";
+const POLICY_2: &str = "2.16.840.1.101.3.2.1.48.2";
+
+fn params() -> ValidationParams {
+    ValidationParams::new(DateTime::new(2025, 1, 1, 0, 0, 0).unwrap())
+}
+
+/// Wrap a self-signed certificate in a `TrustAnchorInfo` carrying its name.
+fn anchor<'a>(cert: &'a Certificate<'a>) -> TrustAnchorInfo<'a> {
+    let (_, skid) = cert
+        .tbs_certificate
+        .get::<SubjectKeyIdentifier<'_>>()
+        .unwrap()
+        .unwrap();
+
+    TrustAnchorInfo {
+        version: Version::V1,
+        pub_key: cert.tbs_certificate.subject_public_key_info,
+        key_id: OctetStringRef::new(skid.0.as_bytes()).unwrap(),
+        ta_title: None,
+        cert_path: Some(CertPathControls {
+            ta_name: cert.tbs_certificate.subject.clone(),
+            certificate: None,
+            policy_set: None,
+            policy_flags: None,
+            name_constr: None,
+            path_len_constraint: None,
+        }),
+        extensions: None,
+        ta_title_lang_tag: None,
+    }
+}
+
+#[test]
+fn validate_leaf() {
+    let root_der = include_bytes!("examples/path-root.der");
+    let root = Certificate::from_der(root_der).unwrap();
+    let ca = SignedCertificate::from_der(include_bytes!("examples/path-ca.der")).unwrap();
+    let ca_nc = SignedCertificate::from_der(include_bytes!("examples/path-ca-nc.der")).unwrap();
+    let leaf = SignedCertificate::from_der(include_bytes!("examples/path-leaf.der")).unwrap();
+
+    let anchors = [anchor(&root)];
+    let intermediates = [ca_nc, ca];
+
+    let path = validate(
+        &leaf,
+        &intermediates,
+        &anchors,
+        &params(),
+        &EcdsaP256Verifier,
+    )
+    .unwrap();
+    assert_eq!(path.certificates, [&intermediates[1], &leaf]);
+    assert_eq!(path.policies, [POLICY_1.parse().unwrap()]);
+    assert_eq!(path.public_key, leaf.tbs.subject_public_key_info);
+
+    // the root itself is not an acceptable intermediate
+    let intermediates = [SignedCertificate::from_der(root_der).unwrap()];
+    assert_eq!(
+        validate(
+            &leaf,
+            &intermediates,
+            &anchors,
+            &params(),
+            &EcdsaP256Verifier
+        ),
+        Err(Error::PathNotFound)
+    );
+}
+
+#[test]
+fn validate_signatures() {
+    let root = Certificate::from_der(include_bytes!("examples/path-root.der")).unwrap();
+    let leaf = SignedCertificate::from_der(include_bytes!("examples/path-leaf.der")).unwrap();
+    let anchors = [anchor(&root)];
+
+    // a copy of the intermediate with another serial number has its subject
+    // and key identifiers, but not a valid signature
+    let mut der = include_bytes!("examples/path-ca.der").to_vec();
+    der[15] = 0x03;
+    let forged = SignedCertificate::from_der(&der).unwrap();
+    let error = Err(Error::Signature {
+        index: 0,
+        error: verify::Error::Verification,
+    });
+
+    assert_eq!(
+        validate_path(
+            &[&forged, &leaf],
+            &anchors[0],
+            &params(),
+            &EcdsaP256Verifier
+        ),
+        error
+    );
+
+    let intermediates = [forged];
+    assert_eq!(
+        validate(
+            &leaf,
+            &intermediates,
+            &anchors,
+            &params(),
+            &EcdsaP256Verifier
+        ),
+        error
+    );
+}
+
+#[test]
+fn validate_time() {
+    let root = Certificate::from_der(include_bytes!("examples/path-root.der")).unwrap();
+    let ca = SignedCertificate::from_der(include_bytes!("examples/path-ca.der")).unwrap();
+    let expired =
+        SignedCertificate::from_der(include_bytes!("examples/path-leaf-expired.der")).unwrap();
+    let leaf = SignedCertificate::from_der(include_bytes!("examples/path-leaf.der")).unwrap();
+    let anchor = anchor(&root);
+
+    assert_eq!(
+        validate_path(&[&ca, &expired], &anchor, &params(), &EcdsaP256Verifier),
+        Err(Error::Expired { index: 1 })
+    );
+
+    let mut params = params();
+    params.time = DateTime::new(2020, 6, 1, 0, 0, 0).unwrap();
+    assert_eq!(
+        validate_path(&[&ca, &leaf], &anchor, &params, &EcdsaP256Verifier),
+        Err(Error::NotYetValid { index: 1 })
+    );
+}
+
+#[test]
+fn validate_name_chaining() {
+    let root = Certificate::from_der(include_bytes!("examples/path-root.der")).unwrap();
+    let ca_nc = SignedCertificate::from_der(include_bytes!("examples/path-ca-nc.der")).unwrap();
+    let leaf = SignedCertificate::from_der(include_bytes!("examples/path-leaf.der")).unwrap();
+    let anchor = anchor(&root);
+
+    assert_eq!(
+        validate_path(&[&ca_nc, &leaf], &anchor, &params(), &EcdsaP256Verifier),
+        Err(Error::NameChaining { index: 1 })
+    );
+    assert_eq!(
+        validate_path(&[&leaf], &anchor, &params(), &EcdsaP256Verifier),
+        Err(Error::NameChaining { index: 0 })
+    );
+}
+
+#[test]
+fn validate_ca_constraints() {
+    let root = Certificate::from_der(include_bytes!("examples/path-root.der")).unwrap();
+    let ca = SignedCertificate::from_der(include_bytes!("examples/path-ca.der")).unwrap();
+    let sub_ca = SignedCertificate::from_der(include_bytes!("examples/path-sub-ca.der")).unwrap();
+    let sub_leaf =
+        SignedCertificate::from_der(include_bytes!("examples/path-sub-leaf.der")).unwrap();
+    let nokcs = SignedCertificate::from_der(include_bytes!("examples/path-ca-nokcs.der")).unwrap();
+    let nokcs_leaf =
+        SignedCertificate::from_der(include_bytes!("examples/path-nokcs-leaf.der")).unwrap();
+    let anchor = anchor(&root);
+
+    // path-ca has a pathLenConstraint of zero
+    assert_eq!(
+        validate_path(
+            &[&ca, &sub_ca, &sub_leaf],
+            &anchor,
+            &params(),
+            &EcdsaP256Verifier
+        ),
+        Err(Error::PathLength { index: 1 })
+    );
+    assert_eq!(
+        validate_path(
+            &[&nokcs, &nokcs_leaf],
+            &anchor,
+            &params(),
+            &EcdsaP256Verifier
+        ),
+        Err(Error::KeyCertSignNotAsserted { index: 0 })
+    );
+}
+
+#[test]
+fn validate_critical_extensions() {
+    let root = Certificate::from_der(include_bytes!("examples/path-root.der")).unwrap();
+    let ca = SignedCertificate::from_der(include_bytes!("examples/path-ca.der")).unwrap();
+    let leaf =
+        SignedCertificate::from_der(include_bytes!("examples/path-leaf-critical.der")).unwrap();
+    let anchor = anchor(&root);
+    let oid = "1.2.3.4.5.6.7.8.9".parse().unwrap();
+
+    assert_eq!(
+        validate_path(&[&ca, &leaf], &anchor, &params(), &EcdsaP256Verifier),
+        Err(Error::UnknownCriticalExtension { index: 1, oid })
+    );
+
+    let mut params = params();
+    params.known_extensions.push(oid);
+    assert!(validate_path(&[&ca, &leaf], &anchor, &params, &EcdsaP256Verifier).is_ok());
+}
+
+#[test]
+fn validate_name_constraints() {
+    let root = Certificate::from_der(include_bytes!("examples/path-root.der")).unwrap();
+    let ca_nc = SignedCertificate::from_der(include_bytes!("examples/path-ca-nc.der")).unwrap();
+    let good = SignedCertificate::from_der(include_bytes!("examples/path-nc-good.der")).unwrap();
+    let bad = SignedCertificate::from_der(include_bytes!("examples/path-nc-bad.der")).unwrap();
+    let anchor = anchor(&root);
+
+    assert!(validate_path(&[&ca_nc, &good], &anchor, &params(), &EcdsaP256Verifier).is_ok());
+    let err = validate_path(&[&ca_nc, &bad], &anchor, &params(), &EcdsaP256Verifier).unwrap_err();
+    let violation = match err {
+        Error::NameConstraints {
+            index: 1,
+            violation,
+        } => violation,
+        err => panic!("unexpected error: {}", err),
+    };
+    assert_eq!(
+        *violation,
+        Violation::Excluded {
+            name: GeneralName::DnsName(Ia5StringRef::new("www.bad.example.com").unwrap()),
+            excluded: GeneralSubtree {
+                base: GeneralName::DnsName(Ia5StringRef::new("bad.example.com").unwrap()),
+                minimum: 0,
+                maximum: None,
+            },
+        }
+    );
+}
+
+#[test]
+fn validate_policies() {
+    let root = Certificate::from_der(include_bytes!("examples/path-root.der")).unwrap();
+    let ca = SignedCertificate::from_der(include_bytes!("examples/path-ca.der")).unwrap();
+    let leaf = SignedCertificate::from_der(include_bytes!("examples/path-leaf.der")).unwrap();
+    let ca_nc = SignedCertificate::from_der(include_bytes!("examples/path-ca-nc.der")).unwrap();
+    let good = SignedCertificate::from_der(include_bytes!("examples/path-nc-good.der")).unwrap();
+    let anchor = anchor(&root);
+
+    let mut params = params();
+    params.initial_explicit_policy = true;
+    params.user_initial_policy_set = vec![POLICY_1.parse().unwrap()];
+    let path = validate_path(&[&ca, &leaf], &anchor, &params, &EcdsaP256Verifier).unwrap();
+    assert_eq!(path.policies, [POLICY_1.parse().unwrap()]);
+
+    params.user_initial_policy_set = vec![POLICY_2.parse().unwrap()];
+    assert_eq!(
+        validate_path(&[&ca, &leaf], &anchor, &params, &EcdsaP256Verifier),
+        Err(Error::PolicyRequired { index: 1 })
+    );
+
+    // anyPolicy asserted throughout satisfies any user policy...
+    let path = validate_path(&[&ca_nc, &good], &anchor, &params, &EcdsaP256Verifier).unwrap();
+    assert_eq!(path.policies, [POLICY_2.parse().unwrap()]);
+
+    // ...unless anyPolicy is inhibited
+    params.initial_any_policy_inhibit = true;
+    assert_eq!(
+        validate_path(&[&ca_nc, &good], &anchor, &params, &EcdsaP256Verifier),
+        Err(Error::PolicyRequired { index: 0 })
+    );
+}
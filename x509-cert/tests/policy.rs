@@ -0,0 +1,149 @@
+//! Certificate policy processing tests
+
+use const_oid::db::rfc5280::{ANY_POLICY, ID_QT_CPS, ID_QT_UNOTICE};
+use const_oid::ObjectIdentifier;
+use der::Decode;
+use x509_cert::policy::{process_policies, Error, PolicyParams, ValidPolicies};
+use x509_cert::Certificate;
+
+const POLICY_1: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.16.840.1.101.3.2.1.48.1");
+const POLICY_2: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.16.840.1.101.3.2.1.48.2");
+const POLICY_3: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.16.840.1.101.3.2.1.48.3");
+
+fn cert(der: &[u8]) -> Certificate<'_> {
+    Certificate::from_der(der).unwrap()
+}
+
+fn oids(policies: &ValidPolicies<'_>) -> (Vec<ObjectIdentifier>, Vec<ObjectIdentifier>) {
+    (
+        policies
+            .authority_constrained
+            .iter()
+            .map(|p| p.policy)
+            .collect(),
+        policies.user_constrained.iter().map(|p| p.policy).collect(),
+    )
+}
+
+#[test]
+fn policy_sets() {
+    let ca = cert(include_bytes!("examples/path-ca.der"));
+    let leaf = cert(include_bytes!("examples/path-leaf.der"));
+
+    let policies = process_policies(&[&ca, &leaf], &PolicyParams::default()).unwrap();
+    assert_eq!(oids(&policies), (vec![POLICY_1], vec![POLICY_1]));
+
+    // the user-constrained set is restricted to the user initial policy set
+    let mut params = PolicyParams {
+        user_initial_policy_set: vec![POLICY_2, POLICY_3],
+        ..Default::default()
+    };
+    let policies = process_policies(&[&ca, &leaf], &params).unwrap();
+    assert_eq!(oids(&policies), (vec![POLICY_1], vec![]));
+
+    params.initial_explicit_policy = true;
+    assert_eq!(
+        process_policies(&[&ca, &leaf], &params),
+        Err(Error::PolicyRequired { index: 1 })
+    );
+}
+
+#[test]
+fn any_policy() {
+    let ca_nc = cert(include_bytes!("examples/path-ca-nc.der"));
+    let good = cert(include_bytes!("examples/path-nc-good.der"));
+
+    let policies = process_policies(&[&ca_nc, &good], &PolicyParams::default()).unwrap();
+    assert_eq!(oids(&policies), (vec![ANY_POLICY], vec![ANY_POLICY]));
+
+    // anyPolicy is expanded into the user initial policy set
+    let mut params = PolicyParams {
+        user_initial_policy_set: vec![POLICY_1, POLICY_2],
+        ..Default::default()
+    };
+    let policies = process_policies(&[&ca_nc, &good], &params).unwrap();
+    assert_eq!(
+        oids(&policies),
+        (vec![ANY_POLICY], vec![POLICY_1, POLICY_2])
+    );
+
+    // ...unless it is inhibited
+    params.initial_any_policy_inhibit = true;
+    let policies = process_policies(&[&ca_nc, &good], &params).unwrap();
+    assert_eq!(policies, ValidPolicies::default());
+}
+
+#[test]
+fn policy_mappings() {
+    let ca = cert(include_bytes!("examples/path-policy-ca.der"));
+    let leaf = cert(include_bytes!("examples/path-policy-leaf.der"));
+
+    // POLICY_1 in the domain of the trust anchor is mapped to POLICY_3
+    let mut params = PolicyParams {
+        user_initial_policy_set: vec![POLICY_1],
+        ..Default::default()
+    };
+    let policies = process_policies(&[&ca, &leaf], &params).unwrap();
+    assert_eq!(oids(&policies), (vec![POLICY_3], vec![POLICY_3]));
+
+    // qualifiers of the target certificate are reported
+    let qualifiers = &policies.user_constrained[0].qualifiers;
+    assert_eq!(qualifiers.len(), 1);
+    assert_eq!(qualifiers[0].policy_qualifier_id, ID_QT_UNOTICE);
+    // UserNotice with explicitText as a VisibleString
+    let notice = qualifiers[0].qualifier.unwrap();
+    assert_eq!(notice.value(), b"\x1a\x0bTest notice");
+
+    // POLICY_3 is not in the domain of the trust anchor, and the CA requires
+    // an explicit policy
+    params.user_initial_policy_set = vec![POLICY_3];
+    assert_eq!(
+        process_policies(&[&ca, &leaf], &params),
+        Err(Error::PolicyRequired { index: 1 })
+    );
+
+    // without mapping, POLICY_3 is only valid through anyPolicy
+    let mut params = PolicyParams {
+        initial_policy_mapping_inhibit: true,
+        ..Default::default()
+    };
+    let policies = process_policies(&[&ca, &leaf], &params).unwrap();
+    assert_eq!(oids(&policies), (vec![POLICY_3], vec![POLICY_3]));
+
+    params.user_initial_policy_set = vec![POLICY_1];
+    assert_eq!(
+        process_policies(&[&ca, &leaf], &params),
+        Err(Error::PolicyRequired { index: 1 })
+    );
+}
+
+#[test]
+fn policy_constraints() {
+    let ca = cert(include_bytes!("examples/path-policy-ca.der"));
+    let root = cert(include_bytes!("examples/path-root.der"));
+
+    // requireExplicitPolicy in the CA applies to the certificate without
+    // policies which follows it
+    assert_eq!(
+        process_policies(&[&ca, &root], &PolicyParams::default()),
+        Err(Error::PolicyRequired { index: 1 })
+    );
+
+    // the qualifiers of a path ending at the CA are its own
+    let policies = process_policies(&[&ca], &PolicyParams::default()).unwrap();
+    assert_eq!(
+        oids(&policies),
+        (vec![POLICY_1, ANY_POLICY], vec![POLICY_1, ANY_POLICY])
+    );
+    let qualifiers = &policies.user_constrained[0].qualifiers;
+    assert_eq!(qualifiers[0].policy_qualifier_id, ID_QT_CPS);
+    assert_eq!(
+        qualifiers[0]
+            .qualifier
+            .unwrap()
+            .ia5_string()
+            .unwrap()
+            .as_str(),
+        "https://pki.example.com/cps"
+    );
+}
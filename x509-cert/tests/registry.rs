@@ -0,0 +1,208 @@
+//! Extension registry tests
+
+use const_oid::db::rfc5280::ID_CE_BASIC_CONSTRAINTS;
+use const_oid::{AssociatedOid, ObjectIdentifier};
+use der::{Decode, Reader};
+use x509_cert::crl::CertificateList;
+use x509_cert::ext::dept::{DepartmentIdentity, DEPARTMENT_IDENTITY};
+use x509_cert::ext::pkix::crl::CrlReason;
+use x509_cert::ext::registry::{ExtensionRegistry, ParsedExtension};
+use x509_cert::ext::Extension;
+use x509_cert::Certificate;
+
+/// Signed certificate timestamp list, which is not known to this crate.
+const SCT_LIST: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.6.1.4.1.11129.2.4.2");
+
+/// Department identity extension decoded as its raw JSON text.
+#[derive(Debug, PartialEq, Eq)]
+struct RawDepartmentIdentity(String);
+
+impl AssociatedOid for RawDepartmentIdentity {
+    const OID: ObjectIdentifier = DEPARTMENT_IDENTITY;
+}
+
+impl<'a> Decode<'a> for RawDepartmentIdentity {
+    fn decode<R: Reader<'a>>(reader: &mut R) -> der::Result<Self> {
+        let bytes = reader.read_slice(reader.remaining_len())?;
+        let json = core::str::from_utf8(bytes)?;
+        Ok(Self(json.into()))
+    }
+}
+
+#[test]
+fn decode_certificate_extensions() {
+    let cert = Certificate::from_der(include_bytes!("examples/amazon.der")).unwrap();
+    let registry = ExtensionRegistry::new();
+    let parsed: Vec<_> = cert
+        .tbs_certificate
+        .parsed_extensions(&registry)
+        .map(|(ext, parsed)| (ext.extn_id, parsed.unwrap()))
+        .collect();
+
+    assert_eq!(parsed.len(), 10);
+    assert!(matches!(
+        parsed[0].1,
+        ParsedExtension::AuthorityKeyIdentifier(_)
+    ));
+    assert!(matches!(
+        parsed[1].1,
+        ParsedExtension::SubjectKeyIdentifier(_)
+    ));
+    match &parsed[2].1 {
+        ParsedExtension::SubjectAltName(san) => assert_eq!(san.0.len(), 36),
+        other => panic!("unexpected {:?}", other),
+    }
+    assert!(matches!(parsed[3].1, ParsedExtension::KeyUsage(_)));
+    assert!(matches!(parsed[4].1, ParsedExtension::ExtendedKeyUsage(_)));
+    match &parsed[5].1 {
+        ParsedExtension::CrlDistributionPoints(dps) => assert_eq!(dps.0.len(), 2),
+        other => panic!("unexpected {:?}", other),
+    }
+    assert!(matches!(
+        parsed[6].1,
+        ParsedExtension::CertificatePolicies(_)
+    ));
+    assert!(matches!(
+        parsed[7].1,
+        ParsedExtension::AuthorityInfoAccess(_)
+    ));
+    match &parsed[8].1 {
+        ParsedExtension::BasicConstraints(bc) => assert!(!bc.ca),
+        other => panic!("unexpected {:?}", other),
+    }
+
+    assert_eq!(parsed[9].0, SCT_LIST);
+    assert!(!registry.contains(&SCT_LIST));
+    let ext = &cert.tbs_certificate.extensions.as_ref().unwrap()[9];
+    match parsed[9].1 {
+        ParsedExtension::Unknown(bytes) => assert_eq!(bytes, ext.extn_value),
+        ref other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn decode_crl_extensions() {
+    let crl = CertificateList::from_der(include_bytes!("examples/path-ca.crl")).unwrap();
+    let registry = ExtensionRegistry::new();
+
+    let mut exts = crl.tbs_cert_list.parsed_extensions(&registry);
+    let (_, parsed) = exts.next().unwrap();
+    match parsed.unwrap() {
+        ParsedExtension::CrlNumber(number) => assert_eq!(number.0.as_bytes(), [2]),
+        other => panic!("unexpected {:?}", other),
+    }
+    assert!(exts.next().is_none());
+
+    let revoked = &crl.tbs_cert_list.revoked_certificates.as_ref().unwrap()[0];
+    let mut exts = revoked.parsed_extensions(&registry);
+    let (_, parsed) = exts.next().unwrap();
+    assert!(matches!(
+        parsed.unwrap(),
+        ParsedExtension::CrlReason(CrlReason::KeyCompromise)
+    ));
+    assert!(exts.next().is_none());
+}
+
+#[test]
+fn register_custom_extension() {
+    let cert = Certificate::from_der(include_bytes!("examples/dept-identity.der")).unwrap();
+    let exts = cert.tbs_certificate.extensions.as_deref().unwrap();
+    let ext = exts
+        .iter()
+        .find(|ext| ext.extn_id == DEPARTMENT_IDENTITY)
+        .unwrap();
+
+    // the department identity extension is only decoded once registered
+    let mut registry = ExtensionRegistry::new();
+    assert!(!registry.contains(&DEPARTMENT_IDENTITY));
+    assert!(matches!(
+        registry.decode(ext).unwrap(),
+        ParsedExtension::Unknown(_)
+    ));
+
+    registry.register::<DepartmentIdentity>();
+    assert!(registry.contains(&DEPARTMENT_IDENTITY));
+    let parsed = registry.decode(ext).unwrap();
+    assert_eq!(
+        parsed
+            .downcast_ref::<DepartmentIdentity>()
+            .unwrap()
+            .dept_name,
+        "812"
+    );
+
+    // later registrations replace earlier ones
+    registry.register::<RawDepartmentIdentity>();
+    let parsed = registry.decode(ext).unwrap();
+    assert!(matches!(parsed, ParsedExtension::Custom(_)));
+    assert_eq!(
+        parsed.downcast_ref::<RawDepartmentIdentity>().unwrap().0,
+        core::str::from_utf8(ext.extn_value).unwrap()
+    );
+    assert!(parsed.downcast_ref::<String>().is_none());
+
+    // other extensions are unaffected
+    let parsed: Vec<_> = registry.decode_all(exts).collect();
+    assert_eq!(parsed.len(), exts.len());
+    assert!(parsed.iter().all(|(_, parsed)| parsed.is_ok()));
+
+    registry.register_with(SCT_LIST, |bytes| Ok(ParsedExtension::Unknown(&bytes[1..])));
+    assert!(registry.contains(&SCT_LIST));
+    let sct = Extension {
+        extn_id: SCT_LIST,
+        critical: false,
+        extn_value: &[0x04, 0x01, 0xff],
+    };
+    assert!(matches!(
+        registry.decode(&sct).unwrap(),
+        ParsedExtension::Unknown([0x01, 0xff])
+    ));
+
+    // registered decoders take precedence over the built-in ones
+    registry.register_with(ID_CE_BASIC_CONSTRAINTS, |bytes| {
+        Ok(ParsedExtension::Unknown(bytes))
+    });
+    let bc = Extension {
+        extn_id: ID_CE_BASIC_CONSTRAINTS,
+        critical: true,
+        extn_value: &[0x30, 0x00],
+    };
+    assert!(matches!(
+        registry.decode(&bc).unwrap(),
+        ParsedExtension::Unknown([0x30, 0x00])
+    ));
+}
+
+#[test]
+fn per_extension_errors() {
+    let exts = [
+        Extension {
+            extn_id: ID_CE_BASIC_CONSTRAINTS,
+            critical: true,
+            extn_value: &[0x30, 0x03, 0x01, 0x01, 0xff],
+        },
+        Extension {
+            extn_id: ID_CE_BASIC_CONSTRAINTS,
+            critical: true,
+            extn_value: &[0x30, 0x03, 0x01],
+        },
+        Extension {
+            extn_id: SCT_LIST,
+            critical: false,
+            extn_value: &[0x04, 0x00],
+        },
+    ];
+
+    let registry = ExtensionRegistry::new();
+    let parsed: Vec<_> = registry
+        .decode_all(&exts)
+        .map(|(_, parsed)| parsed)
+        .collect();
+
+    assert!(matches!(
+        parsed[0],
+        Ok(ParsedExtension::BasicConstraints(ref bc)) if bc.ca
+    ));
+    assert!(parsed[1].is_err());
+    assert!(matches!(parsed[2], Ok(ParsedExtension::Unknown(_))));
+}
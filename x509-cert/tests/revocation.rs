@@ -0,0 +1,348 @@
+//! Revocation checking tests
+
+use const_oid::db::rfc5912::ID_EC_PUBLIC_KEY;
+use der::asn1::{GeneralizedTime, Ia5StringRef};
+use der::{DateTime, Decode};
+use hex_literal::hex;
+use spki::{AlgorithmIdentifier, SubjectPublicKeyInfo};
+use x509_cert::builder::{CertificateBuilder, CrlBuilder, Result, Signer};
+use x509_cert::crl::CertificateList;
+use x509_cert::ext::pkix::crl::dp::{DistributionPoint, Reasons};
+use x509_cert::ext::pkix::name::{DistributionPointName, GeneralName};
+use x509_cert::ext::pkix::{
+    BasicConstraints, CrlDistributionPoints, CrlReason, IssuingDistributionPoint,
+};
+use x509_cert::name::Name;
+use x509_cert::revocation::{Error, RevocationChecker, Status};
+use x509_cert::time::{Time, Validity};
+use x509_cert::Certificate;
+
+/// Revocation checking does not verify signatures.
+struct NullSigner;
+
+impl Signer for NullSigner {
+    fn signature_algorithm(&self) -> AlgorithmIdentifier<'_> {
+        AlgorithmIdentifier {
+            oid: ID_EC_PUBLIC_KEY,
+            parameters: None,
+        }
+    }
+
+    fn sign(&self, _msg: &[u8]) -> Result<Vec<u8>> {
+        Ok(Vec::new())
+    }
+}
+
+fn time(year: u16) -> Time {
+    Time::GeneralTime(GeneralizedTime::from_date_time(
+        DateTime::new(year, 1, 1, 0, 0, 0).unwrap(),
+    ))
+}
+
+fn checker(year: u16) -> RevocationChecker {
+    RevocationChecker::new(DateTime::new(year, 6, 1, 0, 0, 0).unwrap())
+}
+
+fn name(s: &str) -> Vec<u8> {
+    Name::encode_from_string(s).unwrap()
+}
+
+fn uri(s: &str) -> DistributionPointName<'_> {
+    DistributionPointName::FullName(vec![GeneralName::UniformResourceIdentifier(
+        Ia5StringRef::new(s).unwrap(),
+    )])
+}
+
+/// Build a certificate with an optional `CRLDistributionPoints` extension.
+fn certificate(issuer: &[u8], serial: u8, ca: bool, dp: Option<DistributionPoint<'_>>) -> Vec<u8> {
+    let subject = name("CN=Subject");
+    let spki = SubjectPublicKeyInfo {
+        algorithm: AlgorithmIdentifier {
+            oid: ID_EC_PUBLIC_KEY,
+            parameters: None,
+        },
+        subject_public_key: &[4],
+    };
+    let validity = Validity {
+        not_before: time(2020),
+        not_after: time(2040),
+    };
+
+    let mut builder = CertificateBuilder::new(
+        &[serial],
+        validity,
+        &Name::from_der(&subject).unwrap(),
+        &spki,
+    )
+    .unwrap()
+    .issuer(&Name::from_der(issuer).unwrap())
+    .unwrap()
+    .basic_constraints(BasicConstraints {
+        ca,
+        path_len_constraint: None,
+    })
+    .unwrap();
+
+    if let Some(dp) = dp {
+        builder = builder
+            .extension(false, &CrlDistributionPoints(vec![dp]))
+            .unwrap();
+    }
+
+    builder.build(&NullSigner).unwrap()
+}
+
+#[test]
+fn check_complete_crl() {
+    let crl = CertificateList::from_der(include_bytes!("examples/path-ca.crl")).unwrap();
+    let leaf = Certificate::from_der(include_bytes!("examples/path-leaf.der")).unwrap();
+    let expired = Certificate::from_der(include_bytes!("examples/path-leaf-expired.der")).unwrap();
+    let nc_good = Certificate::from_der(include_bytes!("examples/path-nc-good.der")).unwrap();
+
+    assert_eq!(
+        checker(2027).check(&leaf, &crl),
+        Ok(Status::Revoked {
+            date: crl.tbs_cert_list.this_update,
+            reason: Some(CrlReason::KeyCompromise),
+        })
+    );
+    assert_eq!(checker(2027).check(&expired, &crl), Ok(Status::Good));
+    assert_eq!(
+        checker(2027).check(&nc_good, &crl),
+        Err(Error::IssuerMismatch)
+    );
+
+    // freshness
+    assert_eq!(checker(2025).check(&leaf, &crl), Err(Error::NotYetValid));
+    assert_eq!(checker(2037).check(&leaf, &crl), Err(Error::Expired));
+}
+
+/// Issuer names are compared per RFC 5280 Section 7.1, not by encoding.
+#[test]
+fn check_differently_encoded_issuer() {
+    let leaf = certificate(&name("CN=CA"), 1, false, None);
+    let leaf = Certificate::from_der(&leaf).unwrap();
+
+    // CN=ca as a PrintableString
+    let issuer = hex!("300D310B3009060355040313026361");
+    let der = CrlBuilder::new(&Name::from_der(&issuer).unwrap(), time(2024))
+        .unwrap()
+        .next_update(time(2026))
+        .revoke(&[1], time(2024), None)
+        .unwrap()
+        .build(&NullSigner)
+        .unwrap();
+    let crl = CertificateList::from_der(&der).unwrap();
+
+    assert!(matches!(
+        checker(2025).check(&leaf, &crl),
+        Ok(Status::Revoked { reason: None, .. })
+    ));
+}
+
+#[test]
+fn check_distribution_point() {
+    let issuer = name("CN=CA");
+    let dp = DistributionPoint {
+        distribution_point: Some(uri("http://crl.example.com/1.crl")),
+        reasons: None,
+        crl_issuer: None,
+    };
+    let leaf = certificate(&issuer, 1, false, Some(dp.clone()));
+    let leaf = Certificate::from_der(&leaf).unwrap();
+    let ca = certificate(&issuer, 2, true, Some(dp));
+    let ca = Certificate::from_der(&ca).unwrap();
+    let other = certificate(&issuer, 3, false, None);
+    let other = Certificate::from_der(&other).unwrap();
+
+    let crl_der = |idp: IssuingDistributionPoint<'_>| {
+        CrlBuilder::new(&Name::from_der(&issuer).unwrap(), time(2024))
+            .unwrap()
+            .next_update(time(2026))
+            .issuing_distribution_point(&idp)
+            .unwrap()
+            .revoke(&[1], time(2024), None)
+            .unwrap()
+            .build(&NullSigner)
+            .unwrap()
+    };
+
+    let idp = IssuingDistributionPoint {
+        distribution_point: Some(uri("http://crl.example.com/1.crl")),
+        only_contains_user_certs: false,
+        only_contains_ca_certs: false,
+        only_some_reasons: None,
+        indirect_crl: false,
+        only_contains_attribute_certs: false,
+    };
+
+    let der = crl_der(idp.clone());
+    let crl = CertificateList::from_der(&der).unwrap();
+    assert!(matches!(
+        checker(2025).check(&leaf, &crl),
+        Ok(Status::Revoked { reason: None, .. })
+    ));
+    assert_eq!(checker(2025).check(&ca, &crl), Ok(Status::Good));
+    assert_eq!(checker(2025).check(&other, &crl), Err(Error::OutOfScope));
+
+    let der = crl_der(IssuingDistributionPoint {
+        distribution_point: Some(uri("http://crl.example.com/2.crl")),
+        ..idp.clone()
+    });
+    let crl = CertificateList::from_der(&der).unwrap();
+    assert_eq!(checker(2025).check(&leaf, &crl), Err(Error::OutOfScope));
+
+    let der = crl_der(IssuingDistributionPoint {
+        only_contains_ca_certs: true,
+        ..idp.clone()
+    });
+    let crl = CertificateList::from_der(&der).unwrap();
+    assert_eq!(checker(2025).check(&leaf, &crl), Err(Error::OutOfScope));
+    assert_eq!(checker(2025).check(&ca, &crl), Ok(Status::Good));
+
+    let der = crl_der(IssuingDistributionPoint {
+        only_some_reasons: Some(Reasons::KeyCompromise.into()),
+        ..idp
+    });
+    let crl = CertificateList::from_der(&der).unwrap();
+    assert!(matches!(
+        checker(2025).check(&leaf, &crl),
+        Ok(Status::Revoked { .. })
+    ));
+    assert_eq!(checker(2025).check(&ca, &crl), Err(Error::PartialReasons));
+}
+
+#[test]
+fn check_indirect_crl() {
+    let ca_name = name("CN=CA");
+    let crl_issuer = name("CN=CRL Issuer");
+    let dp = DistributionPoint {
+        distribution_point: None,
+        reasons: None,
+        crl_issuer: Some(vec![GeneralName::DirectoryName(
+            Name::from_der(&crl_issuer).unwrap(),
+        )]),
+    };
+
+    let revoked = certificate(&ca_name, 5, false, Some(dp.clone()));
+    let revoked = Certificate::from_der(&revoked).unwrap();
+    let good = certificate(&ca_name, 1, false, Some(dp));
+    let good = Certificate::from_der(&good).unwrap();
+    let unrelated = certificate(&ca_name, 1, false, None);
+    let unrelated = Certificate::from_der(&unrelated).unwrap();
+
+    let crl_der = |indirect_crl: bool| {
+        CrlBuilder::new(&Name::from_der(&crl_issuer).unwrap(), time(2024))
+            .unwrap()
+            .issuing_distribution_point(&IssuingDistributionPoint {
+                distribution_point: None,
+                only_contains_user_certs: false,
+                only_contains_ca_certs: false,
+                only_some_reasons: None,
+                indirect_crl,
+                only_contains_attribute_certs: false,
+            })
+            .unwrap()
+            // issued by the CRL issuer itself
+            .revoke(&[1], time(2024), None)
+            .unwrap()
+            .certificate_issuer(&[GeneralName::DirectoryName(
+                Name::from_der(&ca_name).unwrap(),
+            )])
+            .unwrap()
+            .revoke(&[4], time(2024), None)
+            .unwrap()
+            .revoke(&[5], time(2024), Some(CrlReason::Superseded))
+            .unwrap()
+            .build(&NullSigner)
+            .unwrap()
+    };
+
+    let der = crl_der(true);
+    let crl = CertificateList::from_der(&der).unwrap();
+    assert_eq!(
+        checker(2025).check(&revoked, &crl),
+        Ok(Status::Revoked {
+            date: time(2024),
+            reason: Some(CrlReason::Superseded),
+        })
+    );
+    assert_eq!(checker(2025).check(&good, &crl), Ok(Status::Good));
+    assert_eq!(
+        checker(2025).check(&unrelated, &crl),
+        Err(Error::IssuerMismatch)
+    );
+
+    let der = crl_der(false);
+    let crl = CertificateList::from_der(&der).unwrap();
+    assert_eq!(
+        checker(2025).check(&revoked, &crl),
+        Err(Error::IssuerMismatch)
+    );
+}
+
+#[test]
+fn check_delta_crl() {
+    let issuer = name("CN=CA");
+    let certs = [5, 6, 7].map(|serial| certificate(&issuer, serial, false, None));
+    let [held, compromised, good] = certs
+        .each_ref()
+        .map(|der| Certificate::from_der(der).unwrap());
+
+    let base_der = CrlBuilder::new(&Name::from_der(&issuer).unwrap(), time(2024))
+        .unwrap()
+        .next_update(time(2025))
+        .crl_number(&[10])
+        .unwrap()
+        .revoke(&[5], time(2024), Some(CrlReason::CertificateHold))
+        .unwrap()
+        .build(&NullSigner)
+        .unwrap();
+    let base = CertificateList::from_der(&base_der).unwrap();
+
+    let delta_der = |base_number: u8| {
+        CrlBuilder::new(&Name::from_der(&issuer).unwrap(), time(2025))
+            .unwrap()
+            .next_update(time(2026))
+            .crl_number(&[11])
+            .unwrap()
+            .base_crl_number(&[base_number])
+            .unwrap()
+            .revoke(&[5], time(2025), Some(CrlReason::RemoveFromCRL))
+            .unwrap()
+            .revoke(&[6], time(2025), Some(CrlReason::KeyCompromise))
+            .unwrap()
+            .build(&NullSigner)
+            .unwrap()
+    };
+
+    let der = delta_der(10);
+    let delta = CertificateList::from_der(&der).unwrap();
+
+    // the complete CRL alone is stale by now
+    assert_eq!(checker(2025).check(&held, &base), Err(Error::Expired));
+    assert_eq!(
+        checker(2025).check_with_delta(&held, &base, &delta),
+        Ok(Status::Good)
+    );
+    assert_eq!(
+        checker(2025).check_with_delta(&compromised, &base, &delta),
+        Ok(Status::Revoked {
+            date: time(2025),
+            reason: Some(CrlReason::KeyCompromise),
+        })
+    );
+    assert_eq!(
+        checker(2025).check_with_delta(&good, &base, &delta),
+        Ok(Status::Good)
+    );
+    assert_eq!(checker(2025).check(&good, &delta), Err(Error::DeltaCrl));
+
+    // delta CRLs must not be based on a later complete CRL
+    let der = delta_der(12);
+    let delta = CertificateList::from_der(&der).unwrap();
+    assert_eq!(
+        checker(2025).check_with_delta(&good, &base, &delta),
+        Err(Error::DeltaMismatch)
+    );
+}
@@ -0,0 +1,254 @@
+//! Serde tests
+
+#![cfg(feature = "serde")]
+
+use der::{Decode, Encode};
+use hex_literal::hex;
+use serde_json::json;
+use x509_cert::anchor::TrustAnchorChoice;
+use x509_cert::crl::{CertificateList, CertificateListOwned};
+use x509_cert::der::asn1::{BitString, GeneralizedTime, OctetStringRef};
+use x509_cert::ext::pkix::name::GeneralName;
+use x509_cert::ext::pkix::{
+    BasicConstraints, ExtendedKeyUsage, KeyUsage, KeyUsages, SubjectAltName,
+};
+use x509_cert::name::NameOwned;
+use x509_cert::request::CertReq;
+use x509_cert::time::{Time, Validity};
+use x509_cert::{Certificate, CertificateOwned};
+
+#[test]
+fn certificate() {
+    let der = include_bytes!("examples/path-leaf.der");
+    let cert = Certificate::from_der(der).unwrap();
+    let value = serde_json::to_value(&cert).unwrap();
+
+    let tbs = &value["tbs_certificate"];
+    assert_eq!(tbs["version"], "v3");
+    assert_eq!(tbs["issuer"], "C=US,O=RustCrypto,CN=Test Intermediate CA");
+    assert_eq!(tbs["subject"], "C=US,O=RustCrypto,CN=leaf.example.com");
+    assert_eq!(
+        tbs["validity"],
+        json!({
+            "not_before": "2021-01-01T00:00:00Z",
+            "not_after": "2031-01-01T00:00:00Z",
+        })
+    );
+    assert_eq!(
+        value["signature_algorithm"],
+        json!({
+            "algorithm": {"oid": "1.2.840.10045.4.3.2", "name": "ecdsa-with-SHA256"},
+        })
+    );
+    assert_eq!(
+        tbs["subject_public_key_info"]["algorithm"],
+        json!({
+            "algorithm": {"oid": "1.2.840.10045.2.1", "name": "id-ecPublicKey"},
+            "parameters": "06082a8648ce3d030107",
+        })
+    );
+    assert!(tbs.get("issuer_unique_id").is_none());
+    assert_eq!(value["signature"]["unused_bits"], 0);
+
+    let extensions = tbs["extensions"].as_array().unwrap();
+    assert_eq!(extensions.len(), 6);
+    assert_eq!(
+        extensions[0],
+        json!({
+            "extn_id": {"oid": "2.5.29.19", "name": "id-ce-basicConstraints"},
+            "critical": true,
+            "extn_value": "3000",
+        })
+    );
+
+    // owned certificates serialize alike, and round trip
+    let owned = CertificateOwned::from(&cert);
+    assert_eq!(serde_json::to_value(&owned).unwrap(), value);
+
+    // the names of this certificate have UTF8String values, so its encoding
+    // is reproduced exactly
+    let owned: CertificateOwned = serde_json::from_value(value).unwrap();
+    assert_eq!(owned.to_vec().unwrap(), der);
+}
+
+#[test]
+fn unique_ids() {
+    let der = include_bytes!("examples/path-leaf.der");
+    let mut cert = CertificateOwned::from_der(der).unwrap();
+    let tbs = &mut cert.tbs_certificate;
+    tbs.issuer_unique_id = Some(BitString::new(3, [0xa8]).unwrap());
+    tbs.subject_unique_id = Some(BitString::new(0, [0x01, 0x02]).unwrap());
+
+    let value = serde_json::to_value(&cert).unwrap();
+    let tbs = &value["tbs_certificate"];
+    assert_eq!(
+        tbs["issuer_unique_id"],
+        json!({"bytes": "a8", "unused_bits": 3})
+    );
+    assert_eq!(
+        tbs["subject_unique_id"],
+        json!({"bytes": "0102", "unused_bits": 0})
+    );
+
+    let owned: CertificateOwned = serde_json::from_value(value).unwrap();
+    assert_eq!(owned, cert);
+}
+
+#[test]
+fn time() {
+    let validity = Validity {
+        not_before: Time::from(GeneralizedTime::from_unix_duration(Default::default()).unwrap()),
+        not_after: Time::from(GeneralizedTime::from_date_time(
+            der::DateTime::new(2050, 1, 1, 0, 0, 0).unwrap(),
+        )),
+    };
+    let json = serde_json::to_string(&validity).unwrap();
+    assert_eq!(
+        json,
+        r#"{"not_before":"1970-01-01T00:00:00Z","not_after":"2050-01-01T00:00:00Z"}"#
+    );
+
+    // UTCTime is used in the years it can represent
+    let validity: Validity = serde_json::from_str(&json).unwrap();
+    assert!(matches!(validity.not_before, Time::UtcTime(_)));
+    assert!(matches!(validity.not_after, Time::GeneralTime(_)));
+
+    let time: Time = serde_json::from_str(r#""2049-12-31T23:59:59Z""#).unwrap();
+    assert!(matches!(time, Time::UtcTime(_)));
+    assert_eq!(time.to_string(), "2049-12-31T23:59:59Z");
+
+    let time: Time = serde_json::from_str(r#""1950-01-01T00:00:00Z""#).unwrap();
+    assert!(matches!(time, Time::UtcTime(_)));
+    assert_eq!(time.to_date_time().unix_timestamp(), -631152000);
+
+    let time: Time = serde_json::from_str(r#""1949-12-31T23:59:59Z""#).unwrap();
+    assert!(matches!(time, Time::GeneralTime(_)));
+
+    for invalid in [
+        r#""2049-12-31 23:59:59Z""#,
+        r#""2049-12-31T23:59:59+01:00""#,
+        r#""2049-13-01T00:00:00Z""#,
+        r#""+049-12-31T23:59:59Z""#,
+    ] {
+        assert!(
+            serde_json::from_str::<Time>(invalid).is_err(),
+            "{}",
+            invalid
+        );
+    }
+}
+
+#[test]
+fn name() {
+    let name: NameOwned = serde_json::from_str(r#""CN=Jane Doe,O=Example\\, Inc.,C=US""#).unwrap();
+    assert_eq!(name.0.len(), 3);
+    assert_eq!(
+        serde_json::to_string(&name.as_ref()).unwrap(),
+        r#""CN=Jane Doe,O=Example\\, Inc.,C=US""#
+    );
+
+    assert!(serde_json::from_str::<NameOwned>(r#""CN""#).is_err());
+}
+
+#[test]
+fn extensions() {
+    let bc = BasicConstraints {
+        ca: true,
+        path_len_constraint: None,
+    };
+    assert_eq!(serde_json::to_value(&bc).unwrap(), json!({"ca": true}));
+    assert!(
+        !serde_json::from_value::<BasicConstraints>(json!({}))
+            .unwrap()
+            .ca
+    );
+
+    let ku = KeyUsage(KeyUsages::DigitalSignature | KeyUsages::CRLSign);
+    let value = serde_json::to_value(ku).unwrap();
+    assert_eq!(value, json!(["digitalSignature", "cRLSign"]));
+    assert_eq!(serde_json::from_value::<KeyUsage>(value).unwrap(), ku);
+    assert!(serde_json::from_value::<KeyUsage>(json!(["signing"])).is_err());
+
+    // names of OIDs are informative
+    let eku: ExtendedKeyUsage =
+        serde_json::from_value(json!([{"oid": "1.3.6.1.5.5.7.3.1", "name": "bogus"}])).unwrap();
+    assert_eq!(
+        serde_json::to_value(&eku).unwrap(),
+        json!([{"oid": "1.3.6.1.5.5.7.3.1", "name": "id-kp-serverAuth"}])
+    );
+
+    let san = SubjectAltName(vec![
+        GeneralName::IpAddress(OctetStringRef::new(&hex!("c0a80001")).unwrap()),
+        GeneralName::IpAddress(
+            OctetStringRef::new(&hex!("20010db8000000000000000000000001")).unwrap(),
+        ),
+        GeneralName::IpAddress(OctetStringRef::new(&hex!("c0a80000 ffff0000")).unwrap()),
+        GeneralName::RegisteredId("1.2.3.4".parse().unwrap()),
+    ]);
+    assert_eq!(
+        serde_json::to_value(&san).unwrap(),
+        json!([
+            {"ip_address": "192.168.0.1"},
+            {"ip_address": "2001:db8:0:0:0:0:0:1"},
+            {"ip_address": "192.168.0.0/255.255.0.0"},
+            {"registered_id": {"oid": "1.2.3.4"}},
+        ])
+    );
+}
+
+#[test]
+fn crl() {
+    let der = include_bytes!("examples/GoodCACRL.crl");
+    let crl = CertificateList::from_der(der).unwrap();
+    let value = serde_json::to_value(&crl).unwrap();
+
+    let tbs = &value["tbs_cert_list"];
+    assert_eq!(tbs["version"], "v2");
+    assert_eq!(tbs["issuer"], crl.tbs_cert_list.issuer.to_string());
+
+    let revoked = tbs["revoked_certificates"].as_array().unwrap();
+    assert_eq!(revoked.len(), 2);
+    assert_eq!(revoked[0]["serial_number"], "0e");
+    assert_eq!(tbs["crl_extensions"].as_array().unwrap().len(), 2);
+
+    // names are re-encoded as described for `RdnSequenceOwned`
+    let owned: CertificateListOwned = serde_json::from_value(value).unwrap();
+    let tbs = &owned.tbs_cert_list;
+    assert!(tbs.issuer.matches(&crl.tbs_cert_list.issuer));
+    assert_eq!(tbs.this_update, crl.tbs_cert_list.this_update);
+    assert_eq!(
+        owned.as_ref().tbs_cert_list.revoked_certificates,
+        crl.tbs_cert_list.revoked_certificates
+    );
+    assert_eq!(owned.as_ref().signature, crl.signature);
+}
+
+#[test]
+fn cert_req() {
+    let der = include_bytes!("examples/rsa2048-csr.der");
+    let req = CertReq::from_der(der).unwrap();
+    let value = serde_json::to_value(&req).unwrap();
+
+    assert_eq!(value["info"]["version"], "v1");
+    assert_eq!(value["info"]["subject"], req.info.subject.to_string());
+    assert_eq!(
+        value["info"]["public_key"]["algorithm"]["algorithm"]["name"],
+        "rsaEncryption"
+    );
+    assert_eq!(
+        value["algorithm"]["algorithm"]["oid"],
+        req.algorithm.oid.to_string()
+    );
+}
+
+#[test]
+fn trust_anchor() {
+    let der = include_bytes!("examples/eca_policies.ta");
+    let ta = TrustAnchorChoice::from_der(der).unwrap();
+    let value = serde_json::to_value(&ta).unwrap();
+
+    let info = &value["ta_info"];
+    assert_eq!(info["version"], "v1");
+    assert!(info["cert_path"]["ta_name"].is_string());
+    assert!(info["cert_path"]["certificate"]["tbs_certificate"].is_object());
+}
@@ -0,0 +1,237 @@
+//! Certificate store tests
+
+use der::{Decode, Encode};
+use hex_literal::hex;
+use x509_cert::name::Name;
+use x509_cert::store::CertStore;
+#[cfg(feature = "pem")]
+use x509_cert::store::{LoadReport, Source};
+use x509_cert::{Certificate, CertificateOwned};
+
+const ROOT: &[u8] = include_bytes!("examples/path-root.der");
+const CA: &[u8] = include_bytes!("examples/path-ca.der");
+const CA_REKEY: &[u8] = include_bytes!("examples/path-ca-rekey.der");
+const SUB_CA: &[u8] = include_bytes!("examples/path-sub-ca.der");
+const LEAF: &[u8] = include_bytes!("examples/path-leaf.der");
+
+fn store(ders: &[&[u8]]) -> CertStore {
+    let mut store = CertStore::new();
+    for der in ders {
+        assert!(store.add_der(der).unwrap());
+    }
+    store
+}
+
+fn subject(cert: &x509_cert::CertificateOwned) -> String {
+    cert.tbs_certificate.subject.to_string()
+}
+
+#[test]
+fn lookup_by_index() {
+    let store = store(&[ROOT, CA, CA_REKEY, SUB_CA]);
+    assert_eq!(store.len(), 4);
+
+    // duplicates are ignored
+    let mut dup = store.clone();
+    assert!(!dup.add_der(CA).unwrap());
+    assert_eq!(dup.len(), 4);
+
+    let ca = Certificate::from_der(CA).unwrap();
+    let found: Vec<_> = store.find_by_subject(&ca.tbs_certificate.subject).collect();
+    assert_eq!(found.len(), 2);
+    assert!(found
+        .iter()
+        .all(|c| subject(c) == "C=US,O=RustCrypto,CN=Test Intermediate CA"));
+
+    let found: Vec<_> = store
+        .find_by_key_id(&hex!("E95FBB115B6DECFDB5A46083AF5846EC732963DB"))
+        .collect();
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].as_ref(), ca);
+
+    let found: Vec<_> = store
+        .find_by_issuer_serial(
+            &ca.tbs_certificate.issuer,
+            &ca.tbs_certificate.serial_number,
+        )
+        .collect();
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].as_ref(), ca);
+
+    assert_eq!(store.find_by_key_id(&[0; 20]).count(), 0);
+}
+
+#[test]
+fn normalized_subjects() {
+    let store = store(&[ROOT, CA]);
+
+    // case and whitespace differences in string values are insignificant
+    let der = Name::encode_from_string("C=us,O=rustcrypto,CN=test  intermediate CA ").unwrap();
+    let name = Name::from_der(&der).unwrap();
+    let found: Vec<_> = store.find_by_subject(&name).collect();
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].to_vec().unwrap(), CA);
+
+    let der = Name::encode_from_string("C=US,O=RustCrypto,CN=Test Intermediate").unwrap();
+    let name = Name::from_der(&der).unwrap();
+    assert_eq!(store.find_by_subject(&name).count(), 0);
+}
+
+#[test]
+fn find_issuers() {
+    let store = store(&[ROOT, CA_REKEY, CA, SUB_CA]);
+
+    // the re-keyed CA has the same subject but a different key identifier
+    let leaf = Certificate::from_der(LEAF).unwrap();
+    let issuers = store.find_issuers(&leaf);
+    assert_eq!(issuers.len(), 1);
+    assert_eq!(issuers[0].to_vec().unwrap(), CA);
+
+    // without an AuthorityKeyIdentifier both CAs are candidates
+    let mut leaf = leaf;
+    leaf.tbs_certificate
+        .extensions
+        .as_mut()
+        .unwrap()
+        .retain(|ext| ext.extn_id != const_oid::db::rfc5280::ID_CE_AUTHORITY_KEY_IDENTIFIER);
+    assert_eq!(store.find_issuers(&leaf).len(), 2);
+
+    // self-signed certificates are their own issuer
+    let root = Certificate::from_der(ROOT).unwrap();
+    let issuers = store.find_issuers(&root);
+    assert_eq!(issuers.len(), 1);
+    assert_eq!(issuers[0].as_ref(), root);
+}
+
+#[test]
+fn collect_from_iterator() {
+    let certs = [ROOT, CA, CA, LEAF]
+        .iter()
+        .map(|der| CertificateOwned::from(&Certificate::from_der(der).unwrap()));
+    let store: CertStore = certs.collect();
+
+    assert_eq!(store.len(), 3);
+    let subjects: Vec<_> = store.iter().map(subject).collect();
+    assert_eq!(
+        subjects,
+        [
+            "C=US,O=RustCrypto,CN=Test Root CA",
+            "C=US,O=RustCrypto,CN=Test Intermediate CA",
+            "C=US,O=RustCrypto,CN=leaf.example.com"
+        ]
+    );
+}
+
+#[test]
+#[cfg(feature = "pem")]
+fn add_pem_bundle() {
+    let mut store = CertStore::new();
+    let report = store.add_pem(include_bytes!("examples/path-bundle.pem"));
+
+    // the CRL in the bundle is skipped
+    assert_eq!(report.added, 3);
+    assert!(report.errors.is_empty());
+    let ders: Vec<_> = store.iter().map(|c| c.to_vec().unwrap()).collect();
+    assert_eq!(ders, [ROOT, CA, LEAF]);
+
+    let report = store.add_pem(include_bytes!("examples/path-bundle.pem"));
+    assert_eq!(report, LoadReport::default());
+}
+
+#[test]
+#[cfg(feature = "pem")]
+fn add_pem_bad_blocks() {
+    let mut pem = String::new();
+    pem.push_str("-----BEGIN CERTIFICATE-----\nMII=\n-----END CERTIFICATE-----\n");
+    pem.push_str(&pem_encode(ROOT));
+    pem.push_str("-----BEGIN CERTIFICATE-----\n!!!!\n-----END CERTIFICATE-----\n");
+    pem.push_str(&pem_encode(CA));
+    pem.push_str("-----BEGIN CERTIFICATE-----\nMIIB");
+
+    let mut store = CertStore::new();
+    let report = store.add_pem(pem.as_bytes());
+
+    // bad blocks are reported without preventing the others from being added
+    assert_eq!(report.added, 2);
+    let sources: Vec<_> = report.errors.iter().map(|(source, _)| source).collect();
+    assert_eq!(
+        sources,
+        [&Source::Block(0), &Source::Block(2), &Source::Block(4)]
+    );
+    assert_eq!(store.len(), 2);
+}
+
+#[cfg(feature = "pem")]
+fn pem_encode(der: &[u8]) -> String {
+    der::pem::encode_string("CERTIFICATE", der::pem::LineEnding::LF, der).unwrap()
+}
+
+#[test]
+#[cfg(all(feature = "std", feature = "pem"))]
+fn add_files() {
+    let dir = std::env::temp_dir().join(format!("x509-cert-store-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("bundle.pem"),
+        include_bytes!("examples/path-bundle.pem"),
+    )
+    .unwrap();
+    std::fs::write(dir.join("sub-ca.der"), SUB_CA).unwrap();
+    std::fs::write(dir.join("README"), "not a certificate").unwrap();
+    std::fs::write(
+        dir.join("bad.pem"),
+        "-----BEGIN CERTIFICATE-----\nMII=\n-----END CERTIFICATE-----\n",
+    )
+    .unwrap();
+
+    let mut store = CertStore::new();
+    assert_eq!(store.add_file(dir.join("sub-ca.der")).unwrap().added, 1);
+    assert!(store.add_file(dir.join("README")).is_err());
+    assert!(store.add_file(dir.join("missing.der")).is_err());
+
+    let mut report = store.add_dir(&dir).unwrap();
+    assert_eq!(report.added, 3);
+    assert_eq!(store.len(), 4);
+
+    // files which cannot be decoded are reported to the caller
+    report
+        .errors
+        .sort_by_key(|(source, _)| format!("{:?}", source));
+    let sources: Vec<_> = report
+        .errors
+        .into_iter()
+        .map(|(source, _)| source)
+        .collect();
+    assert_eq!(
+        sources,
+        [
+            Source::File {
+                path: dir.join("README"),
+                block: None
+            },
+            Source::File {
+                path: dir.join("bad.pem"),
+                block: Some(0)
+            },
+        ]
+    );
+    assert!(store.add_dir(dir.join("missing")).is_err());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+#[cfg(feature = "fingerprint")]
+fn lookup_by_fingerprint() {
+    let store = store(&[ROOT, CA]);
+    let ca = Certificate::from_der(CA).unwrap();
+    let fingerprint = ca
+        .tbs_certificate
+        .subject_public_key_info
+        .fingerprint_bytes()
+        .unwrap();
+
+    let found: Vec<_> = store.find_by_spki_fingerprint(&fingerprint).collect();
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].as_ref(), ca);
+}
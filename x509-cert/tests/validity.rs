@@ -1,8 +1,8 @@
 //! Validity tests
 
-use der::Encode;
+use der::{Decode, Encode};
 use hex_literal::hex;
-use x509_cert::time::Validity;
+use x509_cert::time::{Time, Validity};
 
 #[test]
 fn decode_validity() {
@@ -134,3 +134,56 @@ fn encode_validity() {
         &hex!("3020180F32303032303130313132303130305A170D3330313233313038333030305A")[..]
     );
 }
+
+#[test]
+fn no_well_defined_expiration() {
+    //  SEQUENCE {
+    //    UTCTime 01/01/2010 08:30:00 GMT
+    //    GeneralizedTime 31/12/9999 23:59:59 GMT
+    //  }
+    let der = hex!("3020170D3130303130313038333030305A180F39393939313233313233353935395A");
+    let validity = Validity::try_from(&der[..]).unwrap();
+    assert!(validity.not_after.is_infinity());
+    assert!(!validity.has_expiration());
+    assert_eq!(validity.not_after.to_string(), "9999-12-31T23:59:59Z");
+    assert_eq!(
+        validity.not_after.to_unix_duration().as_secs(),
+        253402300799
+    );
+
+    let built = Validity::without_expiration(validity.not_before);
+    assert_eq!(built, validity);
+    assert_eq!(built.to_vec().unwrap(), der);
+
+    let time = Time::from_der(&hex!("170D3130303130313038333030305A")).unwrap();
+    assert!(!time.is_infinity());
+}
+
+#[test]
+fn before_unix_epoch() {
+    //  SEQUENCE {
+    //    UTCTime 01/01/1950 00:00:00 GMT
+    //    UTCTime 31/12/1969 23:59:59 GMT
+    //  }
+    let der = hex!("301E170D3530303130313030303030305A170D3639313233313233353935395A");
+    let validity = Validity::try_from(&der[..]).unwrap();
+    assert_eq!(validity.not_before.to_string(), "1950-01-01T00:00:00Z");
+    assert_eq!(validity.not_after.to_string(), "1969-12-31T23:59:59Z");
+    assert_eq!(validity.not_after.to_date_time().unix_timestamp(), -1);
+    assert!(validity.not_before.to_date_time() < validity.not_after.to_date_time());
+    assert_eq!(validity.to_vec().unwrap(), der);
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn chrono_roundtrip() {
+    use der::chrono::{self, TimeZone, Utc};
+
+    let time = Time::from_der(&hex!("170D3535303630373038303931305A")).unwrap();
+    let chrono = chrono::DateTime::<Utc>::try_from(time).unwrap();
+    assert_eq!(chrono, Utc.with_ymd_and_hms(1955, 6, 7, 8, 9, 10).unwrap());
+    assert_eq!(
+        Time::try_from(chrono).unwrap().to_date_time(),
+        time.to_date_time()
+    );
+}
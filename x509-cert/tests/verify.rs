@@ -0,0 +1,89 @@
+//! Signature verification tests
+
+mod common;
+
+use common::EcdsaP256Verifier;
+use const_oid::db::rfc5912::ID_EC_PUBLIC_KEY;
+use der::{Decode, Encode};
+use x509_cert::verify::{Error, SignedCertReq, SignedCertificate, SignedCertificateList};
+use x509_cert::Certificate;
+
+#[test]
+fn verify_certificate() {
+    let root = Certificate::from_der(include_bytes!("examples/path-root.der")).unwrap();
+    let ca = Certificate::from_der(include_bytes!("examples/path-ca.der")).unwrap();
+    let der = include_bytes!("examples/path-leaf.der");
+    let leaf = SignedCertificate::from_der(der).unwrap();
+
+    assert_eq!(leaf.certificate(), Certificate::from_der(der).unwrap());
+    assert_eq!(leaf.to_vec().unwrap(), der);
+    assert!(leaf.verify_signed_by(&ca, &EcdsaP256Verifier).is_ok());
+    assert_eq!(
+        leaf.verify_signed_by(&root, &EcdsaP256Verifier),
+        Err(Error::Verification)
+    );
+
+    let root = SignedCertificate::from_der(include_bytes!("examples/path-root.der")).unwrap();
+    assert!(root
+        .verify_signed_by(&root.certificate(), &EcdsaP256Verifier)
+        .is_ok());
+}
+
+#[test]
+fn verify_original_encoding() {
+    let ca = Certificate::from_der(include_bytes!("examples/path-ca.der")).unwrap();
+    let mut der = Vec::from(&include_bytes!("examples/path-leaf.der")[..]);
+    let leaf = SignedCertificate::from_der(&der).unwrap();
+
+    // tbsCertificate immediately follows the outer SEQUENCE header
+    let tbs = leaf.tbs_bytes();
+    assert_eq!(tbs, &der[4..4 + tbs.len()]);
+    assert_eq!(tbs, leaf.tbs.to_vec().unwrap());
+
+    // flip a bit in the last byte of the tbsCertificate
+    let offset = tbs.len() + 3;
+    der[offset] ^= 1;
+    let leaf = SignedCertificate::from_der(&der).unwrap();
+    assert_eq!(
+        leaf.verify_signed_by(&ca, &EcdsaP256Verifier),
+        Err(Error::Verification)
+    );
+}
+
+#[test]
+fn verify_algorithm_mismatch() {
+    let root = Certificate::from_der(include_bytes!("examples/path-root.der")).unwrap();
+    let mut leaf = SignedCertificate::from_der(include_bytes!("examples/path-ca.der")).unwrap();
+    leaf.signature_algorithm.oid = ID_EC_PUBLIC_KEY;
+
+    assert_eq!(
+        leaf.verify_signed_by(&root, &EcdsaP256Verifier),
+        Err(Error::AlgorithmMismatch)
+    );
+}
+
+#[test]
+fn verify_crl() {
+    let ca = Certificate::from_der(include_bytes!("examples/path-ca.der")).unwrap();
+    let root = Certificate::from_der(include_bytes!("examples/path-root.der")).unwrap();
+    let crl = SignedCertificateList::from_der(include_bytes!("examples/path-ca.crl")).unwrap();
+
+    assert!(crl.verify_signed_by(&ca, &EcdsaP256Verifier).is_ok());
+    assert_eq!(
+        crl.verify_signed_by(&root, &EcdsaP256Verifier),
+        Err(Error::Verification)
+    );
+}
+
+#[test]
+fn verify_certreq() {
+    let req = SignedCertReq::from_der(include_bytes!("examples/path-leaf.csr")).unwrap();
+    assert!(req.verify_self_signed(&EcdsaP256Verifier).is_ok());
+
+    // RSA is not supported by the verifier
+    let req = SignedCertReq::from_der(include_bytes!("examples/rsa2048-csr.der")).unwrap();
+    assert!(matches!(
+        req.verify_self_signed(&EcdsaP256Verifier),
+        Err(Error::UnsupportedAlgorithm(_))
+    ));
+}